@@ -5,6 +5,8 @@
 
 extern crate fui;
 
+use fui::cursive::theme::{BaseColor, Color};
+use fui::cursive::utils::markup::StyledString;
 use fui::feeders::DirItems;
 use fui::fields::{Autocomplete, Multiselect};
 use fui::form::FormView;
@@ -16,6 +18,14 @@ fn hdlr(v: Value) {
     println!("user input (from fn) {:?}", v);
 }
 
+const BANNER: &str = r#"
+ _
+| |_ __ _ _ __
+| __/ _` | '__|
+| || (_| | |
+ \__\__,_|_|
+"#;
+
 fn main() {
     let formats = vec!["none", "gzip", "bzip2"];
     let compression = Autocomplete::new("compression-type", formats.clone())
@@ -25,6 +35,7 @@ fn main() {
         .help("Archive format");
 
     Fui::new("app_tar_like")
+        .banner(StyledString::styled(BANNER, Color::Dark(BaseColor::Blue)))
         .action(
             "archive-files",
             "Create an archive from files",
@@ -8,7 +8,7 @@ use cursive::traits::Resizable;
 use cursive::views::{Dialog, DummyView, LinearLayout};
 use cursive::Cursive;
 
-use fui::feeders::DirItems;
+use fui::feeders::{DirItems, Grouped};
 use fui::views::Autocomplete;
 
 fn handler(c: &mut Cursive, submitted: Rc<String>) {
@@ -32,6 +32,16 @@ fn main() {
         .child(DummyView)
         // completes paths as absolute paths
         .child(Autocomplete::new(DirItems::new().use_full_paths()).on_submit(handler))
+        .child(DummyView)
+        // lists suggestions under section headers
+        .child(
+            Autocomplete::new(
+                Grouped::new()
+                    .group("Branches", vec!["master", "develop"])
+                    .group("Tags", vec!["v1.0", "v1.1", "v2.0"]),
+            )
+            .on_submit(handler),
+        )
         .child(DummyView);
 
     c.add_layer(Dialog::around(layout).full_width());
@@ -27,6 +27,8 @@ fn main() {
         .field(Autocomplete::new("basic-field", options.clone()))
         .field(Autocomplete::new("field-with-help", options.clone()).help("help message"))
         .field(Autocomplete::new("initialized-field", options.clone()).initial("3"))
+        .field(Autocomplete::new("with-inline-completion", options.clone()).inline_completion(true))
+        .field(Autocomplete::new("with-tab-cycling", options.clone()).tab_cycling(true))
         .field(Autocomplete::new("with-validator", options.clone()).validator(Required))
         .field(
             Autocomplete::new("with-validators", options.clone()).validator(OneOf(options.clone())),
@@ -37,6 +39,7 @@ fn main() {
                 .initial("some text")
                 .validator(Required),
         )
+        .required_progress()
         .on_submit(show_data);
     siv.add_layer(Dialog::around(form).full_screen());
 
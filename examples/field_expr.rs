@@ -0,0 +1,33 @@
+// Demonstrates features of field Expr
+extern crate cursive;
+extern crate fui;
+extern crate serde_json;
+
+use cursive::views::Dialog;
+use cursive::Cursive;
+use serde_json::value::Value;
+
+use fui::fields::Expr;
+use fui::form::FormView;
+use fui::validators::Required;
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = cursive::default();
+
+    let form = FormView::new()
+        .field(
+            Expr::new("memory-limit")
+                .help("e.g. 512MiB or 3*1024")
+                .initial("512MiB"),
+        )
+        .field(Expr::new("timeout").help("e.g. 2h+30m").validator(Required))
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form));
+
+    siv.run();
+}
@@ -26,6 +26,11 @@ fn main() {
         .field(Multiselect::new("basic-field", options.clone()))
         .field(Multiselect::new("field-with-help", options.clone()).help("help message"))
         .field(Multiselect::new("initialized-field", options.clone()).initial(options.clone()))
+        .field(
+            Multiselect::new("with-chips", options.clone())
+                .initial(options.clone())
+                .chips(),
+        )
         .field(Multiselect::new("with-validator", options.clone()).validator(Required))
         .field(
             Multiselect::new("with-validators", options.clone()).validator(OneOf(options.clone())),
@@ -3,12 +3,14 @@ extern crate cursive;
 extern crate fui;
 extern crate serde_json;
 
-use cursive::traits::Resizable;
 use cursive::views::Dialog;
 use cursive::Cursive;
 use serde_json::value::Value;
 
-use fui::fields::{Autocomplete, Checkbox, Multiselect, Text};
+use fui::fields::{
+    Autocomplete, Checkbox, Cron, Decimal, Endpoint, FileMode, Json, Multiselect, Number, SavePath,
+    SemVer, SemVerReq, Text, Uuid,
+};
 use fui::form::FormView;
 
 fn submit_handler(c: &mut Cursive, data: Value) {
@@ -24,6 +26,9 @@ fn main() {
     let form = FormView::new()
         .field(Checkbox::new("verbose").help("this is help for checkbox"))
         .field(Text::new("text-field").help("this is help for text"))
+        .separator()
+        .heading("Choices")
+        .note("Options come from a static list")
         .field(
             Autocomplete::new("autocomplete-field", options.clone())
                 .help("this is help for autocomplete"),
@@ -32,10 +37,28 @@ fn main() {
             Multiselect::new("multiselect-field", options.clone())
                 .help("this is help for multiselect"),
         )
+        .field(
+            Cron::new("cron-field")
+                .help("this is help for cron")
+                .initial("30 9 * * mon-fri"),
+        )
+        .field(Json::new("json-field").help("this is help for json, e.g. {\"retries\": 3}"))
+        .field(
+            Number::new("number-field")
+                .help("this is help for number, adjust it with - and +")
+                .step(0.5),
+        )
+        .field(Decimal::new("decimal-field").help("this is help for decimal"))
+        .field(Endpoint::new("endpoint-field").initial("localhost:8080"))
+        .field(FileMode::new("file-mode-field").initial("644"))
+        .field(SavePath::new("save-path-field").help("this is help for save path"))
+        .field(SemVer::new("semver-field").initial("1.2.3"))
+        .field(SemVerReq::new("semver-req-field").initial("^1.2"))
+        .field(Uuid::new("uuid-field").help("this is help for uuid (Ctrl+g generates one)"))
         .on_submit(submit_handler)
         .on_cancel(|c| c.quit());
 
-    siv.add_layer(form.full_screen());
+    siv.add_layer(form.max_width(100).fill_screen());
 
     siv.run();
 }
@@ -0,0 +1,87 @@
+//! Append-only log of submissions, see [Fui::audit_log].
+//!
+//! [Fui::audit_log]: ../struct.Fui.html#method.audit_log
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde_json::value::Value;
+use utils;
+
+/// Placeholder replacing values of sensitive fields.
+const REDACTED: &str = "***";
+
+/// Replaces values of `sensitive` fields (data paths of them) in `values` with a placeholder.
+fn redact(values: &Value, sensitive: &[String]) -> Value {
+    let mut redacted = values.clone();
+    for path in sensitive {
+        if let Some(v) = utils::get_path_mut(&mut redacted, path) {
+            *v = Value::String(REDACTED.to_string());
+        }
+    }
+    redacted
+}
+
+/// Builds a record (JSON line) about submission of `values` for `action`.
+fn record(
+    timestamp: &str,
+    action: &str,
+    values: &Value,
+    sensitive: &[String],
+    outcome: &str,
+) -> String {
+    let record = json!({
+        "timestamp": timestamp,
+        "action": action,
+        "values": redact(values, sensitive),
+        "outcome": outcome,
+    });
+    record.to_string()
+}
+
+/// Appends a record about submission of `values` for `action` to log at `path`.
+pub(crate) fn write_record(
+    path: &Path,
+    action: &str,
+    values: &Value,
+    sensitive: &[String],
+    outcome: &str,
+) -> io::Result<()> {
+    let timestamp = utils::format_rfc3339(SystemTime::now());
+    let record = record(&timestamp, action, values, sensitive, outcome);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_hides_sensitive_values_only() {
+        let values = json!({"user": "u", "password": "secret", "db": {"password": "x"}});
+        let sensitive = ["password", "missing", "db.password"].map(String::from);
+
+        let redacted = redact(&values, &sensitive);
+
+        assert_eq!(
+            redacted,
+            json!({"user": "u", "password": "***", "db": {"password": "***"}})
+        );
+    }
+
+    #[test]
+    fn record_lists_values_in_fields_order() {
+        let values = json!({"user": "u", "password": "secret", "host": "h"});
+        let sensitive = vec!["password".to_string()];
+
+        let record = record("now", "login", &values, &sensitive, "ok");
+
+        assert_eq!(
+            record,
+            r#"{"timestamp":"now","action":"login","values":{"user":"u","password":"***","host":"h"},"outcome":"ok"}"#
+        );
+    }
+}
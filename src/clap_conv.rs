@@ -591,7 +591,6 @@ mod positional_args {
         let initial = action.form.as_ref().unwrap().get_field_value("0");
         assert_eq!(initial, Some("default".to_string()));
     }
-
 }
 
 #[cfg(test)]
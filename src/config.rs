@@ -0,0 +1,821 @@
+//! Writing forms' values as config files, see [Fui::config_generator].
+//!
+//! [Fui::config_generator]: ../struct.Fui.html#method.config_generator
+use std::cell::Cell;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+use cursive;
+use cursive::traits::Scrollable;
+use cursive::views::Dialog;
+use serde_json::map::Map;
+use serde_json::value::Value;
+use utils;
+use views::Diff;
+
+/// Format of generated config file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// JSON, which doesn't support comments so help texts are skipped.
+    Json,
+    /// TOML
+    Toml,
+    /// YAML
+    Yaml,
+}
+
+/// Renders `values` (object keyed by fields' keys) in `format`.
+///
+/// Entries keep order of `values` and are preceded by comments made from `helps` (pairs
+/// of data path, like `server.port` for fields in [FormView::group], and help).
+/// Nested objects are rendered as TOML tables or YAML blocks, so their entries get
+/// comments too.
+///
+/// [FormView::group]: ../form/struct.FormView.html#method.group
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// # fn main() {
+/// use fui::config::{render, Format};
+///
+/// let values = json!({"name": "app", "debug": true});
+/// let helps = vec![("name".to_string(), "Name of the app".to_string())];
+///
+/// assert_eq!(
+///     render(&values, &helps, Format::Toml),
+///     "# Name of the app\nname = \"app\"\n\ndebug = true\n"
+/// );
+/// # }
+/// ```
+pub fn render(values: &Value, helps: &[(String, String)], format: Format) -> String {
+    let map = match values {
+        Value::Object(map) => map,
+        // forms' values are always objects
+        _ => return String::new(),
+    };
+    let helps = helps
+        .iter()
+        .map(|(path, help)| (utils::path_keys(path), help.as_str()))
+        .collect::<Vec<(Vec<String>, &str)>>();
+    match format {
+        Format::Json => format!("{}\n", serde_json::to_string_pretty(values).unwrap()),
+        Format::Toml => render_table(map, &[], &helps).join("\n"),
+        Format::Yaml => render_block(map, &[], &helps).join("\n"),
+    }
+}
+
+/// Makes comment lines from help of entry at `path`.
+fn comment(path: &[String], helps: &[(Vec<String>, &str)], indent: &str) -> String {
+    let help = helps.iter().find(|(help_path, _)| help_path == path);
+    help.map(|(_, help)| {
+        help.lines()
+            .map(|line| format!("{}# {}\n", indent, line))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn is_table(value: &Value) -> bool {
+    matches!(value, Value::Object(map) if !map.is_empty())
+}
+
+/// Renders entries of TOML table at `path`, followed by its sub-tables.
+fn render_table(
+    map: &Map<String, Value>,
+    path: &[String],
+    helps: &[(Vec<String>, &str)],
+) -> Vec<String> {
+    let entries = map.iter().collect::<Vec<(&String, &Value)>>();
+    let mut rendered = Vec::with_capacity(entries.len());
+    for (key, value) in entries.iter().filter(|(_, value)| !is_table(value)) {
+        let entry_path = [path, &[key.to_string()]].concat();
+        rendered.push(format!(
+            "{}{} = {}\n",
+            comment(&entry_path, helps, ""),
+            toml_key(key),
+            toml_value(value)
+        ));
+    }
+    if !path.is_empty() && !rendered.is_empty() {
+        let keys = path.iter().map(|k| toml_key(k)).collect::<Vec<String>>();
+        let header = format!("{}[{}]\n", comment(path, helps, ""), keys.join("."));
+        rendered[0] = format!("{}{}", header, rendered[0]);
+    }
+    for (key, value) in entries.iter().filter(|(_, value)| is_table(value)) {
+        let table_path = [path, &[key.to_string()]].concat();
+        let table = value.as_object().unwrap();
+        rendered.extend(render_table(table, &table_path, helps));
+    }
+    rendered
+}
+
+/// Renders entries of YAML mapping at `path`, indented by its depth.
+fn render_block(
+    map: &Map<String, Value>,
+    path: &[String],
+    helps: &[(Vec<String>, &str)],
+) -> Vec<String> {
+    let indent = "  ".repeat(path.len());
+    let mut rendered = Vec::with_capacity(map.len());
+    for (key, value) in map.iter() {
+        let entry_path = [path, &[key.to_string()]].concat();
+        let comment = comment(&entry_path, helps, &indent);
+        let entry = match value {
+            Value::Object(block) if !block.is_empty() => {
+                let entries = render_block(block, &entry_path, helps);
+                format!("{}:\n{}", yaml_key(key), entries.join("\n"))
+            }
+            _ => format!("{}: {}\n", yaml_key(key), yaml_value(value)),
+        };
+        rendered.push(format!("{}{}{}", comment, indent, entry));
+    }
+    rendered
+}
+
+/// Writes `values` rendered in `format` to file at `path`.
+pub fn write<P: AsRef<Path>>(
+    path: P,
+    values: &Value,
+    helps: &[(String, String)],
+    format: Format,
+) -> io::Result<()> {
+    fs::write(path, render(values, helps, format))
+}
+
+/// Decision about existing config file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Overwrite {
+    /// Replace old file with new values.
+    Replace,
+    /// Keep old entries missing in new values.
+    Merge,
+    /// Leave old file untouched.
+    Cancel,
+}
+
+/// Lists entries (pairs of key and value rendered in `format`) of config `text`.
+///
+/// Keys of nested tables are dotted paths, like `server.port` for `port` in `[server]`.
+/// Text which can't be parsed has no entries.
+pub(crate) fn entries(text: &str, format: Format) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    if let Ok(map) = parse(text, format) {
+        flatten_entries("", &map, format, &mut found);
+    }
+    found
+}
+
+fn flatten_entries(
+    prefix: &str,
+    map: &Map<String, Value>,
+    format: Format,
+    found: &mut Vec<(String, String)>,
+) {
+    for (key, value) in map.iter() {
+        let path = match prefix {
+            "" => toml_key(key),
+            prefix => format!("{}.{}", prefix, toml_key(key)),
+        };
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                flatten_entries(&path, nested, format, found)
+            }
+            _ => found.push((path, render_value(value, format))),
+        }
+    }
+}
+
+/// Renders `values` like [render], keeping entries of `old` text missing in `values`
+/// (nested ones too) and comments above old entries & at the start of `old`.
+///
+/// Fails when `old` text can't be parsed, so nothing of it is lost silently.
+///
+/// [render]: fn.render.html
+pub(crate) fn merge(
+    old: &str,
+    values: &Value,
+    helps: &[(String, String)],
+    format: Format,
+) -> Result<String, String> {
+    let old_map = parse(old, format)?;
+    let new_map = match values {
+        Value::Object(map) => map,
+        _ => return Ok(render(values, helps, format)),
+    };
+    // comments written by user replace helps
+    let (header, comments) = old_comments(old, format, &old_map);
+    let mut helps = helps.to_vec();
+    for (path, comment) in comments {
+        match helps.iter_mut().find(|(help_path, _)| *help_path == path) {
+            Some((_, help)) => *help = comment,
+            None => helps.push((path, comment)),
+        }
+    }
+    // old entries follow new ones (commented ones too)
+    let mut merged = new_map.clone();
+    keep_old(&mut merged, &old_map);
+    let rendered = render(&Value::Object(merged), &helps, format);
+    Ok(format!("{}{}", header, rendered))
+}
+
+/// Appends entries of `old` missing in `map` after its entries,
+/// objects in both of them are merged.
+fn keep_old(map: &mut Map<String, Value>, old: &Map<String, Value>) {
+    for (key, value) in old.iter() {
+        match (map.get_mut(key), value) {
+            (Some(Value::Object(new)), Value::Object(old)) => keep_old(new, old),
+            (Some(_), _) => (),
+            (None, _) => {
+                map.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Parses config `text` into nested object, empty `text` gives empty one.
+fn parse(text: &str, format: Format) -> Result<Map<String, Value>, String> {
+    if text.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    let value = match format {
+        Format::Json => serde_json::from_str(text).map_err(|e| e.to_string())?,
+        Format::Toml => toml_to_json(toml::from_str(text).map_err(|e| e.to_string())?),
+        Format::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string())?,
+    };
+    match value {
+        Value::Object(map) => Ok(map),
+        // YAML document with comments only
+        Value::Null => Ok(Map::new()),
+        _ => Err("config isn't a table of entries".to_string()),
+    }
+}
+
+/// Converts TOML `value` to JSON, dates become strings.
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, toml_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Finds comments of config `text`: the ones at its start (followed by empty line)
+/// and the ones right above entries of `map`, paired with data paths of the entries.
+fn old_comments(
+    text: &str,
+    format: Format,
+    map: &Map<String, Value>,
+) -> (String, Vec<(String, String)>) {
+    if format == Format::Json {
+        return (String::new(), Vec::new());
+    }
+    let mut header = String::new();
+    let mut found = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut at_start = true;
+    // TOML table or YAML mappings (with indentation) containing the current line
+    let mut parents: Vec<(usize, Vec<String>)> = Vec::new();
+    // lines of multi-line strings are skipped, they hold no comments
+    let mut in_string = false;
+    let mut block_indent: Option<usize> = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        if in_string {
+            in_string = trimmed.matches("\"\"\"").count() % 2 == 0
+                && trimmed.matches("'''").count() % 2 == 0;
+            continue;
+        }
+        match block_indent {
+            Some(block) if trimmed.is_empty() || indent > block => continue,
+            _ => block_indent = None,
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            pending.push(comment.strip_prefix(' ').unwrap_or(comment));
+            continue;
+        }
+        if trimmed.is_empty() {
+            if at_start && !pending.is_empty() {
+                header = pending
+                    .iter()
+                    .map(|l| format!("#{}\n", prefixed(l)))
+                    .collect();
+                header.push('\n');
+            }
+            at_start = false;
+            pending.clear();
+            continue;
+        }
+        at_start = false;
+        let path = match format {
+            Format::Toml => toml_line_path(trimmed, &mut parents),
+            _ => yaml_line_path(trimmed, indent, &mut parents),
+        };
+        if let Some(path) = path {
+            let sep = if format == Format::Toml { '=' } else { ':' };
+            let value = find_unquoted(trimmed, sep).map_or("", |idx| trimmed[idx + 1..].trim());
+            match format {
+                Format::Toml => {
+                    in_string = value.matches("\"\"\"").count() % 2 == 1
+                        || value.matches("'''").count() % 2 == 1
+                }
+                _ if value.starts_with('|') || value.starts_with('>') => {
+                    block_indent = Some(indent)
+                }
+                _ => {}
+            }
+            if !pending.is_empty() && has_path(map, &path) {
+                let path = path
+                    .iter()
+                    .map(|k| utils::escape_key(k))
+                    .collect::<Vec<_>>();
+                found.push((path.join(&utils::PATH_SEP.to_string()), pending.join("\n")));
+            }
+        }
+        pending.clear();
+    }
+    (header, found)
+}
+
+/// Gives space separating `#` from `comment` (unless it's empty).
+fn prefixed(comment: &str) -> String {
+    match comment {
+        "" => String::new(),
+        comment => format!(" {}", comment),
+    }
+}
+
+/// Gets path of TOML entry or table in `line`, tracking current table in `parents`.
+fn toml_line_path(line: &str, parents: &mut Vec<(usize, Vec<String>)>) -> Option<Vec<String>> {
+    let header = line
+        .strip_prefix("[[")
+        .and_then(|l| l.split("]]").next())
+        .or_else(|| line.strip_prefix('[').and_then(|l| l.split(']').next()));
+    if let Some(header) = header {
+        let table = split_key(header);
+        *parents = vec![(0, table.clone())];
+        return Some(table);
+    }
+    let idx = find_unquoted(line, '=')?;
+    let table = parents.last().map(|(_, p)| p.clone()).unwrap_or_default();
+    Some([table, split_key(&line[..idx])].concat())
+}
+
+/// Gets path of YAML entry in `line` indented by `indent`, tracking mappings in `parents`.
+fn yaml_line_path(
+    line: &str,
+    indent: usize,
+    parents: &mut Vec<(usize, Vec<String>)>,
+) -> Option<Vec<String>> {
+    while parents.last().is_some_and(|(i, _)| *i >= indent) {
+        parents.pop();
+    }
+    if line.starts_with('-') {
+        return None;
+    }
+    let idx = find_unquoted(line, ':')?;
+    let key = line[..idx].trim().trim_matches(|c| c == '"' || c == '\'');
+    let mut path = parents.last().map(|(_, p)| p.clone()).unwrap_or_default();
+    path.push(key.to_string());
+    if line[idx + 1..].trim().is_empty() {
+        parents.push((indent, path.clone()));
+    }
+    Some(path)
+}
+
+/// Checks that `map` has entry at `path`.
+fn has_path(map: &Map<String, Value>, path: &[String]) -> bool {
+    let (last, parent) = match path.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+    let mut map = map;
+    for key in parent {
+        map = match map.get(key) {
+            Some(Value::Object(nested)) => nested,
+            _ => return false,
+        };
+    }
+    map.contains_key(last)
+}
+
+/// Splits dotted TOML key (like `a."b.c"`) into its parts.
+fn split_key(key: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = key;
+    while let Some(idx) = find_unquoted(rest, '.') {
+        parts.push(rest[..idx].to_string());
+        rest = &rest[idx + 1..];
+    }
+    parts.push(rest.to_string());
+    parts
+        .iter()
+        .map(|part| {
+            part.trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+        .collect()
+}
+
+/// Finds `sep` outside of quoted strings of `text`.
+fn find_unquoted(text: &str, sep: char) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, c) in text.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == sep => return Some(idx),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Asks user (showing differences) what to do with `old` config when `values` are written.
+///
+/// Asks in TUI dialog, see [ask_overwrite] for asking without it. Merging is offered
+/// only when `old` config can be parsed.
+///
+/// [ask_overwrite]: fn.ask_overwrite.html
+pub(crate) fn confirm_overwrite(
+    old: &str,
+    values: &Value,
+    helps: &[(String, String)],
+    format: Format,
+) -> Overwrite {
+    let new_text = render(values, helps, format);
+    let diff = Diff::new(entries(old, format), entries(&new_text, format));
+    if !diff.has_changes() {
+        return Overwrite::Replace;
+    }
+    let decision = Rc::new(Cell::new(Overwrite::Cancel));
+    let on_replace = Rc::clone(&decision);
+    let on_merge = Rc::clone(&decision);
+    let mut dialog = Dialog::around(diff.scrollable()).button("Cancel", |c| c.quit());
+    match parse(old, format) {
+        Ok(_) => {
+            dialog.set_title("Config file exists, overwrite it?");
+            dialog.add_button("Merge", move |c| {
+                on_merge.set(Overwrite::Merge);
+                c.quit();
+            });
+        }
+        Err(e) => dialog.set_title(format!(
+            "Config file can't be merged ({}), overwrite it?",
+            e
+        )),
+    }
+    dialog.add_button("Overwrite", move |c| {
+        on_replace.set(Overwrite::Replace);
+        c.quit();
+    });
+    let mut c = cursive::default();
+    c.add_layer(dialog);
+    c.run();
+    decision.get()
+}
+
+/// Asks user like [confirm_overwrite] but line by line through `input` & `output`.
+///
+/// End of input cancels writing.
+///
+/// [confirm_overwrite]: fn.confirm_overwrite.html
+pub(crate) fn ask_overwrite<R: BufRead, W: Write>(
+    old: &str,
+    values: &Value,
+    helps: &[(String, String)],
+    format: Format,
+    mut input: R,
+    mut output: W,
+) -> io::Result<Overwrite> {
+    let new_text = render(values, helps, format);
+    let diff = Diff::new(entries(old, format), entries(&new_text, format));
+    if !diff.has_changes() {
+        return Ok(Overwrite::Replace);
+    }
+    writeln!(output, "Config file exists, changes:")?;
+    for line in diff.changes() {
+        writeln!(output, "  {}", line)?;
+    }
+    let mergeable = match parse(old, format) {
+        Ok(_) => true,
+        Err(e) => {
+            writeln!(output, "Config file can't be merged: {}", e)?;
+            false
+        }
+    };
+    loop {
+        if mergeable {
+            write!(output, "[o]verwrite, [m]erge or [c]ancel: ")?;
+        } else {
+            write!(output, "[o]verwrite or [c]ancel: ")?;
+        }
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(Overwrite::Cancel);
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "o" | "overwrite" => return Ok(Overwrite::Replace),
+            "m" | "merge" if mergeable => return Ok(Overwrite::Merge),
+            "c" | "cancel" => return Ok(Overwrite::Cancel),
+            _ => continue,
+        }
+    }
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn toml_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn render_value(value: &Value, format: Format) -> String {
+    match format {
+        Format::Json => value.to_string(),
+        Format::Toml => toml_value(value),
+        Format::Yaml => yaml_value(value),
+    }
+}
+
+fn toml_value(value: &Value) -> String {
+    match value {
+        Value::Null => "\"\"".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(toml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{} = {}", toml_key(k), toml_value(v)))
+                .collect();
+            format!("{{ {} }}", items.join(", "))
+        }
+        // JSON strings, numbers & bools are valid in TOML too
+        _ => value.to_string(),
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    toml_key(key)
+}
+
+fn yaml_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(yaml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}: {}", yaml_key(k), yaml_value(v)))
+                .collect();
+            format!("{{{}}}", items.join(", "))
+        }
+        // JSON scalars are valid YAML flow scalars
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helps() -> Vec<(String, String)> {
+        vec![
+            ("name".to_string(), "Name\nof the app".to_string()),
+            ("tags".to_string(), "".to_string()),
+        ]
+    }
+
+    #[test]
+    fn renders_toml_with_comments() {
+        let values = json!({"name": "a \"b\"", "tags": ["x", "y"], "my key": 1});
+
+        assert_eq!(
+            render(&values, &helps(), Format::Toml),
+            "# Name\n# of the app\nname = \"a \\\"b\\\"\"\n\ntags = [\"x\", \"y\"]\n\n\"my key\" = 1\n"
+        );
+    }
+
+    #[test]
+    fn renders_yaml_with_comments() {
+        let values = json!({"name": "app", "tags": [], "nested": {"a": true}});
+
+        assert_eq!(
+            render(&values, &helps(), Format::Yaml),
+            "# Name\n# of the app\nname: \"app\"\n\ntags: []\n\nnested:\n  a: true\n"
+        );
+    }
+
+    #[test]
+    fn helps_of_grouped_fields_comment_nested_entries() {
+        let values = json!({"server": {"port": 80, "host": "h"}, "name": "app"});
+        let helps = vec![
+            ("server.port".to_string(), "Listened port".to_string()),
+            ("server.host".to_string(), "".to_string()),
+            ("name".to_string(), "".to_string()),
+        ];
+
+        assert_eq!(
+            render(&values, &helps, Format::Toml),
+            "name = \"app\"\n\n[server]\n# Listened port\nport = 80\n\nhost = \"h\"\n"
+        );
+        assert_eq!(
+            render(&values, &helps, Format::Yaml),
+            "server:\n  # Listened port\n  port: 80\n\n  host: \"h\"\n\nname: \"app\"\n"
+        );
+        let yaml = render(&values, &helps, Format::Yaml);
+        assert_eq!(
+            entries(&yaml, Format::Yaml)[0],
+            ("server.port".to_string(), "80".to_string())
+        );
+    }
+
+    #[test]
+    fn renders_json_without_comments() {
+        let values = json!({"tags": ["x"], "name": "app"});
+
+        assert_eq!(
+            render(&values, &helps(), Format::Json),
+            "{\n  \"tags\": [\n    \"x\"\n  ],\n  \"name\": \"app\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn entries_are_found_in_toml() {
+        let text = "# comment\nname = \"app\"\n\n[table]\nkey = 1\n\"my key\" = [1, 2]\n";
+
+        assert_eq!(
+            entries(text, Format::Toml),
+            vec![
+                ("name".to_string(), "\"app\"".to_string()),
+                ("table.key".to_string(), "1".to_string()),
+                ("table.\"my key\"".to_string(), "[1, 2]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_old_entries_missing_in_new_values() {
+        let old = "name = \"old\"\nkeep = true\n";
+
+        let merged = merge(old, &json!({"name": "new"}), &[], Format::Toml).unwrap();
+
+        assert_eq!(merged, "name = \"new\"\n\nkeep = true\n");
+    }
+
+    #[test]
+    fn merge_keeps_keys_in_their_tables() {
+        let old = "name = \"old\"\n\n[server]\nport = 1\nhost = \"a\"\n";
+
+        let merged = merge(old, &json!({"server": {"port": 2}}), &[], Format::Toml).unwrap();
+
+        assert_eq!(
+            merged,
+            "name = \"old\"\n\n[server]\nport = 2\n\nhost = \"a\"\n"
+        );
+
+        assert_eq!(
+            entries(&merged, Format::Toml),
+            vec![
+                ("name".to_string(), "\"old\"".to_string()),
+                ("server.port".to_string(), "2".to_string()),
+                ("server.host".to_string(), "\"a\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_are_found_in_nested_yaml() {
+        let text = "name: app\nserver:\n  port: 1\n  hosts:\n    - a\n    - b\n";
+
+        assert_eq!(
+            entries(text, Format::Yaml),
+            vec![
+                ("name".to_string(), "\"app\"".to_string()),
+                ("server.port".to_string(), "1".to_string()),
+                ("server.hosts".to_string(), "[\"a\", \"b\"]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn overwrite_is_asked_through_lines() {
+        let mut output = Vec::new();
+
+        let decision = ask_overwrite(
+            "name = \"old\"\n",
+            &json!({"name": "new"}),
+            &[],
+            Format::Toml,
+            "x\nm\n".as_bytes(),
+            &mut output,
+        );
+
+        assert_eq!(decision.unwrap(), Overwrite::Merge);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("- name = \"old\"\n  + name = \"new\""));
+        assert_eq!(output.matches("[o]verwrite").count(), 2);
+    }
+
+    #[test]
+    fn merge_keeps_multiline_strings_and_comments_of_toml() {
+        let old = "# Project config\n\n# Written by hand\ndesc = \"\"\"\nline one\n# line two = \"x\"\nline two = \"x\"\n\"\"\"\n\n# Server\n[server]\nport = 1\n";
+
+        let merged = merge(old, &json!({"name": "new"}), &[], Format::Toml).unwrap();
+
+        assert_eq!(
+            merged,
+            "# Project config\n\nname = \"new\"\n\n# Written by hand\ndesc = \"line one\\n# line two = \\\"x\\\"\\nline two = \\\"x\\\"\\n\"\n\n# Server\n[server]\nport = 1\n"
+        );
+        assert_eq!(
+            toml::from_str::<toml::Value>(&merged).unwrap()["desc"].as_str(),
+            Some("line one\n# line two = \"x\"\nline two = \"x\"\n")
+        );
+    }
+
+    #[test]
+    fn merge_keeps_block_scalars_and_comments_of_yaml() {
+        let old = "# Commands run\nscript: |\n  echo hi\n  # kept\n  echo: there\nname: old\n";
+
+        let merged = merge(old, &json!({"name": "new"}), &[], Format::Yaml).unwrap();
+
+        assert_eq!(
+            merged,
+            "name: \"new\"\n\n# Commands run\nscript: \"echo hi\\n# kept\\necho: there\\n\"\n"
+        );
+        assert_eq!(
+            serde_yaml::from_str::<Value>(&merged).unwrap(),
+            json!({"name": "new", "script": "echo hi\n# kept\necho: there\n"})
+        );
+    }
+
+    #[test]
+    fn merge_refuses_config_which_cant_be_parsed() {
+        let old = r#"{"name": "old", "keep": 1,}"#;
+
+        assert!(merge(old, &json!({"name": "new"}), &[], Format::Json).is_err());
+        assert!(merge("a = [", &json!({}), &[], Format::Toml).is_err());
+        assert!(merge("a: [", &json!({}), &[], Format::Yaml).is_err());
+
+        let mut output = Vec::new();
+        let decision = ask_overwrite(
+            old,
+            &json!({"name": "new"}),
+            &[],
+            Format::Json,
+            "m\nc\n".as_bytes(),
+            &mut output,
+        );
+
+        assert_eq!(decision.unwrap(), Overwrite::Cancel);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Config file can't be merged"));
+        assert_eq!(output.matches("[o]verwrite or [c]ancel").count(), 2);
+    }
+
+    #[test]
+    fn merge_json_overrides_old_keys() {
+        let old = r#"{"name": "old", "keep": 1}"#;
+
+        let merged = merge(old, &json!({"name": "new"}), &[], Format::Json).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&merged).unwrap(),
+            json!({"name": "new", "keep": 1})
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Details of running action passed to its handler, see [Fui::action_with_context].
+//!
+//! [Fui::action_with_context]: ../struct.Fui.html#method.action_with_context
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use cursive::CbSink;
+use serde_json::value::Value;
+use signal_hook::consts::SIGINT;
+use signal_hook::flag;
+use signal_hook::low_level;
+use signal_hook::SigId;
+
+/// What handler of action gets when it's run.
+pub struct ActionContext {
+    /// Name of the running action.
+    pub action_name: String,
+    /// Values of action's form, in order its fields were declared.
+    pub values: Value,
+    /// Sink of TUI (if it's used) for posting UI updates.
+    ///
+    /// The sink isn't drained while handler runs: posted callbacks run only when TUI
+    /// runs again (e.g. with form of the next action) and are dropped if it doesn't.
+    pub cursive_sink: Option<CbSink>,
+    /// Tells if user asked to stop the handler.
+    pub cancellation: Cancellation,
+    /// Logs messages of the handler.
+    pub logger: Logger,
+}
+
+/// Flag telling handler that user asked to stop it (by pressing `Ctrl+c`).
+///
+/// Handlers which check it can stop gracefully, the second `Ctrl+c` ends program at once.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Cancellation {
+    /// Creates a new not cancelled `Cancellation`.
+    pub fn new() -> Self {
+        Cancellation::default()
+    }
+
+    /// Creates a new `Cancellation` which is cancelled by `Ctrl+c` (`SIGINT`)
+    /// until returned [InterruptGuard] is dropped.
+    ///
+    /// [InterruptGuard]: struct.InterruptGuard.html
+    pub(crate) fn on_interrupt() -> (Self, InterruptGuard) {
+        let cancellation = Cancellation::new();
+        let guard = InterruptGuard::new(&cancellation.cancelled);
+        (cancellation, guard)
+    }
+
+    /// Asks handler to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks if handler was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Keeps `Ctrl+c` cancelling [Cancellation] instead of ending program, while it lives.
+///
+/// [Cancellation]: struct.Cancellation.html
+pub(crate) struct InterruptGuard {
+    ids: Vec<SigId>,
+}
+
+impl InterruptGuard {
+    fn new(cancelled: &Arc<AtomicBool>) -> Self {
+        idle_interrupts().store(false, Ordering::SeqCst);
+        // shutdown must be registered first, so it's armed by the first interrupt
+        let shutdown = flag::register_conditional_shutdown(SIGINT, 130, Arc::clone(cancelled));
+        let cancel = flag::register(SIGINT, Arc::clone(cancelled));
+        InterruptGuard {
+            ids: shutdown.into_iter().chain(cancel).collect(),
+        }
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        for id in self.ids.drain(..) {
+            low_level::unregister(id);
+        }
+        idle_interrupts().store(true, Ordering::SeqCst);
+    }
+}
+
+/// Flag telling that no [InterruptGuard] lives, so `Ctrl+c` ends program as usual.
+///
+/// Unregistering actions doesn't restore the default handler of the signal,
+/// so it's emulated while the flag is set.
+///
+/// [InterruptGuard]: struct.InterruptGuard.html
+fn idle_interrupts() -> &'static Arc<AtomicBool> {
+    static IDLE: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+    IDLE.get_or_init(|| {
+        let idle = Arc::new(AtomicBool::new(true));
+        let _ = flag::register_conditional_default(SIGINT, Arc::clone(&idle));
+        idle
+    })
+}
+
+/// Writes handler's messages to stderr, prefixed with name of its action.
+#[derive(Clone, Debug)]
+pub struct Logger {
+    action_name: String,
+}
+
+impl Logger {
+    /// Creates a new `Logger` of action named `action_name`.
+    pub fn new<S: Into<String>>(action_name: S) -> Self {
+        Logger {
+            action_name: action_name.into(),
+        }
+    }
+
+    fn line(&self, level: &str, message: &str) -> String {
+        format!("{} [{}] {}", self.action_name, level, message)
+    }
+
+    /// Logs informative `message`.
+    pub fn info(&self, message: &str) {
+        eprintln!("{}", self.line("info", message));
+    }
+
+    /// Logs `message` about something unexpected.
+    pub fn warn(&self, message: &str) {
+        eprintln!("{}", self.line("warn", message));
+    }
+
+    /// Logs `message` about failure.
+    pub fn error(&self, message: &str) {
+        eprintln!("{}", self.line("error", message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancellation_is_shared_by_clones() {
+        let cancellation = Cancellation::new();
+        let clone = cancellation.clone();
+
+        clone.cancel();
+
+        assert!(cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn interrupts_are_handled_only_while_guard_lives() {
+        let (cancellation, guard) = Cancellation::on_interrupt();
+        assert!(!idle_interrupts().load(Ordering::SeqCst));
+        assert!(!cancellation.is_cancelled());
+
+        drop(guard);
+
+        assert!(idle_interrupts().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn logger_prefixes_lines_with_action() {
+        assert_eq!(Logger::new("copy").line("warn", "slow"), "copy [warn] slow");
+    }
+}
@@ -0,0 +1,441 @@
+//! Parses and describes cron expressions, used by [fields::Cron].
+//!
+//! Expressions have five fields: minute, hour, day of month, month and day of week.
+//! Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`), steps (`*/10`, `8-18/2`)
+//! and names of months (`jan`) and days (`mon`). Macros like `@daily` are supported too.
+//!
+//! ```
+//! use fui::cron::Schedule;
+//!
+//! let schedule = Schedule::parse("30 9 * * mon-fri").unwrap();
+//! assert_eq!(schedule.describe(), "At 09:30, on Monday through Friday");
+//! assert!(Schedule::parse("61 * * * *").is_err());
+//! ```
+//!
+//! [fields::Cron]: ../fields/struct.Cron.html
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use utils::civil_from_days;
+
+const MONTHS: &[&str] = &[
+    "",
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: &[&str] = &[
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MACROS: &[(&str, &str)] = &[
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+/// How far next runs are searched for (covers leap days).
+const SEARCH_DAYS: i64 = 366 * 8;
+
+/// Allowed values of a cron field.
+struct Spec {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: Spec = Spec {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Spec = Spec {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY: Spec = Spec {
+    name: "day",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Spec = Spec {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: MONTHS,
+};
+const WEEKDAY: Spec = Spec {
+    name: "day of week",
+    min: 0,
+    max: 7,
+    names: WEEKDAYS,
+};
+
+/// Parsed cron expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    fields: Vec<String>,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+}
+
+impl Schedule {
+    /// Parses cron `expression`.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let expanded = MACROS
+            .iter()
+            .find(|(name, _)| *name == expression)
+            .map_or(expression, |(_, expanded)| *expanded);
+        let fields: Vec<String> = expanded
+            .split_whitespace()
+            .map(|x| x.to_lowercase())
+            .collect();
+        if fields.len() != 5 {
+            return Err(format!("Expected 5 fields, got {}", fields.len()));
+        }
+        let mut weekdays = parse_field(&fields[4], &WEEKDAY)?;
+        if weekdays & (1 << 7) != 0 {
+            // both 0 & 7 mean Sunday
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Schedule {
+            minutes: parse_field(&fields[0], &MINUTE)?,
+            hours: parse_field(&fields[1], &HOUR)?,
+            days: parse_field(&fields[2], &DAY)?,
+            months: parse_field(&fields[3], &MONTH)?,
+            weekdays,
+            fields,
+        })
+    }
+
+    /// Describes schedule in English.
+    pub fn describe(&self) -> String {
+        let (minute, hour) = (&self.fields[0], &self.fields[1]);
+        let mut text = match (number(minute), number(hour)) {
+            (Some(m), Some(h)) => format!("At {:02}:{:02}", h, m),
+            (Some(m), None) if hour == "*" => format!("At minute {} past every hour", m),
+            _ if minute == "*" && hour == "*" => "Every minute".to_string(),
+            _ if hour == "*" => capitalize(&describe_field(minute, &MINUTE)),
+            _ => format!(
+                "At {} past {}",
+                describe_field(minute, &MINUTE),
+                describe_field(hour, &HOUR)
+            ),
+        };
+        if self.fields[2] != "*" {
+            text += &format!(
+                ", on {} of the month",
+                describe_field(&self.fields[2], &DAY)
+            );
+        }
+        if self.fields[4] != "*" {
+            let joint = if self.fields[2] != "*" { " and" } else { "" };
+            text += &format!(
+                "{}, on {}",
+                joint,
+                describe_field(&self.fields[4], &WEEKDAY)
+            );
+        }
+        if self.fields[3] != "*" {
+            text += &format!(", in {}", describe_field(&self.fields[3], &MONTH));
+        }
+        text
+    }
+
+    /// Gets `count` next times (in UTC) matching schedule after `after`.
+    pub fn next_runs(&self, after: SystemTime, count: usize) -> Vec<SystemTime> {
+        let mut runs = Vec::new();
+        let secs = after
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        // the next full minute
+        let start_minute = secs / 60 + 1;
+        let start_day = (start_minute / 1440) as i64;
+        for day in start_day..start_day + SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            for minute_of_day in 0..1440 {
+                let minute = day as u64 * 1440 + minute_of_day;
+                if minute < start_minute || !self.matches_minute(minute_of_day as u32) {
+                    continue;
+                }
+                runs.push(UNIX_EPOCH + Duration::from_secs(minute * 60));
+                if runs.len() == count {
+                    return runs;
+                }
+            }
+        }
+        runs
+    }
+
+    fn matches_day(&self, day: i64) -> bool {
+        let (_, month, day_of_month) = civil_from_days(day);
+        // 1970-01-01 was Thursday
+        let weekday = (day + 4).rem_euclid(7) as u32;
+        if !has(self.months, month) {
+            return false;
+        }
+        let day_matches = has(self.days, day_of_month);
+        let weekday_matches = has(self.weekdays, weekday);
+        // when both are restricted, matching either of them is enough
+        match (self.fields[2] == "*", self.fields[4] == "*") {
+            (false, false) => day_matches || weekday_matches,
+            _ => day_matches && weekday_matches,
+        }
+    }
+
+    fn matches_minute(&self, minute_of_day: u32) -> bool {
+        has(self.hours, minute_of_day / 60) && has(self.minutes, minute_of_day % 60)
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schedule::parse(s)
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn number(field: &str) -> Option<u32> {
+    field.parse().ok()
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Parses value of `spec`, which can be a number or a name.
+fn parse_value(text: &str, spec: &Spec) -> Result<u32, String> {
+    let value = match spec
+        .names
+        .iter()
+        .position(|name| !name.is_empty() && name[..3].eq_ignore_ascii_case(text))
+    {
+        Some(idx) => idx as u32,
+        None => text
+            .parse()
+            .map_err(|_| format!("Invalid {} {:?}", spec.name, text))?,
+    };
+    if value < spec.min || value > spec.max {
+        return Err(format!(
+            "{} must be between {} and {}",
+            capitalize(spec.name),
+            spec.min,
+            spec.max
+        ));
+    }
+    Ok(value)
+}
+
+/// Parses cron `field` to set of its values.
+fn parse_field(field: &str, spec: &Spec) -> Result<u64, String> {
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.find('/') {
+            Some(idx) => {
+                let step: u32 = part[idx + 1..]
+                    .parse()
+                    .map_err(|_| format!("Invalid step {:?}", &part[idx + 1..]))?;
+                if step == 0 {
+                    return Err("Step must be greater than 0".to_string());
+                }
+                (&part[..idx], step)
+            }
+            None => (part, 1),
+        };
+        let (from, to) = if range == "*" {
+            (spec.min, spec.max)
+        } else if let Some(idx) = range.find('-') {
+            (
+                parse_value(&range[..idx], spec)?,
+                parse_value(&range[idx + 1..], spec)?,
+            )
+        } else {
+            let value = parse_value(range, spec)?;
+            // `5/15` means from 5 to the end
+            let to = if step > 1 { spec.max } else { value };
+            (value, to)
+        };
+        if from > to {
+            return Err(format!("Invalid range {:?}", range));
+        }
+        for value in (from..=to).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// Names value of `spec` for description.
+fn value_name(text: &str, spec: &Spec) -> String {
+    match parse_value(text, spec) {
+        // 7 is Sunday too
+        Ok(value) if !spec.names.is_empty() => {
+            spec.names[value as usize % spec.names.len()].to_string()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Describes cron `field` like "every 15 minutes" or "hour 9 through 17".
+fn describe_field(field: &str, spec: &Spec) -> String {
+    let parts: Vec<String> = field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.find('/') {
+                Some(idx) => (&part[..idx], Some(&part[idx + 1..])),
+                None => (part, None),
+            };
+            let range = match range.find('-') {
+                Some(idx) => format!(
+                    "{} through {}",
+                    value_name(&range[..idx], spec),
+                    value_name(&range[idx + 1..], spec)
+                ),
+                None if range == "*" => String::new(),
+                None => value_name(range, spec),
+            };
+            match step {
+                Some(step) if range.is_empty() => format!("every {} {}s", step, spec.name),
+                Some(step) => format!("every {} {}s from {}", step, spec.name, range),
+                None if range.is_empty() => format!("every {}", spec.name),
+                None if spec.names.is_empty() => format!("{} {}", spec.name, range),
+                None => range,
+            }
+        })
+        .collect();
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => parts.join(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::format_rfc3339;
+
+    fn runs(expression: &str, after: &str) -> Vec<String> {
+        // `after` is "YYYY-MM-DD" (UTC midnight)
+        let days = (0..40000)
+            .find(|&d| {
+                let (y, m, day) = civil_from_days(d);
+                format!("{:04}-{:02}-{:02}", y, m, day) == after
+            })
+            .unwrap();
+        let after = UNIX_EPOCH + Duration::from_secs(days as u64 * 86400);
+        Schedule::parse(expression)
+            .unwrap()
+            .next_runs(after, 3)
+            .into_iter()
+            .map(format_rfc3339)
+            .collect()
+    }
+
+    #[test]
+    fn next_runs_are_found() {
+        assert_eq!(
+            runs("*/20 * * * *", "2024-01-01"),
+            vec![
+                "2024-01-01T00:20:00Z",
+                "2024-01-01T00:40:00Z",
+                "2024-01-01T01:00:00Z"
+            ]
+        );
+        // 2024-01-01 was Monday
+        assert_eq!(
+            runs("0 9 * * sat,sun", "2024-01-01"),
+            vec![
+                "2024-01-06T09:00:00Z",
+                "2024-01-07T09:00:00Z",
+                "2024-01-13T09:00:00Z"
+            ]
+        );
+        assert_eq!(
+            runs("@yearly", "2024-06-01"),
+            vec![
+                "2025-01-01T00:00:00Z",
+                "2026-01-01T00:00:00Z",
+                "2027-01-01T00:00:00Z"
+            ]
+        );
+        assert_eq!(runs("0 0 29 2 *", "2024-03-01")[0], "2028-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn day_of_month_or_week_is_enough_when_both_are_set() {
+        // 2024-01-01 was Monday, so the 13th was Saturday
+        assert_eq!(
+            runs("0 0 13 * 1", "2024-01-02"),
+            vec![
+                "2024-01-08T00:00:00Z",
+                "2024-01-13T00:00:00Z",
+                "2024-01-15T00:00:00Z"
+            ]
+        );
+    }
+
+    #[test]
+    fn schedules_are_described() {
+        let describe = |e: &str| Schedule::parse(e).unwrap().describe();
+        assert_eq!(describe("* * * * *"), "Every minute");
+        assert_eq!(describe("*/15 * * * *"), "Every 15 minutes");
+        assert_eq!(describe("5 * * * *"), "At minute 5 past every hour");
+        assert_eq!(
+            describe("0,30 9-17 * * *"),
+            "At minute 0 and minute 30 past hour 9 through 17"
+        );
+        assert_eq!(
+            describe("0 0 1 jan,jul *"),
+            "At 00:00, on day 1 of the month, in January and July"
+        );
+        assert_eq!(describe("@weekly"), "At 00:00, on Sunday");
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        let error = |e: &str| Schedule::parse(e).unwrap_err();
+        assert_eq!(error("* * *"), "Expected 5 fields, got 3");
+        assert_eq!(error("60 * * * *"), "Minute must be between 0 and 59");
+        assert_eq!(error("* * * foo *"), "Invalid month \"foo\"");
+        assert_eq!(error("*/0 * * * *"), "Step must be greater than 0");
+        assert_eq!(error("* 5-2 * * *"), "Invalid range \"5-2\"");
+    }
+}
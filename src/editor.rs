@@ -0,0 +1,209 @@
+//! Running external programs for fields: editor (`$VISUAL` or `$EDITOR`) of values
+//! and pickers (like `fzf`) of paths.
+//!
+//! Views can't run programs themselves while TUI owns the terminal, so they
+//! [request_edit] or [request_pick] & quit TUI, which runs the program ([run_pending])
+//! and restarts.
+
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+use cursive::Cursive;
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+const DEFAULT_EDITOR: &str = "vi";
+/// Placeholder in picker's command replaced with path of file where the picker saves choice.
+const CHOICE_FILE: &str = "{file}";
+
+/// Program waiting to be run & place where its result is put.
+enum Request {
+    Edit {
+        text: String,
+        extension: &'static str,
+        result: Rc<RefCell<Option<String>>>,
+    },
+    Pick {
+        command: String,
+        result: Rc<RefCell<Option<String>>>,
+    },
+}
+
+thread_local! {
+    static PENDING: RefCell<Option<Request>> = const { RefCell::new(None) };
+}
+
+fn request(siv: &mut Cursive, request: Request) {
+    PENDING.with(|p| *p.borrow_mut() = Some(request));
+    siv.quit();
+}
+
+/// Asks for editing `text` in external editor (in file with `extension`, so
+/// the editor can e.g. highlight syntax), the result is put to `edited`.
+///
+/// TUI is stopped, so the editor is run when control gets back to [run_pending].
+pub(crate) fn request_edit(
+    siv: &mut Cursive,
+    text: String,
+    extension: &'static str,
+    edited: Rc<RefCell<Option<String>>>,
+) {
+    request(
+        siv,
+        Request::Edit {
+            text,
+            extension,
+            result: edited,
+        },
+    );
+}
+
+/// Asks for picking a path with external `command`, the path is put to `picked`.
+///
+/// Picked path is read from the command's output (like `fzf` prints it) or from
+/// file replacing [CHOICE_FILE] in the command (like `ranger --choosefile={file}`).
+///
+/// TUI is stopped, so the picker is run when control gets back to [run_pending].
+pub(crate) fn request_pick(
+    siv: &mut Cursive,
+    command: String,
+    picked: Rc<RefCell<Option<String>>>,
+) {
+    request(
+        siv,
+        Request::Pick {
+            command,
+            result: picked,
+        },
+    );
+}
+
+/// Runs program for the pending request if there's any, returning `false` otherwise.
+///
+/// Result is left unset when the program fails.
+pub(crate) fn run_pending() -> bool {
+    let request = match PENDING.with(|p| p.borrow_mut().take()) {
+        Some(request) => request,
+        None => return false,
+    };
+    match request {
+        Request::Edit {
+            text,
+            extension,
+            result,
+        } => match edit(&text, extension) {
+            Ok(text) => *result.borrow_mut() = Some(text),
+            Err(e) => eprintln!("Editor failed: {}", e),
+        },
+        Request::Pick { command, result } => match pick(&command) {
+            Ok(Some(path)) => *result.borrow_mut() = Some(path),
+            Ok(None) => (),
+            Err(e) => eprintln!("Picker failed: {}", e),
+        },
+    }
+    true
+}
+
+/// Gets command (possibly with arguments, like `code --wait`) of user's editor.
+fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|cmd| !cmd.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Builds command from its text with arguments separated by whitespaces.
+fn command(text: &str, choice_file: &Path) -> io::Result<Command> {
+    let mut parts = text
+        .split_whitespace()
+        .map(|p| p.replace(CHOICE_FILE, &choice_file.to_string_lossy()));
+    let program = parts
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Command is empty"))?;
+    let mut command = Command::new(program);
+    command.args(parts);
+    Ok(command)
+}
+
+/// Edits `text` in user's editor through temporary file, returning the edited text.
+fn edit(text: &str, extension: &str) -> io::Result<String> {
+    let path = env::temp_dir().join(format!("fui-{}.{}", ::std::process::id(), extension));
+    fs::write(&path, text)?;
+    let edited = command(&editor_command(), &path).and_then(|mut cmd| {
+        let status = cmd.arg(&path).status()?;
+        if status.success() {
+            fs::read_to_string(&path)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Editor exited with {}", status),
+            ))
+        }
+    });
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+/// Runs picker `command`, returning the picked path (unless picking was cancelled).
+fn pick(command_text: &str) -> io::Result<Option<String>> {
+    let path = env::temp_dir().join(format!("fui-{}.choice", ::std::process::id()));
+    let _ = fs::remove_file(&path);
+    // status is ignored, pickers exit with failure when nothing is picked
+    let output = command(command_text, &path)?
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    let choice = if command_text.contains(CHOICE_FILE) {
+        let choice = fs::read_to_string(&path).unwrap_or_default();
+        let _ = fs::remove_file(&path);
+        choice
+    } else {
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    let picked = choice
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string);
+    Ok(picked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_text_is_edited() {
+        assert!(!run_pending());
+
+        // `true` leaves the file as it is, so the text comes back unchanged
+        env::set_var("VISUAL", "true");
+        let edited = Rc::new(RefCell::new(None));
+        let request = Request::Edit {
+            text: "{\"a\": 1}".to_string(),
+            extension: "json",
+            result: Rc::clone(&edited),
+        };
+        PENDING.with(|p| *p.borrow_mut() = Some(request));
+        assert!(run_pending());
+        assert_eq!(*edited.borrow(), Some("{\"a\": 1}".to_string()));
+        assert!(!run_pending());
+        env::remove_var("VISUAL");
+    }
+
+    #[test]
+    fn picked_path_is_read_from_output_or_file() {
+        assert_eq!(
+            pick("echo /tmp/picked").unwrap(),
+            Some("/tmp/picked".to_string())
+        );
+        assert_eq!(pick("true").unwrap(), None);
+        assert_eq!(pick("touch {file}").unwrap(), None);
+        assert!(pick("fui-missing-picker").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Contexts in which a composed command runs.
+//!
+//! [ExecutionTarget::field] lets user pick the context in a form,
+//! then handler runs the command with [ExecutionTarget::run].
+//!
+//! `Ssh` & `Docker` targets require `remote-execution` feature.
+//!
+//! [ExecutionTarget::field]: enum.ExecutionTarget.html#method.field
+//! [ExecutionTarget::run]: enum.ExecutionTarget.html#method.run
+use std::fmt;
+use std::io;
+use std::process::{Command, ExitStatus};
+use std::str::FromStr;
+
+use fields::autocomplete::AutocompleteManager;
+use fields::{Autocomplete, Field};
+use validators::{OneOf, Required};
+
+/// Context in which a command runs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecutionTarget {
+    /// Current machine.
+    Local,
+    /// Remote host reached with `ssh`.
+    #[cfg(feature = "remote-execution")]
+    Ssh(String),
+    /// Running container reached with `docker exec`.
+    #[cfg(feature = "remote-execution")]
+    Docker(String),
+}
+
+impl ExecutionTarget {
+    /// Builds a field picking one of `targets`, its value can be parsed back with `from_str`.
+    pub fn field<IS: Into<String>>(
+        label: IS,
+        targets: &[ExecutionTarget],
+    ) -> Field<AutocompleteManager, String> {
+        let options: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+        let initial = options.first().cloned().unwrap_or_default();
+        Autocomplete::new(label, options.clone())
+            .initial(initial)
+            .validator(Required)
+            .validator(OneOf(options))
+    }
+
+    /// Builds command running `program` with `args` in this context.
+    pub fn command(&self, program: &str, args: &[String]) -> Command {
+        match self {
+            ExecutionTarget::Local => {
+                let mut cmd = Command::new(program);
+                cmd.args(args);
+                cmd
+            }
+            #[cfg(feature = "remote-execution")]
+            ExecutionTarget::Ssh(host) => {
+                // remote shell gets a single string, so args must be quoted
+                let remote: Vec<String> = Some(program.to_string())
+                    .iter()
+                    .chain(args.iter())
+                    .map(|a| shell_quote(a))
+                    .collect();
+                let mut cmd = Command::new("ssh");
+                cmd.arg(host).arg("--").arg(remote.join(" "));
+                cmd
+            }
+            #[cfg(feature = "remote-execution")]
+            ExecutionTarget::Docker(container) => {
+                let mut cmd = Command::new("docker");
+                cmd.arg("exec").arg(container).arg(program).args(args);
+                cmd
+            }
+        }
+    }
+
+    /// Runs `program` with `args` in this context, its output goes to the current terminal.
+    pub fn run(&self, program: &str, args: &[String]) -> io::Result<ExitStatus> {
+        self.command(program, args).status()
+    }
+}
+
+impl fmt::Display for ExecutionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecutionTarget::Local => write!(f, "local"),
+            #[cfg(feature = "remote-execution")]
+            ExecutionTarget::Ssh(host) => write!(f, "ssh:{}", host),
+            #[cfg(feature = "remote-execution")]
+            ExecutionTarget::Docker(container) => write!(f, "docker:{}", container),
+        }
+    }
+}
+
+impl FromStr for ExecutionTarget {
+    type Err = String;
+
+    /// Parses `local`, `ssh:<host>` or `docker:<container>`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.split_once(':') {
+            None if text == "local" => Ok(ExecutionTarget::Local),
+            #[cfg(feature = "remote-execution")]
+            Some(("ssh", host)) if !host.is_empty() => Ok(ExecutionTarget::Ssh(host.to_string())),
+            #[cfg(feature = "remote-execution")]
+            Some(("docker", container)) if !container.is_empty() => {
+                Ok(ExecutionTarget::Docker(container.to_string()))
+            }
+            _ => Err(format!("Unknown execution target: {:?}", text)),
+        }
+    }
+}
+
+/// Quotes `arg` for POSIX shell.
+#[cfg(feature = "remote-execution")]
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Vec<String> {
+        vec!["-la".to_string(), "it's here".to_string()]
+    }
+
+    #[test]
+    fn local_runs_program_directly() {
+        let cmd = ExecutionTarget::Local.command("ls", &args());
+
+        assert_eq!(cmd.get_program(), "ls");
+        assert_eq!(cmd.get_args().collect::<Vec<_>>(), vec!["-la", "it's here"]);
+    }
+
+    #[test]
+    fn target_round_trips_through_string() {
+        assert_eq!("local".parse(), Ok(ExecutionTarget::Local));
+        assert_eq!(ExecutionTarget::Local.to_string(), "local");
+        assert!("mars:base".parse::<ExecutionTarget>().is_err());
+    }
+
+    #[cfg(feature = "remote-execution")]
+    #[test]
+    fn ssh_quotes_remote_command() {
+        let cmd = ExecutionTarget::Ssh("host".to_string()).command("ls", &args());
+
+        assert_eq!(cmd.get_program(), "ssh");
+        assert_eq!(
+            cmd.get_args().collect::<Vec<_>>(),
+            vec!["host", "--", "'ls' '-la' 'it'\\''s here'"]
+        );
+    }
+
+    #[cfg(feature = "remote-execution")]
+    #[test]
+    fn docker_execs_in_container() {
+        let target: ExecutionTarget = "docker:web".parse().unwrap();
+        let cmd = target.command("ls", &args());
+
+        assert_eq!(cmd.get_program(), "docker");
+        assert_eq!(
+            cmd.get_args().collect::<Vec<_>>(),
+            vec!["exec", "web", "ls", "-la", "it's here"]
+        );
+    }
+}
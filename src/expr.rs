@@ -0,0 +1,210 @@
+//! Evaluates simple arithmetic expressions with size and time units,
+//! used by [fields::Expr].
+//!
+//! Supported are `+`, `-`, `*`, `/`, `%`, parentheses and units following a number
+//! (or parentheses):
+//!
+//! * sizes: `B`, `KB`, `MB`, `GB`, `TB` (powers of 1000) and `KiB`, `MiB`, `GiB`, `TiB`
+//!   (powers of 1024), evaluated to bytes
+//! * time: `ms`, `s`, `m`, `h`, `d`, `w`, evaluated to seconds
+//!
+//! ```
+//! use fui::expr::eval;
+//!
+//! assert_eq!(eval("3*1024"), Ok(3072.0));
+//! assert_eq!(eval("2h+30m"), Ok(9000.0));
+//! assert_eq!(eval("1.5 GiB"), Ok(1610612736.0));
+//! assert!(eval("2 +").is_err());
+//! ```
+//!
+//! [fields::Expr]: ../fields/struct.Expr.html
+use serde_json::value::Value;
+use std::iter::Peekable;
+use std::str::Chars;
+
+const UNITS: &[(&str, f64)] = &[
+    ("B", 1.0),
+    ("KB", 1e3),
+    ("MB", 1e6),
+    ("GB", 1e9),
+    ("TB", 1e12),
+    ("KiB", 1024.0),
+    ("MiB", 1048576.0),
+    ("GiB", 1073741824.0),
+    ("TiB", 1099511627776.0),
+    ("ms", 0.001),
+    ("s", 1.0),
+    ("m", 60.0),
+    ("h", 3600.0),
+    ("d", 86400.0),
+    ("w", 604800.0),
+];
+
+/// Evaluates `text` to a number.
+pub fn eval(text: &str) -> Result<f64, String> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+    };
+    let value = parser.expr()?;
+    parser.skip_spaces();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some(c) => Err(format!("Unexpected {:?}", c)),
+    }
+}
+
+/// Converts evaluated `number` to JSON, whole numbers become integers.
+pub(crate) fn to_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        json!(number as i64)
+    } else {
+        json!(number)
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_spaces(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    /// Takes next operator if it's one of `ops`.
+    fn operator(&mut self, ops: &str) -> Option<char> {
+        self.skip_spaces();
+        let op = *self.chars.peek()?;
+        if ops.contains(op) {
+            self.chars.next();
+            Some(op)
+        } else {
+            None
+        }
+    }
+
+    fn expr(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(op) = self.operator("+-") {
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.factor()?;
+        while let Some(op) = self.operator("*/%") {
+            let rhs = self.factor()?;
+            if op != '*' && rhs == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            value = match op {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f64, String> {
+        if let Some(op) = self.operator("+-") {
+            let value = self.factor()?;
+            return Ok(if op == '-' { -value } else { value });
+        }
+        let value = if self.operator("(").is_some() {
+            let value = self.expr()?;
+            if self.operator(")").is_none() {
+                return Err("Missing `)`".to_string());
+            }
+            value
+        } else {
+            self.number()?
+        };
+        Ok(value * self.unit()?)
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.skip_spaces();
+        let mut digits = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_digit() && c != '.' && c != '_' {
+                break;
+            }
+            if c != '_' {
+                digits.push(c);
+            }
+            self.chars.next();
+        }
+        if digits.is_empty() {
+            return match self.chars.peek() {
+                Some(c) => Err(format!("Expected number, got {:?}", c)),
+                None => Err("Expected number".to_string()),
+            };
+        }
+        digits
+            .parse()
+            .map_err(|_| format!("Invalid number {:?}", digits))
+    }
+
+    /// Gets multiplier of unit following a number, `1` if there is none.
+    fn unit(&mut self) -> Result<f64, String> {
+        self.skip_spaces();
+        let mut unit = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_alphabetic() {
+                break;
+            }
+            unit.push(c);
+            self.chars.next();
+        }
+        if unit.is_empty() {
+            return Ok(1.0);
+        }
+        UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, multiplier)| *multiplier)
+            .ok_or_else(|| format!("Unknown unit {:?}", unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_follow_precedence() {
+        assert_eq!(eval("2+3*4"), Ok(14.0));
+        assert_eq!(eval("(2+3)*4"), Ok(20.0));
+        assert_eq!(eval("-2 * -(1 + 1)"), Ok(4.0));
+        assert_eq!(eval("10 % 4 / 2"), Ok(1.0));
+        assert_eq!(eval("1_000 - 1"), Ok(999.0));
+    }
+
+    #[test]
+    fn units_are_applied() {
+        assert_eq!(eval("512MiB"), Ok(536870912.0));
+        assert_eq!(eval("2KB + 24B"), Ok(2024.0));
+        assert_eq!(eval("(1d - 1h) / 1m"), Ok(1380.0));
+        assert_eq!(eval("250ms"), Ok(0.25));
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        assert_eq!(eval(""), Err("Expected number".to_string()));
+        assert_eq!(eval("2x"), Err("Unknown unit \"x\"".to_string()));
+        assert_eq!(eval("(1+2"), Err("Missing `)`".to_string()));
+        assert_eq!(eval("1/0"), Err("Division by zero".to_string()));
+        assert_eq!(eval("1 2"), Err("Unexpected '2'".to_string()));
+    }
+
+    #[test]
+    fn whole_numbers_become_integers() {
+        assert_eq!(to_value(9000.0), json!(9000));
+        assert_eq!(to_value(0.25), json!(0.25));
+    }
+}
@@ -7,17 +7,139 @@
 //! [Autocomplete]: ../views/struct.Autocomplete.html
 //! [Multiselect]: ../views/struct.Multiselect.html
 
-use dirs;
+#[cfg(feature = "timezones")]
+use chrono_tz;
 use glob::{glob_with, MatchOptions};
+use locales::LOCALES;
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use utils::expand_path;
 
 /// Makes data querable.
 pub trait Feeder: 'static {
     /// Returns data filtered by `text`, `position` limited to `items_count`.
     fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String>;
+    /// Same as [query] but also tells under which group each item is listed,
+    /// so views can show it under a section header (e.g. "Branches", "Tags").
+    ///
+    /// Default implementation lists all items outside of any group.
+    ///
+    /// [query]: #tymethod.query
+    fn query_grouped(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        self.query(text, position, items_count)
+            .into_iter()
+            .map(Suggestion::from)
+            .collect()
+    }
+    /// Same as [query_grouped] but may stop early (returning partial data) once `token`
+    /// gets cancelled, because a newer query superseded it. Used by views querying feeder
+    /// in background (see [Autocomplete::background]).
+    ///
+    /// Default implementation ignores `token`, so long running feeders (walking dirs,
+    /// calling network) should override it.
+    ///
+    /// [query_grouped]: #method.query_grouped
+    /// [Autocomplete::background]: ../views/struct.Autocomplete.html#method.background
+    fn query_cancellable(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        _token: &CancelToken,
+    ) -> Vec<Suggestion> {
+        self.query_grouped(text, position, items_count)
+    }
+    /// Checks if `item` is exactly one of feeder's data.
+    ///
+    /// Default implementation pages through [query] results (up to 10 000 items, stopping
+    /// at short or repeated page), so feeders which can look `item` up directly
+    /// should override it.
+    ///
+    /// [query]: #tymethod.query
+    fn contains(&self, item: &str) -> bool {
+        let page_size = 100;
+        let mut previous = Vec::new();
+        for page_idx in 0..100 {
+            let page = self.query(item, page_idx * page_size, page_size);
+            if page.iter().any(|x| x == item) {
+                return true;
+            }
+            // feeders ignoring position return the same page forever
+            if page.len() < page_size || page == previous {
+                return false;
+            }
+            previous = page;
+        }
+        false
+    }
+    /// Lists items considered for `text` before they're ordered by a [Ranker].
+    ///
+    /// Default implementation returns first 1000 items of [query] results,
+    /// feeders which filter only by exact substring should return all data.
+    ///
+    /// [Ranker]: ../ranking/trait.Ranker.html
+    /// [query]: #tymethod.query
+    fn candidates(&self, text: &str) -> Vec<String> {
+        self.query(text, 0, 1000)
+    }
+    /// Gets directory whose content is suggested for `text`, so views can refresh
+    /// suggestions when it changes (see [Autocomplete::watch]).
+    ///
+    /// [Autocomplete::watch]: ../views/struct.Autocomplete.html#method.watch
+    fn watched_dir(&self, _text: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Item returned by [Feeder::query_grouped].
+///
+/// [Feeder::query_grouped]: trait.Feeder.html#method.query_grouped
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Suggested item.
+    pub value: String,
+    /// Title of the section the item is listed in.
+    pub group: Option<String>,
+}
+
+impl From<String> for Suggestion {
+    fn from(value: String) -> Self {
+        Suggestion { value, group: None }
+    }
+}
+
+/// Tells query of [Feeder::query_cancellable] that its results aren't needed anymore.
+///
+/// ```
+/// # use fui::feeders::CancelToken;
+/// let token = CancelToken::new();
+/// let shared = token.clone();
+/// shared.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+///
+/// [Feeder::query_cancellable]: trait.Feeder.html#method.query_cancellable
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a new not cancelled `CancelToken`.
+    pub fn new() -> Self {
+        CancelToken(Arc::new(AtomicBool::new(false)))
+    }
+    /// Marks query as superseded.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+    /// Checks if query was superseded.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
 }
 
 /// Dummy feeder which does nothing
@@ -29,6 +151,160 @@ impl Feeder for DummyFeeder {
     }
 }
 
+/// Lists data of several feeders, each in its own section.
+///
+/// ```
+/// use fui::feeders::{Feeder, Grouped};
+///
+/// let feeder = Grouped::new()
+///     .group("Branches", vec!["master", "develop"])
+///     .group("Tags", vec!["v1.0", "v1.1"]);
+/// assert_eq!(feeder.query("", 1, 2), vec!["develop", "v1.0"]);
+///
+/// let found = feeder.query_grouped("v1", 0, 1);
+/// assert_eq!(found[0].group, Some("Tags".to_string()));
+/// ```
+#[derive(Clone, Default)]
+pub struct Grouped {
+    groups: Vec<(String, Rc<dyn Feeder>)>,
+}
+
+impl Grouped {
+    /// Creates a new `Grouped` without any section.
+    pub fn new() -> Self {
+        Grouped { groups: Vec::new() }
+    }
+
+    /// Appends section `title` listing data of `feeder`.
+    pub fn group<S: Into<String>, F: Feeder>(mut self, title: S, feeder: F) -> Self {
+        self.groups.push((title.into(), Rc::new(feeder)));
+        self
+    }
+}
+
+impl Feeder for Grouped {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.query_grouped(text, position, items_count)
+            .into_iter()
+            .map(|x| x.value)
+            .collect()
+    }
+
+    fn query_grouped(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        let mut position = position;
+        let mut found = Vec::new();
+        for (title, feeder) in self.groups.iter() {
+            if found.len() == items_count {
+                break;
+            }
+            let remaining = items_count - found.len();
+            let items = feeder.query(text, 0, position + remaining);
+            if items.len() <= position {
+                // whole section is before requested position
+                position -= items.len();
+                continue;
+            }
+            found.extend(items.into_iter().skip(position).map(|value| Suggestion {
+                value,
+                group: Some(title.clone()),
+            }));
+            position = 0;
+        }
+        found
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        self.groups.iter().any(|(_, feeder)| feeder.contains(item))
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        self.groups
+            .iter()
+            .flat_map(|(_, feeder)| feeder.candidates(text))
+            .collect()
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        self.groups
+            .iter()
+            .find_map(|(_, feeder)| feeder.watched_dir(text))
+    }
+}
+
+/// Fixed options with descriptions, found by value or by words of description.
+///
+/// Only values are suggested & submitted, descriptions help to pick them
+/// (see [Autocomplete::options]).
+///
+/// ```
+/// use fui::feeders::{Described, Feeder};
+///
+/// let compression = Described::new(vec![
+///     ("gzip", "fast, widely supported"),
+///     ("xz", "smallest, but slow"),
+/// ]);
+/// assert_eq!(compression.query("small", 0, 10), vec!["xz"]);
+/// assert_eq!(compression.description("gzip"), Some("fast, widely supported"));
+/// assert!(compression.contains("xz"));
+/// ```
+///
+/// [Autocomplete::options]: ../fields/struct.Autocomplete.html#method.options
+#[derive(Clone, Debug, Default)]
+pub struct Described {
+    options: Vec<(String, String)>,
+}
+
+impl Described {
+    /// Creates a new `Described` from pairs of value & its description.
+    pub fn new<V: Into<String>, D: Into<String>>(options: Vec<(V, D)>) -> Self {
+        Described {
+            options: options
+                .into_iter()
+                .map(|(value, description)| (value.into(), description.into()))
+                .collect(),
+        }
+    }
+
+    /// Gets values of options in order of declaration.
+    pub fn values(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .map(|(value, _)| value.clone())
+            .collect()
+    }
+
+    /// Gets description of option with `value`.
+    pub fn description(&self, value: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(v, _)| v == value)
+            .map(|(_, description)| description.as_str())
+    }
+}
+
+impl Feeder for Described {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        let text = text.to_lowercase();
+        self.options
+            .iter()
+            .filter(|(value, description)| {
+                value.to_lowercase().contains(&text) || description.to_lowercase().contains(&text)
+            })
+            .map(|(value, _)| value.clone())
+            .skip(position)
+            .take(items_count)
+            .collect()
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        self.options.iter().any(|(value, _)| value == item)
+    }
+
+    fn candidates(&self, _text: &str) -> Vec<String> {
+        self.values()
+    }
+}
+
 #[derive(Clone, Debug)]
 enum DirItemType {
     Dir,
@@ -100,16 +376,21 @@ fn add_glob<P: AsRef<str>>(path: P) -> String {
     }
 }
 
-impl Feeder for DirItems {
-    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+impl DirItems {
+    /// Lists items like [query], stopping when `token` gets cancelled.
+    ///
+    /// [query]: trait.Feeder.html#tymethod.query
+    fn list(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        token: &CancelToken,
+    ) -> Vec<String> {
         let path = if text == "" {
             format!("./")
-        } else if text.starts_with('~') {
-            // TODO: remove unwraps
-            let path = text.replace("~", dirs::home_dir().unwrap().to_str().unwrap());
-            format!("{}", path)
         } else {
-            format!("{}", text)
+            expand_path(text)
         };
         let path = add_glob(path);
         if let Ok(v) = glob_with(
@@ -120,47 +401,178 @@ impl Feeder for DirItems {
                 require_literal_leading_dot: true,
             },
         ) {
-            v.filter(|x| {
-                if let Err(e) = x.as_ref() {
-                    eprintln!("{:?}", e);
-                    false
-                } else {
-                    true
-                }
-            })
-            .filter(|x| {
-                let path = x.as_ref().unwrap().metadata().unwrap();
-                match self.dir_item_type {
-                    DirItemType::Dir => path.is_dir(),
-                    DirItemType::All => true,
-                }
-            })
-            .map(|x| {
-                let path = x.unwrap();
-                let path = if self.use_full_paths {
-                    fs::canonicalize(path).unwrap()
-                } else {
-                    path
-                };
-                let text = format!("{}", path.display());
-                text
+            v.take_while(|_| !token.is_cancelled())
+                .filter(|x| {
+                    if let Err(e) = x.as_ref() {
+                        eprintln!("{:?}", e);
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .filter(|x| {
+                    let path = x.as_ref().unwrap().metadata().unwrap();
+                    match self.dir_item_type {
+                        DirItemType::Dir => path.is_dir(),
+                        DirItemType::All => true,
+                    }
+                })
+                .map(|x| {
+                    let path = x.unwrap();
+                    let path = if self.use_full_paths {
+                        fs::canonicalize(path).unwrap()
+                    } else {
+                        path
+                    };
+                    let text = format!("{}", path.display());
+                    text
+                })
+                .skip(position)
+                .take(items_count)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Feeder for DirItems {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.list(text, position, items_count, &CancelToken::new())
+    }
+
+    fn query_cancellable(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        token: &CancelToken,
+    ) -> Vec<Suggestion> {
+        self.list(text, position, items_count, token)
+            .into_iter()
+            .map(Suggestion::from)
+            .collect()
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        let path = expand_path(text);
+        if path.is_empty() {
+            return Some(PathBuf::from("."));
+        }
+        if path.ends_with('/') {
+            return Some(PathBuf::from(path));
+        }
+        let dir = match Path::new(&path).parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            Some(_) => PathBuf::from("."),
+            // path is root
+            None => PathBuf::from(path),
+        };
+        Some(dir)
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        match fs::metadata(expand_path(item)) {
+            Ok(meta) => match self.dir_item_type {
+                DirItemType::Dir => meta.is_dir(),
+                DirItemType::All => true,
+            },
+            Err(_) => false,
+        }
+    }
+}
+
+/// Common locales as BCP 47 tags (like `en-US`), found by tag or English name.
+///
+/// ```
+/// use fui::feeders::{Feeder, Locales};
+///
+/// assert_eq!(Locales.query("portug", 0, 10), vec!["pt-BR", "pt-PT"]);
+/// assert!(Locales.contains("de-CH"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Locales;
+
+impl Feeder for Locales {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        let text = text.to_lowercase();
+        LOCALES
+            .iter()
+            .filter(|(tag, name)| {
+                tag.to_lowercase().contains(&text) || name.to_lowercase().contains(&text)
             })
             .skip(position)
             .take(items_count)
+            .map(|(tag, _)| tag.to_string())
+            .collect()
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        LOCALES.iter().any(|(tag, _)| *tag == item)
+    }
+}
+
+/// IANA time zones (like `Europe/Warsaw`), spaces in query match underscores.
+///
+/// ```
+/// use fui::feeders::{Feeder, Timezones};
+///
+/// assert_eq!(Timezones.query("new york", 0, 10), vec!["America/New_York"]);
+/// assert!(Timezones.contains("UTC"));
+/// ```
+#[cfg(feature = "timezones")]
+#[derive(Clone, Debug)]
+pub struct Timezones;
+
+#[cfg(feature = "timezones")]
+impl Feeder for Timezones {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        let text = text.to_lowercase().replace(' ', "_");
+        chrono_tz::TZ_VARIANTS
+            .iter()
+            .map(|tz| tz.name())
+            .filter(|name| name.to_lowercase().contains(&text))
+            .skip(position)
+            .take(items_count)
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        item.parse::<chrono_tz::Tz>().is_ok()
+    }
+
+    fn candidates(&self, _text: &str) -> Vec<String> {
+        chrono_tz::TZ_VARIANTS
+            .iter()
+            .map(|tz| tz.name().to_string())
             .collect()
-        } else {
-            Vec::new()
-        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dirs;
     use std::collections::HashSet;
     use std::fs;
     use std::iter::FromIterator;
 
+    /// Feeder returning the same full page at any position.
+    struct Endless;
+
+    impl Feeder for Endless {
+        fn query(&self, _text: &str, _position: usize, items_count: usize) -> Vec<String> {
+            vec!["x".to_string(); items_count]
+        }
+    }
+
+    #[test]
+    fn contains_stops_at_repeated_page() {
+        assert!(Endless.contains("x"));
+        assert!(!Endless.contains("y"));
+    }
+
     fn expected(start: &str) -> HashSet<String> {
         let found = {
             if let Ok(v) = fs::read_dir(start) {
@@ -194,6 +606,76 @@ mod tests {
         assert_eq!(di.query("", 1, 1), vec!["Cargo.lock"]);
     }
 
+    #[test]
+    fn test_dir_items_stops_when_cancelled() {
+        let di = DirItems::new();
+        let token = CancelToken::new();
+        token.cancel();
+        assert_eq!(di.query_cancellable("", 0, 10, &token), Vec::new());
+        assert!(!di
+            .query_cancellable("", 0, 10, &CancelToken::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_dir_items_contains_existing_paths() {
+        assert!(DirItems::new().contains("Cargo.toml"));
+        assert!(DirItems::dirs().contains("src"));
+        assert!(!DirItems::dirs().contains("Cargo.toml"));
+        assert!(!DirItems::new().contains("missing-file"));
+    }
+
+    #[test]
+    fn test_default_contains_pages_through_query() {
+        struct Numbers;
+        impl Feeder for Numbers {
+            fn query(&self, _: &str, position: usize, items_count: usize) -> Vec<String> {
+                (position..1000)
+                    .take(items_count)
+                    .map(|x| x.to_string())
+                    .collect()
+            }
+        }
+        assert!(Numbers.contains("999"));
+        assert!(!Numbers.contains("1000"));
+    }
+
+    #[test]
+    fn test_grouped_pages_across_groups() {
+        let feeder = Grouped::new()
+            .group("Branches", vec!["master", "develop"])
+            .group("Empty", Vec::<String>::new())
+            .group("Tags", vec!["v1.0", "v1.1"]);
+        let found = feeder.query_grouped("", 1, 3);
+        let found: Vec<_> = found
+            .iter()
+            .map(|x| (x.group.as_ref().unwrap().as_str(), x.value.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("Branches", "develop"), ("Tags", "v1.0"), ("Tags", "v1.1")]
+        );
+        assert_eq!(feeder.query("", 3, 10), vec!["v1.1"]);
+        assert!(feeder.contains("v1.0"));
+    }
+
+    #[test]
+    fn test_dir_items_watch_completed_dir() {
+        let dir = |text| DirItems::new().watched_dir(text);
+        assert_eq!(dir(""), Some(PathBuf::from(".")));
+        assert_eq!(dir("Carg"), Some(PathBuf::from(".")));
+        assert_eq!(dir("src/fe"), Some(PathBuf::from("src")));
+        assert_eq!(dir("src/"), Some(PathBuf::from("src/")));
+        assert_eq!(dir("/"), Some(PathBuf::from("/")));
+        assert_eq!(
+            Grouped::new()
+                .group("files", DirItems::new())
+                .watched_dir("src/x"),
+            dir("src/x")
+        );
+        assert_eq!(vec!["a"].watched_dir("src/x"), None);
+    }
+
     #[test]
     fn test_glob_is_added_ok() {
         assert_eq!(add_glob(""), "*");
@@ -292,10 +774,111 @@ impl<T: Display + 'static> Feeder for Vec<T> {
             .take(items_count)
             .collect()
     }
+
+    fn contains(&self, item: &str) -> bool {
+        self.iter().any(|x| format!("{}", x) == item)
+    }
+
+    fn candidates(&self, _text: &str) -> Vec<String> {
+        self.iter().map(|x| format!("{}", x)).collect()
+    }
+}
+
+/// Feeder whose data can be changed while it's in use.
+impl<F: Feeder> Feeder for Rc<RefCell<F>> {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.borrow().query(text, position, items_count)
+    }
+
+    fn query_grouped(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        self.borrow().query_grouped(text, position, items_count)
+    }
+
+    fn query_cancellable(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        token: &CancelToken,
+    ) -> Vec<Suggestion> {
+        self.borrow()
+            .query_cancellable(text, position, items_count, token)
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        self.borrow().contains(item)
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        self.borrow().candidates(text)
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        self.borrow().watched_dir(text)
+    }
 }
 
 impl Feeder for Rc<dyn Feeder> {
     fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
         (**self).query(text, position, items_count)
     }
+
+    fn query_grouped(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        (**self).query_grouped(text, position, items_count)
+    }
+
+    fn query_cancellable(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        token: &CancelToken,
+    ) -> Vec<Suggestion> {
+        (**self).query_cancellable(text, position, items_count, token)
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        (**self).contains(item)
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        (**self).candidates(text)
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        (**self).watched_dir(text)
+    }
+}
+
+/// Feeder shared with threads querying it in background.
+impl Feeder for Arc<dyn Feeder + Send + Sync> {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        (**self).query(text, position, items_count)
+    }
+
+    fn query_grouped(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        (**self).query_grouped(text, position, items_count)
+    }
+
+    fn query_cancellable(
+        &self,
+        text: &str,
+        position: usize,
+        items_count: usize,
+        token: &CancelToken,
+    ) -> Vec<Suggestion> {
+        (**self).query_cancellable(text, position, items_count, token)
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        (**self).contains(item)
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        (**self).candidates(text)
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        (**self).watched_dir(text)
+    }
 }
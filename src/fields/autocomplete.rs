@@ -1,13 +1,18 @@
 use std::rc::Rc;
+use std::sync::Arc;
 
 use clap;
+use cursive::utils::markup::StyledString;
 use cursive::views::BoxedView;
 use serde_json::value::Value;
 
-use feeders::{DummyFeeder, Feeder};
+use feeders::{Described, DummyFeeder, Feeder};
 use fields;
 use fields::{FieldErrors, WidgetManager};
+use ranking::Ranker;
+use validators::OneOf;
 use views;
+use views::ItemRenderer;
 
 /// Convienient wrapper around `Field<AutocompleteManager, String>`.
 pub struct Autocomplete;
@@ -24,12 +29,75 @@ impl Autocomplete {
             "".to_string(),
         )
     }
+
+    /// Creates a new `Field<AutocompleteManager, String>` querying `feeder` in background
+    /// while text is typed (see [views::Autocomplete::background]).
+    ///
+    /// [views::Autocomplete::background]: ../views/struct.Autocomplete.html#method.background
+    pub fn background<IS: Into<String>, F: Feeder + Send + Sync>(
+        label: IS,
+        feeder: F,
+    ) -> fields::Field<AutocompleteManager, String> {
+        fields::Field::new(
+            label,
+            AutocompleteManager::with_background_feeder(feeder),
+            "".to_string(),
+        )
+    }
+
+    /// Creates a new `Field<AutocompleteManager, String>` accepting one of `options`,
+    /// which are pairs of value & its description.
+    ///
+    /// Descriptions are shown next to suggested values and can be searched,
+    /// but only the value is submitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Autocomplete;
+    /// use fui::form::FormView;
+    ///
+    /// let mut form = FormView::new().field(
+    ///     Autocomplete::options(
+    ///         "compression",
+    ///         vec![("gzip", "fast, widely supported"), ("xz", "smallest, but slow")],
+    ///     )
+    ///     .initial("gzip"),
+    /// );
+    ///
+    /// assert_eq!(form.validate().unwrap(), json!({"compression": "gzip"}));
+    /// # }
+    /// ```
+    pub fn options<IS: Into<String>, V: Into<String>, D: Into<String>>(
+        label: IS,
+        options: Vec<(V, D)>,
+    ) -> fields::Field<AutocompleteManager, String> {
+        let options = Described::new(options);
+        let values = options.values();
+        let described = options.clone();
+        Autocomplete::new(label, options)
+            .validator(OneOf(values))
+            .item_renderer(move |value| fields::option_label(value, described.description(value)))
+    }
 }
 
 #[derive(Clone)]
 pub struct AutocompleteManager {
     feeder: Rc<dyn Feeder>,
+    // the same feeder as `feeder` when it's queried in background
+    background: Option<Arc<dyn Feeder + Send + Sync>>,
     view_factory: Option<Rc<dyn Fn() -> views::Autocomplete>>,
+    inline_completion: bool,
+    tab_cycling: bool,
+    ranker: Option<Rc<dyn Ranker>>,
+    item_renderer: Option<ItemRenderer>,
+    min_chars: usize,
+    show_on_empty: bool,
+    #[cfg(feature = "watch")]
+    watch: bool,
 }
 
 impl AutocompleteManager {
@@ -44,9 +112,29 @@ impl AutocompleteManager {
     pub fn with_feeder<T: Feeder>(feeder: T) -> Self {
         AutocompleteManager {
             feeder: Rc::new(feeder),
+            background: None,
             view_factory: None,
+            inline_completion: false,
+            tab_cycling: false,
+            ranker: None,
+            item_renderer: None,
+            min_chars: 0,
+            show_on_empty: true,
+            #[cfg(feature = "watch")]
+            watch: false,
         }
     }
+    /// Creates an instance with a [Feeder] queried in background, see
+    /// [views::Autocomplete::background].
+    ///
+    /// [Feeder]: ../../feeders/index.html
+    /// [views::Autocomplete::background]: ../../views/struct.Autocomplete.html#method.background
+    pub fn with_background_feeder<T: Feeder + Send + Sync>(feeder: T) -> Self {
+        let feeder: Arc<dyn Feeder + Send + Sync> = Arc::new(feeder);
+        let mut manager = Self::with_feeder(Arc::clone(&feeder));
+        manager.background = Some(feeder);
+        manager
+    }
     /// Creates an instance with customized [views::Autocomplete].
     ///
     /// If you want to specify only a [Feeder] (and use a default [views::Autocomplete])
@@ -58,17 +146,106 @@ impl AutocompleteManager {
     pub fn with_factory_view(factory: Rc<dyn Fn() -> views::Autocomplete>) -> Self {
         AutocompleteManager {
             feeder: Rc::new(DummyFeeder),
+            background: None,
             view_factory: Some(factory),
+            inline_completion: false,
+            tab_cycling: false,
+            ranker: None,
+            item_renderer: None,
+            min_chars: 0,
+            show_on_empty: true,
+            #[cfg(feature = "watch")]
+            watch: false,
         }
     }
 
     fn get_view(&self) -> views::Autocomplete {
-        let view = if let Some(ref fun) = self.view_factory {
+        let mut view = if let Some(ref fun) = self.view_factory {
             fun()
+        } else if let Some(ref feeder) = self.background {
+            views::Autocomplete::with_background(Arc::clone(feeder))
         } else {
             views::Autocomplete::new(Rc::clone(&self.feeder))
         };
-        view
+        if let Some(ref ranker) = self.ranker {
+            view.set_ranker(Rc::clone(ranker));
+        }
+        if let Some(ref renderer) = self.item_renderer {
+            view.set_item_renderer(Rc::clone(renderer));
+        }
+        let view = view
+            .min_chars(self.min_chars)
+            .show_on_empty(self.show_on_empty);
+        #[cfg(feature = "watch")]
+        let view = view.watch(self.watch);
+        let view = if self.inline_completion {
+            view.inline_completion(true)
+        } else {
+            view
+        };
+        if self.tab_cycling {
+            view.tab_cycling(true)
+        } else {
+            view
+        }
+    }
+}
+
+impl fields::Field<AutocompleteManager, String> {
+    /// Shows remainder of the top suggestion as dimmed text after the cursor
+    /// (accepted with `Right` or `Tab` key).
+    pub fn inline_completion(mut self, enabled: bool) -> Self {
+        self.widget_manager.inline_completion = enabled;
+        self
+    }
+
+    /// Cycles through suggestions with `Tab` (`Shift+Tab` backwards) directly in the input
+    /// instead of showing them below, which suits very small terminals.
+    pub fn tab_cycling(mut self, enabled: bool) -> Self {
+        self.widget_manager.tab_cycling = enabled;
+        self
+    }
+
+    /// Renders listed suggestions with `renderer` instead of plain text
+    /// (see [views::Autocomplete::item_renderer]).
+    ///
+    /// [views::Autocomplete::item_renderer]: ../views/struct.Autocomplete.html#method.item_renderer
+    pub fn item_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&str) -> StyledString + 'static,
+    {
+        self.widget_manager.item_renderer = Some(Rc::new(renderer));
+        self
+    }
+
+    /// Orders suggestions with `ranker`.
+    pub fn ranker<R: Ranker>(mut self, ranker: R) -> Self {
+        self.widget_manager.ranker = Some(Rc::new(ranker));
+        self
+    }
+
+    /// Doesn't query the feeder until at least `min_chars` characters are typed
+    /// (0 by default), useful for huge feeders.
+    pub fn min_chars(mut self, min_chars: usize) -> Self {
+        self.widget_manager.min_chars = min_chars;
+        self
+    }
+
+    /// Controls if empty input lists the first page of all suggestions (it does by default).
+    pub fn show_on_empty(mut self, show: bool) -> Self {
+        self.widget_manager.show_on_empty = show;
+        self
+    }
+
+    /// Refreshes suggestions when directory they come from changes, so files created
+    /// while the form is open are listed (see [views::Autocomplete::watch]).
+    /// Requires `watch` feature.
+    ///
+    /// [views::Autocomplete::watch]: ../views/struct.Autocomplete.html#method.watch
+    #[cfg(feature = "watch")]
+    pub fn watch(mut self) -> Self {
+        self.widget_manager.watch = true;
+        self
     }
 }
 
@@ -88,6 +265,21 @@ impl WidgetManager for AutocompleteManager {
         let view = BoxedView::new(Box::new(widget.value(value)));
         view
     }
+    fn set_masked(&self, view_box: &mut BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let autocomplete: &mut views::Autocomplete =
+            (**view_box).as_any_mut().downcast_mut().unwrap();
+        autocomplete.set_masked(masked);
+    }
+    fn set_max_dropdown_rows(&self, view_box: &mut BoxedView, rows: Option<u8>) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let autocomplete: &mut views::Autocomplete =
+            (**view_box).as_any_mut().downcast_mut().unwrap();
+        autocomplete.set_max_shown_count(rows);
+    }
+    fn suggest(&self, text: &str, count: usize) -> Vec<String> {
+        self.feeder.query(text, 0, count)
+    }
 }
 
 impl fields::FormField for fields::Field<AutocompleteManager, String> {
@@ -123,15 +315,15 @@ impl fields::FormField for fields::Field<AutocompleteManager, String> {
     }
 
     fn clap_arg(&self) -> clap::Arg {
-        clap::Arg::with_name(&self.label)
+        clap::Arg::with_name(self.data_key())
             .help(&self.help)
-            .long(&self.label)
+            .long(self.data_key())
             .required(self.is_required())
             .takes_value(true)
     }
 
     fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
-        args.value_of(&self.label).unwrap_or("").to_string()
+        args.value_of(self.data_key()).unwrap_or("").to_string()
     }
 
     fn is_required(&self) -> bool {
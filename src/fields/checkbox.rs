@@ -6,6 +6,7 @@ use serde_json::value::Value;
 
 use fields;
 use fields::{FieldErrors, WidgetManager};
+use views::Masked;
 
 /// Convienient wrapper around `Field<CheckboxManager, bool>`.
 pub struct Checkbox;
@@ -27,15 +28,21 @@ impl fields::WidgetManager for CheckboxManager {
     }
     fn get_value(&self, view_box: &views::BoxedView) -> String {
         let view_box = fields::value_view_from_layout(view_box);
-        let checkbox: &views::Checkbox = (**view_box).as_any().downcast_ref().unwrap();
-        let value = checkbox.is_checked();
+        let checkbox: &Masked<views::Checkbox> = (**view_box).as_any().downcast_ref().unwrap();
+        let value = checkbox.get_inner().is_checked();
         format!("{}", value)
     }
     fn build_value_view(&self, value: &str) -> views::BoxedView {
         let value = FromStr::from_str(value).unwrap();
         let mut checkbox = views::Checkbox::new();
         checkbox.set_checked(value);
-        views::BoxedView::new(Box::new(checkbox))
+        views::BoxedView::new(Box::new(Masked::new(checkbox)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let checkbox: &mut Masked<views::Checkbox> =
+            (**view_box).as_any_mut().downcast_mut().unwrap();
+        checkbox.set_masked(masked);
     }
 }
 
@@ -70,13 +77,13 @@ impl fields::FormField for fields::Field<CheckboxManager, bool> {
     }
 
     fn clap_arg(&self) -> clap::Arg {
-        clap::Arg::with_name(&self.label)
+        clap::Arg::with_name(self.data_key())
             .help(&self.help)
-            .long(&self.label)
+            .long(self.data_key())
     }
 
     fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
-        let v = if args.is_present(&self.label) {
+        let v = if args.is_present(self.data_key()) {
             "true"
         } else {
             "false"
@@ -87,12 +94,21 @@ impl fields::FormField for fields::Field<CheckboxManager, bool> {
     fn is_required(&self) -> bool {
         self.is_required()
     }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "boolean" });
+        if !self.help.is_empty() {
+            schema["description"] = json!(self.help);
+        }
+        schema
+    }
 }
 
 impl<W: WidgetManager> fields::Field<W, bool> {
     /// Sets initial `value` of `field`.
     pub fn initial(mut self, value: bool) -> Self {
         self.initial = value;
+        self.own_initial = true;
         self
     }
 }
@@ -0,0 +1,109 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use cron::Schedule;
+use fields;
+use fields::{FieldErrors, WidgetManager};
+
+/// Convienient wrapper around `Field<CronManager, String>`.
+///
+/// Validates cron expression (like `30 9 * * mon-fri`) as described in [cron],
+/// showing its description and next runs below the input.
+///
+/// [cron]: ../cron/index.html
+pub struct Cron;
+
+impl Cron {
+    /// Creates a new `Field<CronManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<CronManager, String> {
+        fields::Field::new(label, CronManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct CronManager;
+
+impl WidgetManager for CronManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Cron = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::Cron::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Cron = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<CronManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        Schedule::parse(data)
+            .map(|_| Value::String(data.trim().to_owned()))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string" });
+        if self.is_required() {
+            schema["minLength"] = json!(1);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
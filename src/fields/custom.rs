@@ -0,0 +1,318 @@
+//! Helpers for new kinds of fields defined outside of `fui`.
+//!
+//! Implementing [FormField] directly means following conventions of widget layout,
+//! CLI args & validation. Instead [ValueWidget] covers only the view holding value
+//! and [SimpleField] wraps it into [Field] providing the rest.
+//!
+//! [FormField]: ../trait.FormField.html
+//! [ValueWidget]: trait.ValueWidget.html
+//! [SimpleField]: struct.SimpleField.html
+//! [Field]: ../struct.Field.html
+use std::rc::Rc;
+
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::Masked;
+
+/// View holding value of a custom field, see [SimpleField].
+///
+/// Value is passed around as a string, like the one typed in CLI.
+///
+/// [SimpleField]: struct.SimpleField.html
+pub trait ValueWidget {
+    /// Builds view showing `value`.
+    fn build(&self, value: &str) -> views::BoxedView;
+    /// Gets value of `view`.
+    fn get_value(&self, view: &views::BoxedView) -> String;
+    /// Shows `value` in `view`, by default `view` is rebuilt.
+    fn set_value(&self, view: &mut views::BoxedView, value: &str) {
+        *view = self.build(value);
+    }
+    /// Marks `view` as invalid, `error` is already shown below it
+    /// (empty `error` clears it).
+    fn set_error(&self, _view: &mut views::BoxedView, _error: &str) {}
+    /// Shows placeholders instead of value of `view` when `masked` (e.g. for screenshots),
+    /// returns `false` when `view` can't do it, so it's covered by placeholders as a whole.
+    fn set_masked(&self, _view: &mut views::BoxedView, _masked: bool) -> bool {
+        false
+    }
+    /// Converts valid `value` to submitted data, a string by default.
+    fn parse(&self, value: &str) -> Result<Value, String> {
+        Ok(Value::String(value.to_owned()))
+    }
+}
+
+/// Convienient wrapper around `Field<SimpleManager, String>`.
+///
+/// Field of any [ValueWidget] with label, help, validators, CLI arg etc. of [Field].
+///
+/// # Examples
+///
+/// ```
+/// extern crate cursive;
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use cursive::views::{BoxedView, Checkbox};
+/// use fui::fields::custom::{SimpleField, ValueWidget};
+/// use fui::form::FormView;
+///
+/// struct Toggle;
+///
+/// impl ValueWidget for Toggle {
+///     fn build(&self, value: &str) -> BoxedView {
+///         let checkbox = Checkbox::new().with_checked(value == "true");
+///         BoxedView::new(Box::new(checkbox))
+///     }
+///     fn get_value(&self, view: &BoxedView) -> String {
+///         let checkbox: &Checkbox = view.as_any().downcast_ref().unwrap();
+///         checkbox.is_checked().to_string()
+///     }
+///     fn parse(&self, value: &str) -> Result<serde_json::Value, String> {
+///         Ok(json!(value == "true"))
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut form = FormView::new()
+///     .field(SimpleField::new("verbose", Toggle).help("Logs more"))
+///     .field(SimpleField::new("quiet", Toggle).initial("true"));
+/// assert_eq!(form.validate(), Ok(json!({"verbose": false, "quiet": true})));
+/// # }
+/// ```
+///
+/// [ValueWidget]: trait.ValueWidget.html
+/// [Field]: ../struct.Field.html
+pub struct SimpleField;
+
+impl SimpleField {
+    /// Creates a new `Field<SimpleManager, String>` holding its value in `widget`.
+    pub fn new<IS, V>(label: IS, widget: V) -> fields::Field<SimpleManager, String>
+    where
+        IS: Into<String>,
+        V: ValueWidget + 'static,
+    {
+        let manager = SimpleManager {
+            widget: Rc::new(widget),
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+/// Manages widget of [SimpleField] through its [ValueWidget].
+///
+/// [SimpleField]: struct.SimpleField.html
+/// [ValueWidget]: trait.ValueWidget.html
+#[derive(Clone)]
+pub struct SimpleManager {
+    widget: Rc<dyn ValueWidget>,
+}
+
+impl SimpleManager {
+    /// Finds wrapper of view built by widget in widget layout.
+    fn masked_mut<'a>(
+        &self,
+        view_box: &'a mut views::BoxedView,
+    ) -> &'a mut Masked<views::BoxedView> {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        (**view_box).as_any_mut().downcast_mut().unwrap()
+    }
+}
+
+impl WidgetManager for SimpleManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &Masked<views::BoxedView> = (**view_box).as_any().downcast_ref().unwrap();
+        self.widget.get_value(view.get_inner())
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(Masked::new(self.widget.build(value))))
+    }
+    fn set_value(&self, view_box: &mut views::BoxedView, value: &str) {
+        let view = self.masked_mut(view_box);
+        self.widget.set_value(view.get_inner_mut(), value);
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view = self.masked_mut(view_box);
+        let own = self.widget.set_masked(view.get_inner_mut(), masked);
+        view.set_masked(masked && !own);
+    }
+}
+
+impl fields::FormField for fields::Field<SimpleManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        self.widget_manager.widget.parse(data).map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn set_error(&self, viewbox: &mut views::BoxedView, error: &str) {
+        if let Some(text) = fields::error_view_from_layout_mut(viewbox) {
+            text.set_content(error);
+        }
+        let view = self.widget_manager.masked_mut(viewbox);
+        self.widget_manager
+            .widget
+            .set_error(view.get_inner_mut(), error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use fields::FormField;
+
+    /// Widget keeping value in text view, counting errors set on it.
+    struct Label {
+        errors: Rc<Cell<usize>>,
+    }
+
+    impl ValueWidget for Label {
+        fn build(&self, value: &str) -> views::BoxedView {
+            views::BoxedView::new(Box::new(views::TextView::new(value)))
+        }
+        fn get_value(&self, view: &views::BoxedView) -> String {
+            let text: &views::TextView = view.as_any().downcast_ref().unwrap();
+            text.get_content().source().to_string()
+        }
+        fn set_error(&self, _view: &mut views::BoxedView, error: &str) {
+            if !error.is_empty() {
+                self.errors.set(self.errors.get() + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn simple_field_delegates_to_widget() {
+        let errors = Rc::new(Cell::new(0));
+        let widget = Label {
+            errors: Rc::clone(&errors),
+        };
+        let field = SimpleField::new("name", widget).initial("fui");
+        let manager = field.get_widget_manager();
+        let mut view = field.build_widget();
+        assert_eq!(manager.get_value(&view), "fui");
+
+        manager.set_value(&mut view, "cursive");
+        assert_eq!(manager.get_value(&view), "cursive");
+        assert_eq!(
+            field.validate("cursive"),
+            Ok(Value::String("cursive".into()))
+        );
+
+        field.set_error(&mut view, "Name is taken");
+        assert_eq!(errors.get(), 1);
+    }
+
+    /// Widget masking its text view by itself.
+    struct Secret;
+
+    impl ValueWidget for Secret {
+        fn build(&self, value: &str) -> views::BoxedView {
+            views::BoxedView::new(Box::new(views::TextView::new(value)))
+        }
+        fn get_value(&self, view: &views::BoxedView) -> String {
+            let text: &views::TextView = view.as_any().downcast_ref().unwrap();
+            text.get_content().source().to_string()
+        }
+        fn set_masked(&self, view: &mut views::BoxedView, masked: bool) -> bool {
+            let text: &mut views::TextView = view.as_any_mut().downcast_mut().unwrap();
+            text.set_content(if masked { "***" } else { "fui" });
+            true
+        }
+    }
+
+    #[test]
+    fn simple_field_is_masked_by_widget_or_placeholders() {
+        let widget = Label {
+            errors: Rc::new(Cell::new(0)),
+        };
+        let field = SimpleField::new("name", widget).initial("fui");
+        let manager = field.get_widget_manager();
+        let mut view = field.build_widget();
+        manager.set_masked(&mut view, true);
+        let masked = field.widget_manager.masked_mut(&mut view);
+        assert!(masked.is_masked());
+
+        let field = SimpleField::new("name", Secret).initial("fui");
+        let manager = field.get_widget_manager();
+        let mut view = field.build_widget();
+        manager.set_masked(&mut view, true);
+        assert!(!field.widget_manager.masked_mut(&mut view).is_masked());
+        assert_eq!(manager.get_value(&view), "***");
+        manager.set_masked(&mut view, false);
+        assert_eq!(manager.get_value(&view), "fui");
+    }
+
+    #[test]
+    fn simple_field_keeps_common_settings() {
+        let widget = Label {
+            errors: Rc::new(Cell::new(0)),
+        };
+        let field: Box<dyn FormField> = Box::new(
+            SimpleField::new("Password", widget)
+                .key("password")
+                .alias("pass")
+                .sensitive()
+                .tab_index(2)
+                .autofocus(),
+        );
+
+        assert_eq!(field.get_key(), "password");
+        assert_eq!(field.get_aliases(), ["pass"]);
+        assert!(field.is_sensitive());
+        assert_eq!(field.get_tab_index(), Some(2));
+        assert!(field.is_autofocus());
+    }
+}
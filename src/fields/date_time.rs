@@ -0,0 +1,199 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use locales::{date_format, DateOrder};
+use views::{date_time, date_time_range};
+
+/// Convienient wrapper around `Field<DateTimeManager, String>`.
+///
+/// Date with time (like `2024-01-31 12:00`) followed by UTC offset (like `+02:00`)
+/// or, with feature `timezones`, name of IANA time zone (like `Europe/Warsaw`)
+/// which can also be picked from suggestions in a separate input (see [timezone_picker]).
+/// Time without offset or zone is in UTC. It's submitted as RFC 3339 timestamp.
+///
+/// Date can be also typed in format of form's locale (like `31.01.2024` for `de-DE`).
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{DateTime, FormField};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = DateTime::new("deadline");
+/// assert_eq!(
+///     field.validate("2024-01-31 18:00 +02:00"),
+///     Ok(Value::from("2024-01-31T18:00:00+02:00"))
+/// );
+/// assert_eq!(
+///     field.validate("2024-01-31 18:00"),
+///     Ok(Value::from("2024-01-31T18:00:00Z"))
+/// );
+/// assert!(field.validate("2024-01-32 18:00").is_err());
+///
+/// let mut field = DateTime::new("deadline");
+/// field.set_locale("de-DE");
+/// assert_eq!(
+///     field.validate("31.01.2024 18:00"),
+///     Ok(Value::from("2024-01-31T18:00:00Z"))
+/// );
+/// assert_eq!(
+///     field.value2str(&Value::from("2024-01-31T18:00:00Z")),
+///     "31.01.2024 18:00:00Z"
+/// );
+/// # }
+/// ```
+///
+/// [timezone_picker]: ../fields/struct.Field.html#method.timezone_picker
+pub struct DateTime;
+
+impl DateTime {
+    /// Creates a new `Field<DateTimeManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<DateTimeManager, String> {
+        let manager = DateTimeManager {
+            locale: String::new(),
+            #[cfg(feature = "timezones")]
+            timezone_picker: false,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DateTimeManager {
+    locale: String,
+    #[cfg(feature = "timezones")]
+    timezone_picker: bool,
+}
+
+impl DateTimeManager {
+    fn date_format(&self) -> (DateOrder, char) {
+        date_format(&self.locale)
+    }
+}
+
+#[cfg(feature = "timezones")]
+impl fields::Field<DateTimeManager, String> {
+    /// Adds input of time zone next to date & time, suggesting IANA names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate fui;
+    ///
+    /// use fui::fields::{DateTime, FormField};
+    /// use fui::Value;
+    ///
+    /// # fn main() {
+    /// let field = DateTime::new("deadline").timezone_picker();
+    /// assert_eq!(
+    ///     field.validate("2024-07-31 18:00 Europe/Warsaw"),
+    ///     Ok(Value::from("2024-07-31T18:00:00+02:00"))
+    /// );
+    /// # }
+    /// ```
+    pub fn timezone_picker(mut self) -> Self {
+        self.widget_manager.timezone_picker = true;
+        self
+    }
+}
+
+impl WidgetManager for DateTimeManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::DateTime = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let view = ::views::DateTime::new();
+        #[cfg(feature = "timezones")]
+        let view = view.timezone_picker(self.timezone_picker);
+        views::BoxedView::new(Box::new(view.content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::DateTime = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<DateTimeManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        let (order, _) = self.widget_manager.date_format();
+        date_time::parse(data, order)
+            .map(Value::String)
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let (order, sep) = self.widget_manager.date_format();
+        date_time_range::iso_to_localized(value.as_str().unwrap_or(""), order, sep)
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string", "format": "date-time" });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+
+    fn set_locale(&mut self, locale: &str) -> bool {
+        self.widget_manager.locale = locale.to_string();
+        true
+    }
+}
@@ -0,0 +1,198 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use locales::{date_format, DateOrder};
+use utils::format_rfc3339_secs;
+use views::date_time_range;
+
+/// Convienient wrapper around `Field<DateTimeRangeManager, String>`.
+///
+/// Start & end of a range (dates like `2024-01-31`, optionally with time like
+/// `2024-01-31 12:00`, in UTC) are edited separately or picked from a menu of presets
+/// (like "last 24h" or "this week"). Dates can be also typed in format of form's locale
+/// (like `31.01.2024` for `de-DE`). The range is submitted as an object with keys
+/// `start` & `end` holding RFC 3339 timestamps, end mustn't be before start.
+///
+/// In CLI the range is passed as start & end separated by `/`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{DateTimeRange, FormField};
+///
+/// # fn main() {
+/// let field = DateTimeRange::new("period");
+/// assert_eq!(
+///     field.validate("2024-01-01/2024-01-31 18:00"),
+///     Ok(json!({"start": "2024-01-01T00:00:00Z", "end": "2024-01-31T18:00:00Z"}))
+/// );
+/// assert_eq!(
+///     field.validate("2024-01-31/2024-01-01"),
+///     Err(vec!["End is before start".to_string()])
+/// );
+///
+/// let mut field = DateTimeRange::new("period");
+/// field.set_locale("en-US");
+/// assert_eq!(
+///     field.validate("01/01/2024/01/31/2024"),
+///     Ok(json!({"start": "2024-01-01T00:00:00Z", "end": "2024-01-31T00:00:00Z"}))
+/// );
+/// # }
+/// ```
+pub struct DateTimeRange;
+
+impl DateTimeRange {
+    /// Creates a new `Field<DateTimeRangeManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<DateTimeRangeManager, String> {
+        let manager = DateTimeRangeManager {
+            locale: String::new(),
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DateTimeRangeManager {
+    locale: String,
+}
+
+impl DateTimeRangeManager {
+    fn date_format(&self) -> (DateOrder, char) {
+        date_format(&self.locale)
+    }
+}
+
+impl WidgetManager for DateTimeRangeManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::DateTimeRange = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::DateTimeRange::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::DateTimeRange = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<DateTimeRangeManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        let (order, _) = self.widget_manager.date_format();
+        let (start, end) = date_time_range::split(data);
+        let parse = |text: &str, name: &str| {
+            if text.trim().is_empty() {
+                Err(format!("{} is missing", name))
+            } else {
+                date_time_range::parse_date_time(&date_time_range::localized_to_iso(text, order))
+                    .map_err(|_| format!("Invalid date: {}", text.trim()))
+            }
+        };
+        let (start, end) = match (parse(start, "Start"), parse(end, "End")) {
+            (Ok(start), Ok(end)) => (start, end),
+            (start, end) => {
+                return Err(vec![start.err(), end.err()].into_iter().flatten().collect());
+            }
+        };
+        if end < start {
+            return Err(vec!["End is before start".to_string()]);
+        }
+        Ok(json!({
+            "start": format_rfc3339_secs(start),
+            "end": format_rfc3339_secs(end),
+        }))
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match (value["start"].as_str(), value["end"].as_str()) {
+            (Some(start), Some(end)) => {
+                let (order, sep) = self.widget_manager.date_format();
+                format!(
+                    "{}{}{}",
+                    date_time_range::iso_to_localized(start, order, sep),
+                    date_time_range::RANGE_SEP,
+                    date_time_range::iso_to_localized(end, order, sep)
+                )
+            }
+            _ => "".to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "start": { "type": "string", "format": "date-time" },
+                "end": { "type": "string", "format": "date-time" }
+            },
+            "required": ["start", "end"]
+        });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+
+    fn set_locale(&mut self, locale: &str) -> bool {
+        self.widget_manager.locale = locale.to_string();
+        true
+    }
+}
@@ -0,0 +1,208 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use locales::number_separators;
+use views::decimal;
+
+/// Convienient wrapper around `Field<DecimalManager, String>`.
+///
+/// Decimal number with fixed precision (like money), shown with thousands separators
+/// of a locale and submitted exactly (without floats) as a string (like `"1234.50"`)
+/// or as an integer of minor units (like `123450`, see [minor_units]).
+///
+/// [minor_units]: struct.Field.html#method.minor_units
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{Decimal, FormField};
+///
+/// # fn main() {
+/// let field = Decimal::new("price");
+/// assert_eq!(field.validate("1,234.5"), Ok(json!("1234.50")));
+///
+/// let field = Decimal::new("price").locale("de-DE").minor_units();
+/// assert_eq!(field.validate("1.234,5"), Ok(json!(123450)));
+/// assert_eq!(field.value2str(&json!(123450)), "1.234,50");
+/// # }
+/// ```
+pub struct Decimal;
+
+impl Decimal {
+    /// Creates a new `Field<DecimalManager, String>` with 2 decimal places.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<DecimalManager, String> {
+        let manager = DecimalManager {
+            precision: 2,
+            locale: None,
+            form_locale: "en-US".to_string(),
+            minor_units: false,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DecimalManager {
+    precision: u8,
+    // explicitly set locale, wins over one of the form
+    locale: Option<String>,
+    form_locale: String,
+    minor_units: bool,
+}
+
+impl DecimalManager {
+    fn separators(&self) -> (char, char) {
+        number_separators(self.locale.as_ref().unwrap_or(&self.form_locale))
+    }
+
+    fn parse(&self, data: &str) -> Result<i128, String> {
+        let (group, decimal) = self.separators();
+        decimal::parse(data, self.precision, group, decimal)
+    }
+}
+
+impl fields::Field<DecimalManager, String> {
+    /// Sets count of decimal places (2 by default).
+    pub fn precision(mut self, precision: u8) -> Self {
+        self.widget_manager.precision = precision;
+        self
+    }
+
+    /// Uses separators of thousands and decimals of `locale` (like `de-DE`),
+    /// otherwise ones of form's locale (English by default) are used.
+    pub fn locale<IS: Into<String>>(mut self, locale: IS) -> Self {
+        self.widget_manager.locale = Some(locale.into());
+        self
+    }
+
+    /// Submits number as an integer of minor units (like cents) instead of a string.
+    pub fn minor_units(mut self) -> Self {
+        self.widget_manager.minor_units = true;
+        self
+    }
+}
+
+impl WidgetManager for DecimalManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Decimal = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let (group, decimal) = self.separators();
+        let view = ::views::Decimal::new(self.precision)
+            .separators(group, decimal)
+            .content(value);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Decimal = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<DecimalManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        let manager = &self.widget_manager;
+        let minor = manager.parse(data).map_err(|e| vec![e])?;
+        if manager.minor_units {
+            if minor > i128::from(i64::MAX) || minor < i128::from(i64::MIN) {
+                return Err(vec!["Number is too big".to_string()]);
+            }
+            Ok(json!(minor as i64))
+        } else {
+            Ok(json!(decimal::format(minor, manager.precision, None, '.')))
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let manager = &self.widget_manager;
+        let minor = match *value {
+            Value::Number(ref n) => n.as_i64().map(i128::from),
+            Value::String(ref s) => decimal::parse(s, manager.precision, ',', '.').ok(),
+            _ => None,
+        };
+        match minor {
+            Some(minor) => {
+                let (group, decimal) = manager.separators();
+                decimal::format(minor, manager.precision, Some(group), decimal)
+            }
+            // invalid text is kept, so validation reports it
+            None => value.as_str().unwrap_or("").to_owned(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = if self.widget_manager.minor_units {
+            json!({ "type": "integer" })
+        } else {
+            json!({ "type": "string", "pattern": r"^-?[0-9]+(\.[0-9]+)?$" })
+        };
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+
+    fn set_locale(&mut self, locale: &str) -> bool {
+        self.widget_manager.form_locale = locale.to_string();
+        self.widget_manager.locale.is_none()
+    }
+}
@@ -0,0 +1,125 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use validators::{DirExists, Validator};
+
+/// Convienient wrapper around `Field<DirPickerManager, String>`.
+///
+/// Path of an existing dir, typed or picked in a modal tree of dirs opened
+/// with `[browse]` button or `Ctrl+o`. In the tree `Right` expands a dir,
+/// `Left` collapses it (or moves to the parent) and `Enter` picks it.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{DirPicker, FormField};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = DirPicker::new("target");
+/// assert_eq!(field.validate("./src"), Ok(Value::from("./src")));
+/// assert!(field.validate("./Cargo.toml").is_err());
+/// # }
+/// ```
+pub struct DirPicker;
+
+impl DirPicker {
+    /// Creates a new `Field<DirPickerManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<DirPickerManager, String> {
+        fields::Field::new(label, DirPickerManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DirPickerManager;
+
+impl WidgetManager for DirPickerManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::DirPicker = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::DirPicker::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::DirPicker = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<DirPickerManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        match DirExists.validate(data) {
+            Some(e) => Err(vec![e]),
+            None => Ok(Value::String(data.to_string())),
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        value.as_str().unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string" });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
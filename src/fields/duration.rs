@@ -0,0 +1,158 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::duration;
+
+/// Convienient wrapper around `Field<DurationManager, String>`.
+///
+/// Accepts human-friendly duration (like `1h30m`, `90s` or `2d`) with units
+/// `d`, `h`, `m` & `s`, showing it normalized below the input as it's typed.
+/// Submitted as a normalized string (like `"1h30m"`) or as total seconds
+/// (see [seconds]), which suits timeouts or retention settings.
+///
+/// [seconds]: struct.Field.html#method.seconds
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{Duration, FormField};
+///
+/// # fn main() {
+/// let field = Duration::new("timeout");
+/// assert_eq!(field.validate("90m"), Ok(json!("1h30m")));
+/// assert_eq!(
+///     field.validate("1h30"),
+///     Err(vec!["Number 30 has no unit".to_string()])
+/// );
+///
+/// let field = Duration::new("retention").seconds();
+/// assert_eq!(field.validate("2d"), Ok(json!(172800)));
+/// assert_eq!(field.value2str(&json!(5400)), "1h30m");
+/// # }
+/// ```
+pub struct Duration;
+
+impl Duration {
+    /// Creates a new `Field<DurationManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<DurationManager, String> {
+        let manager = DurationManager { seconds: false };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DurationManager {
+    seconds: bool,
+}
+
+impl fields::Field<DurationManager, String> {
+    /// Submits duration as an integer of total seconds instead of a string.
+    pub fn seconds(mut self) -> Self {
+        self.widget_manager.seconds = true;
+        self
+    }
+}
+
+impl WidgetManager for DurationManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Duration = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::Duration::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Duration = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<DurationManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        let secs = duration::parse(data).map_err(|e| vec![e])?;
+        if self.widget_manager.seconds {
+            Ok(json!(secs))
+        } else {
+            Ok(json!(duration::format(secs)))
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let secs = match *value {
+            Value::Number(ref n) => n.as_u64(),
+            Value::String(ref s) => duration::parse(s).ok(),
+            _ => None,
+        };
+        secs.map(duration::format).unwrap_or_default()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = if self.widget_manager.seconds {
+            json!({ "type": "integer", "minimum": 0 })
+        } else {
+            json!({ "type": "string", "pattern": r"^([0-9]+d)?([0-9]+h)?([0-9]+m)?([0-9]+s)?$" })
+        };
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
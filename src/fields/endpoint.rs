@@ -0,0 +1,166 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::endpoint;
+
+/// Convienient wrapper around `Field<EndpointManager, String>`.
+///
+/// Host and port of a network endpoint (like `localhost:8080`) are edited separately
+/// and submitted as an object with keys `host` and `port`.
+///
+/// With feature `endpoint-ping` the endpoint can also be checked
+/// for reachability, see [ping].
+///
+/// [ping]: struct.Field.html#method.ping
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{Endpoint, FormField};
+///
+/// # fn main() {
+/// let field = Endpoint::new("database");
+/// assert_eq!(
+///     field.validate("db.local:5432"),
+///     Ok(json!({"host": "db.local", "port": 5432}))
+/// );
+/// assert_eq!(
+///     field.validate("db.local"),
+///     Err(vec!["Port is missing".to_string()])
+/// );
+/// assert_eq!(field.value2str(&json!({"host": "::1", "port": 80})), "[::1]:80");
+/// # }
+/// ```
+pub struct Endpoint;
+
+impl Endpoint {
+    /// Creates a new `Field<EndpointManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<EndpointManager, String> {
+        fields::Field::new(label, EndpointManager::default(), "".to_string())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct EndpointManager {
+    #[cfg(feature = "endpoint-ping")]
+    ping: bool,
+}
+
+impl fields::Field<EndpointManager, String> {
+    /// Checks in background whether the endpoint accepts connections when the field
+    /// loses focus and shows the result inline (see [views::Endpoint::ping]).
+    ///
+    /// [views::Endpoint::ping]: ../views/struct.Endpoint.html#method.ping
+    #[cfg(feature = "endpoint-ping")]
+    pub fn ping(mut self) -> Self {
+        self.widget_manager.ping = true;
+        self
+    }
+}
+
+impl WidgetManager for EndpointManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Endpoint = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let view = ::views::Endpoint::new().content(value);
+        #[cfg(feature = "endpoint-ping")]
+        let view = view.ping(self.ping);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Endpoint = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<EndpointManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        endpoint::parse(data)
+            .map(|(host, port)| json!({"host": host.trim_matches(|c| c == '[' || c == ']'), "port": port}))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let host = value["host"].as_str().unwrap_or("");
+        match value["port"].as_u64() {
+            Some(port) if host.contains(':') => format!("[{}]:{}", host, port),
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "host": { "type": "string" },
+                "port": { "type": "integer", "minimum": 1, "maximum": 65535 }
+            },
+            "required": ["host", "port"]
+        });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
@@ -0,0 +1,175 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+
+/// Convienient wrapper around `Field<IntNumberManager, String>`.
+///
+/// Integer typed in a spin box adjustable with `[-]` `[+]` buttons
+/// (or `-` and `+` keys), submitted as a JSON number.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, IntNumber};
+///
+/// # fn main() {
+/// let field = IntNumber::new("workers").min(1).max(64).step(2);
+/// assert_eq!(field.validate("8"), Ok(json!(8)));
+/// assert!(field.validate("0").is_err());
+/// assert!(field.validate("2.5").is_err());
+/// # }
+/// ```
+pub struct IntNumber;
+
+impl IntNumber {
+    /// Creates a new unbounded `Field<IntNumberManager, String>` changed by 1 with buttons.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<IntNumberManager, String> {
+        let manager = IntNumberManager {
+            step: 1,
+            min: None,
+            max: None,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct IntNumberManager {
+    step: i64,
+    min: Option<i64>,
+    max: Option<i64>,
+}
+
+impl fields::Field<IntNumberManager, String> {
+    /// Sets the lowest accepted number.
+    pub fn min(mut self, min: i64) -> Self {
+        self.widget_manager.min = Some(min);
+        self
+    }
+
+    /// Sets the highest accepted number.
+    pub fn max(mut self, max: i64) -> Self {
+        self.widget_manager.max = Some(max);
+        self
+    }
+
+    /// Sets how much the number changes with a button (1 by default).
+    pub fn step(mut self, step: i64) -> Self {
+        self.widget_manager.step = step;
+        self
+    }
+}
+
+impl WidgetManager for IntNumberManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::IntNumber = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let view = ::views::IntNumber::new()
+            .step(self.step)
+            .bounds(self.min, self.max)
+            .content(value);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::IntNumber = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<IntNumberManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let data = data.trim();
+        if data.is_empty() {
+            return Ok(Value::Null);
+        }
+        let number = data
+            .parse::<i64>()
+            .map_err(|_| vec![format!("Invalid integer: {}", data)])?;
+        if let Some(min) = self.widget_manager.min.filter(|&min| number < min) {
+            return Err(vec![format!("Number must be at least {}", min)]);
+        }
+        if let Some(max) = self.widget_manager.max.filter(|&max| number > max) {
+            return Err(vec![format!("Number must be at most {}", max)]);
+        }
+        Ok(json!(number))
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .allow_hyphen_values(true)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match value {
+            Value::String(s) => s.to_owned(),
+            _ => value.as_i64().map(|n| n.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "integer" });
+        if let Some(min) = self.widget_manager.min {
+            schema["minimum"] = json!(min);
+        }
+        if let Some(max) = self.widget_manager.max {
+            schema["maximum"] = json!(max);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
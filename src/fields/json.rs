@@ -0,0 +1,124 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+
+/// Convienient wrapper around `Field<JsonManager, String>`.
+///
+/// Content of its multi-line editor is submitted as parsed JSON (not a string),
+/// which suits escape-hatch fields like "extra options".
+pub struct Json;
+
+impl Json {
+    /// Creates a new `Field<JsonManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<JsonManager, String> {
+        fields::Field::new(label, JsonManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonManager;
+
+impl WidgetManager for JsonManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Json = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content().to_string()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::Json::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Json = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<JsonManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        ::views::json::parse(data).map_err(|e| vec![format!("Invalid JSON: {}", e)])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match *value {
+            Value::Null => "".to_string(),
+            ref v => v.to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({});
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::FormField;
+
+    #[test]
+    fn content_is_submitted_as_parsed_json() {
+        let field = Json::new("extra");
+        assert_eq!(field.validate(r#"{"a": [1]}"#), Ok(json!({"a": [1]})));
+        assert_eq!(field.validate(" "), Ok(Value::Null));
+        assert_eq!(
+            field.validate("{\n  \"a\": 1,\n}"),
+            Err(vec![
+                "Invalid JSON: trailing comma at line 3 column 1".to_string()
+            ])
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use clap;
+use cursive::views;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::key_value::{split_row, PAIR_SEP};
+
+/// Convienient wrapper around `Field<KeyValueManager, String>`.
+///
+/// Rows of keys & values (like environment variables or HTTP headers)
+/// submitted as a JSON object of strings. Keys must be unique and not empty.
+///
+/// In CLI each row is given as `key=value` (like `--env HOST=localhost --env PORT=80`).
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, KeyValue};
+///
+/// # fn main() {
+/// let field = KeyValue::new("env");
+/// assert_eq!(
+///     field.validate("HOST=localhost\nPORT = 80"),
+///     Ok(json!({"HOST": "localhost", "PORT": "80"}))
+/// );
+/// assert_eq!(
+///     field.validate("A=1\n=2\nA=3"),
+///     Err(vec![
+///         "Row 2 has no key".to_string(),
+///         "Key \"A\" is duplicated".to_string(),
+///     ])
+/// );
+/// assert_eq!(field.value2str(&json!({"A": "1", "B": 2})), "A=1\nB=2");
+/// # }
+/// ```
+pub struct KeyValue;
+
+impl KeyValue {
+    /// Creates a new `Field<KeyValueManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<KeyValueManager, String> {
+        fields::Field::new(label, KeyValueManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyValueManager;
+
+impl WidgetManager for KeyValueManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::KeyValue = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::KeyValue::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::KeyValue = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<KeyValueManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let mut pairs = Map::new();
+        let rows = data.lines().filter(|row| !row.trim().is_empty());
+        for (idx, row) in rows.enumerate() {
+            let (key, value) = split_row(row);
+            if key.is_empty() {
+                errors.push(format!("Row {} has no key", idx + 1));
+            } else if pairs.contains_key(key) {
+                errors.push(format!("Key {:?} is duplicated", key));
+            } else {
+                pairs.insert(key.to_string(), Value::String(value.to_string()));
+            }
+        }
+        if errors.is_empty() {
+            Ok(Value::Object(pairs))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .multiple(true)
+            .number_of_values(1)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        let values = args.values_of(self.data_key()).unwrap_or_default();
+        values.collect::<Vec<&str>>().join("\n")
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let pairs = match value.as_object() {
+            Some(pairs) => pairs,
+            None => return "".to_string(),
+        };
+        pairs
+            .iter()
+            .map(|(key, value)| {
+                let value = value
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| value.to_string());
+                format!("{}{}{}", key, PAIR_SEP, value)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "additionalProperties": { "type": "string" },
+        });
+        if self.is_required() {
+            schema["minProperties"] = json!(1);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
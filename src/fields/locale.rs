@@ -0,0 +1,25 @@
+use feeders::Locales;
+use fields;
+use fields::autocomplete::AutocompleteManager;
+use validators::InFeeder;
+
+/// Convienient wrapper around `Field<AutocompleteManager, String>` picking one of common
+/// locales (like `en-US`), searchable by tag or English name.
+///
+/// # Examples
+///
+/// ```
+/// use fui::fields::{FormField, Locale};
+///
+/// let field = Locale::new("language");
+/// assert!(field.validate("pl-PL").is_ok());
+/// assert!(field.validate("Polish").is_err());
+/// ```
+pub struct Locale;
+
+impl Locale {
+    /// Creates a new `Field<AutocompleteManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<AutocompleteManager, String> {
+        fields::Autocomplete::new(label, Locales).validator(InFeeder::new(Locales))
+    }
+}
@@ -1,20 +1,84 @@
 //! Includes `form's` building blocks, `fields`.
 use clap;
+use cursive::theme::ColorStyle;
+use cursive::traits::Resizable;
+use cursive::utils::markup::StyledString;
 use cursive::view::View;
 use cursive::views;
+use form::FormSnapshot;
 use serde_json::value::Value;
 use std::rc::Rc;
+use std::sync::Arc;
 use validators::{Required, Validator};
 
 pub(crate) mod autocomplete;
 mod checkbox;
+mod cron;
+pub mod custom;
+mod date_time;
+mod date_time_range;
+mod decimal;
+mod dir_picker;
+mod duration;
+mod endpoint;
+#[cfg(feature = "expr")]
+mod expr;
+mod file_mode;
+mod file_picker;
+mod glob_pattern;
+mod int_number;
+mod json;
+mod key_value;
+mod locale;
 pub(crate) mod multiselect;
+mod number;
+mod number_list;
+mod radio_group;
+mod regex;
+mod save_path;
+mod select;
+mod semver;
+mod slider;
+mod tags;
 mod text;
+mod time;
+#[cfg(feature = "timezones")]
+mod timezone;
+mod uuid;
 
 pub use self::autocomplete::Autocomplete;
 pub use self::checkbox::Checkbox;
+pub use self::cron::Cron;
+pub use self::date_time::DateTime;
+pub use self::date_time_range::DateTimeRange;
+pub use self::decimal::Decimal;
+pub use self::dir_picker::DirPicker;
+pub use self::duration::Duration;
+pub use self::endpoint::Endpoint;
+#[cfg(feature = "expr")]
+pub use self::expr::Expr;
+pub use self::file_mode::FileMode;
+pub use self::file_picker::FilePicker;
+pub use self::glob_pattern::GlobPattern;
+pub use self::int_number::IntNumber;
+pub use self::json::Json;
+pub use self::key_value::KeyValue;
+pub use self::locale::Locale;
 pub use self::multiselect::Multiselect;
+pub use self::number::Number;
+pub use self::number_list::NumberList;
+pub use self::radio_group::RadioGroup;
+pub use self::regex::Regex;
+pub use self::save_path::SavePath;
+pub use self::select::Select;
+pub use self::semver::{SemVer, SemVerReq};
+pub use self::slider::Slider;
+pub use self::tags::Tags;
 pub use self::text::Text;
+pub use self::time::Time;
+#[cfg(feature = "timezones")]
+pub use self::timezone::Timezone;
+pub use self::uuid::Uuid;
 
 /// Covers communication from [Field] to [View].
 ///
@@ -40,10 +104,9 @@ pub trait WidgetManager {
     )]
     // TODO:: rm it
     fn set_error(&self, viewbox: &mut views::BoxedView, error: &str) {
-        let layout: &mut views::LinearLayout = (**viewbox).as_any_mut().downcast_mut().unwrap();
-        let child: &mut dyn View = (*layout).get_child_mut(2).unwrap();
-        let text: &mut views::TextView = (*child).as_any_mut().downcast_mut().unwrap();
-        text.set_content(error);
+        if let Some(text) = error_view_from_layout_mut(viewbox) {
+            text.set_content(error);
+        }
     }
     /// Builds container `view` with placeholders for `help`, `value`, `error`.
     ///
@@ -58,15 +121,45 @@ pub trait WidgetManager {
     )]
     // TODO:: rm it
     fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView;
+    /// Shows `value` in widget `view`, by default its value view is rebuilt.
+    fn set_value(&self, view: &mut views::BoxedView, value: &str) {
+        replace_value_view(view, self.build_value_view(value));
+    }
+    /// Shows placeholders instead of value of widget `view` when `masked`,
+    /// e.g. for taking screenshots without leaking data.
+    fn set_masked(&self, _view: &mut views::BoxedView, _masked: bool) {}
+    /// Lists at most `rows` suggestions in dropdown of widget `view` (if it has any),
+    /// `None` removes the limit. It's used by compact layouts of forms.
+    fn set_max_dropdown_rows(&self, _view: &mut views::BoxedView, _rows: Option<u8>) {}
+    /// Suggests up to `count` values matching `text`, used for completion outside of TUI.
+    fn suggest(&self, _text: &str, _count: usize) -> Vec<String> {
+        Vec::new()
+    }
 }
 
+/// Function probing system for a hint about field's value (see [Field::probe]).
+///
+/// [Field::probe]: struct.Field.html#method.probe
+pub type Probe = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
 /// Building block for `Form`s which stores `data` and `Widget`.
 #[derive(Clone)]
 pub struct Field<W: WidgetManager, T> {
     label: String,
+    key: Option<String>,
+    aliases: Vec<String>,
+    deprecated: Option<(String, String)>,
     help: String,
     initial: T,
+    // `initial` was given by the app, it's not a default of the field
+    own_initial: bool,
     validators: Vec<Rc<dyn Validator>>,
+    validators_if: Vec<ValidatorIf>,
+    sensitive: bool,
+    tab_index: Option<usize>,
+    autofocus: bool,
+    probe: Option<Probe>,
+    layout: Option<Rc<dyn FieldLayout>>,
     widget_manager: W,
 }
 
@@ -79,9 +172,19 @@ impl<W: WidgetManager, T> Field<W, T> {
     pub fn new<IS: Into<String>>(label: IS, widget_manager: W, initial: T) -> Self {
         Field {
             label: label.into(),
+            key: None,
+            aliases: Vec::new(),
+            deprecated: None,
             help: "".into(),
             initial: initial,
+            own_initial: false,
             validators: vec![],
+            validators_if: vec![],
+            sensitive: false,
+            tab_index: None,
+            autofocus: false,
+            probe: None,
+            layout: None,
             widget_manager: widget_manager,
         }
     }
@@ -90,11 +193,169 @@ impl<W: WidgetManager, T> Field<W, T> {
         self.help = msg.into();
         self
     }
+    /// Sets `key` of field's value in submitted data, which is also name of its CLI arg
+    /// (defaults to label).
+    ///
+    /// It lets label stay human-friendly while data uses machine-friendly keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    ///
+    /// let mut form = FormView::new()
+    ///     .field(Text::new("Destination directory").key("dst_dir").initial("/tmp"));
+    ///
+    /// assert_eq!(form.validate().unwrap(), json!({"dst_dir": "/tmp"}));
+    /// # }
+    /// ```
+    pub fn key<IS: Into<String>>(mut self, key: IS) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+    /// Gets key of field's value in data (label unless [key] is set).
+    ///
+    /// [key]: #method.key
+    fn data_key(&self) -> &str {
+        self.key.as_ref().unwrap_or(&self.label)
+    }
+    /// Adds `old_key` under which field's value was stored before (e.g. before renaming),
+    /// so old data & CLI args using it still fill the field.
+    ///
+    /// Uses of `old_key` are reported by [FormView::deprecation_warnings].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    ///
+    /// let mut form = FormView::new().field(Text::new("dst").alias("target"));
+    /// let old_profile = json!({"target": "/tmp"});
+    /// form.set_values(&old_profile);
+    ///
+    /// assert_eq!(form.validate().unwrap(), json!({"dst": "/tmp"}));
+    /// assert_eq!(
+    ///     form.deprecation_warnings(&old_profile),
+    ///     vec!["`target` is renamed to `dst`"]
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [FormView::deprecation_warnings]: ../form/struct.FormView.html#method.deprecation_warnings
+    pub fn alias<IS: Into<String>>(mut self, old_key: IS) -> Self {
+        self.aliases.push(old_key.into());
+        self
+    }
+    /// Marks field as deprecated `since` version, `note` tells users what to use instead.
+    ///
+    /// Field still works, but its use is reported by [FormView::deprecation_warnings]
+    /// and the note is listed among its rules.
+    ///
+    /// [FormView::deprecation_warnings]: ../form/struct.FormView.html#method.deprecation_warnings
+    pub fn deprecated<IS1: Into<String>, IS2: Into<String>>(
+        mut self,
+        since: IS1,
+        note: IS2,
+    ) -> Self {
+        self.deprecated = Some((since.into(), note.into()));
+        self
+    }
+    /// Gets version since which field is deprecated & the note about it.
+    fn deprecation(&self) -> Option<(&str, &str)> {
+        self.deprecated
+            .as_ref()
+            .map(|(since, note)| (since.as_str(), note.as_str()))
+    }
     /// Append `validator`.
     pub fn validator<V: Validator + 'static>(mut self, validator: V) -> Self {
         self.validators.push(Rc::new(validator));
         self
     }
+    /// Append `validator` which is used only when `predicate` holds for value of field
+    /// labeled `other_label`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fui::fields::Text;
+    /// use fui::validators::Required;
+    ///
+    /// let key_file = Text::new("key-file").validator_if("auth-method", |v| v == "key", Required);
+    /// ```
+    pub fn validator_if<IS, P, V>(mut self, other_label: IS, predicate: P, validator: V) -> Self
+    where
+        IS: Into<String>,
+        P: Fn(&str) -> bool + 'static,
+        V: Validator + 'static,
+    {
+        self.validators_if.push(ValidatorIf {
+            other_label: other_label.into(),
+            predicate: Rc::new(predicate),
+            validator: Rc::new(validator),
+        });
+        self
+    }
+    /// Marks field's value as sensitive (like passwords or tokens),
+    /// so it's redacted in logs.
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+    /// Overrides position of field in `Tab` order.
+    ///
+    /// Fields with `tab_index` are focused first (in ascending order),
+    /// the rest follows in order of declaration.
+    pub fn tab_index(mut self, index: usize) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+    /// Focuses field when form opens (instead of the first one).
+    pub fn autofocus(mut self) -> Self {
+        self.autofocus = true;
+        self
+    }
+    /// Shows hint found by `probe` dimmed below the field, e.g. `8080 appears free`
+    /// next to a port.
+    ///
+    /// `probe` gets field's value and runs in background when the form opens
+    /// and whenever the value changes & field loses focus; `None` clears the hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::TcpListener;
+    ///
+    /// use fui::fields::Text;
+    ///
+    /// let port = Text::new("port").probe(|value| {
+    ///     let port = value.parse::<u16>().ok()?;
+    ///     let free = TcpListener::bind(("127.0.0.1", port)).is_ok();
+    ///     Some(format!("{} appears {}", port, if free { "free" } else { "taken" }))
+    /// });
+    /// ```
+    pub fn probe<F>(mut self, probe: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.probe = Some(Arc::new(probe));
+        self
+    }
+    /// Arranges label, help & error of field with `layout` instead of the one
+    /// of form (see [FormView::field_layout]).
+    ///
+    /// [FormView::field_layout]: ../form/struct.FormView.html#method.field_layout
+    pub fn layout<L: FieldLayout + 'static>(mut self, layout: L) -> Self {
+        self.layout = Some(Rc::new(layout));
+        self
+    }
     /// Checks if Field is required
     pub fn is_required(&self) -> bool {
         self.validators
@@ -106,17 +367,262 @@ impl<W: WidgetManager, T> Field<W, T> {
 /// Container for field's errors
 pub type FieldErrors = Vec<String>;
 
+/// Formats `errors` as a message shown below field's widget.
+///
+/// Many errors are rendered as a bulleted list preceded by their count.
+///
+/// # Examples
+///
+/// ```
+/// use fui::fields::format_errors;
+///
+/// assert_eq!(format_errors(&["Field is required".to_string()]), "Field is required");
+/// assert_eq!(
+///     format_errors(&["first".to_string(), "second".to_string()]),
+///     "2 errors:\n• first\n• second"
+/// );
+/// ```
+pub fn format_errors(errors: &[String]) -> String {
+    match errors.len() {
+        0 => "".to_string(),
+        1 => errors[0].clone(),
+        count => {
+            let items = errors
+                .iter()
+                .map(|e| format!("• {}", e))
+                .collect::<Vec<String>>();
+            format!("{} errors:\n{}", count, items.join("\n"))
+        }
+    }
+}
+
+/// [Validator] used only when other field's value fulfills a predicate.
+///
+/// Created by [Field::validator_if].
+///
+/// [Validator]: ../validators/trait.Validator.html
+/// [Field::validator_if]: struct.Field.html#method.validator_if
+#[derive(Clone)]
+pub struct ValidatorIf {
+    other_label: String,
+    predicate: Rc<dyn Fn(&str) -> bool>,
+    validator: Rc<dyn Validator>,
+}
+
+impl ValidatorIf {
+    /// Validates `data` if condition is met for `form`, otherwise `data` is valid.
+    pub fn validate(&self, data: &str, form: &FormSnapshot) -> Option<String> {
+        if form
+            .get(&self.other_label)
+            .is_some_and(|v| (self.predicate)(v))
+        {
+            self.validator.validate(data)
+        } else {
+            None
+        }
+    }
+    /// Describes rule of the validator together with field it depends on.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} (depending on {})",
+            self.validator.describe(),
+            self.other_label
+        )
+    }
+}
+
+/// Settings shared by all kinds of fields, set by builders of [Field].
+///
+/// It's implemented once for every [Field], so [FormField]s don't have to repeat it.
+///
+/// Breaking change: it's required by [FormField], so fields which aren't built on [Field]
+/// have to implement it too.
+///
+/// [Field]: struct.Field.html
+/// [FormField]: trait.FormField.html
+pub trait FieldCommon {
+    /// Gets key of `field`'s value in submitted data & name of its CLI arg.
+    fn get_key(&self) -> &str;
+    /// Gets old keys of `field`'s value, still accepted in data & CLI args.
+    fn get_aliases(&self) -> &[String];
+    /// Gets version since which `field` is deprecated & the note about it.
+    fn get_deprecation(&self) -> Option<(&str, &str)>;
+    /// Gets validators of field.
+    fn get_validators(&self) -> &[Rc<dyn Validator>];
+    /// Gets validators which depend on other fields.
+    fn get_validators_if(&self) -> &[ValidatorIf];
+    /// Checks if field's value is sensitive.
+    fn is_sensitive(&self) -> bool;
+    /// Gets explicit position of field in `Tab` order.
+    fn get_tab_index(&self) -> Option<usize>;
+    /// Checks if field should be focused when form opens.
+    fn is_autofocus(&self) -> bool;
+    /// Gets probe showing hint about `field`'s value (see [Field::probe]).
+    ///
+    /// [Field::probe]: struct.Field.html#method.probe
+    fn get_probe(&self) -> Option<&Probe>;
+    /// Gets layout arranging label, help & error of field, `None` leaves it to form.
+    fn get_layout(&self) -> Option<Rc<dyn FieldLayout>>;
+    /// Checks if initial value of field was given by the app.
+    fn has_initial(&self) -> bool {
+        false
+    }
+}
+
+impl<W: WidgetManager, T> FieldCommon for Field<W, T> {
+    fn get_key(&self) -> &str {
+        self.data_key()
+    }
+
+    fn get_aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn get_deprecation(&self) -> Option<(&str, &str)> {
+        self.deprecation()
+    }
+
+    fn get_validators(&self) -> &[Rc<dyn Validator>] {
+        &self.validators
+    }
+
+    fn get_validators_if(&self) -> &[ValidatorIf] {
+        &self.validators_if
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    fn get_tab_index(&self) -> Option<usize> {
+        self.tab_index
+    }
+
+    fn is_autofocus(&self) -> bool {
+        self.autofocus
+    }
+
+    fn get_probe(&self) -> Option<&Probe> {
+        self.probe.as_ref()
+    }
+
+    fn get_layout(&self) -> Option<Rc<dyn FieldLayout>> {
+        self.layout.clone()
+    }
+
+    fn has_initial(&self) -> bool {
+        self.own_initial
+    }
+}
+
 /// Covers communication from `Form` to `Field`.
-pub trait FormField {
+///
+/// Shared settings (key, validators, sensitivity, ...) come from [FieldCommon].
+///
+/// [FieldCommon]: trait.FieldCommon.html
+pub trait FormField: FieldCommon {
     /// Builds `widget` representing this `field`.
     fn build_widget(&self) -> views::BoxedView {
         let view = self
             .get_widget_manager()
             .build_value_view(&self.get_initial());
-        label_with_help_layout(view, self.get_label(), &self.get_help())
+        match self.get_layout() {
+            Some(layout) => build_layout(&*layout, view, self.get_label(), &self.get_help()),
+            None => label_with_help_layout(view, self.get_label(), &self.get_help()),
+        }
     }
     /// Validates `data`.
     fn validate(&self, data: &str) -> Result<Value, FieldErrors>;
+    /// Validates `data` together with validators depending on other fields of `form`.
+    fn validate_in_form(&self, data: &str, form: &FormSnapshot) -> Result<Value, FieldErrors> {
+        let mut cond_errors = self
+            .get_validators_if()
+            .iter()
+            .filter_map(|v| v.validate(data, form))
+            .collect::<FieldErrors>();
+        match self.validate(data) {
+            Ok(_) if !cond_errors.is_empty() => Err(cond_errors),
+            Ok(value) => Ok(value),
+            Err(mut errors) => {
+                errors.append(&mut cond_errors);
+                Err(errors)
+            }
+        }
+    }
+    /// Converts submitted `value` back to data understood by field's widget.
+    ///
+    /// It's a reverse of [validate], used when a form is filled with existing data.
+    ///
+    /// [validate]: #tymethod.validate
+    fn value2str(&self, value: &Value) -> String {
+        match value {
+            Value::String(s) => s.to_owned(),
+            Value::Array(items) => items
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_owned()).unwrap_or(v.to_string()))
+                .collect::<Vec<String>>()
+                .join(","),
+            Value::Null => "".to_string(),
+            v => v.to_string(),
+        }
+    }
+    /// Describes field's data as [JSON Schema].
+    ///
+    /// [JSON Schema]: https://json-schema.org
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string" });
+        if self.is_required() {
+            schema["minLength"] = json!(1);
+        }
+        if let Some(values) = allowed_values(self.get_validators()) {
+            schema["enum"] = json!(values);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+    /// Makes field parse & show its value in format of `locale` (like `de-DE`),
+    /// submitted value stays in canonical form.
+    ///
+    /// Returns `false` when field doesn't depend on locale.
+    fn set_locale(&mut self, _locale: &str) -> bool {
+        false
+    }
+    /// Describes rules enforced by field's validators in human terms,
+    /// preceded by field's deprecation (if any).
+    fn describe_rules(&self) -> Vec<String> {
+        self.get_deprecation()
+            .map(|(since, note)| format!("deprecated since {}: {}", since, note))
+            .into_iter()
+            .chain(self.get_validators().iter().map(|v| v.describe()))
+            .chain(self.get_validators_if().iter().map(|v| v.describe()))
+            .collect()
+    }
+    /// Builds help of [clap::Arg] from field's help followed by its [rules].
+    ///
+    /// [clap::Arg]: ../../clap/struct.Arg.html
+    /// [rules]: #method.describe_rules
+    fn cli_help(&self) -> String {
+        let rules = self.describe_rules();
+        match (self.get_help().is_empty(), rules.is_empty()) {
+            (_, true) => self.get_help().to_owned(),
+            (true, false) => format!("[{}]", rules.join("; ")),
+            (false, false) => format!("{} [{}]", self.get_help(), rules.join("; ")),
+        }
+    }
+    /// Gets question user confirms in TUI before [prepare_submit] is run for `value`
+    /// (e.g. if dirs needed by it should be created).
+    ///
+    /// [prepare_submit]: #method.prepare_submit
+    fn confirm_submit(&self, _value: &Value) -> Option<String> {
+        None
+    }
+    /// Prepares environment for validated `value` just before the form is submitted
+    /// (e.g. creates dirs needed by it).
+    fn prepare_submit(&self, _value: &Value) -> Result<(), String> {
+        Ok(())
+    }
     /// Gets `field`'s label.
     fn get_label(&self) -> &str;
     /// Gets `field`'s help
@@ -138,13 +644,26 @@ pub trait FormField {
     fn is_required(&self) -> bool;
     /// Sets `error` on widget.
     fn set_error(&self, viewbox: &mut views::BoxedView, error: &str) {
-        let layout: &mut views::LinearLayout = (**viewbox).as_any_mut().downcast_mut().unwrap();
-        let child: &mut dyn View = (*layout).get_child_mut(2).unwrap();
-        let text: &mut views::TextView = (*child).as_any_mut().downcast_mut().unwrap();
-        text.set_content(error);
+        if let Some(text) = error_view_from_layout_mut(viewbox) {
+            text.set_content(error);
+        }
     }
 }
 
+/// Builds label of option `value` followed by its dimmed `description`.
+pub(crate) fn option_label(value: &str, description: Option<&str>) -> StyledString {
+    let mut label = StyledString::plain(value);
+    if let Some(description) = description {
+        label.append_styled(format!(" — {}", description), ColorStyle::secondary());
+    }
+    label
+}
+
+/// Finds values allowed by the first `validator` limiting data to a fixed set.
+pub(crate) fn allowed_values(validators: &[Rc<dyn Validator>]) -> Option<Vec<String>> {
+    validators.iter().filter_map(|v| v.allowed_values()).next()
+}
+
 fn format_annotation(label: &str, help: &str) -> String {
     if help.len() > 0 {
         format!("{:20}: {}", label, help)
@@ -159,19 +678,229 @@ pub fn label_with_help_layout(
     label: &str,
     help: &str,
 ) -> views::BoxedView {
-    let text = format_annotation(label, help);
-    let widget = views::LinearLayout::vertical()
-        .child(views::TextView::new(text))
-        .child(view_box)
-        .child(views::TextView::new(""))
-        .child(views::DummyView);
+    AnnotationAbove.build(view_box, label, help)
+}
 
-    views::BoxedView::new(Box::new(widget))
+/// Arranges label, help & error around value view of field.
+///
+/// Built widget should be a [LinearLayout] with annotation ([TextView]), value view
+/// and error ([TextView]) as its first three children and a spacer as the last one,
+/// so form can show errors, hints and change density. Building a field with a layout
+/// missing the value view or the error panics.
+///
+/// Closures taking value view, label & help are layouts too.
+///
+/// # Examples
+///
+/// ```
+/// extern crate cursive;
+/// extern crate fui;
+///
+/// use cursive::views::{BoxedView, DummyView, LinearLayout, TextView};
+/// use fui::fields::{AnnotationLeft, Text};
+/// use fui::form::FormView;
+///
+/// # fn main() {
+/// let shouting = |value: BoxedView, label: &str, _help: &str| {
+///     let widget = LinearLayout::vertical()
+///         .child(TextView::new(label.to_uppercase()))
+///         .child(value)
+///         .child(TextView::new(""))
+///         .child(DummyView);
+///     BoxedView::new(Box::new(widget))
+/// };
+/// let form = FormView::new()
+///     .field_layout(AnnotationLeft)
+///     .field(Text::new("user"))
+///     .field(Text::new("password").layout(shouting));
+/// # }
+/// ```
+///
+/// [LinearLayout]: ../../cursive/views/struct.LinearLayout.html
+/// [TextView]: ../../cursive/views/struct.TextView.html
+pub trait FieldLayout {
+    /// Builds widget of field labeled `label` around its `value` view.
+    fn build(&self, value: views::BoxedView, label: &str, help: &str) -> views::BoxedView;
+    /// Formats text of annotation, used when form changes density.
+    fn annotation(&self, label: &str, help: &str) -> String {
+        format_annotation(label, help)
+    }
+    /// Builds spacer putting `rows` blank rows below widget, used when form changes density.
+    fn spacer(&self, rows: usize) -> views::BoxedView {
+        views::BoxedView::new(Box::new(views::DummyView.fixed_height(rows)))
+    }
+}
+
+impl<F> FieldLayout for F
+where
+    F: Fn(views::BoxedView, &str, &str) -> views::BoxedView,
+{
+    fn build(&self, value: views::BoxedView, label: &str, help: &str) -> views::BoxedView {
+        self(value, label, help)
+    }
+}
+
+/// Layout with label & help in a row above the value, error below it (default one).
+#[derive(Clone, Copy, Debug)]
+pub struct AnnotationAbove;
+
+impl FieldLayout for AnnotationAbove {
+    fn build(&self, value: views::BoxedView, label: &str, help: &str) -> views::BoxedView {
+        let widget = views::LinearLayout::vertical()
+            .child(views::TextView::new(self.annotation(label, help)))
+            .child(value)
+            .child(views::TextView::new(""))
+            .child(self.spacer(1));
+        views::BoxedView::new(Box::new(widget))
+    }
+}
+
+/// Layout with label left to the value and error right to it.
+///
+/// Help isn't shown, so it suits forms with self-explanatory labels.
+#[derive(Clone, Copy, Debug)]
+pub struct AnnotationLeft;
+
+impl FieldLayout for AnnotationLeft {
+    fn build(&self, value: views::BoxedView, label: &str, help: &str) -> views::BoxedView {
+        let widget = views::LinearLayout::horizontal()
+            .child(views::TextView::new(self.annotation(label, help)))
+            .child(value)
+            .child(views::TextView::new(""))
+            .child(self.spacer(1));
+        views::BoxedView::new(Box::new(widget))
+    }
+    fn annotation(&self, label: &str, _help: &str) -> String {
+        format!("{:20}: ", label)
+    }
+    fn spacer(&self, rows: usize) -> views::BoxedView {
+        // spacer is beside the value, so it covers its row too
+        views::BoxedView::new(Box::new(views::DummyView.fixed_height(rows + 1)))
+    }
+}
+
+/// Layout without label & help, only the value with error below it.
+#[derive(Clone, Copy, Debug)]
+pub struct NoAnnotation;
+
+impl FieldLayout for NoAnnotation {
+    fn build(&self, value: views::BoxedView, label: &str, help: &str) -> views::BoxedView {
+        let widget = views::LinearLayout::vertical()
+            .child(views::TextView::new(self.annotation(label, help)))
+            .child(value)
+            .child(views::TextView::new(""))
+            .child(self.spacer(1));
+        views::BoxedView::new(Box::new(widget))
+    }
+    fn annotation(&self, _label: &str, _help: &str) -> String {
+        "".to_string()
+    }
+}
+
+const LAYOUT_STRUCTURE: &str = "FieldLayout should build a LinearLayout \
+     with value view and error TextView as its second and third children";
+
+/// Builds widget of field with `layout`, panics if it misses the value view or the error.
+pub(crate) fn build_layout(
+    layout: &dyn FieldLayout,
+    value: views::BoxedView,
+    label: &str,
+    help: &str,
+) -> views::BoxedView {
+    let mut widget = layout.build(value, label, help);
+    if find_value_view(&widget).is_none() || error_view_from_layout_mut(&mut widget).is_none() {
+        panic!("{} (field `{}`)", LAYOUT_STRUCTURE, label);
+    }
+    widget
+}
+
+fn find_value_view(layout: &views::BoxedView) -> Option<&views::BoxedView> {
+    (**layout)
+        .as_any()
+        .downcast_ref::<views::LinearLayout>()
+        .and_then(|layout| layout.get_child(1))
+        .and_then(|child| child.as_any().downcast_ref())
 }
 
 /// Finds view storing value in widget layout
 pub fn value_view_from_layout(layout: &views::BoxedView) -> &views::BoxedView {
-    let layout: &views::LinearLayout = (**layout).as_any().downcast_ref().unwrap();
-    let value_view: &dyn View = layout.get_child(1).unwrap();
-    (*value_view).as_any().downcast_ref().unwrap()
+    find_value_view(layout).expect(LAYOUT_STRUCTURE)
+}
+
+/// Finds mutable view storing value in widget layout.
+pub fn value_view_from_layout_mut(layout: &mut views::BoxedView) -> &mut views::BoxedView {
+    (**layout)
+        .as_any_mut()
+        .downcast_mut::<views::LinearLayout>()
+        .and_then(|layout| layout.get_child_mut(1))
+        .and_then(|child| child.as_any_mut().downcast_mut())
+        .expect(LAYOUT_STRUCTURE)
+}
+
+/// Finds view showing error in widget layout.
+pub(crate) fn error_view_from_layout_mut(
+    layout: &mut views::BoxedView,
+) -> Option<&mut views::TextView> {
+    (**layout)
+        .as_any_mut()
+        .downcast_mut::<views::LinearLayout>()
+        .and_then(|layout| layout.get_child_mut(2))
+        .and_then(|child| child.as_any_mut().downcast_mut())
+}
+
+/// Shows `annotation` (see [FieldLayout::annotation]) in widget layout,
+/// returns `false` for custom layouts.
+///
+/// [FieldLayout::annotation]: trait.FieldLayout.html#method.annotation
+pub(crate) fn set_annotation(layout: &mut views::BoxedView, annotation: String) -> bool {
+    let text = (**layout)
+        .as_any_mut()
+        .downcast_mut::<views::LinearLayout>()
+        .and_then(|layout| layout.get_child_mut(0))
+        .and_then(|child| child.as_any_mut().downcast_mut::<views::TextView>());
+    match text {
+        Some(text) => {
+            text.set_content(annotation);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Replaces the last child of widget layout with `spacer` (see [FieldLayout::spacer]),
+/// returns `false` for custom layouts.
+///
+/// [FieldLayout::spacer]: trait.FieldLayout.html#method.spacer
+pub(crate) fn set_spacer(layout: &mut views::BoxedView, spacer: views::BoxedView) -> bool {
+    match (**layout)
+        .as_any_mut()
+        .downcast_mut::<views::LinearLayout>()
+    {
+        Some(layout) if layout.len() > 3 => {
+            let last = layout.len() - 1;
+            layout.remove_child(last);
+            layout.add_child(spacer);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Puts `hint_view` below error in widget layout, returns `false` for custom layouts.
+pub(crate) fn insert_hint_view<V: View>(layout: &mut views::BoxedView, hint_view: V) -> bool {
+    match (**layout)
+        .as_any_mut()
+        .downcast_mut::<views::LinearLayout>()
+    {
+        Some(layout) if layout.len() > 3 => {
+            layout.insert_child(3, hint_view);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Replaces view storing value in widget layout with `value_view`.
+pub fn replace_value_view(layout: &mut views::BoxedView, value_view: views::BoxedView) {
+    *value_view_from_layout_mut(layout) = value_view;
 }
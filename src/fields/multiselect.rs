@@ -2,17 +2,22 @@ use std::ops::Deref;
 use std::rc::Rc;
 
 use clap;
+use cursive::utils::markup::StyledString;
 use cursive::views::BoxedView;
 use serde_json::value::Value;
 
 use feeders::{DummyFeeder, Feeder};
 use fields;
 use fields::{label_with_help_layout, Field, FieldErrors, FormField, WidgetManager};
+use ranking::Ranker;
 use views;
+use views::ItemRenderer;
 
-const VALUE_SEP: &'static str = ",";
+pub(crate) const VALUE_SEP: &'static str = ",";
 
 /// Convienient wrapper around `Field<MultiselectManager, Vec<String>>`.
+///
+/// Nothing selected is submitted as an empty list (`[]`), formerly it was `[""]`.
 pub struct Multiselect;
 
 impl Multiselect {
@@ -30,6 +35,11 @@ impl Multiselect {
 pub struct MultiselectManager {
     feeder: Rc<dyn Feeder>,
     view_factory: Option<Rc<dyn Fn() -> views::Multiselect>>,
+    ranker: Option<Rc<dyn Ranker>>,
+    item_renderer: Option<ItemRenderer>,
+    chips: bool,
+    #[cfg(feature = "watch")]
+    watch: bool,
 }
 
 impl MultiselectManager {
@@ -45,6 +55,11 @@ impl MultiselectManager {
         MultiselectManager {
             feeder: Rc::new(feeder),
             view_factory: None,
+            ranker: None,
+            item_renderer: None,
+            chips: false,
+            #[cfg(feature = "watch")]
+            watch: false,
         }
     }
     /// Creates an instance with customized [views::Multiselect].
@@ -60,19 +75,72 @@ impl MultiselectManager {
             // it should be an option of Rc :)
             feeder: Rc::new(DummyFeeder),
             view_factory: Some(factory),
+            ranker: None,
+            item_renderer: None,
+            chips: false,
+            #[cfg(feature = "watch")]
+            watch: false,
         }
     }
 
     fn get_view(&self) -> views::Multiselect {
-        let view = if let Some(ref fun) = self.view_factory {
+        let mut view = if let Some(ref fun) = self.view_factory {
             fun()
         } else {
             views::Multiselect::new(Rc::clone(&self.feeder))
         };
+        if let Some(ref ranker) = self.ranker {
+            view.set_ranker(Rc::clone(ranker));
+        }
+        if let Some(ref renderer) = self.item_renderer {
+            view.set_item_renderer(Rc::clone(renderer));
+        }
+        if self.chips {
+            view.set_chips(true);
+        }
+        #[cfg(feature = "watch")]
+        view.set_watch(self.watch);
         view
     }
 }
 
+impl Field<MultiselectManager, Vec<String>> {
+    /// Renders listed suggestions with `renderer` instead of plain text
+    /// (see [views::Autocomplete::item_renderer]).
+    ///
+    /// [views::Autocomplete::item_renderer]: ../views/struct.Autocomplete.html#method.item_renderer
+    pub fn item_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&str) -> StyledString + 'static,
+    {
+        self.widget_manager.item_renderer = Some(Rc::new(renderer));
+        self
+    }
+
+    /// Orders suggestions with `ranker`.
+    pub fn ranker<R: Ranker>(mut self, ranker: R) -> Self {
+        self.widget_manager.ranker = Some(Rc::new(ranker));
+        self
+    }
+
+    /// Shows selected items as compact chips wrapped on lines.
+    pub fn chips(mut self) -> Self {
+        self.widget_manager.chips = true;
+        self
+    }
+
+    /// Refreshes suggestions when directory they come from changes, so files created
+    /// while the form is open are listed (see [views::Autocomplete::watch]).
+    /// Requires `watch` feature.
+    ///
+    /// [views::Autocomplete::watch]: ../views/struct.Autocomplete.html#method.watch
+    #[cfg(feature = "watch")]
+    pub fn watch(mut self) -> Self {
+        self.widget_manager.watch = true;
+        self
+    }
+}
+
 impl WidgetManager for MultiselectManager {
     fn build_value_view(&self, initial: &str) -> BoxedView {
         let mut widget = self.get_view();
@@ -100,6 +168,19 @@ impl WidgetManager for MultiselectManager {
             .collect();
         result.join(VALUE_SEP)
     }
+    fn set_masked(&self, view_box: &mut BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let ms: &mut views::Multiselect = (**view_box).as_any_mut().downcast_mut().unwrap();
+        ms.set_masked(masked);
+    }
+    fn set_max_dropdown_rows(&self, view_box: &mut BoxedView, rows: Option<u8>) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let ms: &mut views::Multiselect = (**view_box).as_any_mut().downcast_mut().unwrap();
+        ms.set_max_shown_count(rows);
+    }
+    fn suggest(&self, text: &str, count: usize) -> Vec<String> {
+        self.feeder.query(text, 0, count)
+    }
 }
 
 impl FormField for Field<MultiselectManager, Vec<String>> {
@@ -119,6 +200,10 @@ impl FormField for Field<MultiselectManager, Vec<String>> {
         if errors.len() > 0 {
             Err(errors)
         } else {
+            // nothing selected is an empty list, not a list with empty item
+            if data.is_empty() {
+                return Ok(Value::Array(Vec::new()));
+            }
             let vec_str = items
                 .iter()
                 .map(|x| Value::String(x.to_string()))
@@ -141,8 +226,8 @@ impl FormField for Field<MultiselectManager, Vec<String>> {
     }
 
     fn clap_arg(&self) -> clap::Arg {
-        clap::Arg::with_name(&self.label)
-            .long(&self.label)
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
             .help(&self.help)
             .required(self.is_required())
             .multiple(true)
@@ -151,13 +236,28 @@ impl FormField for Field<MultiselectManager, Vec<String>> {
 
     fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
         let values = args
-            .values_of(&self.label)
+            .values_of(self.data_key())
             .unwrap_or(clap::Values::default());
         values.collect::<Vec<&str>>().join(VALUE_SEP)
     }
     fn is_required(&self) -> bool {
         self.is_required()
     }
+
+    fn json_schema(&self) -> Value {
+        let mut items = json!({ "type": "string" });
+        if let Some(values) = fields::allowed_values(&self.validators) {
+            items["enum"] = json!(values);
+        }
+        let mut schema = json!({ "type": "array", "items": items });
+        if self.is_required() {
+            schema["minItems"] = json!(1);
+        }
+        if !self.help.is_empty() {
+            schema["description"] = json!(self.help);
+        }
+        schema
+    }
 }
 
 impl<W: WidgetManager> Field<W, Vec<String>> {
@@ -167,6 +267,19 @@ impl<W: WidgetManager> Field<W, Vec<String>> {
             .iter()
             .map(|x| (*x).to_string())
             .collect::<Vec<String>>();
+        self.own_initial = true;
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_selected_is_submitted_as_empty_list() {
+        let field = Multiselect::new("files", vec!["a", "b"]);
+        assert_eq!(field.validate(""), Ok(json!([])));
+        assert_eq!(field.validate("a,b"), Ok(json!(["a", "b"])));
+    }
+}
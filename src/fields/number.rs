@@ -0,0 +1,181 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use locales::number_separators;
+use views::number;
+
+/// Convienient wrapper around `Field<NumberManager, String>`.
+///
+/// Number adjustable with `[-]` `[+]` buttons (or `-` and `+` keys),
+/// typed in format of form's locale and submitted as a JSON number.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, Number};
+///
+/// # fn main() {
+/// let field = Number::new("ratio").step(0.1);
+/// assert_eq!(field.validate("0.5"), Ok(json!(0.5)));
+/// assert_eq!(field.validate("3"), Ok(json!(3)));
+/// assert!(field.validate("three").is_err());
+/// # }
+/// ```
+pub struct Number;
+
+impl Number {
+    /// Creates a new `Field<NumberManager, String>` changed by 1 with buttons.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<NumberManager, String> {
+        let manager = NumberManager {
+            step: 1.0,
+            acceleration: 10.0,
+            locale: "en-US".to_string(),
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct NumberManager {
+    step: f64,
+    acceleration: f64,
+    locale: String,
+}
+
+impl NumberManager {
+    fn separators(&self) -> (char, char) {
+        number_separators(&self.locale)
+    }
+}
+
+impl fields::Field<NumberManager, String> {
+    /// Sets how much the number changes with a button (1 by default).
+    pub fn step(mut self, step: f64) -> Self {
+        self.widget_manager.step = step;
+        self
+    }
+
+    /// Sets how many times the step grows after every 10 quickly repeated presses
+    /// (10 by default, 1 disables it).
+    pub fn acceleration(mut self, acceleration: f64) -> Self {
+        self.widget_manager.acceleration = acceleration;
+        self
+    }
+}
+
+impl WidgetManager for NumberManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Number = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let (group, decimal) = self.separators();
+        let view = ::views::Number::new()
+            .separators(group, decimal)
+            .step(self.step)
+            .acceleration(self.acceleration)
+            .content(value);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Number = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<NumberManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        let (group, decimal) = self.widget_manager.separators();
+        // integers stay integers (`3` not `3.0`)
+        if let Some(Ok(integer)) = number::normalize(data, group, decimal).map(|n| n.parse::<i64>())
+        {
+            return Ok(json!(integer));
+        }
+        number::parse(data, group, decimal)
+            .map(|number| json!(number))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match *value {
+            Value::Number(ref n) => {
+                let (_, decimal) = self.widget_manager.separators();
+                n.to_string().replace('.', &decimal.to_string())
+            }
+            // kept as is, so validation reports invalid ones
+            Value::String(ref s) => s.to_owned(),
+            _ => "".to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "number" });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+
+    fn set_locale(&mut self, locale: &str) -> bool {
+        self.widget_manager.locale = locale.to_string();
+        true
+    }
+}
@@ -0,0 +1,163 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+
+/// Convienient wrapper around `Field<NumberListManager, String>`.
+///
+/// List of numbers separated by commas or spaces (like `80, 443 8080`),
+/// submitted as a JSON array of numbers. Each invalid item is reported
+/// with its position.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, NumberList};
+///
+/// # fn main() {
+/// let field = NumberList::new("ports");
+/// assert_eq!(field.validate("80, 443 8080"), Ok(json!([80, 443, 8080])));
+/// assert_eq!(field.validate("0.5,-1"), Ok(json!([0.5, -1])));
+/// assert_eq!(
+///     field.validate("80, 44e, 8o8o"),
+///     Err(vec![
+///         "Item 2 is not a number: 44e".to_string(),
+///         "Item 3 is not a number: 8o8o".to_string(),
+///     ])
+/// );
+/// assert_eq!(field.value2str(&json!([80, 443])), "80, 443");
+/// # }
+/// ```
+pub struct NumberList;
+
+impl NumberList {
+    /// Creates a new `Field<NumberListManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<NumberListManager, String> {
+        fields::Field::new(label, NumberListManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct NumberListManager;
+
+impl WidgetManager for NumberListManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let edit: &views::EditView = (**view_box).as_any().downcast_ref().unwrap();
+        (*edit.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(views::EditView::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let edit: &mut views::EditView = (**view_box).as_any_mut().downcast_mut().unwrap();
+        edit.set_secret(masked);
+    }
+}
+
+/// Parses `item` as a finite number, integers stay integers (`3` not `3.0`).
+fn parse_item(item: &str) -> Option<Value> {
+    if let Ok(integer) = item.parse::<i64>() {
+        return Some(json!(integer));
+    }
+    match item.parse::<f64>() {
+        Ok(number) if number.is_finite() => Some(json!(number)),
+        _ => None,
+    }
+}
+
+impl fields::FormField for fields::Field<NumberListManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let mut numbers = Vec::new();
+        let items = data
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|item| !item.is_empty());
+        for (idx, item) in items.enumerate() {
+            match parse_item(item) {
+                Some(number) => numbers.push(number),
+                None => errors.push(format!("Item {} is not a number: {}", idx + 1, item)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(Value::Array(numbers))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .allow_hyphen_values(true)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match value.as_array() {
+            Some(items) => items
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+            None => "".to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "array", "items": { "type": "number" } });
+        if self.is_required() {
+            schema["minItems"] = json!(1);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
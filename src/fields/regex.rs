@@ -0,0 +1,142 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::regex;
+
+/// Convienient wrapper around `Field<RegexManager, String>`.
+///
+/// Regular expression which must compile. Optionally what it captures
+/// in a sample text is shown below while it's typed (see [sample]).
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, Regex};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = Regex::new("pattern").sample("release-1.2.3");
+/// assert_eq!(field.validate(r"(\d+)\.(\d+)"), Ok(Value::from(r"(\d+)\.(\d+)")));
+/// assert_eq!(
+///     field.validate("release-(1"),
+///     Err(vec!["Invalid regex: unclosed group".to_string()])
+/// );
+/// # }
+/// ```
+///
+/// [sample]: ../fields/struct.Field.html#method.sample
+pub struct Regex;
+
+impl Regex {
+    /// Creates a new `Field<RegexManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<RegexManager, String> {
+        fields::Field::new(label, RegexManager { sample: None }, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct RegexManager {
+    sample: Option<String>,
+}
+
+impl fields::Field<RegexManager, String> {
+    /// Shows what the typed pattern captures in `sample` (or why it doesn't compile).
+    pub fn sample<IS: Into<String>>(mut self, sample: IS) -> Self {
+        self.widget_manager.sample = Some(sample.into());
+        self
+    }
+}
+
+impl WidgetManager for RegexManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Regex = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let mut view = ::views::Regex::new();
+        if let Some(ref sample) = self.sample {
+            view = view.sample(sample.as_str());
+        }
+        views::BoxedView::new(Box::new(view.content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Regex = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<RegexManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        regex::parse(data)
+            .map(|_| Value::String(data.to_string()))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        value.as_str().unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string", "format": "regex" });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
@@ -0,0 +1,292 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use utils::expand_path;
+use views::save_path::missing_parent;
+
+/// Convienient wrapper around `Field<SavePathManager, String>`.
+///
+/// Suggests files and dirs like [Autocomplete] with [DirItems] but also:
+///
+/// * notices (without blocking) that a typed file exists and will be overwritten,
+/// * offers creating missing parent dirs when the form is submitted (see [create_dirs]),
+/// * ensures the target dir is writable.
+///
+/// [Autocomplete]: struct.Autocomplete.html
+/// [DirItems]: ../feeders/struct.DirItems.html
+/// [create_dirs]: struct.Field.html#method.create_dirs
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, SavePath};
+///
+/// # fn main() {
+/// let field = SavePath::new("output");
+/// assert!(field.validate("./target/report.txt").is_ok());
+/// assert!(field.validate("./src").is_err());
+/// # }
+/// ```
+pub struct SavePath;
+
+impl SavePath {
+    /// Creates a new `Field<SavePathManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<SavePathManager, String> {
+        let manager = SavePathManager {
+            create_dirs: true,
+            picker: None,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct SavePathManager {
+    create_dirs: bool,
+    picker: Option<String>,
+}
+
+impl fields::Field<SavePathManager, String> {
+    /// Offers creating missing parent dirs on submit when `enabled` (default),
+    /// otherwise paths inside missing dirs are invalid.
+    ///
+    /// Form in TUI asks before creating them (submitting is cancelled when refused),
+    /// CLI creates them right away as there's nobody to ask.
+    pub fn create_dirs(mut self, enabled: bool) -> Self {
+        self.widget_manager.create_dirs = enabled;
+        self
+    }
+
+    /// Picks path with external `command` (like `fzf` or `ranger --choosefile={file}`)
+    /// run with `Ctrl+o`, the built-in suggestions stay available
+    /// (see [views::SavePath::picker]).
+    ///
+    /// [views::SavePath::picker]: ../views/struct.SavePath.html#method.picker
+    pub fn picker<S: Into<String>>(mut self, command: S) -> Self {
+        self.widget_manager.picker = Some(command.into());
+        self
+    }
+}
+
+impl WidgetManager for SavePathManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::SavePath = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_value()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let mut view = ::views::SavePath::new();
+        if let Some(ref command) = self.picker {
+            view = view.picker(command.as_str());
+        }
+        views::BoxedView::new(Box::new(view.value(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::SavePath = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+/// Checks if current user may create files in `dir`.
+///
+/// It's checked on every change, so nothing is written there; failures not visible
+/// in permissions (like read-only mounts) show up when the file is saved.
+#[cfg(unix)]
+fn is_writable(dir: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    match CString::new(dir.as_os_str().as_bytes()) {
+        // SAFETY: `path` is a valid nul-terminated string living through the call
+        Ok(path) => unsafe { libc::access(path.as_ptr(), libc::W_OK | libc::X_OK) == 0 },
+        Err(_) => false,
+    }
+}
+
+/// Checks if permissions of `dir` allow creating files in it.
+#[cfg(not(unix))]
+fn is_writable(dir: &Path) -> bool {
+    fs::metadata(dir)
+        .map(|m| !m.permissions().readonly())
+        .unwrap_or(false)
+}
+
+/// Returns the nearest dir of `path` which exists.
+fn existing_dir(path: &Path) -> PathBuf {
+    path.ancestors()
+        .skip(1)
+        .map(|p| {
+            if p.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                p
+            }
+        })
+        .find(|p| p.exists())
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+impl fields::Field<SavePathManager, String> {
+    /// Gets missing parent dir of submitted `value` which should be created.
+    fn dir_to_create(&self, value: &Value) -> Option<PathBuf> {
+        let path = match value.as_str() {
+            Some(path) if !path.is_empty() && self.widget_manager.create_dirs => {
+                PathBuf::from(expand_path(path))
+            }
+            _ => return None,
+        };
+        path.parent()
+            .filter(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+            .map(|dir| dir.to_path_buf())
+    }
+}
+
+impl fields::FormField for fields::Field<SavePathManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        let path = PathBuf::from(expand_path(data));
+        if path.is_dir() {
+            return Err(vec!["It's a dir".to_string()]);
+        }
+        if let Some(dir) = missing_parent(&path) {
+            if !self.widget_manager.create_dirs {
+                return Err(vec![format!("Dir {} doesn't exist", dir.display())]);
+            }
+        }
+        let dir = existing_dir(&path);
+        if !dir.is_dir() {
+            return Err(vec![format!("{} is not a dir", dir.display())]);
+        }
+        if !is_writable(&dir) {
+            return Err(vec![format!("Dir {} is not writable", dir.display())]);
+        }
+        Ok(Value::String(data.to_owned()))
+    }
+
+    fn confirm_submit(&self, value: &Value) -> Option<String> {
+        self.dir_to_create(value)
+            .map(|dir| format!("Create dir {}?", dir.display()))
+    }
+
+    fn prepare_submit(&self, value: &Value) -> Result<(), String> {
+        match self.dir_to_create(value) {
+            Some(dir) => fs::create_dir_all(&dir)
+                .map_err(|e| format!("Can't create dir {}: {}", dir.display(), e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::FormField;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn missing_dirs_are_created_on_submit() {
+        let root = env::temp_dir().join(format!("fui-save-path-{}", process::id()));
+        let target = root.join("nested/out.txt");
+        let data = target.to_str().unwrap();
+
+        let strict = SavePath::new("out").create_dirs(false);
+        assert_eq!(
+            strict.validate(data).unwrap_err(),
+            vec![format!("Dir {} doesn't exist", root.display())]
+        );
+
+        let field = SavePath::new("out");
+        let value = field.validate(data).unwrap();
+        let question = format!("Create dir {}?", target.parent().unwrap().display());
+        assert_eq!(field.confirm_submit(&value), Some(question));
+        field.prepare_submit(&value).unwrap();
+        assert_eq!(field.confirm_submit(&value), None);
+        assert!(target.parent().unwrap().is_dir());
+        assert!(!target.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn validation_leaves_dir_untouched() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = env::temp_dir().join(format!("fui-save-path-ro-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let data = root.join("out.txt");
+        let field = SavePath::new("out");
+
+        assert!(field.validate(data.to_str().unwrap()).is_ok());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o555)).unwrap();
+        // root may write anywhere
+        if unsafe { libc::geteuid() } != 0 {
+            assert_eq!(
+                field.validate(data.to_str().unwrap()).unwrap_err(),
+                vec![format!("Dir {} is not writable", root.display())]
+            );
+        }
+        fs::set_permissions(&root, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
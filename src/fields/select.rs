@@ -0,0 +1,159 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use feeders::Feeder;
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use validators::OneOf;
+use views::Masked;
+
+/// Options taken from feeder at most.
+const OPTIONS_LIMIT: usize = 100;
+
+/// Convienient wrapper around `Field<SelectManager, String>`.
+///
+/// Exactly one of fixed options picked from a popup list, nothing else can be typed
+/// (unlike [Autocomplete] with [OneOf] validator). The first option is picked
+/// unless [initial] value is given.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, Select};
+/// use fui::form::FormView;
+///
+/// # fn main() {
+/// let field = Select::new("format", vec!["json", "yaml", "toml"]);
+/// assert_eq!(field.validate("yaml"), Ok(json!("yaml")));
+/// assert!(field.validate("xml").is_err());
+///
+/// let mut form = FormView::new().field(Select::new("format", vec!["json", "yaml"]));
+/// assert_eq!(form.validate().unwrap(), json!({"format": "json"}));
+/// # }
+/// ```
+///
+/// [Autocomplete]: struct.Autocomplete.html
+/// [OneOf]: ../validators/struct.OneOf.html
+/// [initial]: ../fields/struct.Field.html#method.initial
+pub struct Select;
+
+impl Select {
+    /// Creates a new `Field<SelectManager, String>` with options taken once from `feeder`
+    /// (like `Vec<String>`).
+    pub fn new<IS: Into<String>, F: Feeder>(
+        label: IS,
+        feeder: F,
+    ) -> fields::Field<SelectManager, String> {
+        let options = feeder.query("", 0, OPTIONS_LIMIT);
+        let initial = options.first().cloned().unwrap_or_default();
+        let manager = SelectManager {
+            options: options.clone(),
+        };
+        fields::Field::new(label, manager, initial).validator(OneOf(options))
+    }
+}
+
+#[derive(Clone)]
+pub struct SelectManager {
+    options: Vec<String>,
+}
+
+impl WidgetManager for SelectManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &Masked<views::SelectView> = (**view_box).as_any().downcast_ref().unwrap();
+        match view.get_inner().selection() {
+            Some(value) => (*value).clone(),
+            None => "".to_string(),
+        }
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let mut select = views::SelectView::new().popup();
+        select.add_all_str(self.options.iter());
+        if let Some(idx) = self.options.iter().position(|o| o == value) {
+            let _ = select.set_selection(idx);
+        }
+        views::BoxedView::new(Box::new(Masked::new(select)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut Masked<views::SelectView> =
+            (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<SelectManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(Value::String(data.to_string()))
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::FormField;
+
+    #[test]
+    fn picked_option_is_read_from_popup() {
+        let options = vec!["json", "yaml", "toml"];
+        let field = Select::new("format", options.clone()).initial("toml");
+        let view = field.build_widget();
+        assert_eq!(field.get_widget_manager().get_value(&view), "toml");
+
+        let field = Select::new("format", options).initial("xml");
+        let view = field.build_widget();
+        assert_eq!(field.get_widget_manager().get_value(&view), "json");
+    }
+}
@@ -0,0 +1,219 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use semver::{Version, VersionReq};
+
+/// Convienient wrapper around `Field<SemVerManager, String>`.
+///
+/// Validates [semantic version] (like `1.2.3-rc.1+build.5`, optionally prefixed with `v`)
+/// and submits it normalized.
+///
+/// [semantic version]: ../semver/index.html
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, SemVer};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = SemVer::new("version");
+/// assert_eq!(field.validate(" v1.2.3-rc.1 "), Ok(Value::from("1.2.3-rc.1")));
+/// assert!(field.validate("1.2").is_err());
+/// # }
+/// ```
+pub struct SemVer;
+
+impl SemVer {
+    /// Creates a new `Field<SemVerManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<SemVerManager, String> {
+        fields::Field::new(label, SemVerManager, "".to_string())
+    }
+}
+
+/// Convienient wrapper around `Field<SemVerReqManager, String>`.
+///
+/// Validates [version requirement] (like `^1.2` or `>=1.2, <2`)
+/// and submits it normalized.
+///
+/// [version requirement]: ../semver/index.html
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, SemVerReq};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = SemVerReq::new("requirement");
+/// assert_eq!(field.validate(">= 1.2 ,< 2"), Ok(Value::from(">=1.2, <2")));
+/// assert!(field.validate("^1.*").is_err());
+/// # }
+/// ```
+pub struct SemVerReq;
+
+impl SemVerReq {
+    /// Creates a new `Field<SemVerReqManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<SemVerReqManager, String> {
+        fields::Field::new(label, SemVerReqManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct SemVerManager;
+
+impl WidgetManager for SemVerManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let edit: &views::EditView = (**view_box).as_any().downcast_ref().unwrap();
+        (*edit.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(views::EditView::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let edit: &mut views::EditView = (**view_box).as_any_mut().downcast_mut().unwrap();
+        edit.set_secret(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<SemVerManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        Version::parse(data)
+            .map(|parsed| Value::String(parsed.to_string()))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+#[derive(Clone)]
+pub struct SemVerReqManager;
+
+impl WidgetManager for SemVerReqManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let edit: &views::EditView = (**view_box).as_any().downcast_ref().unwrap();
+        (*edit.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(views::EditView::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let edit: &mut views::EditView = (**view_box).as_any_mut().downcast_mut().unwrap();
+        edit.set_secret(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<SemVerReqManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        VersionReq::parse(data)
+            .map(|parsed| Value::String(parsed.to_string()))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
@@ -0,0 +1,206 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::slider::format_value;
+
+/// Convienient wrapper around `Field<SliderManager, String>`.
+///
+/// Number from a range (like compression level 1-9) picked with a keyboard-operable
+/// slider moving by steps, submitted as a JSON number.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, Slider};
+///
+/// # fn main() {
+/// let field = Slider::new("level", 1.0, 9.0);
+/// assert_eq!(field.validate("6"), Ok(json!(6)));
+/// assert!(field.validate("10").is_err());
+///
+/// let field = Slider::new("ratio", 0.0, 1.0).step(0.25);
+/// assert_eq!(field.validate("0.75"), Ok(json!(0.75)));
+/// assert_eq!(
+///     field.validate("0.3"),
+///     Err(vec!["Value must be in steps of 0.25 from 0".to_string()])
+/// );
+/// # }
+/// ```
+pub struct Slider;
+
+impl Slider {
+    /// Creates a new `Field<SliderManager, String>` from `min` to `max` changed by 1,
+    /// set to `min` unless [initial] value is given.
+    ///
+    /// [initial]: ../fields/struct.Field.html#method.initial
+    pub fn new<IS: Into<String>>(
+        label: IS,
+        min: f64,
+        max: f64,
+    ) -> fields::Field<SliderManager, String> {
+        let manager = SliderManager {
+            min,
+            max,
+            step: 1.0,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct SliderManager {
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl SliderManager {
+    /// Checks if steps & bounds are whole numbers.
+    fn is_integral(&self) -> bool {
+        self.step.fract() == 0.0 && self.min.fract() == 0.0
+    }
+}
+
+impl fields::Field<SliderManager, String> {
+    /// Sets how much the value changes with each move of the slider (1 by default).
+    pub fn step(mut self, step: f64) -> Self {
+        self.widget_manager.step = step;
+        self
+    }
+}
+
+impl WidgetManager for SliderManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Slider = (**view_box).as_any().downcast_ref().unwrap();
+        view.get_content()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let view = ::views::Slider::new(self.min, self.max)
+            .step(self.step)
+            .content(value);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Slider = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<SliderManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let data = data.trim();
+        if data.is_empty() {
+            return Ok(Value::Null);
+        }
+        let manager = &self.widget_manager;
+        let number = match data.parse::<f64>() {
+            Ok(number) if number.is_finite() => number,
+            _ => return Err(vec![format!("Invalid number: {}", data)]),
+        };
+        if number < manager.min || number > manager.max {
+            return Err(vec![format!(
+                "Value must be between {} and {}",
+                manager.min, manager.max
+            )]);
+        }
+        let steps = (number - manager.min) / manager.step;
+        if (steps - steps.round()).abs() > 1e-9 {
+            return Err(vec![format!(
+                "Value must be in steps of {} from {}",
+                manager.step, manager.min
+            )]);
+        }
+        if manager.is_integral() {
+            Ok(json!(number as i64))
+        } else {
+            Ok(json!(number))
+        }
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .allow_hyphen_values(true)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match value {
+            Value::String(s) => s.to_owned(),
+            _ => value
+                .as_f64()
+                .map(|n| format_value(n, self.widget_manager.step))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let manager = &self.widget_manager;
+        let kind = if manager.is_integral() {
+            "integer"
+        } else {
+            "number"
+        };
+        let mut schema = json!({
+            "type": kind,
+            "minimum": manager.min,
+            "maximum": manager.max,
+        });
+        if (manager.min / manager.step).fract() == 0.0 {
+            schema["multipleOf"] = json!(manager.step);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
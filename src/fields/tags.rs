@@ -0,0 +1,151 @@
+use clap;
+use cursive::views::BoxedView;
+use serde_json::value::Value;
+
+use fields;
+use fields::{label_with_help_layout, Field, FieldErrors, FormField, WidgetManager};
+use views;
+
+/// Separates tags in field's raw value (tags can contain commas).
+const TAG_SEP: &str = "\n";
+
+/// Convienient wrapper around `Field<TagsManager, Vec<String>>`.
+///
+/// Free-form list of strings, each typed and added as a chip with `Enter`
+/// (unlike [Multiselect] there are no suggestions to pick from).
+/// Submitted as a JSON array of strings, validators check each tag.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// #[macro_use]
+/// extern crate serde_json;
+///
+/// use fui::fields::{FormField, Tags};
+/// use fui::form::FormView;
+///
+/// # fn main() {
+/// let field = Tags::new("labels");
+/// assert_eq!(field.validate("bug\nneeds, review"), Ok(json!(["bug", "needs, review"])));
+/// assert_eq!(field.validate(""), Ok(json!([])));
+///
+/// let mut form = FormView::new().field(Tags::new("labels").initial(vec!["bug", "ui"]));
+/// assert_eq!(form.validate().unwrap(), json!({"labels": ["bug", "ui"]}));
+/// # }
+/// ```
+///
+/// [Multiselect]: struct.Multiselect.html
+pub struct Tags;
+
+impl Tags {
+    /// Creates a new `Field<TagsManager, Vec<String>>`.
+    pub fn new<IS: Into<String>>(label: IS) -> Field<TagsManager, Vec<String>> {
+        Field::new(label, TagsManager, Vec::new())
+    }
+}
+
+#[derive(Clone)]
+pub struct TagsManager;
+
+impl WidgetManager for TagsManager {
+    fn build_value_view(&self, initial: &str) -> BoxedView {
+        let tags = initial.split(TAG_SEP).filter(|t| !t.is_empty());
+        BoxedView::new(Box::new(views::Tags::new().tags(tags)))
+    }
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> BoxedView {
+        let view = self.build_value_view(initial);
+        label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let tags: &views::Tags = (**view_box).as_any().downcast_ref().unwrap();
+        tags.get_tags().join(TAG_SEP)
+    }
+    fn set_masked(&self, view_box: &mut BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let tags: &mut views::Tags = (**view_box).as_any_mut().downcast_mut().unwrap();
+        tags.set_masked(masked);
+    }
+}
+
+impl FormField for Field<TagsManager, Vec<String>> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        let items = data.split(TAG_SEP).collect::<Vec<&str>>();
+        for item in items.iter() {
+            for v in &self.validators {
+                if let Some(e) = v.validate(item) {
+                    errors.push(e);
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        // no tag is an empty list, not a list with empty tag
+        let tags = items
+            .iter()
+            .filter(|_| !data.is_empty())
+            .map(|x| Value::String(x.to_string()))
+            .collect::<Vec<Value>>();
+        Ok(Value::Array(tags))
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.join(TAG_SEP)
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .multiple(true)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        let values = args.values_of(self.data_key()).unwrap_or_default();
+        values.collect::<Vec<&str>>().join(TAG_SEP)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_owned()).unwrap_or(v.to_string()))
+                .collect::<Vec<String>>()
+                .join(TAG_SEP),
+            Value::String(s) => s.to_owned(),
+            _ => "".to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "array", "items": { "type": "string" } });
+        if self.is_required() {
+            schema["minItems"] = json!(1);
+        }
+        if !self.help.is_empty() {
+            schema["description"] = json!(self.help);
+        }
+        schema
+    }
+}
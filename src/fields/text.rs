@@ -32,6 +32,11 @@ impl WidgetManager for TextManager {
     fn build_value_view(&self, value: &str) -> views::BoxedView {
         views::BoxedView::new(Box::new(views::EditView::new().content(value)))
     }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let edit: &mut views::EditView = (**view_box).as_any_mut().downcast_mut().unwrap();
+        edit.set_secret(masked);
+    }
 }
 
 impl fields::FormField for fields::Field<TextManager, String> {
@@ -67,15 +72,15 @@ impl fields::FormField for fields::Field<TextManager, String> {
     }
 
     fn clap_arg(&self) -> clap::Arg {
-        clap::Arg::with_name(&self.label)
-            .long(&self.label)
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
             .help(&self.help)
             .required(self.is_required())
             .takes_value(true)
     }
 
     fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
-        args.value_of(&self.label).unwrap_or("").to_string()
+        args.value_of(self.data_key()).unwrap_or("").to_string()
     }
 
     fn is_required(&self) -> bool {
@@ -87,6 +92,7 @@ impl<W: WidgetManager> fields::Field<W, String> {
     /// Sets initial `value` of `field`.
     pub fn initial<IS: Into<String>>(mut self, initial: IS) -> Self {
         self.initial = initial.into();
+        self.own_initial = true;
         self
     }
 }
@@ -0,0 +1,168 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::time;
+
+/// Convienient wrapper around `Field<TimeManager, String>`.
+///
+/// Accepts time of day as `HH:MM[:SS]` (or `h:MM[:SS] am/pm` in [twelve_hour] mode)
+/// and submits it as `HH:MM:SS`. `Up` & `Down` keys change the component under cursor.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, Time};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = Time::new("start");
+/// assert_eq!(field.validate("7:30"), Ok(Value::from("07:30:00")));
+/// assert!(field.validate("7:30 pm").is_err());
+///
+/// let field = Time::new("start").twelve_hour();
+/// assert_eq!(field.validate("7:30 pm"), Ok(Value::from("19:30:00")));
+/// assert_eq!(field.value2str(&Value::from("19:30:00")), "7:30 pm");
+///
+/// let field = Time::new("start").with_seconds();
+/// assert_eq!(field.value2str(&Value::from("19:30:00")), "19:30:00");
+/// # }
+/// ```
+///
+/// [twelve_hour]: ../fields/struct.Field.html#method.twelve_hour
+pub struct Time;
+
+impl Time {
+    /// Creates a new `Field<TimeManager, String>` in 24-hour mode.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<TimeManager, String> {
+        let manager = TimeManager {
+            twelve_hour: false,
+            with_seconds: false,
+        };
+        fields::Field::new(label, manager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct TimeManager {
+    twelve_hour: bool,
+    with_seconds: bool,
+}
+
+impl fields::Field<TimeManager, String> {
+    /// Makes field accept & show time as `h:MM am/pm` instead of `HH:MM`.
+    pub fn twelve_hour(mut self) -> Self {
+        self.widget_manager.twelve_hour = true;
+        self
+    }
+
+    /// Makes field show time with seconds segment (`HH:MM:SS`), which is changed
+    /// with arrow keys like hours & minutes.
+    pub fn with_seconds(mut self) -> Self {
+        self.widget_manager.with_seconds = true;
+        self
+    }
+}
+
+impl WidgetManager for TimeManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Time = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        let view = ::views::Time::new()
+            .twelve_hour(self.twelve_hour)
+            .with_seconds(self.with_seconds)
+            .content(value);
+        views::BoxedView::new(Box::new(view))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Time = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<TimeManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        time::parse(data, self.widget_manager.twelve_hour)
+            .map(|t| Value::String(t.canonical()))
+            .map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        let text = value.as_str().unwrap_or("");
+        match time::parse(text, false) {
+            Ok(t) => {
+                let with_seconds = self.widget_manager.with_seconds || t.second != 0;
+                time::format(&t, self.widget_manager.twelve_hour, with_seconds)
+            }
+            Err(_) => text.to_string(),
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({
+            "type": "string",
+            "pattern": "^([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]$"
+        });
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
@@ -0,0 +1,25 @@
+use feeders::Timezones;
+use fields;
+use fields::autocomplete::AutocompleteManager;
+use validators::InFeeder;
+
+/// Convienient wrapper around `Field<AutocompleteManager, String>` picking one of IANA
+/// time zones (like `Europe/Warsaw`).
+///
+/// # Examples
+///
+/// ```
+/// use fui::fields::{FormField, Timezone};
+///
+/// let field = Timezone::new("timezone");
+/// assert!(field.validate("Asia/Tokyo").is_ok());
+/// assert!(field.validate("Mars/Olympus_Mons").is_err());
+/// ```
+pub struct Timezone;
+
+impl Timezone {
+    /// Creates a new `Field<AutocompleteManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<AutocompleteManager, String> {
+        fields::Autocomplete::new(label, Timezones).validator(InFeeder::new(Timezones))
+    }
+}
@@ -0,0 +1,123 @@
+use clap;
+use cursive::views;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::uuid;
+
+/// Convienient wrapper around `Field<UuidManager, String>`.
+///
+/// Accepts UUID in common notations and submits it as a lowercase hyphenated string.
+/// With feature `uuid` pressing `Ctrl+g` generates a fresh random (v4) UUID in place.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::fields::{FormField, Uuid};
+/// use fui::Value;
+///
+/// # fn main() {
+/// let field = Uuid::new("id");
+/// assert_eq!(
+///     field.validate("67E55044-10B1-426F-9247-BB680E5FE0C8"),
+///     Ok(Value::from("67e55044-10b1-426f-9247-bb680e5fe0c8"))
+/// );
+/// assert!(field.validate("67e55044").is_err());
+/// # }
+/// ```
+pub struct Uuid;
+
+impl Uuid {
+    /// Creates a new `Field<UuidManager, String>`.
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<UuidManager, String> {
+        fields::Field::new(label, UuidManager, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct UuidManager;
+
+impl WidgetManager for UuidManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::BoxedView {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::BoxedView) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let view: &::views::Uuid = (**view_box).as_any().downcast_ref().unwrap();
+        (*view.get_content()).clone()
+    }
+    fn build_value_view(&self, value: &str) -> views::BoxedView {
+        views::BoxedView::new(Box::new(::views::Uuid::new().content(value)))
+    }
+    fn set_masked(&self, view_box: &mut views::BoxedView, masked: bool) {
+        let view_box = fields::value_view_from_layout_mut(view_box);
+        let view: &mut ::views::Uuid = (**view_box).as_any_mut().downcast_mut().unwrap();
+        view.set_masked(masked);
+    }
+}
+
+impl fields::FormField for fields::Field<UuidManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        for v in &self.validators {
+            if let Some(e) = v.validate(data) {
+                errors.push(e);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if data.trim().is_empty() {
+            return Ok(Value::String(String::new()));
+        }
+        uuid::parse(data).map(Value::String).map_err(|e| vec![e])
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg {
+        clap::Arg::with_name(self.data_key())
+            .long(self.data_key())
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(self.data_key()).unwrap_or("").to_string()
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string", "format": "uuid" });
+        if self.is_required() {
+            schema["minLength"] = json!(1);
+        }
+        if !self.get_help().is_empty() {
+            schema["description"] = json!(self.get_help());
+        }
+        schema
+    }
+}
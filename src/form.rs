@@ -1,32 +1,371 @@
 //! Contains form related concetps like `FormView`.
+use std::cell::Cell;
+use std::cmp::min;
 use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::Index;
+use std::process::{Command, Stdio};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use clap;
 use cursive::event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::theme::{ColorStyle, Effect};
+use cursive::utils::markup::StyledString;
 use cursive::view::{View, ViewWrapper};
-use cursive::views::{BoxedView, Dialog, DialogFocus, LinearLayout};
-use cursive::Cursive;
+use cursive::views::{
+    BoxedView, Canvas, Dialog, DialogFocus, DummyView, LinearLayout, TextContent, TextView,
+};
+use cursive::{Cursive, Printer, Rect, Vec2};
 use serde_json::map::Map;
 use serde_json::value::Value;
+use unicode_width::UnicodeWidthStr;
 
-use fields::{FieldErrors, FormField};
+use fields;
+use fields::{AnnotationAbove, FieldErrors, FieldLayout, FormField, Probe};
+use metrics;
+use utils;
+use views::{OutputPane, Redraw};
 
-/// Container for form's errors.
-pub type FormErrors = HashMap<String, FieldErrors>;
+/// Errors of form's fields, listed in order they were found (fields' order for validation).
+///
+/// # Examples
+///
+/// ```
+/// use fui::form::FormErrors;
+///
+/// let mut errors = FormErrors::new();
+/// errors.insert("port", vec!["Value is not a number".to_string()]);
+/// errors.push("host", "Field is required");
+///
+/// assert_eq!(errors.first(), Some(("port", "Value is not a number")));
+/// assert_eq!(errors.for_field("host"), Some(&["Field is required".to_string()][..]));
+/// assert_eq!(
+///     errors.to_string(),
+///     "port: Value is not a number\nhost: Field is required\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormErrors {
+    errors: Vec<(String, FieldErrors)>,
+}
+
+impl FormErrors {
+    /// Creates empty `FormErrors`.
+    pub fn new() -> Self {
+        FormErrors { errors: Vec::new() }
+    }
+
+    /// Sets `errors` of field labeled `label`, replacing its previous errors.
+    pub fn insert<IS: Into<String>>(&mut self, label: IS, errors: FieldErrors) {
+        let label = label.into();
+        match self.errors.iter_mut().find(|(l, _)| *l == label) {
+            Some(entry) => entry.1 = errors,
+            None => self.errors.push((label, errors)),
+        }
+    }
+
+    /// Appends `error` to errors of field labeled `label`.
+    pub fn push<IS: Into<String>, E: Into<String>>(&mut self, label: IS, error: E) {
+        let label = label.into();
+        match self.errors.iter_mut().find(|(l, _)| *l == label) {
+            Some(entry) => entry.1.push(error.into()),
+            None => self.errors.push((label, vec![error.into()])),
+        }
+    }
+
+    /// Iterates over labels of invalid fields with their errors.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.errors.iter().map(|(l, e)| (l.as_str(), e.as_slice()))
+    }
+
+    /// Gets label of the first invalid field with its first error.
+    pub fn first(&self) -> Option<(&str, &str)> {
+        self.iter()
+            .find_map(|(label, errors)| errors.first().map(|e| (label, e.as_str())))
+    }
+
+    /// Gets errors of field labeled `label`, `None` if the field is valid.
+    pub fn for_field(&self, label: &str) -> Option<&[String]> {
+        self.iter().find(|(l, _)| *l == label).map(|(_, e)| e)
+    }
+
+    /// Counts invalid fields.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Checks if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Converts errors to JSON object mapping labels to lists of errors.
+    pub fn to_value(&self) -> Value {
+        let map = self
+            .errors
+            .iter()
+            .map(|(label, errors)| (label.clone(), json!(errors)))
+            .collect::<Map<String, Value>>();
+        Value::Object(map)
+    }
+}
+
+impl Index<&str> for FormErrors {
+    type Output = [String];
+
+    /// Gets errors of field labeled `label`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the field has no errors.
+    fn index(&self, label: &str) -> &[String] {
+        self.for_field(label)
+            .unwrap_or_else(|| panic!("No errors of field {:?}", label))
+    }
+}
+
+/// Renders each error on its own line as `label: error`, suited for CLI output.
+impl fmt::Display for FormErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (label, errors) in self.iter() {
+            for error in errors {
+                writeln!(f, "{}: {}", label, error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Forms lower than this (in rows of terminal) get compact layout with `Density::Auto`.
+const COMPACT_BELOW_ROWS: usize = 30;
+
+/// How densely fields of form are laid out (see [FormView::density]).
+///
+/// [FormView::density]: struct.FormView.html#method.density
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Density {
+    /// Compact layout on terminals lower than 30 rows, comfortable otherwise.
+    Auto,
+    /// Layout fitting as many fields as possible on small screens.
+    Compact,
+    /// Layout with spaces between fields and their helps shown.
+    Comfortable,
+}
+
+/// Layout of fields used for one of [Density] levels.
+///
+/// [Density]: enum.Density.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutProfile {
+    /// Blank rows below each field.
+    pub spacing: usize,
+    /// Shows help next to field's label.
+    pub show_help: bool,
+    /// Limits suggestions listed by dropdowns (like [Autocomplete]), `None` keeps fields' own.
+    ///
+    /// [Autocomplete]: ../fields/struct.Autocomplete.html
+    pub dropdown_rows: Option<u8>,
+}
+
+impl LayoutProfile {
+    /// Creates profile without spaces between fields, helps & with short dropdowns.
+    pub fn compact() -> Self {
+        LayoutProfile {
+            spacing: 0,
+            show_help: false,
+            dropdown_rows: Some(3),
+        }
+    }
+
+    /// Creates profile with a blank row between fields, which have helps
+    /// & dropdowns of their own heights.
+    pub fn comfortable() -> Self {
+        LayoutProfile {
+            spacing: 1,
+            show_help: true,
+            dropdown_rows: None,
+        }
+    }
+}
+
+impl Default for LayoutProfile {
+    fn default() -> Self {
+        Self::comfortable()
+    }
+}
+
+/// Not yet validated values of form's fields, keyed by field's label.
+pub type FormSnapshot = HashMap<String, String>;
 
 type OnSubmit = Option<Rc<dyn Fn(&mut Cursive, Value)>>;
 type OnCancel = Option<Rc<dyn Fn(&mut Cursive)>>;
+type OnSaveDraft = Option<Rc<dyn Fn(&mut Cursive, FormSnapshot)>>;
+type BeforeSubmit = Rc<dyn Fn(&mut Value) -> Result<(), FormErrors>>;
+type AfterSubmit = Rc<dyn Fn(&Value)>;
+/// Command run before submitting (see `FormView::external_check`)
+/// with index of layout child showing its output.
+struct ExternalCheck {
+    template: String,
+    child: usize,
+}
+
+/// Commands added with `FormView::external_check` running in background for submitted `data`.
+struct RunningChecks {
+    data: Value,
+    // errors of commands (`None` for succeeded ones) set once all of them finish
+    errors: Arc<Mutex<Option<Vec<Option<String>>>>>,
+}
+
+/// Submitted `data` waiting until user answers questions of `FormField::confirm_submit`.
+struct PendingConfirm {
+    data: Value,
+    // set by buttons of the confirm dialog
+    answer: Rc<Cell<Option<bool>>>,
+}
+
+/// Condition of enabling `Submit` & explanation shown while it isn't met.
+type SubmitCondition = (Rc<dyn Fn(&Value) -> bool>, String);
+
+/// Result of form reported by [FormView::into_view_with].
+///
+/// [FormView::into_view_with]: struct.FormView.html#method.into_view_with
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormResult {
+    /// Form was submitted with valid data.
+    Submitted(Value),
+    /// Form was cancelled.
+    Cancelled,
+}
+
+/// Key which submits form.
+const SUBMIT_KEY: Event = Event::CtrlChar('f');
+/// Key showing validation rules of the focused field.
+const RULES_KEY: Event = Event::Key(Key::F2);
+/// Key focusing the next field with errors (when header is sticky).
+const NEXT_ERROR_KEY: Event = Event::Key(Key::F8);
+/// Key toggling masking of values.
+const MASK_KEY: Event = Event::Key(Key::F10);
+
+/// Place of form which can be focused with `Tab`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FocusStop {
+    Field(usize),
+    Button(usize),
+}
+
+/// Hint shown below field, found by its [Probe] in background.
+struct ProbeHint {
+    probe: Probe,
+    content: TextContent,
+    // value of the last probe (`None` before the first one)
+    probed: Option<String>,
+    // identifies the latest probe, so results of older ones are dropped
+    generation: Arc<AtomicUsize>,
+}
+
+impl ProbeHint {
+    /// Runs probe for `value` in background, asking `redraw` to show its result.
+    fn spawn(&mut self, value: String, redraw: &Redraw) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let probe = Arc::clone(&self.probe);
+        let content = self.content.clone();
+        let latest = Arc::clone(&self.generation);
+        let redraw = redraw.clone();
+        self.probed = Some(value.clone());
+        thread::spawn(move || {
+            let hint = probe(&value).unwrap_or_default();
+            if latest.load(Ordering::SeqCst) == generation {
+                content.set_content(hint);
+                redraw.request();
+            }
+        });
+    }
+}
 
 /// Aggregates [Fields] and handles process of `submitting` (or `canceling`).
 ///
+/// Pressing `F2` shows validation rules of the focused field.
+///
+/// `Tab` and `Shift+Tab` cycle focus through fields (see [Field::tab_index])
+/// and then buttons, wrapping at the ends.
+///
+/// Cancelling form after user changed some fields asks whether to discard
+/// the changes (see [modified_fields]).
+///
 /// [Fields]: ../fields/index.html
+/// [modified_fields]: #method.modified_fields
+/// [Field::tab_index]: ../fields/struct.Field.html#method.tab_index
 pub struct FormView {
     view: Dialog,
 
     fields: Vec<Box<dyn FormField>>,
+    // prefix of group (see `group`) nesting data of each field
+    prefixes: Vec<String>,
+    group: String,
+    // dots in fields' keys nest data too (see `nest_dotted_labels`)
+    nest_dotted: bool,
+    // layout of fields appended after `field_layout` was called (`None` for the default one)
+    layout: Option<Rc<dyn FieldLayout>>,
+    // layout arranging widget of each field
+    layouts: Vec<Rc<dyn FieldLayout>>,
+    // index of layout child holding widget of each field (layout includes decorations too)
+    field_children: Vec<usize>,
+    // values of fields' widgets before user changed them
+    initial_values: Vec<String>,
+    // fields filled by `set_values`, their values are used for options missing in CLI
+    preset: Vec<bool>,
+    // hints of fields with probes
+    probe_hints: Vec<Option<ProbeHint>>,
+    redraw: Redraw,
+    // help of fields' CLI args, kept here because clap borrows it
+    cli_helps: Vec<String>,
+    submit_text: String,
+    // descriptions of keys replacing built-in ones in hints
+    hint_texts: Vec<(Event, String)>,
+    name: String,
+    width: Option<usize>,
+    max_width: Option<usize>,
+    fill_screen: bool,
+    // horizontal offset centering the dialog
+    offset_x: usize,
+    masked: bool,
+    cancel_hidden: bool,
+    discard_silently: bool,
+    review: bool,
+    required_progress: bool,
+    submit_until_complete: bool,
+    submit_conditions: Vec<SubmitCondition>,
+    // text of each field with its value seen by submit conditions, validated on change only
+    condition_values: Vec<Option<(String, Value)>>,
+    // explanation why `Submit` is disabled
+    submit_blocked: Option<String>,
+    sticky_header: bool,
+    density: Density,
+    // if true fields have no blank rows below them whatever the density
+    compact: bool,
+    compact_profile: LayoutProfile,
+    comfortable_profile: LayoutProfile,
+    // profile fields are laid out with (`None` when it needs to be applied)
+    applied_profile: Option<LayoutProfile>,
+    // indexes of fields with errors found by the last validation
+    invalid_fields: Vec<usize>,
+    // values of fields when errors were shown, fields edited since then are validated live
+    validated_values: Vec<String>,
+    // row where header pinned by `sticky_header` was last drawn
+    header_row: Cell<Option<usize>>,
     on_submit: OnSubmit,
     on_cancel: OnCancel,
+    on_save_draft: OnSaveDraft,
+    before_submit: Vec<BeforeSubmit>,
+    after_submit: Vec<AfterSubmit>,
+    external_checks: Vec<ExternalCheck>,
+    running_checks: Option<RunningChecks>,
+    confirming: Option<PendingConfirm>,
 }
 impl FormView {
     /// Creates a new `FormView` with two buttons `submit` and `cancel`.
@@ -35,12 +374,54 @@ impl FormView {
         let layout = Dialog::new()
             .content(LinearLayout::vertical())
             .button("Cancel", |_| {})
-            .button("Submit (Ctrl+f)", |_| {});
+            .button("Submit", |_| {});
         FormView {
             view: layout,
             fields: Vec::new(),
+            prefixes: Vec::new(),
+            group: String::new(),
+            nest_dotted: false,
+            layout: None,
+            layouts: Vec::new(),
+            field_children: Vec::new(),
+            initial_values: Vec::new(),
+            preset: Vec::new(),
+            probe_hints: Vec::new(),
+            redraw: Redraw::default(),
+            cli_helps: Vec::new(),
+            submit_text: "Submit".to_string(),
+            hint_texts: Vec::new(),
+            name: String::new(),
+            width: None,
+            max_width: None,
+            fill_screen: false,
+            offset_x: 0,
+            masked: false,
+            cancel_hidden: false,
+            discard_silently: false,
+            review: false,
+            required_progress: false,
+            submit_until_complete: false,
+            submit_conditions: Vec::new(),
+            condition_values: Vec::new(),
+            submit_blocked: None,
+            sticky_header: false,
+            density: Density::Comfortable,
+            compact: false,
+            compact_profile: LayoutProfile::compact(),
+            comfortable_profile: LayoutProfile::comfortable(),
+            applied_profile: None,
+            invalid_fields: Vec::new(),
+            validated_values: Vec::new(),
+            header_row: Cell::new(None),
             on_submit: None,
             on_cancel: None,
+            on_save_draft: None,
+            before_submit: Vec::new(),
+            after_submit: Vec::new(),
+            external_checks: Vec::new(),
+            running_checks: None,
+            confirming: None,
         }
     }
 
@@ -51,240 +432,2118 @@ impl FormView {
 
     /// Appends boxed `field` to field list.
     pub fn boxed_field(mut self, field: Box<dyn FormField>) -> Self {
-        let widget = field.build_widget();
-        self.view
-            .get_content_mut()
-            .as_any_mut()
-            .downcast_mut::<LinearLayout>()
-            .unwrap()
-            .add_child(widget);
+        let widget = match self.layout {
+            Some(ref layout) if field.get_layout().is_none() => {
+                let initial = field.get_initial();
+                let value = field.get_widget_manager().build_value_view(&initial);
+                fields::build_layout(&**layout, value, field.get_label(), field.get_help())
+            }
+            _ => field.build_widget(),
+        };
+        let layout = field.get_layout().or_else(|| self.layout.clone());
+        self.layouts
+            .push(layout.unwrap_or_else(|| Rc::new(AnnotationAbove)));
+        let child_idx = self.add_child(widget);
+        self.field_children.push(child_idx);
+        self.cli_helps.push(field.cli_help());
+        let autofocus = field.is_autofocus();
+        let probe = field.get_probe().cloned();
         self.fields.push(field);
+        let hint = probe.and_then(|probe| self.add_probe_hint(child_idx, probe));
+        self.probe_hints.push(hint);
+        self.prefixes.push(self.group.clone());
+        let initial = self.field_value(self.fields.len() - 1);
+        self.initial_values.push(initial);
+        self.preset.push(false);
+        if autofocus {
+            self.focus_stop(FocusStop::Field(self.fields.len() - 1));
+        }
+        self.refresh_submit();
+        // new widget is laid out comfortably, so profile is applied again
+        self.applied_profile = None;
         self
     }
 
-    /// Sets the function to be called when submit is triggered.
-    pub fn set_on_submit<F>(&mut self, callback: F)
-    where
-        F: Fn(&mut Cursive, Value) + 'static,
-    {
-        self.on_submit = Some(Rc::new(callback));
+    /// Adds view of hint found by `probe` to widget at layout child `child_idx`.
+    fn add_probe_hint(&mut self, child_idx: usize, probe: Probe) -> Option<ProbeHint> {
+        let content = TextContent::new("");
+        let view = TextView::new_with_content(content.clone()).style(ColorStyle::secondary());
+        let widget = field_view_mut(&mut self.view, child_idx);
+        if !fields::insert_hint_view(widget, view) {
+            return None;
+        }
+        Some(ProbeHint {
+            probe,
+            content,
+            probed: None,
+            generation: Arc::new(AtomicUsize::new(0)),
+        })
     }
 
-    /// Sets the function to be called when submit is triggered.
+    /// Probes values of fields which changed since their last probe,
+    /// except the focused one which is probed when user leaves it.
+    fn refresh_probes(&mut self) {
+        let focused = self.focused_field();
+        for idx in 0..self.fields.len() {
+            let value = match self.probe_hints[idx] {
+                Some(ref hint) if hint.probed.is_none() || Some(idx) != focused => {
+                    self.field_value(idx)
+                }
+                _ => continue,
+            };
+            if let Some(ref mut hint) = self.probe_hints[idx] {
+                if hint.probed.as_ref() != Some(&value) {
+                    hint.spawn(value, &self.redraw);
+                }
+            }
+        }
+    }
+
+    /// Focuses field labeled `label` when form opens (see also [Field::autofocus]).
     ///
-    /// Chainable variant.
-    pub fn on_submit<F>(mut self, callback: F) -> Self
-    where
-        F: Fn(&mut Cursive, Value) + 'static,
-    {
-        self.set_on_submit(callback);
+    /// Unknown labels are ignored.
+    ///
+    /// [Field::autofocus]: ../fields/struct.Field.html#method.autofocus
+    pub fn focus(mut self, label: &str) -> Self {
+        if let Some(idx) = self.fields.iter().position(|f| f.get_label() == label) {
+            self.focus_stop(FocusStop::Field(idx));
+        }
         self
     }
 
-    /// Sets the function to be called when cancel is triggered.
-    pub fn set_on_cancel<F>(&mut self, callback: F)
-    where
-        F: Fn(&mut Cursive) + 'static,
-    {
-        self.on_cancel = Some(Rc::new(callback));
+    /// Nests data of fields appended after it under `prefix` (like `network.proxy`),
+    /// until `group` is called again. Empty `prefix` ends the group.
+    ///
+    /// Labels (and keys) with dots are kept as they are, unless [nest_dotted_labels]
+    /// is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    ///
+    /// let mut form = FormView::new()
+    ///     .field(Text::new("name").initial("app"))
+    ///     .heading("Proxy")
+    ///     .group("network.proxy")
+    ///     .field(Text::new("host").initial("localhost"))
+    ///     .field(Text::new("port").initial("8080"));
+    ///
+    /// assert_eq!(
+    ///     form.validate().unwrap(),
+    ///     json!({"name": "app", "network": {"proxy": {"host": "localhost", "port": "8080"}}})
+    /// );
+    /// # }
+    /// ```
+    pub fn group<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.group = prefix.into();
+        self
     }
 
-    /// Sets the function to be called when cancel is triggered.
+    /// Nests data of fields with dotted labels (or keys) into sub-objects, so field labeled
+    /// `network.proxy.host` gives the same data as field labeled `host` in
+    /// `group("network.proxy")` (see [group]).
     ///
-    /// Chainable variant.
-    pub fn on_cancel<F>(mut self, callback: F) -> Self
-    where
-        F: Fn(&mut Cursive) + 'static,
-    {
-        self.set_on_cancel(callback);
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    ///
+    /// let field = || Text::new("proxy.host").initial("localhost");
+    ///
+    /// let mut flat = FormView::new().field(field());
+    /// assert_eq!(flat.validate().unwrap(), json!({"proxy.host": "localhost"}));
+    ///
+    /// let mut nested = FormView::new().nest_dotted_labels().field(field());
+    /// assert_eq!(nested.validate().unwrap(), json!({"proxy": {"host": "localhost"}}));
+    /// # }
+    /// ```
+    ///
+    /// [group]: #method.group
+    pub fn nest_dotted_labels(mut self) -> Self {
+        self.nest_dotted = true;
         self
     }
 
-    /// Translates form's fields to [clap::Arg].
+    /// Converts field's `key` to path of its data (without group), see [nest_dotted_labels].
     ///
-    /// [clap::Arg]: ../../clap/struct.Arg.html
-    pub fn fields2clap_args(&self) -> Vec<clap::Arg> {
-        let mut args = Vec::with_capacity(self.fields.len());
-        for field in &self.fields {
-            let arg = field.clap_arg();
-            args.push(arg);
+    /// [nest_dotted_labels]: #method.nest_dotted_labels
+    fn key_path(&self, key: &str) -> String {
+        if self.nest_dotted {
+            key.to_owned()
+        } else {
+            utils::escape_key(key)
         }
-        return args;
     }
 
-    /// Translates [clap::ArgMatches] to [serde_json::Value] based on fields.
+    /// Arranges label, help & error of fields appended after it with `layout`,
+    /// until `field_layout` is called again. Fields with their own layout
+    /// (see [Field::layout]) keep it.
     ///
-    /// [clap::ArgMatches]: ../../clap/struct.ArgMatches.html
-    /// [serde_json::Value]: ../../serde_json/enum.Value.html
-    pub fn clap_arg_matches2value(&self, arg_matches: &clap::ArgMatches) -> Value {
-        let mut form_data = Map::with_capacity(self.fields.len());
-        for field in self.fields.iter() {
-            let data = field.clap_args2str(&arg_matches);
-            match field.validate(data.as_ref()) {
-                Ok(v) => {
-                    form_data.insert(field.get_label().to_string(), v);
-                }
-                Err(e) => {
-                    let msg: Vec<String> = e.iter().map(|s| format!("ERROR: {:?}", s)).collect();
-                    eprintln!("{}", msg.join("\n"));
-                }
-            }
+    /// # Examples
+    ///
+    /// ```
+    /// use fui::fields::{AnnotationAbove, AnnotationLeft, Text};
+    /// use fui::form::FormView;
+    ///
+    /// let form = FormView::new()
+    ///     .field_layout(AnnotationLeft)
+    ///     .field(Text::new("host"))
+    ///     .field(Text::new("port"))
+    ///     .field_layout(AnnotationAbove)
+    ///     .field(Text::new("comment").help("Shown to other admins"));
+    /// ```
+    ///
+    /// [Field::layout]: ../fields/struct.Field.html#method.layout
+    pub fn field_layout<L: FieldLayout + 'static>(mut self, layout: L) -> Self {
+        self.layout = Some(Rc::new(layout));
+        self
+    }
+
+    /// Gets dotted path of data of field at `idx` (its key prefixed with its group).
+    pub(crate) fn data_path(&self, idx: usize) -> String {
+        let key = self.key_path(self.fields[idx].get_key());
+        match self.prefixes[idx].as_str() {
+            "" => key,
+            prefix => format!("{}{}{}", prefix, utils::PATH_SEP, key),
         }
-        Value::Object(form_data)
     }
 
-    /// Validates form.
-    pub fn validate(&mut self) -> Result<Value, FormErrors> {
-        let mut data = Map::with_capacity(self.fields.len());
-        let mut errors: FormErrors = HashMap::with_capacity(self.fields.len());
+    /// Nests `data` keyed by data paths of fields (see [utils::unflatten]),
+    /// a field whose data path is also a prefix of others' paths gets an error.
+    ///
+    /// [utils::unflatten]: ../utils/fn.unflatten.html
+    fn nest(&self, data: Map<String, Value>) -> Result<Value, FormErrors> {
+        utils::unflatten(&Value::Object(data)).map_err(|path| {
+            let label = (0..self.fields.len())
+                .find(|&idx| self.data_path(idx) == path)
+                .map(|idx| self.fields[idx].get_label().to_owned())
+                .unwrap_or(path);
+            let mut errors = FormErrors::new();
+            errors.push(label, "Its key is also a group of other fields");
+            errors
+        })
+    }
+
+    /// Gets pairs of key & data path (see [group]) of fields in order of declaration.
+    ///
+    /// [group]: #method.group
+    pub(crate) fn data_paths(&self) -> Vec<(String, String)> {
+        (0..self.fields.len())
+            .map(|idx| (self.fields[idx].get_key().to_owned(), self.data_path(idx)))
+            .collect()
+    }
 
+    /// Gets value of field at `idx` from (nested) `data` or from `data` keyed by fields' keys,
+    /// falling back to field's old keys (see [Field::alias]).
+    ///
+    /// [Field::alias]: ../fields/struct.Field.html#method.alias
+    pub(crate) fn field_data<'a>(&self, idx: usize, data: &'a Value) -> Option<&'a Value> {
+        self.current_data(idx, data).or_else(|| {
+            self.fields[idx]
+                .get_aliases()
+                .iter()
+                .find_map(|alias| self.legacy_data(idx, alias, data))
+        })
+    }
+
+    /// Gets value of field at `idx` stored under its current key.
+    fn current_data<'a>(&self, idx: usize, data: &'a Value) -> Option<&'a Value> {
+        utils::get_path(data, &self.data_path(idx)).or_else(|| data.get(self.fields[idx].get_key()))
+    }
+
+    /// Gets value of field at `idx` stored under its old key `alias`.
+    fn legacy_data<'a>(&self, idx: usize, alias: &str, data: &'a Value) -> Option<&'a Value> {
+        let alias_path = self.key_path(alias);
+        let path = match self.prefixes[idx].as_str() {
+            "" => alias_path,
+            prefix => format!("{}{}{}", prefix, utils::PATH_SEP, alias_path),
+        };
+        utils::get_path(data, &path).or_else(|| data.get(alias))
+    }
+
+    /// Describes uses of fields' old keys (see [Field::alias]) and of deprecated fields
+    /// (see [Field::deprecated]) in `values`, e.g. in a profile saved by older version.
+    ///
+    /// [Field::alias]: ../fields/struct.Field.html#method.alias
+    /// [Field::deprecated]: ../fields/struct.Field.html#method.deprecated
+    pub fn deprecation_warnings(&self, values: &Value) -> Vec<String> {
+        let mut warnings = Vec::new();
         for (idx, field) in self.fields.iter().enumerate() {
-            let view = self
-                .view
-                .get_content()
-                .as_any()
-                .downcast_ref::<LinearLayout>()
-                .unwrap()
-                .get_child(idx)
-                .unwrap();
-            let view_box: &BoxedView = (*view).as_any().downcast_ref().unwrap();
-            let value = field.get_widget_manager().get_value(view_box);
-            let label = field.get_label();
-            match field.validate(value.as_ref()) {
-                Ok(v) => {
-                    data.insert(label.to_owned(), v);
-                }
-                Err(e) => {
-                    errors.insert(label.to_owned(), e);
-                }
+            let current = self.current_data(idx, values).is_some();
+            let legacy = field
+                .get_aliases()
+                .iter()
+                .find(|alias| self.legacy_data(idx, alias, values).is_some());
+            if let (false, Some(alias)) = (current, legacy) {
+                warnings.push(renamed_warning(alias, field.get_key()));
+            }
+            if current || legacy.is_some() {
+                warnings.extend(deprecated_warning(&**field));
             }
         }
+        warnings
+    }
 
-        if errors.is_empty() {
-            Ok(Value::Object(data))
-        } else {
-            self.show_errors(&errors);
-            Err(errors)
+    /// Describes uses of fields' old keys and of deprecated fields in CLI `args`.
+    pub(crate) fn cli_deprecation_warnings(&self, args: &[String]) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for arg in args.iter() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name.split('=').next().unwrap_or(name),
+                None => continue,
+            };
+            for field in self.fields.iter() {
+                if field.get_aliases().iter().any(|alias| alias == name) {
+                    warnings.push(renamed_warning(
+                        &format!("--{}", name),
+                        &format!("--{}", field.get_key()),
+                    ));
+                } else if field.get_key() != name {
+                    continue;
+                }
+                warnings.extend(deprecated_warning(&**field));
+            }
         }
+        warnings
     }
 
-    fn show_errors(&mut self, form_errors: &FormErrors) {
-        for (idx, field) in self.fields.iter().enumerate() {
-            let label = field.get_label();
-            let error = form_errors
-                .get(label)
-                .and_then(|field_errors| field_errors.first());
-            // can't call method which returns suitable view because of ownership
-            //  * such method would get &mut self
-            //  * self.field gets &self
-            //  so this clash of &mut and &, illegal
-            //  possible solution is to use clone on WidgetManager (needs implementation)
-            //  or
-            //  form should only call field.validate and rest would be handled by field
-            //  which should solve this issue?
-            let view = self
-                .view
-                .get_content_mut()
-                .as_any_mut()
-                .downcast_mut::<LinearLayout>()
-                .unwrap()
-                .get_child_mut(idx)
-                .unwrap();
-            let viewbox: &mut BoxedView = view.as_any_mut().downcast_mut().unwrap();
-            field.set_error(viewbox, error.unwrap_or(&"".to_string()));
+    /// Appends a horizontal line separating fields.
+    pub fn separator(mut self) -> Self {
+        let line = Canvas::new(())
+            .with_draw(|_, printer| printer.print_hline((0, 0), printer.size.x, "─"))
+            .with_required_size(|_, _| Vec2::new(1, 1));
+        self.add_child(line);
+        self
+    }
+
+    /// Appends a heading `text` starting a group of fields.
+    pub fn heading<S: Into<String>>(mut self, text: S) -> Self {
+        let heading = TextView::new(StyledString::styled(text.into(), Effect::Bold));
+        self.add_child(heading);
+        self
+    }
+
+    /// Appends a static `text`, like hints applying to fields below.
+    pub fn note<S: Into<String>>(mut self, text: S) -> Self {
+        let note = TextView::new(text.into());
+        self.add_child(LinearLayout::vertical().child(note).child(DummyView));
+        self
+    }
+
+    /// Shows how many required fields are filled in (like "4/7 required fields completed")
+    /// at the bottom of the form.
+    pub fn required_progress(mut self) -> Self {
+        self.required_progress = true;
+        self
+    }
+
+    /// Keeps title of the form and count of its errors (like "2 errors") visible at the top
+    /// while the form is scrolled.
+    ///
+    /// Clicking the count or pressing `F8` focuses the next field with error.
+    pub fn sticky_header(mut self) -> Self {
+        self.sticky_header = true;
+        self
+    }
+
+    /// Counts fields with errors found by the last validation.
+    ///
+    /// Once errors are shown, fields are validated again as they are edited,
+    /// so the count follows fixes.
+    pub fn error_count(&self) -> usize {
+        self.invalid_fields.len()
+    }
+
+    /// Focuses the first field with error after the focused one (wrapping at the end).
+    fn focus_next_invalid(&mut self) -> EventResult {
+        let current = self.focused_field();
+        let next = self
+            .invalid_fields
+            .iter()
+            .find(|&&idx| current.map_or(true, |current| idx > current))
+            .or_else(|| self.invalid_fields.first())
+            .cloned();
+        if let Some(idx) = next {
+            self.focus_stop(FocusStop::Field(idx));
         }
+        EventResult::Consumed(None)
     }
 
-    fn event_submit(&mut self) -> EventResult {
-        match self.validate() {
-            Ok(data_map) => {
-                let opt_cb = self
-                    .on_submit
-                    .clone()
-                    .map(|cb| Callback::from_fn(move |c| cb(c, data_map.clone())));
-                EventResult::Consumed(opt_cb)
-            }
-            Err(_) => {
-                // TODO: the event focus next required/invalid field?
-                EventResult::Consumed(None)
-            }
+    /// Draws title & count of errors on `row`, which is the top of visible part of form.
+    fn draw_header(&self, printer: &Printer, row: usize) {
+        let errors = match self.error_count() {
+            0 => String::new(),
+            1 => " 1 error ".to_string(),
+            count => format!(" {} errors ", count),
+        };
+        if row > 0 {
+            // title on the top border is scrolled away
+            let line = " ".repeat(printer.size.x);
+            printer.with_color(ColorStyle::title_primary(), |p| {
+                p.print((0, row), &line);
+                p.print((1, row), self.view.get_title());
+            });
         }
+        if !errors.is_empty() && errors.len() + 2 <= printer.size.x {
+            printer.with_color(ColorStyle::highlight(), |p| {
+                p.print((printer.size.x - errors.len() - 2, row), &errors);
+            });
+        }
+        self.header_row.set(Some(row));
     }
 
-    fn event_cancel(&mut self) -> EventResult {
-        let cb = self
-            .on_cancel
-            .clone()
-            .map(|cb| Callback::from_fn(move |c| cb(c)));
-        EventResult::Consumed(cb)
+    /// Disables `Submit` button until all required fields are filled in.
+    pub fn disable_submit_until_complete(mut self) -> Self {
+        self.submit_until_complete = true;
+        self.refresh_submit();
+        self
     }
 
-    /// Sets `title` of the form on the top of it.
-    pub fn title(mut self, title: &str) -> Self {
-        self.view.set_title(title);
+    /// Disables `Submit` button (greyed out) until `condition` holds for data of the form,
+    /// showing `reason` on the bottom border meanwhile.
+    ///
+    /// `condition` is checked whenever values change, it gets values of fields passing
+    /// their validators (others are `null`), each field is validated again only when it changes.
+    /// Data from CLI args, prompt or web page is checked too, failing `condition` is
+    /// an error of the form then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Multiselect;
+    /// use fui::form::FormView;
+    ///
+    /// let form = FormView::new()
+    ///     .field(Multiselect::new("files", vec!["a.txt", "b.txt"]))
+    ///     .submit_enabled_if(
+    ///         |data| data["files"].as_array().is_some_and(|f| !f.is_empty()),
+    ///         "Select at least one file",
+    ///     );
+    /// assert_eq!(form.submit_blocked(), Some("Select at least one file"));
+    /// # }
+    /// ```
+    pub fn submit_enabled_if<F, S>(mut self, condition: F, reason: S) -> Self
+    where
+        F: Fn(&Value) -> bool + 'static,
+        S: Into<String>,
+    {
+        self.submit_conditions
+            .push((Rc::new(condition), reason.into()));
+        self.refresh_submit();
         self
     }
 
-    /// Gets fields of `FormView`
-    pub fn get_fields(&self) -> &[Box<dyn FormField>] {
-        &self.fields
+    /// Gets explanation why `Submit` button is disabled (if it is).
+    pub fn submit_blocked(&self) -> Option<&str> {
+        self.submit_blocked.as_deref()
     }
 
-    /// Gets value of a field with label equal to `field_label`
+    /// Gets values of fields without errors (others are `null`) for checking conditions.
     ///
-    /// Returns first occurence when a label occures more than once.
-    pub fn get_field_value(&self, field_label: &str) -> Option<String> {
-        let mut value = None;
-        for (idx, form_field) in self.fields.iter().enumerate() {
-            if form_field.get_label() == field_label {
-                let view = self
-                    .view
-                    .get_content()
-                    .as_any()
-                    .downcast_ref::<LinearLayout>()
-                    .unwrap()
-                    .get_child(idx)
-                    .unwrap();
-                let view_box: &BoxedView = (*view).as_any().downcast_ref().unwrap();
-                value = Some(form_field.get_widget_manager().get_value(view_box));
-                break;
+    /// Only fields changed since the last call are validated, not all of them on each key.
+    fn lenient_data(&mut self) -> Value {
+        self.condition_values.resize(self.fields.len(), None);
+        let mut data = Map::with_capacity(self.fields.len());
+        for idx in 0..self.fields.len() {
+            let text = self.field_value(idx);
+            let changed = self.condition_values[idx]
+                .as_ref()
+                .map_or(true, |(seen, _)| *seen != text);
+            if changed {
+                let value = self.fields[idx].validate(&text).unwrap_or(Value::Null);
+                self.condition_values[idx] = Some((text, value));
             }
+            let (_, value) = self.condition_values[idx].as_ref().unwrap();
+            data.insert(self.data_path(idx), value.clone());
         }
-        value
+        // conditions see no data, until conflicting fields are fixed
+        self.nest(data).unwrap_or_default()
     }
-}
 
-impl ViewWrapper for FormView {
-    wrap_impl!(self.view: Dialog);
+    /// Gets reason of the first submit condition which valid `data` fails.
+    fn failed_condition(&self, data: &Value) -> Option<&str> {
+        self.submit_conditions
+            .iter()
+            .find(|(condition, _)| !condition(data))
+            .map(|(_, reason)| reason.as_str())
+    }
 
-    fn wrap_on_event(&mut self, event: Event) -> EventResult {
-        match event {
-            Event::Mouse {
-                offset: _,
-                position: _,
-                event: MouseEvent::Press(btn),
-            } => {
-                if btn == MouseButton::Left {
-                    self.with_view_mut(|v| v.on_event(event))
-                        .unwrap_or(EventResult::Ignored);
-                    match self.view.focus() {
-                        DialogFocus::Button(0) => self.event_cancel(),
-                        DialogFocus::Button(1) => self.event_submit(),
-                        _ => EventResult::Ignored,
-                    }
-                } else {
+    /// Counts required fields which are filled in and all required fields.
+    fn required_counts(&self) -> (usize, usize) {
+        let required = (0..self.fields.len()).filter(|&idx| self.fields[idx].is_required());
+        let (mut completed, mut total) = (0, 0);
+        for idx in required {
+            total += 1;
+            if !self.field_value(idx).trim().is_empty() {
+                completed += 1;
+            }
+        }
+        (completed, total)
+    }
+
+    /// Enables `Submit` button if submitting is allowed.
+    fn refresh_submit(&mut self) {
+        if self.review || !self.submit_until_complete && self.submit_conditions.is_empty() {
+            return;
+        }
+        let (completed, total) = self.required_counts();
+        let mut enabled = !self.submit_until_complete || completed == total;
+        self.submit_blocked = None;
+        if !self.submit_conditions.is_empty() {
+            let data = self.lenient_data();
+            self.submit_blocked = self.failed_condition(&data).map(|r| r.to_owned());
+            enabled &= self.submit_blocked.is_none();
+        }
+        let submit_idx = self.submit_button_idx();
+        if let Some(submit) = self.view.buttons_mut().nth(submit_idx) {
+            submit.set_enabled(enabled);
+        }
+    }
+
+    fn is_submit_enabled(&self) -> bool {
+        self.view
+            .buttons()
+            .nth(self.submit_button_idx())
+            .map_or(true, |b| b.is_enabled())
+    }
+
+    /// Sets `label` of `Submit` button, which is followed by hint of its key.
+    pub fn submit_label(mut self, label: &str) -> Self {
+        let submit_idx = self.submit_button_idx();
+        if let Some(submit) = self.view.buttons_mut().nth(submit_idx) {
+            submit.set_label(label);
+        }
+        self.submit_text = label.to_owned();
+        self
+    }
+
+    /// Lists keys handled by the form with descriptions, e.g. for [HintBar].
+    ///
+    /// [HintBar]: ../views/struct.HintBar.html
+    pub fn hints(&self) -> Vec<(Event, String)> {
+        let mut hints = Vec::new();
+        if !self.review {
+            hints.push((SUBMIT_KEY, self.submit_text.clone()));
+        }
+        hints.push((Event::Key(Key::Tab), "Next".to_string()));
+        hints.push((RULES_KEY, "Rules".to_string()));
+        if self.sticky_header && !self.review {
+            hints.push((NEXT_ERROR_KEY, "Next error".to_string()));
+        }
+        hints.push((MASK_KEY, "Mask".to_string()));
+        for (key, text) in hints.iter_mut() {
+            if let Some((_, custom)) = self.hint_texts.iter().find(|(k, _)| k == key) {
+                *text = custom.clone();
+            }
+        }
+        hints
+    }
+
+    /// Replaces description of `key` in [hints] with `text` (e.g. to localize it).
+    ///
+    /// Description of submitting key defaults to label set by [submit_label].
+    ///
+    /// [hints]: #method.hints
+    /// [submit_label]: #method.submit_label
+    pub fn hint<S: Into<String>>(mut self, key: Event, text: S) -> Self {
+        self.hint_texts.retain(|(k, _)| *k != key);
+        self.hint_texts.push((key, text.into()));
+        self
+    }
+
+    /// Sets `label` of `Cancel` button.
+    pub fn cancel_label(mut self, label: &str) -> Self {
+        if let Some(idx) = self.cancel_button_idx() {
+            if let Some(cancel) = self.view.buttons_mut().nth(idx) {
+                cancel.set_label(label);
+            }
+        }
+        self
+    }
+
+    /// Hides `Cancel` button, e.g. for confirm forms.
+    pub fn hide_cancel(mut self) -> Self {
+        if let Some(idx) = self.cancel_button_idx() {
+            self.view.remove_button(idx);
+            self.cancel_hidden = true;
+        }
+        self
+    }
+
+    /// Shows `data` (like submitted one) in read-only form with `Close` button only,
+    /// e.g. for inspecting what was submitted in the past.
+    ///
+    /// `Close` calls function set with [on_cancel].
+    ///
+    /// [on_cancel]: #method.on_cancel
+    pub fn review(mut self, data: &Value) -> Self {
+        self.set_values(data);
+        self.review = true;
+        self.submit_until_complete = false;
+        self.view.clear_buttons();
+        self.view.add_button("Close", |_| {});
+        self.cancel_hidden = false;
+        self
+    }
+
+    /// Checks if form is read-only (see [review]).
+    ///
+    /// [review]: #method.review
+    pub fn is_review(&self) -> bool {
+        self.review
+    }
+
+    fn cancel_button_idx(&self) -> Option<usize> {
+        if self.cancel_hidden {
+            None
+        } else {
+            Some(0)
+        }
+    }
+
+    fn submit_button_idx(&self) -> usize {
+        if self.cancel_hidden {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Handles press of `idx` button.
+    fn event_button(&mut self, idx: usize) -> EventResult {
+        if Some(idx) == self.cancel_button_idx() {
+            self.event_cancel()
+        } else if idx == self.submit_button_idx() {
+            self.event_submit()
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    /// Appends `view` to form's layout, returns its index.
+    fn add_child<V: View>(&mut self, view: V) -> usize {
+        let layout = self
+            .view
+            .get_content_mut()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap();
+        layout.add_child(view);
+        layout.len() - 1
+    }
+
+    /// Sets the function to be called when submit is triggered.
+    pub fn set_on_submit<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cursive, Value) + 'static,
+    {
+        self.on_submit = Some(Rc::new(callback));
+    }
+
+    /// Sets the function to be called when submit is triggered.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive, Value) + 'static,
+    {
+        self.set_on_submit(callback);
+        self
+    }
+
+    /// Sets the function to be called when cancel is triggered.
+    pub fn set_on_cancel<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cursive) + 'static,
+    {
+        self.on_cancel = Some(Rc::new(callback));
+    }
+
+    /// Sets the function to be called when cancel is triggered.
+    ///
+    /// Chainable variant.
+    pub fn on_cancel<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive) + 'static,
+    {
+        self.set_on_cancel(callback);
+        self
+    }
+
+    /// Sets the function called with values of fields when user leaves the form
+    /// with unsaved changes and chooses to save them as a draft.
+    ///
+    /// Values can be restored later with [set_field_value].
+    ///
+    /// [set_field_value]: #method.set_field_value
+    pub fn on_save_draft<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive, FormSnapshot) + 'static,
+    {
+        self.on_save_draft = Some(Rc::new(callback));
+        self
+    }
+
+    /// Cancels form without asking whether to discard changes of fields.
+    pub fn discard_changes_silently(mut self) -> Self {
+        self.discard_silently = true;
+        self
+    }
+
+    /// Makes form report its result through `sender` instead of [on_submit] & [on_cancel],
+    /// so it can be embedded in any cursive app, which keeps its own event loop.
+    ///
+    /// Form stays on screen, the app decides what to do with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate fui;
+    ///
+    /// use std::sync::mpsc;
+    ///
+    /// use fui::cursive::Cursive;
+    /// use fui::fields::Text;
+    /// use fui::form::{FormResult, FormView};
+    ///
+    /// # fn main() {
+    /// let (sender, receiver) = mpsc::channel();
+    /// let form = FormView::new()
+    ///     .field(Text::new("name"))
+    ///     .into_view_with(sender);
+    ///
+    /// let mut siv = Cursive::new();
+    /// siv.add_layer(form);
+    /// // in app's event loop (e.g. with `siv.step()`)
+    /// match receiver.try_recv() {
+    ///     Ok(FormResult::Submitted(data)) => println!("{}", data),
+    ///     Ok(FormResult::Cancelled) => { siv.pop_layer(); }
+    ///     Err(_) => {}
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [on_submit]: #method.on_submit
+    /// [on_cancel]: #method.on_cancel
+    pub fn into_view_with(mut self, sender: Sender<FormResult>) -> Self {
+        let on_cancel = sender.clone();
+        self.set_on_submit(move |_, data| {
+            // receiver may be gone already, nothing to report then
+            let _ = sender.send(FormResult::Submitted(data));
+        });
+        self.set_on_cancel(move |_| {
+            let _ = on_cancel.send(FormResult::Cancelled);
+        });
+        self
+    }
+
+    /// Adds `hook` called with valid data before it's submitted.
+    ///
+    /// The `hook` can augment or normalize data (e.g. inject a timestamp)
+    /// or reject it by returning errors which are shown in the form.
+    /// Hooks are called in order of adding.
+    pub fn before_submit<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut Value) -> Result<(), FormErrors> + 'static,
+    {
+        self.before_submit.push(Rc::new(hook));
+        self
+    }
+
+    /// Adds `hook` called with data after it was submitted (useful for logging or auditing).
+    pub fn after_submit<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Value) + 'static,
+    {
+        self.after_submit.push(Rc::new(hook));
+        self
+    }
+
+    /// Adds command run in background with values of the form when it's submitted
+    /// in TUI, its non-zero exit status rejects submitting.
+    ///
+    /// `cmd_template` is split into program & arguments at whitespace, then `{key}`
+    /// placeholders in them are replaced with values of fields (keys of groups
+    /// are joined with `.`), so values don't need quoting. Lines of output of the command
+    /// are shown in a collapsible pane added at this point of the form as they come,
+    /// the pane stays expanded when the command fails.
+    ///
+    /// Values starting with `-` are taken by most programs as options, so put `--`
+    /// before placeholders (like `ls -- {path}`) when the program supports it.
+    ///
+    /// Commands don't run when data comes from CLI, web or [validate_value].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    ///
+    /// let form = FormView::new()
+    ///     .field(Text::new("dir"))
+    ///     .external_check("ls -- {dir}");
+    ///
+    /// // not run without TUI
+    /// assert!(form.validate_value(&json!({"dir": "/no/such/dir"})).is_ok());
+    /// # }
+    /// ```
+    ///
+    /// [validate_value]: #method.validate_value
+    pub fn external_check<S: Into<String>>(mut self, cmd_template: S) -> Self {
+        let template = cmd_template.into();
+        let pane = OutputPane::new(format!("Output of `{}`", template));
+        let child = self.add_child(pane);
+        self.external_checks.push(ExternalCheck { template, child });
+        self
+    }
+
+    /// Validates form, passes its data through [before_submit] hooks
+    /// and lets fields prepare for it.
+    ///
+    /// [before_submit]: #method.before_submit
+    pub(crate) fn submit_data(&mut self) -> Result<Value, FormErrors> {
+        let data = self.checked_data()?;
+        self.prepared_data(data)
+    }
+
+    /// Validates form and passes its data through [before_submit] hooks,
+    /// unlike [submit_data] fields don't prepare for it.
+    ///
+    /// [before_submit]: #method.before_submit
+    /// [submit_data]: #method.submit_data
+    fn checked_data(&mut self) -> Result<Value, FormErrors> {
+        let mut data = self.validate()?;
+        for hook in self.before_submit.clone().iter() {
+            if let Err(errors) = hook(&mut data) {
+                self.show_errors(&errors);
+                return Err(errors);
+            }
+        }
+        Ok(data)
+    }
+
+    /// Lets fields prepare for submitting `data`, showing their errors.
+    fn prepared_data(&mut self, data: Value) -> Result<Value, FormErrors> {
+        let errors = self.prepare_submit(&data);
+        if !errors.is_empty() {
+            self.show_errors(&errors);
+            return Err(errors);
+        }
+        Ok(data)
+    }
+
+    /// Collects questions fields ask before preparing for submitting `data`.
+    fn confirm_questions(&self, data: &Value) -> Vec<String> {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, field)| {
+                self.field_data(idx, data)
+                    .and_then(|value| field.confirm_submit(value))
+            })
+            .collect()
+    }
+
+    /// Lets fields prepare for submitting validated `data`, collecting their errors.
+    fn prepare_submit(&self, data: &Value) -> FormErrors {
+        let mut errors = FormErrors::new();
+        for (idx, field) in self.fields.iter().enumerate() {
+            if let Some(value) = self.field_data(idx, data) {
+                if let Err(e) = field.prepare_submit(value) {
+                    errors.push(field.get_label(), e);
+                }
+            }
+        }
+        errors
+    }
+
+    /// Calls [after_submit] hooks with submitted `data`.
+    ///
+    /// [after_submit]: #method.after_submit
+    pub(crate) fn notify_submitted(&self, data: &Value) {
+        for hook in self.after_submit.iter() {
+            hook(data);
+        }
+    }
+
+    /// Translates form's fields to [clap::Arg], listing their validation rules in help.
+    ///
+    /// [clap::Arg]: ../../clap/struct.Arg.html
+    pub fn fields2clap_args(&self) -> Vec<clap::Arg> {
+        let mut args = Vec::with_capacity(self.fields.len());
+        for (idx, (field, help)) in self.fields.iter().zip(self.cli_helps.iter()).enumerate() {
+            let aliases = field
+                .get_aliases()
+                .iter()
+                .map(|a| a.as_str())
+                .collect::<Vec<&str>>();
+            let mut arg = field.clap_arg().help(help).aliases(&aliases);
+            if self.preset[idx] {
+                // value set by program is used when arg is missing
+                arg = arg.required(false);
+            }
+            args.push(arg);
+        }
+        return args;
+    }
+
+    /// Translates [clap::ArgMatches] to [serde_json::Value] based on fields.
+    ///
+    /// Fields filled with [set_values] keep their values when args are missing.
+    /// Errors (of fields or [before_submit] hooks) are printed to stderr.
+    ///
+    /// [set_values]: #method.set_values
+    /// [before_submit]: #method.before_submit
+    /// [clap::ArgMatches]: ../../clap/struct.ArgMatches.html
+    /// [serde_json::Value]: ../../serde_json/enum.Value.html
+    pub fn clap_arg_matches2value(&self, arg_matches: &clap::ArgMatches) -> Value {
+        let (form_data, errors) = self.clap_arg_matches2data(arg_matches);
+        print_cli_errors(&errors);
+        form_data
+    }
+
+    /// Translates [clap::ArgMatches] to data like [clap_arg_matches2value],
+    /// returning errors with data of valid fields.
+    ///
+    /// [clap::ArgMatches]: ../../clap/struct.ArgMatches.html
+    /// [clap_arg_matches2value]: #method.clap_arg_matches2value
+    pub(crate) fn clap_arg_matches2data(
+        &self,
+        arg_matches: &clap::ArgMatches,
+    ) -> (Value, FormErrors) {
+        let mut form_data = Map::with_capacity(self.fields.len());
+        let mut errors = FormErrors::new();
+        let snapshot: FormSnapshot = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| {
+                let value = if self.preset[idx] && !arg_matches.is_present(f.get_key()) {
+                    self.field_value(idx)
+                } else {
+                    f.clap_args2str(&arg_matches)
+                };
+                (f.get_label().to_owned(), value)
+            })
+            .collect();
+        for (idx, field) in self.fields.iter().enumerate() {
+            let data = &snapshot[field.get_label()];
+            match field.validate_in_form(data, &snapshot) {
+                Ok(v) => {
+                    form_data.insert(self.data_path(idx), v);
+                }
+                Err(e) => errors.insert(field.get_label(), e),
+            }
+        }
+        let mut form_data = match self.nest(form_data) {
+            Ok(data) => data,
+            Err(nest_errors) => {
+                for (label, e) in nest_errors.iter() {
+                    errors.insert(label, e.to_vec());
+                }
+                Value::Object(Map::new())
+            }
+        };
+        if errors.is_empty() {
+            if let Some(reason) = self.failed_condition(&form_data) {
+                errors.push(self.submit_text.as_str(), reason);
+            }
+        }
+        for hook in self.before_submit.iter() {
+            if let Err(hook_errors) = hook(&mut form_data) {
+                for (label, e) in hook_errors.iter() {
+                    errors.insert(label, e.to_vec());
+                }
+            }
+        }
+        // fields prepare (e.g. create dirs) only for valid data
+        if errors.is_empty() {
+            for (label, e) in self.prepare_submit(&form_data).iter() {
+                errors.insert(label, e.to_vec());
+            }
+        }
+        (form_data, errors)
+    }
+
+    /// Validates form.
+    pub fn validate(&mut self) -> Result<Value, FormErrors> {
+        let mut data = Map::with_capacity(self.fields.len());
+        let mut errors = FormErrors::new();
+
+        let values = (0..self.fields.len())
+            .map(|idx| self.field_value(idx))
+            .collect::<Vec<String>>();
+        let snapshot: FormSnapshot = self
+            .fields
+            .iter()
+            .zip(values.iter())
+            .map(|(f, v)| (f.get_label().to_owned(), v.clone()))
+            .collect();
+        for (idx, (field, value)) in self.fields.iter().zip(values.iter()).enumerate() {
+            match field.validate_in_form(value, &snapshot) {
+                Ok(v) => {
+                    data.insert(self.data_path(idx), v);
+                }
+                Err(e) => errors.insert(field.get_label(), e),
+            }
+        }
+
+        let name = if self.name.is_empty() {
+            self.view.get_title().to_owned()
+        } else {
+            self.name.clone()
+        };
+        let data = match self.nest(data) {
+            Ok(data) => data,
+            Err(nest_errors) => {
+                for (label, e) in nest_errors.iter() {
+                    errors.insert(label, e.to_vec());
+                }
+                Value::Null
+            }
+        };
+        metrics::notify(|o| o.on_validation(&name, &errors));
+        if errors.is_empty() {
+            self.invalid_fields.clear();
+            self.validated_values.clear();
+            Ok(data)
+        } else {
+            self.show_errors(&errors);
+            Err(errors)
+        }
+    }
+
+    /// Validates not yet validated `values` coming from outside of the form
+    /// (like web page) and passes them through [before_submit] hooks,
+    /// so they are submitted as if typed into the form.
+    ///
+    /// [before_submit]: #method.before_submit
+    pub fn validate_snapshot(&self, values: &FormSnapshot) -> Result<Value, FormErrors> {
+        let data = self.check_snapshot(values)?;
+        let errors = self.prepare_submit(&data);
+        if errors.is_empty() {
+            Ok(data)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates `values` & passes them through [before_submit] hooks, but unlike
+    /// [validate_snapshot] fields don't prepare for submitting.
+    ///
+    /// [before_submit]: #method.before_submit
+    /// [validate_snapshot]: #method.validate_snapshot
+    fn check_snapshot(&self, values: &FormSnapshot) -> Result<Value, FormErrors> {
+        let mut data = Map::with_capacity(self.fields.len());
+        let mut errors = FormErrors::new();
+        for (idx, field) in self.fields.iter().enumerate() {
+            let label = field.get_label();
+            let value = values.get(label).map(|v| v.as_str()).unwrap_or("");
+            match field.validate_in_form(value, values) {
+                Ok(v) => {
+                    data.insert(self.data_path(idx), v);
+                }
+                Err(e) => errors.insert(label, e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let mut data = self.nest(data)?;
+        if let Some(reason) = self.failed_condition(&data) {
+            let mut errors = FormErrors::new();
+            errors.push(self.submit_text.as_str(), reason);
+            return Err(errors);
+        }
+        for hook in self.before_submit.iter() {
+            hook(&mut data)?;
+        }
+        Ok(data)
+    }
+
+    /// Validates `values` (object like data passed to [on_submit]) without any UI,
+    /// as if they were typed into the form, see [validate_snapshot].
+    ///
+    /// Fields missing in `values` keep their initial values. It's only a check,
+    /// so fields don't prepare for submitting (e.g. [SavePath] doesn't create dirs).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::{Checkbox, Text};
+    /// use fui::form::FormView;
+    /// use fui::validators::Required;
+    ///
+    /// let form = FormView::new()
+    ///     .field(Text::new("name").validator(Required))
+    ///     .field(Checkbox::new("force"));
+    ///
+    /// assert_eq!(
+    ///     form.validate_value(&json!({"name": "app"})),
+    ///     Ok(json!({"name": "app", "force": false}))
+    /// );
+    /// assert!(form.validate_value(&json!({"force": true})).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [on_submit]: #method.on_submit
+    /// [validate_snapshot]: #method.validate_snapshot
+    /// [SavePath]: ../fields/struct.SavePath.html
+    pub fn validate_value(&self, values: &Value) -> Result<Value, FormErrors> {
+        self.check_snapshot(&self.snapshot_of(values))
+    }
+
+    /// Converts `values` (object like data passed to [on_submit]) to values as they'd be
+    /// typed into the form, fields missing in `values` get their initial values.
+    ///
+    /// [on_submit]: #method.on_submit
+    pub(crate) fn snapshot_of(&self, values: &Value) -> FormSnapshot {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| {
+                let value = match self.field_data(idx, values) {
+                    Some(value) => f.value2str(value),
+                    None => f.get_initial(),
+                };
+                (f.get_label().to_owned(), value)
+            })
+            .collect()
+    }
+
+    /// Sets name identifying the form in [metrics] (defaults to form's title).
+    ///
+    /// [metrics]: ../metrics/index.html
+    pub(crate) fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    /// Sets `value` of the first field labeled `field_label`.
+    ///
+    /// Returns `false` when there is no such field.
+    pub fn set_field_value(&mut self, field_label: &str, value: &str) -> bool {
+        let idx = match self
+            .fields
+            .iter()
+            .position(|f| f.get_label() == field_label)
+        {
+            Some(idx) => idx,
+            None => return false,
+        };
+        self.set_value_at(idx, value);
+        self.revalidate_edited();
+        self.refresh_submit();
+        true
+    }
+
+    /// Rebuilds widget of field at `idx` with `value`.
+    fn set_value_at(&mut self, idx: usize, value: &str) {
+        let widget = field_view_mut(&mut self.view, self.field_children[idx]);
+        self.fields[idx]
+            .get_widget_manager()
+            .set_value(widget, value);
+        if self.masked {
+            self.mask_field(idx);
+        }
+        if let Some(ref mut hint) = self.probe_hints[idx] {
+            // value set by program is probed even when field is focused
+            hint.probed = None;
+        }
+        self.initial_values[idx] = self.field_value(idx);
+    }
+
+    /// Makes fields parsing numbers & dates accept & show them in format of `locale`
+    /// (like `de-DE` with comma as decimal separator), see [set_locale].
+    ///
+    /// [set_locale]: #method.set_locale
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.set_locale(locale);
+        self
+    }
+
+    /// Makes fields parsing numbers & dates accept & show them in format of `locale`.
+    ///
+    /// Submitted data stays in canonical form, typed values are converted
+    /// to the new format.
+    pub fn set_locale(&mut self, locale: &str) {
+        for idx in 0..self.fields.len() {
+            let raw = self.field_value(idx);
+            let value = self.fields[idx].validate(&raw);
+            if !self.fields[idx].set_locale(locale) {
+                continue;
+            }
+            let raw = match value {
+                Ok(value) => self.fields[idx].value2str(&value),
+                Err(_) => raw,
+            };
+            self.set_value_at(idx, &raw);
+        }
+        // values are parsed differently now
+        self.condition_values.clear();
+    }
+
+    /// Shows placeholders instead of fields' values when `masked`,
+    /// e.g. for taking screenshots without leaking data.
+    ///
+    /// Values stay untouched, it can be toggled with `F10` key.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        for idx in 0..self.fields.len() {
+            self.mask_field(idx);
+        }
+    }
+
+    /// Sets how densely fields are laid out, see [set_density].
+    ///
+    /// [set_density]: #method.set_density
+    pub fn density(mut self, density: Density) -> Self {
+        self.set_density(density);
+        self
+    }
+
+    /// Sets how densely fields are laid out: `Density::Auto` picks compact profile
+    /// when the form has fewer than 30 rows of terminal, comfortable one otherwise.
+    ///
+    /// Fields with custom layouts (see [FormField::build_widget]) keep it.
+    ///
+    /// [FormField::build_widget]: ../fields/trait.FormField.html#method.build_widget
+    pub fn set_density(&mut self, density: Density) {
+        self.density = density;
+        self.applied_profile = None;
+    }
+
+    /// Removes blank row below each field when `compact`, so about twice as many
+    /// fields fit on screen. Helps & dropdowns stay as [density] sets them.
+    ///
+    /// [density]: #method.density
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self.applied_profile = None;
+        self
+    }
+
+    /// Replaces profiles used for compact & comfortable [density].
+    ///
+    /// [density]: #method.density
+    pub fn layout_profiles(mut self, compact: LayoutProfile, comfortable: LayoutProfile) -> Self {
+        self.set_layout_profiles(compact, comfortable);
+        self
+    }
+
+    /// Replaces profiles used for compact & comfortable [density].
+    ///
+    /// [density]: #method.density
+    pub fn set_layout_profiles(&mut self, compact: LayoutProfile, comfortable: LayoutProfile) {
+        self.compact_profile = compact;
+        self.comfortable_profile = comfortable;
+        self.applied_profile = None;
+    }
+
+    /// Lays out fields with profile of density suitable for `rows` of terminal.
+    fn fit_density(&mut self, rows: usize) {
+        let compact = match self.density {
+            Density::Auto => rows < COMPACT_BELOW_ROWS,
+            Density::Compact => true,
+            Density::Comfortable => false,
+        };
+        let mut profile = if compact {
+            self.compact_profile.clone()
+        } else {
+            self.comfortable_profile.clone()
+        };
+        if self.compact {
+            profile.spacing = 0;
+        }
+        if self.applied_profile.as_ref() == Some(&profile) {
+            return;
+        }
+        for (idx, field) in self.fields.iter().enumerate() {
+            let widget = field_view_mut(&mut self.view, self.field_children[idx]);
+            let help = if profile.show_help {
+                field.get_help()
+            } else {
+                ""
+            };
+            let annotation = self.layouts[idx].annotation(field.get_label(), help);
+            fields::set_annotation(widget, annotation);
+            fields::set_spacer(widget, self.layouts[idx].spacer(profile.spacing));
+            field
+                .get_widget_manager()
+                .set_max_dropdown_rows(widget, profile.dropdown_rows);
+        }
+        self.applied_profile = Some(profile);
+    }
+
+    /// Checks if fields' values are masked.
+    pub fn is_masked(&self) -> bool {
+        self.masked
+    }
+
+    fn mask_field(&mut self, idx: usize) {
+        self.fields[idx].get_widget_manager().set_masked(
+            field_view_mut(&mut self.view, self.field_children[idx]),
+            self.masked,
+        );
+    }
+
+    /// Fills fields with `values`, which is an object like data passed to [on_submit]
+    /// (nested, see [group]) or keyed by fields' keys.
+    ///
+    /// Keys not matching any field are ignored. In CLI the filled fields
+    /// take these values when their options aren't passed.
+    ///
+    /// [on_submit]: #method.on_submit
+    /// [group]: #method.group
+    pub fn set_values(&mut self, values: &Value) {
+        if values.is_object() {
+            for idx in 0..self.fields.len() {
+                let raw = match self.field_data(idx, values) {
+                    Some(value) => self.fields[idx].value2str(value),
+                    None => continue,
+                };
+                let label = self.fields[idx].get_label().to_owned();
+                self.set_field_value(&label, &raw);
+                self.preset[idx] = true;
+            }
+        }
+    }
+
+    /// Fills fields with `values` like [set_values], except fields with `initial` value
+    /// or values set by the program before.
+    ///
+    /// [set_values]: #method.set_values
+    #[cfg(feature = "remote-defaults")]
+    pub(crate) fn set_defaults(&mut self, values: &Value) {
+        if values.is_object() {
+            for idx in 0..self.fields.len() {
+                if self.preset[idx] || self.fields[idx].has_initial() {
+                    continue;
+                }
+                let raw = match self.field_data(idx, values) {
+                    Some(value) => self.fields[idx].value2str(value),
+                    None => continue,
+                };
+                let label = self.fields[idx].get_label().to_owned();
+                self.set_field_value(&label, &raw);
+                self.preset[idx] = true;
+            }
+        }
+    }
+
+    /// Gets not yet validated values of fields.
+    pub fn snapshot(&self) -> FormSnapshot {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| (f.get_label().to_owned(), self.field_value(idx)))
+            .collect()
+    }
+
+    /// Gets labels of fields changed by user.
+    ///
+    /// Values set with [set_field_value] or [set_values] don't count as changes.
+    ///
+    /// [set_field_value]: #method.set_field_value
+    /// [set_values]: #method.set_values
+    pub fn modified_fields(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| self.field_value(idx) != self.initial_values[idx])
+            .map(|(_, f)| f.get_label())
+            .collect()
+    }
+
+    /// Gets current value of widget of field at `idx`.
+    fn field_value(&self, idx: usize) -> String {
+        let view = self
+            .view
+            .get_content()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+            .get_child(self.field_children[idx])
+            .unwrap();
+        let view_box: &BoxedView = (*view).as_any().downcast_ref().unwrap();
+        self.fields[idx].get_widget_manager().get_value(view_box)
+    }
+
+    fn show_errors(&mut self, form_errors: &FormErrors) {
+        self.invalid_fields = (0..self.fields.len())
+            .filter(|&idx| {
+                form_errors
+                    .for_field(self.fields[idx].get_label())
+                    .is_some()
+            })
+            .collect();
+        self.validated_values = (0..self.fields.len())
+            .map(|idx| self.field_value(idx))
+            .collect();
+        for (idx, field) in self.fields.iter().enumerate() {
+            let label = field.get_label();
+            let error = form_errors
+                .for_field(label)
+                .map(fields::format_errors)
+                .unwrap_or_default();
+            // can't call method which returns suitable view because of ownership
+            //  * such method would get &mut self
+            //  * self.field gets &self
+            //  so this clash of &mut and &, illegal
+            //  possible solution is to use clone on WidgetManager (needs implementation)
+            //  or
+            //  form should only call field.validate and rest would be handled by field
+            //  which should solve this issue?
+            let viewbox = field_view_mut(&mut self.view, self.field_children[idx]);
+            field.set_error(viewbox, &error);
+        }
+    }
+
+    /// Validates fields edited since errors were shown, updating their errors & the count.
+    fn revalidate_edited(&mut self) {
+        if self.validated_values.is_empty() {
+            return;
+        }
+        self.validated_values
+            .resize(self.fields.len(), String::new());
+        let snapshot = self.snapshot();
+        for idx in 0..self.fields.len() {
+            let value = self.field_value(idx);
+            if value == self.validated_values[idx] {
+                continue;
+            }
+            let error = match self.fields[idx].validate_in_form(&value, &snapshot) {
+                Ok(_) => String::new(),
+                Err(errors) => fields::format_errors(&errors),
+            };
+            let viewbox = field_view_mut(&mut self.view, self.field_children[idx]);
+            self.fields[idx].set_error(viewbox, &error);
+            self.invalid_fields.retain(|&invalid| invalid != idx);
+            if !error.is_empty() {
+                self.invalid_fields.push(idx);
+                self.invalid_fields.sort_unstable();
+            }
+            self.validated_values[idx] = value;
+        }
+    }
+
+    fn event_submit(&mut self) -> EventResult {
+        if self.review
+            || !self.is_submit_enabled()
+            || self.running_checks.is_some()
+            || self.confirming.is_some()
+        {
+            return EventResult::Consumed(None);
+        }
+        let data = match self.checked_data() {
+            Ok(data) => data,
+            // TODO: the event focus next required/invalid field?
+            Err(_) => return EventResult::Consumed(None),
+        };
+        let questions = self.confirm_questions(&data);
+        if questions.is_empty() {
+            return self.finish_submit(data);
+        }
+        let answer = Rc::new(Cell::new(None));
+        self.confirming = Some(PendingConfirm {
+            data,
+            answer: Rc::clone(&answer),
+        });
+        let cb = Callback::from_fn(move |c| {
+            c.add_layer(confirm_dialog(&questions, Rc::clone(&answer)));
+        });
+        EventResult::Consumed(Some(cb))
+    }
+
+    /// Submits checked `data` once fields are prepared for it
+    /// (after [external_check]s succeed if there are any).
+    ///
+    /// [external_check]: #method.external_check
+    fn finish_submit(&mut self, data: Value) -> EventResult {
+        match self.prepared_data(data) {
+            Ok(data) if self.external_checks.is_empty() => {
+                EventResult::Consumed(Some(self.submit_callback(data)))
+            }
+            Ok(data) => self.spawn_external_checks(data),
+            Err(_) => EventResult::Consumed(None),
+        }
+    }
+
+    /// Submits data waiting for [confirm_dialog] once user agrees,
+    /// refused data is dropped.
+    fn finish_confirm(&mut self) -> Option<EventResult> {
+        let agreed = self.confirming.as_ref()?.answer.get()?;
+        let data = self.confirming.take()?.data;
+        if agreed {
+            Some(self.finish_submit(data))
+        } else {
+            None
+        }
+    }
+
+    /// Gets callback passing submitted `data` to [on_submit] & [after_submit] hooks.
+    ///
+    /// [on_submit]: #method.on_submit
+    /// [after_submit]: #method.after_submit
+    fn submit_callback(&self, data: Value) -> Callback {
+        let on_submit = self.on_submit.clone();
+        let after_submit = self.after_submit.clone();
+        Callback::from_fn(move |c| {
+            if let Some(ref cb) = on_submit {
+                cb(c, data.clone());
+            }
+            for hook in after_submit.iter() {
+                hook(&data);
+            }
+        })
+    }
+
+    /// Gets pane showing output of [external_check] at layout child `idx`.
+    ///
+    /// [external_check]: #method.external_check
+    fn output_pane_mut(&mut self, idx: usize) -> &mut OutputPane {
+        self.view
+            .get_content_mut()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+            .get_child_mut(idx)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<OutputPane>()
+            .unwrap()
+    }
+
+    /// Runs commands added with [external_check] for `data` in background,
+    /// the form is submitted once they all succeed (see [finish_external_checks]).
+    ///
+    /// [external_check]: #method.external_check
+    /// [finish_external_checks]: #method.finish_external_checks
+    fn spawn_external_checks(&mut self, data: Value) -> EventResult {
+        let mut commands = Vec::with_capacity(self.external_checks.len());
+        for idx in 0..self.external_checks.len() {
+            let args = self.external_checks[idx]
+                .template
+                .split_whitespace()
+                .map(|part| fill_placeholders(part, &data))
+                .collect::<Vec<String>>();
+            let pane = self.output_pane_mut(self.external_checks[idx].child);
+            pane.set_output("");
+            pane.set_expanded(true);
+            commands.push((args, pane.get_shared_output()));
+        }
+        let errors = Arc::new(Mutex::new(None));
+        self.running_checks = Some(RunningChecks {
+            data,
+            errors: Arc::clone(&errors),
+        });
+        let cb = Callback::from_fn(move |c| {
+            let sink = c.cb_sink().clone();
+            let commands = commands.clone();
+            let errors = Arc::clone(&errors);
+            thread::spawn(move || {
+                // sending any callback redraws the screen
+                let redraw = || {
+                    let _ = sink.send(Box::new(|_: &mut Cursive| {}));
+                };
+                let results = commands
+                    .iter()
+                    .map(|(args, output)| run_external_check(args, output, &redraw))
+                    .collect();
+                *errors.lock().unwrap() = Some(results);
+                // it fails only when UI is already closed, so the form can't be submitted anyway
+                let _ = sink.send(Box::new(|c: &mut Cursive| c.on_event(Event::Refresh)));
+            });
+        });
+        EventResult::Consumed(Some(cb))
+    }
+
+    /// Submits data once commands of [spawn_external_checks] succeed,
+    /// panes of failed ones stay expanded.
+    ///
+    /// [spawn_external_checks]: #method.spawn_external_checks
+    fn finish_external_checks(&mut self) -> Option<Callback> {
+        let results = self
+            .running_checks
+            .as_ref()?
+            .errors
+            .lock()
+            .unwrap()
+            .take()?;
+        let data = self.running_checks.take()?.data;
+        let mut failed = false;
+        for (idx, error) in results.into_iter().enumerate() {
+            let pane = self.output_pane_mut(self.external_checks[idx].child);
+            pane.set_expanded(error.is_some());
+            if let Some(error) = error {
+                pane.get_shared_output().append(format!("{}\n", error));
+                failed = true;
+            }
+        }
+        if failed {
+            None
+        } else {
+            Some(self.submit_callback(data))
+        }
+    }
+
+    /// Shows popup listing rules of the focused field.
+    fn event_show_rules(&self) -> EventResult {
+        let field = match self.focused_field() {
+            Some(idx) => &self.fields[idx],
+            None => return EventResult::Ignored,
+        };
+        let rules = field.describe_rules();
+        let text = if rules.is_empty() {
+            "No validation rules".to_string()
+        } else {
+            rules
+                .iter()
+                .map(|r| format!("• {}", r))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let title = field.get_label().to_owned();
+        let cb = Callback::from_fn(move |c| {
+            c.add_layer(Dialog::info(text.clone()).title(title.clone()));
+        });
+        EventResult::Consumed(Some(cb))
+    }
+
+    /// Gets index of the field which has focus.
+    fn focused_field(&self) -> Option<usize> {
+        if self.view.focus() != DialogFocus::Content {
+            return None;
+        }
+        let child_idx = self
+            .view
+            .get_content()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+            .get_focus_index();
+        self.field_children.iter().position(|&idx| idx == child_idx)
+    }
+
+    /// Moves focus to the next (or previous when `backwards`) place of form,
+    /// unless the focused field uses `event` itself.
+    fn event_tab(&mut self, event: Event, backwards: bool) -> EventResult {
+        let current = self.focused_stop();
+        match current {
+            Some(FocusStop::Field(idx)) if !self.review => {
+                let view = field_view_mut(&mut self.view, self.field_children[idx]);
+                let result = view.on_event(event);
+                if result.is_consumed() {
+                    return result;
+                }
+            }
+            _ => {}
+        }
+        let stops = self.focus_stops();
+        let count = stops.len();
+        let current = current.and_then(|stop| stops.iter().position(|&s| s == stop));
+        for step in 1..=count {
+            let pos = match (current, backwards) {
+                (Some(pos), false) => (pos + step) % count,
+                (Some(pos), true) => (pos + count - step) % count,
+                (None, false) => step - 1,
+                (None, true) => count - step,
+            };
+            if self.focus_stop(stops[pos]) {
+                break;
+            }
+        }
+        // focus never leaves the form
+        EventResult::Consumed(None)
+    }
+
+    /// Lists places which can be focused in `Tab` order.
+    fn focus_stops(&self) -> Vec<FocusStop> {
+        let mut fields = (0..self.fields.len()).collect::<Vec<usize>>();
+        // stable sort keeps order of declaration for fields without tab index
+        fields.sort_by_key(|&idx| self.fields[idx].get_tab_index().unwrap_or(usize::MAX));
+        fields
+            .into_iter()
+            .map(FocusStop::Field)
+            .chain((0..self.view.buttons_len()).map(FocusStop::Button))
+            .collect()
+    }
+
+    fn focused_stop(&self) -> Option<FocusStop> {
+        match self.view.focus() {
+            DialogFocus::Button(idx) => Some(FocusStop::Button(idx)),
+            DialogFocus::Content => self.focused_field().map(FocusStop::Field),
+        }
+    }
+
+    /// Focuses `stop`, returns false if it can't be focused.
+    fn focus_stop(&mut self, stop: FocusStop) -> bool {
+        match stop {
+            FocusStop::Field(idx) => {
+                let child_idx = self.field_children[idx];
+                let layout = self
+                    .view
+                    .get_content_mut()
+                    .as_any_mut()
+                    .downcast_mut::<LinearLayout>()
+                    .unwrap();
+                if layout.set_focus_index(child_idx).is_err() {
+                    return false;
+                }
+                self.view.set_focus(DialogFocus::Content);
+                true
+            }
+            FocusStop::Button(idx) => {
+                let enabled = self.view.buttons().nth(idx).is_some_and(|b| b.is_enabled());
+                if enabled {
+                    self.view.set_focus(DialogFocus::Button(idx));
+                }
+                enabled
+            }
+        }
+    }
+
+    fn event_cancel(&mut self) -> EventResult {
+        let modified = self
+            .modified_fields()
+            .iter()
+            .map(|&label| label.to_owned())
+            .collect::<Vec<String>>();
+        let on_cancel = match self.on_cancel.clone() {
+            Some(on_cancel) => on_cancel,
+            None => return EventResult::Consumed(None),
+        };
+        if modified.is_empty() || self.discard_silently {
+            return EventResult::with_cb(move |c| on_cancel(c));
+        }
+        let snapshot = self.snapshot();
+        let on_save_draft = self.on_save_draft.clone();
+        EventResult::with_cb(move |c| {
+            let dialog = discard_dialog(
+                &modified,
+                snapshot.clone(),
+                on_save_draft.clone(),
+                Rc::clone(&on_cancel),
+            );
+            c.add_layer(dialog);
+        })
+    }
+
+    /// Sets `title` of the form on the top of it.
+    pub fn title(mut self, title: &str) -> Self {
+        self.view.set_title(title);
+        self
+    }
+
+    /// Describes data produced by the form as [JSON Schema].
+    ///
+    /// Allows validating data which doesn't come from `fui` (batch or headless submissions)
+    /// with any JSON Schema validator.
+    ///
+    /// [JSON Schema]: https://json-schema.org
+    pub fn to_json_schema(&self) -> Value {
+        let object = || {
+            json!({
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false,
+            })
+        };
+        let mut schema = object();
+        for (idx, field) in self.fields.iter().enumerate() {
+            let path = self.data_path(idx);
+            let mut keys = utils::path_keys(&path);
+            let key = keys.pop().unwrap_or_default();
+            // fields of groups are properties of nested objects
+            let mut target = &mut schema;
+            for nested in keys {
+                let property = &mut target["properties"][&nested];
+                if property["type"] != "object" {
+                    *property = object();
+                }
+                target = property;
+            }
+            target["properties"][&key] = field.json_schema();
+            if field.is_required() {
+                if let Value::Array(ref mut required) = target["required"] {
+                    required.push(json!(key));
+                }
+            }
+        }
+        schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+        let title = self.view.get_title();
+        if !title.is_empty() {
+            schema["title"] = json!(title);
+        }
+        schema
+    }
+
+    /// Sets fixed `width` (in columns) of the form, which is centered horizontally.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Limits width of the form to `max_width` columns, the form is centered horizontally.
+    ///
+    /// It keeps forms with long help messages readable on wide terminals.
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Makes the form take all available space.
+    ///
+    /// Combined with [width] or [max_width] the dialog is centered on the screen.
+    /// Unlike `Resizable::full_screen` it returns `FormView`, so it can be chained with other options.
+    ///
+    /// [width]: #method.width
+    /// [max_width]: #method.max_width
+    pub fn fill_screen(mut self) -> Self {
+        self.fill_screen = true;
+        self
+    }
+
+    /// Gets width of the dialog when `available` columns can be used.
+    fn dialog_width(&self, available: usize) -> usize {
+        let width = self.width.unwrap_or(available);
+        let width = self.max_width.map_or(width, |max| min(width, max));
+        min(width, available)
+    }
+
+    /// Gets fields of `FormView`
+    pub fn get_fields(&self) -> &[Box<dyn FormField>] {
+        &self.fields
+    }
+
+    /// Gets value of a field with label equal to `field_label`
+    ///
+    /// Returns first occurence when a label occures more than once.
+    pub fn get_field_value(&self, field_label: &str) -> Option<String> {
+        self.fields
+            .iter()
+            .position(|f| f.get_label() == field_label)
+            .map(|idx| self.field_value(idx))
+    }
+}
+
+/// Builds dialog asking if changes of `modified` fields should be discarded
+/// when leaving form, which can be saved as a draft too.
+fn discard_dialog(
+    modified: &[String],
+    snapshot: FormSnapshot,
+    on_save_draft: OnSaveDraft,
+    on_cancel: Rc<dyn Fn(&mut Cursive)>,
+) -> Dialog {
+    let count = match modified.len() {
+        1 => "1 field".to_string(),
+        n => format!("{} fields", n),
+    };
+    let labels = modified
+        .iter()
+        .map(|label| format!("- {}", label))
+        .collect::<Vec<String>>();
+    let text = format!(
+        "You have modified {} \u{2014} discard?\n\n{}",
+        count,
+        labels.join("\n")
+    );
+    let mut dialog = Dialog::text(text)
+        .title("Unsaved changes")
+        .button("Keep editing", |c| {
+            c.pop_layer();
+        });
+    if let Some(save) = on_save_draft {
+        let on_cancel = Rc::clone(&on_cancel);
+        dialog.add_button("Save draft", move |c| {
+            c.pop_layer();
+            save(c, snapshot.clone());
+            on_cancel(c);
+        });
+    }
+    dialog.add_button("Discard", move |c| {
+        c.pop_layer();
+        on_cancel(c);
+    });
+    dialog
+}
+
+/// Builds dialog asking `questions` of fields before submitting, its buttons set `answer`.
+fn confirm_dialog(questions: &[String], answer: Rc<Cell<Option<bool>>>) -> Dialog {
+    let refused = Rc::clone(&answer);
+    Dialog::text(questions.join("\n"))
+        .title("Confirm submit")
+        .button("Cancel", move |c| {
+            refused.set(Some(false));
+            c.pop_layer();
+            c.on_event(Event::Refresh);
+        })
+        .button("Yes", move |c| {
+            answer.set(Some(true));
+            c.pop_layer();
+            // lets the form submit its data
+            c.on_event(Event::Refresh);
+        })
+}
+
+/// Gets widget at layout child `idx` from form's `dialog`.
+fn field_view_mut(dialog: &mut Dialog, idx: usize) -> &mut BoxedView {
+    let view = dialog
+        .get_content_mut()
+        .as_any_mut()
+        .downcast_mut::<LinearLayout>()
+        .unwrap()
+        .get_child_mut(idx)
+        .unwrap();
+    view.as_any_mut().downcast_mut().unwrap()
+}
+
+/// Runs command `args` (program followed by its arguments), appending lines of its output
+/// to `output` as they come & calling `on_line` after each, getting error when it fails.
+fn run_external_check(
+    args: &[String],
+    output: &TextContent,
+    on_line: &(dyn Fn() + Sync),
+) -> Option<String> {
+    let program = args.first().map(|p| p.as_str()).unwrap_or_default();
+    let child = Command::new(program)
+        .args(args.iter().skip(1))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => return Some(format!("Check can't be run: {}", e)),
+    };
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    thread::scope(|scope| {
+        scope.spawn(|| stream_lines(stderr, output, on_line));
+        stream_lines(stdout, output, on_line);
+    });
+    match child.wait() {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(format!("Check failed ({})", status)),
+        Err(e) => Some(format!("Check can't be run: {}", e)),
+    }
+}
+
+/// Appends lines read from `pipe` to `output`, calling `on_line` after each.
+fn stream_lines<R: Read>(pipe: Option<R>, output: &TextContent, on_line: &(dyn Fn() + Sync)) {
+    if let Some(pipe) = pipe {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            output.append(format!("{}\n", line));
+            on_line();
+        }
+    }
+}
+
+/// Replaces `{key}` placeholders in `text` with values of `data` under these keys.
+fn fill_placeholders(text: &str, data: &Value) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        filled.push_str(&rest[..start]);
+        let value = match utils::get_path(data, &rest[start + 1..end]) {
+            Some(Value::String(s)) => s.to_owned(),
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_owned()).unwrap_or(v.to_string()))
+                .collect::<Vec<String>>()
+                .join(","),
+            Some(Value::Null) | None => String::new(),
+            Some(v) => v.to_string(),
+        };
+        filled.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Tells that value under `old` key belongs to field keyed `new` now.
+fn renamed_warning(old: &str, new: &str) -> String {
+    format!("`{}` is renamed to `{}`", old, new)
+}
+
+/// Prints `errors` of data from CLI args to stderr, line by line.
+pub(crate) fn print_cli_errors(errors: &FormErrors) {
+    for line in errors.to_string().lines() {
+        eprintln!("ERROR: {}", line);
+    }
+}
+
+/// Tells that `field` is deprecated (if it is).
+fn deprecated_warning(field: &dyn FormField) -> Option<String> {
+    field.get_deprecation().map(|(since, note)| {
+        format!(
+            "`{}` is deprecated since {}: {}",
+            field.get_key(),
+            since,
+            note
+        )
+    })
+}
+
+impl ViewWrapper for FormView {
+    wrap_impl!(self.view: Dialog);
+
+    fn wrap_required_size(&mut self, req: Vec2) -> Vec2 {
+        self.fit_density(req.y);
+        if self.fill_screen {
+            return req;
+        }
+        let width = self.dialog_width(req.x);
+        let mut size = self.view.required_size(Vec2::new(width, req.y));
+        if let Some(width) = self.width {
+            size.x = width;
+        }
+        if let Some(max_width) = self.max_width {
+            size.x = min(size.x, max_width);
+        }
+        size
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        let width = self.dialog_width(size.x);
+        self.offset_x = (size.x - width) / 2;
+        self.refresh_probes();
+        self.view.layout(Vec2::new(width, size.y));
+    }
+
+    fn wrap_draw(&self, printer: &Printer) {
+        let width = self.dialog_width(printer.size.x);
+        let printer = printer
+            .offset((self.offset_x, 0))
+            .cropped((width, printer.size.y));
+        self.view.draw(&printer);
+        let mut progress_len = 0;
+        if self.required_progress && printer.size.y > 0 {
+            let (completed, total) = self.required_counts();
+            let text = format!(" {}/{} required fields completed ", completed, total);
+            if text.len() + 4 <= printer.size.x {
+                // on the bottom border, like a title
+                printer.print((2, printer.size.y - 1), &text);
+                progress_len = text.len() + 2;
+            }
+        }
+        if let Some(ref reason) = self.submit_blocked {
+            let text = format!(" {} ", reason);
+            let width = text.width();
+            if printer.size.y > 0 && progress_len + width + 4 <= printer.size.x {
+                printer.with_color(ColorStyle::secondary(), |p| {
+                    p.print((p.size.x - width - 2, p.size.y - 1), &text);
+                });
+            }
+        }
+        if self.sticky_header && printer.size.y > 0 {
+            // the form is scrolled by `content_offset`
+            self.draw_header(&printer, printer.content_offset.y);
+        }
+    }
+
+    fn wrap_important_area(&self, size: Vec2) -> Rect {
+        let width = self.dialog_width(size.x);
+        let mut area = self.view.important_area(Vec2::new(width, size.y));
+        if self.sticky_header && area.top() > 0 {
+            // keeps focused field below the pinned header
+            area = Rect::from_corners((area.left(), area.top() - 1), area.bottom_right());
+        }
+        area.offset((self.offset_x, 0));
+        area
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let submit = self.finish_external_checks();
+        let confirmed = self.finish_confirm();
+        let result = self.handle_event(event);
+        // values might have changed
+        self.revalidate_edited();
+        self.refresh_submit();
+        let result = match submit {
+            Some(cb) => result.and(EventResult::Consumed(Some(cb))),
+            None => result,
+        };
+        let result = match confirmed {
+            Some(confirmed) => result.and(confirmed),
+            None => result,
+        };
+        self.redraw.learn(result)
+    }
+}
+
+impl FormView {
+    fn handle_event(&mut self, event: Event) -> EventResult {
+        let event = event.relativized((self.offset_x, 0));
+        if self.review {
+            return self.handle_review_event(event);
+        }
+        match event {
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } if self.sticky_header
+                && self.error_count() > 0
+                && position.checked_sub(offset).map(|p| p.y) == self.header_row.get() =>
+            {
+                self.focus_next_invalid()
+            }
+            Event::Mouse {
+                offset: _,
+                position: _,
+                event: MouseEvent::Press(btn),
+            } => {
+                if btn == MouseButton::Left {
+                    self.with_view_mut(|v| v.on_event(event))
+                        .unwrap_or(EventResult::Ignored);
+                    match self.view.focus() {
+                        DialogFocus::Button(idx) => self.event_button(idx),
+                        _ => EventResult::Ignored,
+                    }
+                } else {
                     EventResult::Ignored
                 }
             }
             Event::Key(Key::Enter) => match self.view.focus() {
-                DialogFocus::Button(0) => self.event_cancel(),
-                DialogFocus::Button(1) => self.event_submit(),
+                DialogFocus::Button(idx) => self.event_button(idx),
                 _ => self
                     .with_view_mut(|v| v.on_event(event))
                     .unwrap_or(EventResult::Ignored),
             },
             // TODO: ctlr+enter binding?
-            Event::CtrlChar('f') => self.event_submit(),
+            SUBMIT_KEY => self.event_submit(),
+            Event::Key(Key::Tab) => self.event_tab(event, false),
+            Event::Shift(Key::Tab) => self.event_tab(event, true),
+            RULES_KEY => self.event_show_rules(),
+            NEXT_ERROR_KEY if self.sticky_header => self.focus_next_invalid(),
+            MASK_KEY => {
+                let masked = !self.masked;
+                self.set_masked(masked);
+                EventResult::Consumed(None)
+            }
             _ => {
                 // default behaviour from ViewWrapper
                 self.with_view_mut(|v| v.on_event(event))
@@ -293,3 +2552,913 @@ impl ViewWrapper for FormView {
         }
     }
 }
+
+impl FormView {
+    /// Handles `event` of read-only form, letting only focus move & buttons be pressed.
+    fn handle_review_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Tab) => self.event_tab(event, false),
+            Event::Shift(Key::Tab) => self.event_tab(event, true),
+            Event::Key(Key::Enter) => match self.view.focus() {
+                DialogFocus::Button(idx) => self.event_button(idx),
+                _ => EventResult::Ignored,
+            },
+            RULES_KEY => self.event_show_rules(),
+            MASK_KEY => {
+                let masked = !self.masked;
+                self.set_masked(masked);
+                EventResult::Consumed(None)
+            }
+            // other events might change values, ignored ones still scroll the form
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::direction::Direction;
+    use fields::{
+        AnnotationLeft, Autocomplete, Checkbox, Decimal, IntNumber, Multiselect, NoAnnotation,
+        Number, SavePath, Text,
+    };
+    use std::any::Any;
+    use std::cell::Cell;
+    use validators::{OneOf, Required, Validator};
+
+    fn auth_form(method: &str) -> FormView {
+        FormView::new()
+            .field(Text::new("auth-method").initial(method))
+            .field(Text::new("key-file").validator_if("auth-method", |v| v == "key", Required))
+    }
+
+    #[test]
+    fn rules_of_focused_field_are_described() {
+        let form = FormView::new()
+            .field(
+                Text::new("compression")
+                    .validator(Required)
+                    .validator(OneOf(vec!["none", "gzip", "bzip2"])),
+            )
+            .field(Text::new("level").validator_if("compression", |v| v != "none", Required));
+
+        let idx = form.focused_field().unwrap();
+        assert_eq!(
+            form.fields[idx].describe_rules(),
+            vec!["required", "must be one of: none, gzip, bzip2"]
+        );
+        assert_eq!(
+            form.fields[1].describe_rules(),
+            vec!["required (depending on compression)"]
+        );
+    }
+
+    #[test]
+    fn rules_are_appended_to_cli_help() {
+        let form = FormView::new().field(
+            Text::new("compression")
+                .help("compression method")
+                .validator(OneOf(vec!["none", "gzip"])),
+        );
+        let mut help = Vec::new();
+        clap::App::new("app")
+            .args(&form.fields2clap_args())
+            .write_help(&mut help)
+            .unwrap();
+
+        let help = String::from_utf8(help).unwrap();
+        assert!(help.contains("compression method [must be one of: none, gzip]"));
+    }
+
+    #[test]
+    fn keys_name_data_and_cli_args() {
+        let form = FormView::new()
+            .field(Text::new("Destination directory").key("dst_dir"))
+            .field(Checkbox::new("Overwrite files").key("force"));
+        let matches = clap::App::new("app")
+            .args(&form.fields2clap_args())
+            .get_matches_from(vec!["app", "--dst_dir", "/tmp", "--force"]);
+
+        let data = form.clap_arg_matches2value(&matches);
+
+        assert_eq!(data, json!({"dst_dir": "/tmp", "force": true}));
+        assert_eq!(form.get_fields()[0].get_label(), "Destination directory");
+    }
+
+    #[test]
+    fn invalid_cli_args_dont_create_dirs() {
+        let root = ::std::env::temp_dir().join(format!("fui-cli-dirs-{}", ::std::process::id()));
+        let target = root.join("out.txt");
+        let form = FormView::new()
+            .field(::fields::SavePath::new("out"))
+            .field(Text::new("level").validator(OneOf(vec!["low", "high"])));
+        let matches = clap::App::new("app")
+            .args(&form.fields2clap_args())
+            .get_matches_from(vec!["app", "--out", target.to_str().unwrap(), "--level=x"]);
+
+        form.clap_arg_matches2value(&matches);
+
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn old_keys_fill_renamed_fields_with_warnings() {
+        let mut form = FormView::new()
+            .field(Text::new("dst").alias("target"))
+            .group("net")
+            .field(Text::new("host").alias("hostname"))
+            .field(Checkbox::new("legacy").deprecated("2.0", "it's always on"));
+        let old = json!({"target": "/tmp", "net": {"hostname": "h", "legacy": true}});
+
+        form.set_values(&old);
+
+        assert_eq!(
+            form.validate().unwrap(),
+            json!({"dst": "/tmp", "net": {"host": "h", "legacy": true}})
+        );
+        assert_eq!(
+            form.deprecation_warnings(&old),
+            vec![
+                "`target` is renamed to `dst`",
+                "`hostname` is renamed to `host`",
+                "`legacy` is deprecated since 2.0: it's always on",
+            ]
+        );
+        let current = form.validate().unwrap();
+        assert_eq!(
+            form.deprecation_warnings(&current),
+            vec!["`legacy` is deprecated since 2.0: it's always on"]
+        );
+    }
+
+    #[test]
+    fn old_cli_args_are_accepted_with_warnings() {
+        let form = FormView::new()
+            .field(Text::new("dst").alias("target"))
+            .field(Checkbox::new("legacy").deprecated("2.0", "it's always on"));
+        let args = vec!["app", "--target=/tmp", "--legacy"];
+        let matches = clap::App::new("app")
+            .args(&form.fields2clap_args())
+            .get_matches_from(args.clone());
+
+        assert_eq!(
+            form.clap_arg_matches2value(&matches),
+            json!({"dst": "/tmp", "legacy": true})
+        );
+        let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            form.cli_deprecation_warnings(&args),
+            vec![
+                "`--target` is renamed to `--dst`",
+                "`legacy` is deprecated since 2.0: it's always on",
+            ]
+        );
+        assert_eq!(
+            form.get_fields()[1].cli_help(),
+            "[deprecated since 2.0: it's always on]"
+        );
+    }
+
+    #[test]
+    fn buttons_can_be_relabeled_and_cancel_hidden() {
+        let form = FormView::new().submit_label("Run").cancel_label("Back");
+        let labels = form.view.buttons().map(|b| b.label()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["<Back>", "<Run>"]);
+        assert_eq!(form.hints()[0], (SUBMIT_KEY, "Run".to_string()));
+
+        let form = FormView::new().hide_cancel().submit_label("Confirm");
+        let labels = form.view.buttons().map(|b| b.label()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["<Confirm>"]);
+        assert_eq!(form.submit_button_idx(), 0);
+    }
+
+    #[test]
+    fn tab_cycles_through_fields_and_buttons() {
+        let mut form = FormView::new()
+            .field(Text::new("a"))
+            .field(Autocomplete::new("b", vec!["x", "y"]))
+            .field(Text::new("c").tab_index(0));
+        let tab = |form: &mut FormView, event: Event| {
+            form.on_event(event);
+            form.focused_stop().unwrap()
+        };
+
+        form.take_focus(Direction::none()).unwrap();
+        assert_eq!(form.focused_stop(), Some(FocusStop::Field(0)));
+        assert_eq!(tab(&mut form, Event::Key(Key::Tab)), FocusStop::Field(1));
+        // open suggestions of autocomplete, tab leaves it anyway
+        form.on_event(Event::Key(Key::Down));
+        assert_eq!(tab(&mut form, Event::Key(Key::Tab)), FocusStop::Button(0));
+        assert_eq!(tab(&mut form, Event::Key(Key::Tab)), FocusStop::Button(1));
+        assert_eq!(tab(&mut form, Event::Key(Key::Tab)), FocusStop::Field(2));
+        assert_eq!(tab(&mut form, Event::Key(Key::Tab)), FocusStop::Field(0));
+        assert_eq!(tab(&mut form, Event::Shift(Key::Tab)), FocusStop::Field(2));
+        assert_eq!(tab(&mut form, Event::Shift(Key::Tab)), FocusStop::Button(1));
+    }
+
+    #[test]
+    fn form_opens_focused_on_chosen_field() {
+        let mut form = FormView::new()
+            .field(Text::new("user").initial("admin"))
+            .field(Text::new("password").autofocus())
+            .field(Text::new("host"));
+        form.take_focus(Direction::none()).unwrap();
+        assert_eq!(form.focused_stop(), Some(FocusStop::Field(1)));
+
+        let mut form = form.focus("host");
+        form.take_focus(Direction::none()).unwrap();
+        assert_eq!(form.focused_stop(), Some(FocusStop::Field(2)));
+    }
+
+    #[test]
+    fn locale_changes_format_of_numbers() {
+        let form = FormView::new()
+            .field(Number::new("ratio").initial("0.5"))
+            .field(Decimal::new("price").initial("1234.5"))
+            .field(Decimal::new("fee").locale("en-US").initial("1.5"))
+            .field(Text::new("note").initial("1.5"))
+            .locale("de-DE");
+
+        let values = (0..4).map(|idx| form.field_value(idx)).collect::<Vec<_>>();
+        assert_eq!(values, vec!["0,5", "1.234,50", "1.50", "1.5"]);
+        assert_eq!(form.fields[0].validate("1.000,25"), Ok(json!(1000.25)));
+    }
+
+    #[test]
+    fn compact_density_saves_rows() {
+        let form = |density| {
+            FormView::new()
+                .field(Text::new("name").help("Who to greet"))
+                .field(Autocomplete::new("color", vec!["red", "green"]))
+                .density(density)
+        };
+        let height = |mut form: FormView, rows| form.required_size(Vec2::new(80, rows)).y;
+        let comfortable = height(form(Density::Comfortable), 20);
+        // spacing below both fields & 2 rows of dropdown
+        assert_eq!(height(form(Density::Compact), 50), comfortable - 4);
+        assert_eq!(height(form(Density::Auto), 20), comfortable - 4);
+        assert_eq!(height(form(Density::Auto), 50), comfortable);
+
+        let compact = form(Density::Comfortable).compact(true);
+        assert_eq!(height(compact, 50), comfortable - 2);
+
+        let mut form = form(Density::Compact);
+        form.required_size(Vec2::new(80, 50));
+        let widget = field_view_mut(&mut form.view, form.field_children[0]);
+        let label = (**widget)
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .and_then(|layout| layout.get_child(0))
+            .and_then(|child| child.as_any().downcast_ref::<TextView>())
+            .map(|text| text.get_content().source().to_string());
+        assert_eq!(label.unwrap().trim_end(), "name");
+    }
+
+    #[test]
+    fn fields_are_arranged_by_layouts() {
+        let form = |layout: bool, density| {
+            let form = match layout {
+                true => FormView::new().field_layout(AnnotationLeft),
+                false => FormView::new(),
+            };
+            form.field(Text::new("host").help("Server").validator(Required))
+                .field(Text::new("port").layout(NoAnnotation))
+                .density(density)
+        };
+        let height = |mut form: FormView| form.required_size(Vec2::new(80, 50)).y;
+        // label beside value saves its row
+        let comfortable = height(form(false, Density::Comfortable));
+        assert_eq!(height(form(true, Density::Comfortable)), comfortable - 1);
+        let compact = height(form(false, Density::Compact));
+        assert_eq!(height(form(true, Density::Compact)), compact - 1);
+        let unlabeled = FormView::new().field(Text::new("port").layout(NoAnnotation));
+        assert_eq!(
+            height(unlabeled),
+            height(FormView::new().field(Text::new("port"))) - 1
+        );
+
+        let mut form = form(true, Density::Compact);
+        form.required_size(Vec2::new(80, 50));
+        assert!(form.validate().is_err());
+        let widget = field_view_mut(&mut form.view, form.field_children[0]);
+        let texts = (**widget)
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .map(|layout| {
+                [0, 2]
+                    .iter()
+                    .filter_map(|&idx| layout.get_child(idx))
+                    .filter_map(|child| child.as_any().downcast_ref::<TextView>())
+                    .map(|text| text.get_content().source().trim_end().to_string())
+                    .collect::<Vec<String>>()
+            });
+        assert_eq!(
+            texts.unwrap(),
+            vec!["host                :", "Field is required"]
+        );
+    }
+
+    #[test]
+    fn custom_layouts_show_errors() {
+        let boxed = |value: BoxedView, label: &str, _help: &str| {
+            let widget = LinearLayout::vertical()
+                .child(TextView::new(label.to_uppercase()))
+                .child(value)
+                .child(TextView::new(""))
+                .child(DummyView);
+            BoxedView::new(Box::new(widget))
+        };
+        let mut form = FormView::new().field(Text::new("host").validator(Required).layout(boxed));
+        assert!(form.validate().is_err());
+        let widget = field_view_mut(&mut form.view, form.field_children[0]);
+        let error = fields::error_view_from_layout_mut(widget)
+            .map(|text| text.get_content().source().to_string());
+        assert_eq!(error.unwrap(), "Field is required");
+    }
+
+    #[test]
+    #[should_panic(expected = "FieldLayout should build a LinearLayout")]
+    fn layouts_without_value_slot_are_refused() {
+        let panel = |value: BoxedView, _label: &str, _help: &str| {
+            BoxedView::new(Box::new(cursive::views::Panel::new(value)))
+        };
+        FormView::new().field(Text::new("host").layout(panel));
+    }
+
+    #[test]
+    fn int_number_submits_json_number_within_bounds() {
+        let mut form = FormView::new().field(IntNumber::new("workers").min(1).max(8).step(4));
+        form.set_field_value("workers", "6");
+        form.take_focus(Direction::none()).unwrap();
+        form.on_event(Event::Char('+'));
+        assert_eq!(form.validate(), Ok(json!({ "workers": 8 })));
+
+        form.set_field_value("workers", "9");
+        assert!(form.validate().is_err());
+    }
+
+    #[test]
+    fn cancel_asks_before_discarding_changes() {
+        let cancelled = Rc::new(Cell::new(false));
+        let flag = Rc::clone(&cancelled);
+        let mut form = FormView::new()
+            .field(Text::new("name").initial("x"))
+            .field(Text::new("age"))
+            .on_cancel(move |_| flag.set(true));
+        form.set_field_value("age", "3");
+        assert!(form.modified_fields().is_empty());
+
+        let mut siv = Cursive::new();
+        if let EventResult::Consumed(Some(cb)) = form.event_cancel() {
+            cb(&mut siv);
+        }
+        assert!(cancelled.get());
+
+        cancelled.set(false);
+        form.take_focus(Direction::none()).unwrap();
+        form.on_event(Event::Char('y'));
+        assert_eq!(form.modified_fields(), vec!["name"]);
+        if let EventResult::Consumed(Some(cb)) = form.event_cancel() {
+            cb(&mut siv);
+        }
+        assert!(!cancelled.get());
+        assert_eq!(siv.screen().len(), 1);
+    }
+
+    #[test]
+    fn review_shows_data_read_only() {
+        let mut form = FormView::new()
+            .field(Text::new("name"))
+            .field(Checkbox::new("force"))
+            .on_submit(|_, _| panic!("review can't be submitted"))
+            .review(&json!({"name": "backup", "force": true}));
+        let labels = form.view.buttons().map(|b| b.label()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["<Close>"]);
+
+        form.take_focus(Direction::none()).unwrap();
+        form.on_event(Event::Char('x'));
+        form.on_event(Event::Key(Key::Tab));
+        form.on_event(Event::Char(' '));
+        let submit = form.on_event(SUBMIT_KEY);
+        assert!(!matches!(submit, EventResult::Consumed(Some(_))));
+        assert_eq!(form.field_value(0), "backup");
+        assert_eq!(form.field_value(1), "true");
+        assert!(form.modified_fields().is_empty());
+    }
+
+    #[test]
+    fn embedded_form_reports_through_channel() {
+        let (sender, receiver) = ::std::sync::mpsc::channel();
+        let mut form = FormView::new()
+            .field(Text::new("name").initial("fui"))
+            .into_view_with(sender);
+        let mut siv = Cursive::new();
+
+        if let EventResult::Consumed(Some(cb)) = form.on_event(SUBMIT_KEY) {
+            cb(&mut siv);
+        }
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(FormResult::Submitted(json!({"name": "fui"})))
+        );
+        if let EventResult::Consumed(Some(cb)) = form.event_cancel() {
+            cb(&mut siv);
+        }
+        assert_eq!(receiver.try_recv(), Ok(FormResult::Cancelled));
+    }
+
+    #[test]
+    fn validator_if_is_used_when_condition_met() {
+        let errors = auth_form("key").validate().unwrap_err();
+
+        assert_eq!(errors["key-file"], vec!["Field is required".to_string()]);
+    }
+
+    #[test]
+    fn validator_if_is_skipped_when_condition_not_met() {
+        let value = auth_form("password").validate().unwrap();
+
+        assert_eq!(value, json!({"auth-method": "password", "key-file": ""}));
+    }
+
+    #[test]
+    fn json_schema_describes_fields() {
+        let form = FormView::new()
+            .title("archive")
+            .field(Checkbox::new("verbose").help("Be verbose"))
+            .field(
+                Autocomplete::new("compression", vec!["gzip", "bzip2"])
+                    .validator(Required)
+                    .validator(OneOf(vec!["gzip", "bzip2"])),
+            )
+            .field(Multiselect::new("files", vec!["a", "b"]));
+
+        let schema = form.to_json_schema();
+
+        assert_eq!(schema["title"], json!("archive"));
+        assert_eq!(
+            schema["properties"]["verbose"],
+            json!({"type": "boolean", "description": "Be verbose"})
+        );
+        assert_eq!(
+            schema["properties"]["compression"],
+            json!({"type": "string", "minLength": 1, "enum": ["gzip", "bzip2"]})
+        );
+        assert_eq!(
+            schema["properties"]["files"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(schema["required"], json!(["compression"]));
+    }
+
+    #[test]
+    fn dotted_labels_and_groups_nest_data() {
+        let mut form = FormView::new()
+            .nest_dotted_labels()
+            .field(Text::new("network.proxy.host").initial("h"))
+            .group("network")
+            .field(Text::new("proxy.port").initial("80").validator(Required))
+            .group("")
+            .field(Checkbox::new("verbose"));
+
+        let data = form.validate().unwrap();
+
+        assert_eq!(
+            data,
+            json!({"network": {"proxy": {"host": "h", "port": "80"}}, "verbose": false})
+        );
+        let schema = form.to_json_schema();
+        let proxy = &schema["properties"]["network"]["properties"]["proxy"];
+        assert_eq!(proxy["required"], json!(["port"]));
+        assert_eq!(proxy["properties"]["host"]["type"], json!("string"));
+
+        form.set_values(&json!({"network": {"proxy": {"port": "8080"}}}));
+        assert_eq!(form.get_field_value("proxy.port"), Some("8080".to_string()));
+    }
+
+    #[test]
+    fn data_follows_declaration_of_fields() {
+        let mut form = FormView::new()
+            .field(Checkbox::new("verbose"))
+            .group("db")
+            .field(Text::new("port").initial("5432"))
+            .field(Text::new("host").initial("h"))
+            .group("")
+            .field(Text::new("alias").key("name").initial("a"));
+
+        assert_eq!(
+            form.validate().unwrap().to_string(),
+            r#"{"verbose":false,"db":{"port":"5432","host":"h"},"name":"a"}"#
+        );
+    }
+
+    #[test]
+    fn dotted_labels_stay_flat_by_default() {
+        let mut form = FormView::new()
+            .group("servers")
+            .field(Text::new("example.com").initial("1.2.3.4"));
+
+        assert_eq!(
+            form.validate().unwrap(),
+            json!({"servers": {"example.com": "1.2.3.4"}})
+        );
+        form.set_values(&json!({"servers": {"example.com": "5.6.7.8"}}));
+        assert_eq!(
+            form.get_field_value("example.com"),
+            Some("5.6.7.8".to_string())
+        );
+    }
+
+    #[test]
+    fn field_nesting_others_is_invalid() {
+        let mut form = FormView::new()
+            .field(Text::new("proxy"))
+            .group("proxy")
+            .field(Text::new("host"));
+
+        let errors = form.validate().unwrap_err();
+
+        assert_eq!(
+            errors.for_field("proxy"),
+            Some(&["Its key is also a group of other fields".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn validate_value_converts_values_like_widgets() {
+        let form = FormView::new()
+            .group("db")
+            .field(Number::new("port").validator(Required))
+            .group("")
+            .field(Multiselect::new("tags", vec!["a", "b"]))
+            .before_submit(|data| {
+                data["checked"] = json!(true);
+                Ok(())
+            });
+
+        let data = form.validate_value(&json!({"db": {"port": 5432}, "tags": ["b"]}));
+
+        assert_eq!(
+            data,
+            Ok(json!({"db": {"port": 5432}, "tags": ["b"], "checked": true}))
+        );
+        let errors = form.validate_value(&json!({"db.port": "x"})).unwrap_err();
+        assert!(errors.for_field("port").is_some());
+        let errors = form
+            .validate_value(&json!({"db": {"port": "x"}}))
+            .unwrap_err();
+        assert_eq!(errors["port"], vec!["Invalid number: x".to_string()]);
+    }
+
+    #[test]
+    fn validate_value_doesnt_prepare_fields() {
+        let dir = ::std::env::temp_dir().join(format!("fui-check-{}", ::std::process::id()));
+        let path = dir.join("out.txt").to_string_lossy().to_string();
+        let form = FormView::new().field(SavePath::new("out"));
+
+        assert!(form.validate_value(&json!({ "out": path })).is_ok());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn options_submit_values_without_descriptions() {
+        let options = vec![("gzip", "fast"), ("xz", "small")];
+        let mut form = FormView::new().field(Autocomplete::options("compression", options));
+
+        form.set_values(&json!({"compression": "xz"}));
+
+        assert_eq!(form.validate().unwrap(), json!({"compression": "xz"}));
+        form.set_field_value("compression", "xz — small");
+        assert!(form.validate().is_err());
+        assert_eq!(
+            form.get_fields()[0].describe_rules(),
+            vec!["must be one of: gzip, xz"]
+        );
+    }
+
+    #[test]
+    fn set_values_fills_fields() {
+        let mut form = FormView::new()
+            .field(Text::new("text"))
+            .field(Checkbox::new("check"))
+            .field(Multiselect::new("multi", vec!["a", "b"]));
+
+        form.set_values(&json!({"text": "t", "check": true, "multi": ["a", "b"], "x": 1}));
+
+        assert_eq!(
+            form.validate().unwrap(),
+            json!({"text": "t", "check": true, "multi": ["a", "b"]})
+        );
+    }
+
+    #[test]
+    fn before_submit_hooks_augment_data() {
+        let mut form = FormView::new()
+            .field(Text::new("name").initial("n"))
+            .before_submit(|data| {
+                data["stamp"] = json!(1);
+                Ok(())
+            });
+
+        assert_eq!(
+            form.submit_data().unwrap(),
+            json!({"name": "n", "stamp": 1})
+        );
+    }
+
+    #[test]
+    fn before_submit_hooks_can_reject_data() {
+        let mut form = FormView::new()
+            .field(Text::new("name").initial("n"))
+            .before_submit(|_| {
+                let mut errors = FormErrors::new();
+                errors.push("name", "Name is taken");
+                Err(errors)
+            });
+
+        let errors = form.submit_data().unwrap_err();
+
+        assert_eq!(errors["name"], vec!["Name is taken".to_string()]);
+    }
+
+    #[test]
+    fn before_submit_hooks_reject_data_from_cli() {
+        let form = FormView::new().field(Text::new("name")).before_submit(|_| {
+            let mut errors = FormErrors::new();
+            errors.push("name", "Name is taken");
+            Err(errors)
+        });
+        let matches = clap::App::new("app")
+            .args(&form.fields2clap_args())
+            .get_matches_from(vec!["app", "--name", "fui"]);
+
+        let (_, errors) = form.clap_arg_matches2data(&matches);
+
+        assert_eq!(errors["name"], vec!["Name is taken".to_string()]);
+    }
+
+    #[test]
+    fn probes_show_hints_about_values() {
+        let mut form = FormView::new()
+            .field(
+                Text::new("port")
+                    .initial("8080")
+                    .probe(|v| Some(format!("{} is free", v))),
+            )
+            .field(Text::new("host"));
+        let hint = |form: &FormView| {
+            let content = form.probe_hints[0].as_ref().unwrap().content.get_content();
+            content.source().to_string()
+        };
+        let wait_for_hint = |form: &FormView, expected: &str| {
+            for _ in 0..100 {
+                if hint(form) == expected {
+                    return;
+                }
+                thread::sleep(::std::time::Duration::from_millis(10));
+            }
+            assert_eq!(hint(form), expected);
+        };
+
+        form.layout(Vec2::new(80, 30));
+        wait_for_hint(&form, "8080 is free");
+
+        form.set_field_value("port", "8081");
+        form.layout(Vec2::new(80, 30));
+        wait_for_hint(&form, "8081 is free");
+        assert!(form.probe_hints[1].is_none());
+    }
+
+    #[test]
+    fn masking_keeps_values() {
+        let mut form = FormView::new()
+            .field(Text::new("text").initial("secret"))
+            .field(Checkbox::new("check").initial(true))
+            .field(Multiselect::new("multi", vec!["a", "b"]).initial(vec!["a"]));
+
+        form.set_masked(true);
+        form.set_field_value("text", "other");
+
+        assert!(form.is_masked());
+        assert_eq!(
+            form.validate().unwrap(),
+            json!({"text": "other", "check": true, "multi": ["a"]})
+        );
+    }
+
+    #[test]
+    fn decorations_dont_shift_fields() {
+        let mut form = FormView::new()
+            .heading("Network")
+            .field(Text::new("host").initial("localhost"))
+            .separator()
+            .note("Ports below 1024 need root")
+            .field(Text::new("port").validator(Required));
+
+        assert!(form.set_field_value("port", "80"));
+        assert_eq!(
+            form.validate().unwrap(),
+            json!({"host": "localhost", "port": "80"})
+        );
+        form.set_field_value("port", "");
+        assert!(form.validate().unwrap_err().for_field("port").is_some());
+    }
+
+    #[test]
+    fn sticky_header_jumps_to_invalid_fields() {
+        let mut form = FormView::new()
+            .sticky_header()
+            .field(Text::new("name").validator(Required))
+            .field(Text::new("nick"))
+            .field(Text::new("email").validator(Required));
+        assert!(form.validate().is_err());
+        assert_eq!(form.error_count(), 2);
+
+        form.on_event(Event::Key(Key::F8));
+        assert_eq!(form.focused_field(), Some(2));
+        form.on_event(Event::Key(Key::F8));
+        assert_eq!(form.focused_field(), Some(0));
+
+        form.set_field_value("name", "Jan");
+        assert_eq!(form.error_count(), 1);
+        form.on_event(Event::Key(Key::F8));
+        assert_eq!(form.focused_field(), Some(2));
+        form.set_field_value("email", "jan@example.com");
+        assert_eq!(form.error_count(), 0);
+        form.set_field_value("name", "");
+        assert_eq!(form.error_count(), 1);
+        assert!(form.validate().is_err());
+
+        form.set_field_value("name", "Jan");
+        assert!(form.validate().is_ok());
+        assert_eq!(form.error_count(), 0);
+    }
+
+    #[test]
+    fn submit_waits_for_required_fields() {
+        let mut form = FormView::new()
+            .field(Text::new("host").validator(Required))
+            .field(Text::new("port").validator(Required))
+            .field(Text::new("comment"))
+            .disable_submit_until_complete();
+        assert_eq!(form.required_counts(), (0, 2));
+        assert!(!form.is_submit_enabled());
+
+        form.set_field_value("host", "localhost");
+        assert_eq!(form.required_counts(), (1, 2));
+        form.set_field_value("port", "80");
+        assert_eq!(form.required_counts(), (2, 2));
+        assert!(form.is_submit_enabled());
+    }
+
+    #[test]
+    fn submit_waits_for_condition_with_reason() {
+        let mut form = FormView::new()
+            .field(Multiselect::new("files", vec!["a", "b"]))
+            .field(Text::new("port"))
+            .submit_enabled_if(
+                |data| data["files"].as_array().is_some_and(|f| !f.is_empty()),
+                "Select files",
+            )
+            .submit_enabled_if(|data| data["port"] != json!("0"), "Port 0 is reserved");
+        assert!(!form.is_submit_enabled());
+        assert_eq!(form.submit_blocked(), Some("Select files"));
+
+        form.set_field_value("files", "a");
+        form.set_field_value("port", "0");
+        assert_eq!(form.submit_blocked(), Some("Port 0 is reserved"));
+
+        form.set_field_value("port", "80");
+        assert_eq!(form.submit_blocked(), None);
+        assert!(form.is_submit_enabled());
+    }
+
+    #[derive(Debug)]
+    struct CountingValidator(Rc<Cell<usize>>);
+
+    impl Validator for CountingValidator {
+        fn validate(&self, _data: &str) -> Option<String> {
+            self.0.set(self.0.get() + 1);
+            None
+        }
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn submit_conditions_validate_only_changed_fields() {
+        let validations = Rc::new(Cell::new(0));
+        let mut form = FormView::new()
+            .field(Text::new("name").validator(CountingValidator(Rc::clone(&validations))))
+            .field(Text::new("port"))
+            .submit_enabled_if(|data| data["port"] != json!(""), "Port is missing");
+        let validated = validations.get();
+
+        form.set_field_value("port", "80");
+        assert_eq!(validations.get(), validated);
+        form.set_field_value("name", "n");
+        assert_eq!(validations.get(), validated + 1);
+    }
+
+    #[test]
+    fn submit_conditions_reject_data_from_outside() {
+        let form = FormView::new()
+            .field(Text::new("port"))
+            .submit_label("Run")
+            .submit_enabled_if(|data| data["port"] != json!("0"), "Port 0 is reserved");
+        let mut values = FormSnapshot::new();
+        values.insert("port".to_string(), "0".to_string());
+
+        let errors = form.validate_snapshot(&values).unwrap_err();
+
+        assert_eq!(errors["Run"], vec!["Port 0 is reserved".to_string()]);
+    }
+
+    #[test]
+    fn external_check_runs_in_background_before_submit() {
+        let submitted = Rc::new(Cell::new(false));
+        let on_submit = Rc::clone(&submitted);
+        let mut form = FormView::new()
+            .field(Text::new("path"))
+            .external_check("ls -- {path}")
+            .on_submit(move |_, _| on_submit.set(true));
+        let mut siv = Cursive::new();
+        let mut submit = |form: &mut FormView| {
+            if let EventResult::Consumed(Some(cb)) = form.on_event(SUBMIT_KEY) {
+                cb(&mut siv);
+            }
+            while form
+                .running_checks
+                .as_ref()
+                .unwrap()
+                .errors
+                .lock()
+                .unwrap()
+                .is_none()
+            {
+                thread::sleep(::std::time::Duration::from_millis(10));
+            }
+            if let EventResult::Consumed(Some(cb)) = form.on_event(Event::Refresh) {
+                cb(&mut siv);
+            }
+            assert!(form.running_checks.is_none());
+            let child = form.external_checks[0].child;
+            let pane = form.output_pane_mut(child);
+            (pane.get_output(), pane.is_expanded())
+        };
+
+        form.set_field_value("path", "-no-such-path");
+        let (output, expanded) = submit(&mut form);
+        assert!(output.contains("-no-such-path"));
+        assert!(output.contains("Check failed"));
+        assert!(expanded);
+        assert!(!submitted.get());
+
+        form.set_field_value("path", "/");
+        assert!(!submit(&mut form).1);
+        assert!(submitted.get());
+    }
+
+    #[test]
+    fn missing_dirs_are_created_once_confirmed() {
+        let root = ::std::env::temp_dir().join(format!("fui-confirm-{}", ::std::process::id()));
+        let target = root.join("out.txt");
+        let submitted = Rc::new(Cell::new(false));
+        let on_submit = Rc::clone(&submitted);
+        let mut form = FormView::new()
+            .field(SavePath::new("out").initial(target.to_str().unwrap()))
+            .on_submit(move |_, _| on_submit.set(true));
+        let mut siv = Cursive::new();
+        let mut answer = |form: &mut FormView, agreed: bool| {
+            if let EventResult::Consumed(Some(cb)) = form.on_event(SUBMIT_KEY) {
+                cb(&mut siv);
+            }
+            form.confirming.as_ref().unwrap().answer.set(Some(agreed));
+            if let EventResult::Consumed(Some(cb)) = form.on_event(Event::Refresh) {
+                cb(&mut siv);
+            }
+            assert!(form.confirming.is_none());
+        };
+
+        answer(&mut form, false);
+        assert!(!root.exists());
+        assert!(!submitted.get());
+
+        answer(&mut form, true);
+        assert!(root.is_dir());
+        assert!(submitted.get());
+        ::std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn placeholders_are_filled_with_values() {
+        let data = json!({"db": {"port": 80}, "tags": ["a", "b"], "name": "x y"});
+        assert_eq!(
+            fill_placeholders("-p={db.port}:{name}{missing}{tags}", &data),
+            "-p=80:x ya,b"
+        );
+        assert_eq!(fill_placeholders("{unclosed", &data), "{unclosed");
+    }
+}
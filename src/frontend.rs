@@ -0,0 +1,465 @@
+//! Frontends rendering forms of actions & collecting user's input.
+use std::env;
+use std::io;
+use std::io::{BufRead, IsTerminal, Write};
+
+use clap;
+use serde_json::value::Value;
+
+use fields::multiselect::VALUE_SEP;
+use fields::FormField;
+use form::{FormSnapshot, FormView};
+use Fui;
+
+/// Limit of suggestions listed by prompt's completion.
+const MAX_SUGGESTIONS: usize = 10;
+/// Answer clearing field's value (an empty one keeps it).
+const CLEAR: &str = "-";
+
+/// Renders forms of actions & collects user's input.
+pub(crate) trait Frontend {
+    /// Lets user pick an action (unless it's already picked) & fill its form.
+    ///
+    /// Returns name of the action & form data or `None` when user gave up.
+    fn input(&mut self, fui: &mut Fui) -> Option<(String, Value)>;
+}
+
+/// Picks frontend which terminal can handle: TUI or prompt for dumb terminals.
+pub(crate) fn detect() -> Box<dyn Frontend> {
+    let term = env::var("TERM").ok();
+    if is_interactive() && term_supported(term.as_deref()) {
+        Box::new(Tui)
+    } else {
+        prompt()
+    }
+}
+
+/// Checks if user can be asked for input, i.e. both stdin & stdout are terminals
+/// (they aren't when piped or run by cron).
+pub(crate) fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Builds error telling that input missing in `program`'s CLI args can't be asked for,
+/// because terminal isn't interactive.
+pub(crate) fn not_interactive(program: &str) -> clap::Error {
+    let message = format!(
+        "Missing input can't be asked for, because stdin or stdout isn't a terminal.\n\
+         Pass action & its args in command line instead (see `{} --help`).",
+        program
+    );
+    clap::Error::with_description(&message, clap::ErrorKind::MissingSubcommand)
+}
+
+/// Creates prompt asking through stdin & stdout.
+pub(crate) fn prompt() -> Box<dyn Frontend> {
+    let prompt = Prompt::new(io::stdin().lock(), io::stdout());
+    if io::stdin().is_terminal() {
+        Box::new(prompt.hide_sensitive())
+    } else {
+        Box::new(prompt)
+    }
+}
+
+/// Checks if terminal described by `TERM` can show TUI.
+fn term_supported(term: Option<&str>) -> bool {
+    match term {
+        // windows consoles don't set `TERM`
+        None => cfg!(windows),
+        Some("") | Some("dumb") => false,
+        Some(_) => true,
+    }
+}
+
+/// Full screen TUI made with cursive.
+pub(crate) struct Tui;
+
+impl Frontend for Tui {
+    fn input(&mut self, fui: &mut Fui) -> Option<(String, Value)> {
+        fui.input_from_tui()
+    }
+}
+
+/// Turns off echo of stdin's terminal until it's dropped.
+struct NoEcho {
+    #[cfg(unix)]
+    restored: Option<libc::termios>,
+}
+
+impl NoEcho {
+    /// Turns off echo, it's left as is when stdin isn't a terminal (or on non-unix systems).
+    fn start() -> Self {
+        #[cfg(unix)]
+        {
+            // SAFETY: `termios` is filled by `tcgetattr` before it's used
+            unsafe {
+                let mut termios: libc::termios = std::mem::zeroed();
+                if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                    return NoEcho { restored: None };
+                }
+                let restored = termios;
+                termios.c_lflag &= !libc::ECHO;
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios);
+                NoEcho {
+                    restored: Some(restored),
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        NoEcho {}
+    }
+}
+
+impl Drop for NoEcho {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        {
+            if let Some(ref termios) = self.restored {
+                // SAFETY: `termios` was got from the same terminal
+                unsafe {
+                    libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, termios);
+                }
+            }
+        }
+    }
+}
+
+/// Asks for action & values of its fields line by line.
+///
+/// An empty answer keeps field's current value, `-` clears it.
+pub(crate) struct Prompt<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+    // typed values of sensitive fields aren't echoed
+    hide_sensitive: bool,
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    /// Creates a new `Prompt` reading answers from `input` & asking through `output`.
+    pub(crate) fn new(input: R, output: W) -> Self {
+        Prompt {
+            input,
+            output,
+            hide_sensitive: false,
+        }
+    }
+
+    /// Turns off echo of terminal while values of sensitive fields are typed,
+    /// so `input` should be stdin of the terminal.
+    pub(crate) fn hide_sensitive(mut self) -> Self {
+        self.hide_sensitive = true;
+        self
+    }
+
+    /// Reads a line without trailing new line, `None` means end of input.
+    fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+
+    /// Lets user pick one of actions (by number or name), returns its key.
+    fn pick_action(&mut self, fui: &Fui) -> Option<String> {
+        let keys = fui.actions.keys().cloned().collect::<Vec<String>>();
+        if fui.skip_single_action && keys.len() < 2 {
+            return keys.into_iter().next();
+        }
+        let header = fui.header();
+        if !header.is_empty() {
+            writeln!(self.output, "{}", header).ok()?;
+        }
+        for (idx, key) in keys.iter().enumerate() {
+            writeln!(self.output, "{:>3}) {}", idx + 1, key).ok()?;
+        }
+        loop {
+            write!(self.output, "Action: ").ok()?;
+            self.output.flush().ok()?;
+            let answer = self.read_line()?;
+            let answer = answer.trim();
+            let picked = match answer.parse::<usize>() {
+                Ok(number) if number > 0 => keys.get(number - 1),
+                _ => keys.iter().find(|k| fui.actions[k.as_str()].name == answer),
+            };
+            match picked {
+                Some(key) => return Some(key.clone()),
+                None => writeln!(self.output, "No such action: {:?}", answer).ok()?,
+            }
+        }
+    }
+
+    /// Asks for fields of `form` until it's valid, returns form data.
+    fn fill_form(&mut self, form: &FormView) -> Option<Value> {
+        let fields = form.get_fields();
+        if !fields.is_empty() {
+            writeln!(
+                self.output,
+                "Empty answer keeps [current] value, {:?} clears it.",
+                CLEAR
+            )
+            .ok()?;
+        }
+        let mut values: FormSnapshot = fields
+            .iter()
+            .map(|f| (f.get_label().to_owned(), f.get_initial()))
+            .collect();
+        let mut to_ask = fields.iter().collect::<Vec<&Box<dyn FormField>>>();
+        loop {
+            for field in to_ask.iter() {
+                let label = field.get_label();
+                let value = self.ask(&***field, &values[label])?;
+                values.insert(label.to_owned(), value);
+            }
+            let errors = match form.validate_snapshot(&values) {
+                Ok(data) => return Some(data),
+                Err(errors) => errors,
+            };
+            for (label, field_errors) in errors.iter() {
+                writeln!(self.output, "{}: {}", label, field_errors.join(", ")).ok()?;
+            }
+            to_ask = fields
+                .iter()
+                .filter(|f| errors.for_field(f.get_label()).is_some())
+                .collect();
+            if to_ask.is_empty() {
+                // errors not tied to any field, so everything is asked again
+                to_ask = fields.iter().collect();
+            }
+        }
+    }
+
+    /// Asks for value of `field`, keeping `current` value on empty answer
+    /// & clearing it on [CLEAR] one.
+    fn ask(&mut self, field: &dyn FormField, current: &str) -> Option<String> {
+        if !field.get_help().is_empty() {
+            writeln!(self.output, "  {}", field.get_help()).ok()?;
+        }
+        let schema = field.json_schema();
+        let hint = match schema["type"].as_str() {
+            Some("boolean") => " (true/false)".to_string(),
+            Some("array") => format!(" (separated by {:?})", VALUE_SEP),
+            _ => match schema["enum"].as_array() {
+                Some(allowed) => {
+                    let allowed = allowed
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .collect::<Vec<&str>>();
+                    format!(" ({})", allowed.join("/"))
+                }
+                None => "".to_string(),
+            },
+        };
+        let required = if field.is_required() { " *" } else { "" };
+        let default = if current.is_empty() {
+            "".to_string()
+        } else if field.is_sensitive() {
+            " [******]".to_string()
+        } else {
+            format!(" [{}]", current)
+        };
+        let is_array = schema["type"] == "array";
+        loop {
+            write!(
+                self.output,
+                "{}{}{}{}: ",
+                field.get_label(),
+                required,
+                hint,
+                default
+            )
+            .ok()?;
+            self.output.flush().ok()?;
+            let answer = if self.hide_sensitive && field.is_sensitive() {
+                let no_echo = NoEcho::start();
+                let answer = self.read_line();
+                drop(no_echo);
+                // `Enter` wasn't echoed either
+                writeln!(self.output).ok()?;
+                answer?
+            } else {
+                self.read_line()?
+            };
+            if answer.is_empty() {
+                return Some(current.to_string());
+            }
+            if answer == CLEAR {
+                return Some("".to_string());
+            }
+            // `Tab` typed before `Enter` asks for completion
+            match answer.strip_suffix('\t') {
+                Some(typed) => {
+                    if let Some(completed) = self.complete(field, typed, is_array) {
+                        writeln!(self.output, "{}: {}", field.get_label(), completed).ok()?;
+                        return Some(completed);
+                    }
+                }
+                None => return Some(answer),
+            }
+        }
+    }
+
+    /// Completes `typed` value (or its last item for `array` fields) when only one suggestion
+    /// matches, otherwise lists matching suggestions.
+    fn complete(&mut self, field: &dyn FormField, typed: &str, array: bool) -> Option<String> {
+        let (done, last) = match typed.rfind(VALUE_SEP) {
+            Some(idx) if array => typed.split_at(idx + VALUE_SEP.len()),
+            _ => ("", typed),
+        };
+        let suggestions = field.get_widget_manager().suggest(last, MAX_SUGGESTIONS);
+        match suggestions.len() {
+            0 => {
+                writeln!(self.output, "  No suggestions for {:?}", last).ok()?;
+                None
+            }
+            1 => Some(format!("{}{}", done, suggestions[0])),
+            _ => {
+                writeln!(self.output, "  {}", suggestions.join("  ")).ok()?;
+                None
+            }
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Frontend for Prompt<R, W> {
+    fn input(&mut self, fui: &mut Fui) -> Option<(String, Value)> {
+        let picked = match (
+            *fui.active_step.borrow(),
+            fui.picked_action.borrow().clone(),
+        ) {
+            (2, Some(key)) => Some(key),
+            _ => None,
+        };
+        let key = match picked {
+            Some(key) => key,
+            None => self.pick_action(fui)?,
+        };
+        // picked action is a key ("name: desc") or already a name
+        let action = fui.actions.get(&key).or_else(|| fui.action_by_name(&key))?;
+        let data = match action.form {
+            Some(ref form) => self.fill_form(form)?,
+            None => json!({}),
+        };
+        let name = action.name.to_string();
+        *fui.picked_action.borrow_mut() = Some(name.clone());
+        *fui.form_data.borrow_mut() = Some(data.clone());
+        *fui.active_step.borrow_mut() = 3;
+        Some((name, data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    use fields::{Autocomplete, Checkbox, Multiselect, Text};
+    use validators::Required;
+
+    fn fui() -> Fui<'static, 'static> {
+        Fui::new("app")
+            .action(
+                "copy",
+                "Copies files",
+                FormView::new()
+                    .field(Text::new("src").help("Source").validator(Required))
+                    .field(Text::new("dst").initial("/tmp"))
+                    .field(Checkbox::new("force")),
+                |_| {},
+            )
+            .action("list", "Lists files", FormView::new(), |_| {})
+    }
+
+    fn prompt(answers: &str) -> Prompt<Cursor<&str>, Vec<u8>> {
+        Prompt::new(Cursor::new(answers), Vec::new())
+    }
+
+    #[test]
+    fn dumb_terminals_get_prompt() {
+        assert!(term_supported(Some("xterm-256color")));
+        assert!(!term_supported(Some("dumb")));
+        assert!(!term_supported(Some("")));
+    }
+
+    #[test]
+    fn not_interactive_error_points_to_cli() {
+        let error = not_interactive("app");
+        assert_eq!(error.kind, clap::ErrorKind::MissingSubcommand);
+        assert!(error.message.contains("stdin or stdout isn't a terminal"));
+        assert!(error.message.contains("see `app --help`"));
+    }
+
+    #[test]
+    fn prompt_asks_until_form_is_valid() {
+        let mut fui = fui();
+        let mut prompt = prompt("1\n\n\n\nfile.txt\n");
+
+        let input = prompt.input(&mut fui);
+
+        assert_eq!(
+            input,
+            Some((
+                "copy".to_string(),
+                json!({"src": "file.txt", "dst": "/tmp", "force": false})
+            ))
+        );
+        let output = String::from_utf8(prompt.output).unwrap();
+        assert!(output.contains("  1) copy: Copies files\n  2) list: Lists files\n"));
+        assert!(output.contains("  Source\nsrc *: "));
+        assert!(output.contains("dst [/tmp]: "));
+        assert!(output.contains("force (true/false) [false]: "));
+        assert!(output.contains("src: Field is required\n"));
+    }
+
+    #[test]
+    fn prompt_picks_action_by_name() {
+        let mut fui = fui();
+        let mut prompt = prompt("move\nlist\n");
+
+        let input = prompt.input(&mut fui);
+
+        assert_eq!(input, Some(("list".to_string(), json!({}))));
+        let output = String::from_utf8(prompt.output).unwrap();
+        assert!(output.contains("No such action: \"move\""));
+    }
+
+    #[test]
+    fn prompt_completes_values_on_tab() {
+        let form = FormView::new()
+            .field(Autocomplete::new("lang", vec!["rust", "ruby", "python"]))
+            .field(Multiselect::new("tags", vec!["stable", "nightly"]));
+        let mut prompt = prompt("ru\t\nrust\nstable,ni\t\n");
+
+        let data = prompt.fill_form(&form);
+
+        assert_eq!(
+            data,
+            Some(json!({"lang": "rust", "tags": ["stable", "nightly"]}))
+        );
+        let output = String::from_utf8(prompt.output).unwrap();
+        assert!(output.contains("  rust  ruby\n"));
+        assert!(output.contains("tags: stable,nightly\n"));
+    }
+
+    #[test]
+    fn prompt_clears_value_on_dash_and_masks_sensitive_one() {
+        let form = FormView::new()
+            .field(Text::new("dst").initial("/tmp"))
+            .field(Text::new("token").initial("secret").sensitive());
+        let mut prompt = prompt("-\n\n");
+
+        let data = prompt.fill_form(&form);
+
+        assert_eq!(data, Some(json!({"dst": "", "token": "secret"})));
+        let output = String::from_utf8(prompt.output).unwrap();
+        assert!(output.contains("Empty answer keeps [current] value, \"-\" clears it.\n"));
+        assert!(output.contains("token [******]: "));
+        assert!(!output.contains("secret"));
+    }
+
+    #[test]
+    fn prompt_gives_up_at_end_of_input() {
+        let mut fui = fui();
+        assert_eq!(prompt("1\n").input(&mut fui), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! Mounts several `Fui` apps under one binary.
+use std::cell::RefCell;
+use std::env;
+use std::ffi::OsString;
+use std::rc::Rc;
+
+use clap;
+use cursive;
+
+use fields::Autocomplete;
+use form::FormView;
+use frontend;
+use validators::OneOf;
+use Fui;
+
+/// Toolbox binary grouping several independent [Fui] apps.
+///
+/// Each app becomes a subcommand (`toolbox tar create ...`)
+/// and without arguments user picks the app in TUI first.
+///
+/// [Fui]: struct.Fui.html
+///
+/// # Examples
+///
+/// ```
+/// use fui::form::FormView;
+/// use fui::{Fui, Group};
+///
+/// let tar_like = Fui::new("tar").action("create", "Creates archive", FormView::new(), |_| {});
+/// let git_like = Fui::new("git").action("init", "Creates repository", FormView::new(), |_| {});
+/// let group = Group::new("toolbox").app(tar_like).app(git_like);
+/// // group.run();
+/// ```
+pub struct Group<'attrs, 'action> {
+    name: &'attrs str,
+    about: &'attrs str,
+    apps: Vec<Fui<'attrs, 'action>>,
+}
+
+impl<'attrs, 'action> Group<'attrs, 'action> {
+    /// Creates a new empty `Group` named like the binary.
+    pub fn new(name: &'attrs str) -> Self {
+        Group {
+            name,
+            about: "",
+            apps: Vec::new(),
+        }
+    }
+
+    /// Sets description of the group.
+    pub fn about(mut self, about: &'attrs str) -> Self {
+        self.about = about;
+        self
+    }
+
+    /// Appends `app`, which is run as subcommand named like [Fui::name].
+    ///
+    /// # Panics:
+    ///
+    /// Panics if app name is duplicated.
+    ///
+    /// [Fui::name]: struct.Fui.html#method.name
+    pub fn app(mut self, app: Fui<'attrs, 'action>) -> Self {
+        if self.app_index(app.get_name()).is_some() {
+            panic!(
+                "App name must be unique, but it's already defined ({:?})",
+                app.get_name()
+            );
+        }
+        self.apps.push(app);
+        self
+    }
+
+    /// Returns automatically generated [clap::App] with apps as subcommands.
+    ///
+    /// [clap::App]: ../clap/struct.App.html
+    pub fn build_cli_app(&self) -> clap::App {
+        clap::App::new(self.name)
+            .about(self.about)
+            .subcommands(self.apps.iter().map(|app| app.build_cli_app()))
+    }
+
+    /// Runs app picked in CLI (as the first argument) or in TUI.
+    ///
+    /// Cancelling app picked in TUI goes back to the picker of apps.
+    pub fn run(mut self) {
+        let args = env::args_os().collect::<Vec<OsString>>();
+        match args.get(1).and_then(|arg| arg.to_str()) {
+            Some(name) => match self.app_index(name) {
+                Some(idx) => {
+                    let app = self.apps.remove(idx);
+                    let app_args = ::std::iter::once(self.program_name(&app))
+                        .chain(args.into_iter().skip(2))
+                        .collect();
+                    app.run_with_args(app_args);
+                }
+                None => {
+                    // shows help or error & exits
+                    self.build_cli_app().get_matches_from(args);
+                }
+            },
+            None if !frontend::is_interactive() => frontend::not_interactive(self.name).exit(),
+            None => {
+                while let Some(idx) = self.pick_app() {
+                    let program = self.program_name(&self.apps[idx]);
+                    if self.apps[idx].run_until_cancel(vec![program]) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// App sees itself as program, e.g. `toolbox tar`.
+    fn program_name(&self, app: &Fui) -> OsString {
+        OsString::from(format!("{} {}", self.name, app.get_name()))
+    }
+
+    fn app_index(&self, name: &str) -> Option<usize> {
+        self.apps.iter().position(|app| app.get_name() == name)
+    }
+
+    /// Lists apps as "name: about".
+    fn picker_items(&self) -> Vec<String> {
+        self.apps
+            .iter()
+            .map(|app| match app.get_about() {
+                "" => app.get_name().to_string(),
+                about => format!("{}: {}", app.get_name(), about),
+            })
+            .collect()
+    }
+
+    /// Lets user pick app in TUI, returns its index.
+    fn pick_app(&self) -> Option<usize> {
+        let items = self.picker_items();
+        let picked = Rc::new(RefCell::new(None));
+        let on_pick = Rc::clone(&picked);
+        let mut c = cursive::default();
+        let form = FormView::new()
+            .title(self.name)
+            .discard_changes_silently()
+            .field(
+                Autocomplete::new("app", items.clone())
+                    .help("Pick app")
+                    .validator(OneOf(items.clone())),
+            )
+            .on_submit(move |c, data| {
+                *on_pick.borrow_mut() = data["app"].as_str().map(|s| s.to_string());
+                c.quit();
+            })
+            .on_cancel(|c| c.quit())
+            .fill_screen();
+        c.add_layer(form);
+        c.run();
+        let picked = picked.borrow();
+        picked
+            .as_ref()
+            .and_then(|item| items.iter().position(|i| i == item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group() -> Group<'static, 'static> {
+        let tar =
+            Fui::new("tar")
+                .about("archives")
+                .action("create", "desc", FormView::new(), |_| {});
+        let git = Fui::new("git").action("init", "desc", FormView::new(), |_| {});
+        Group::new("toolbox").app(tar).app(git)
+    }
+
+    #[test]
+    fn apps_are_subcommands() {
+        let group = group();
+        let matches = group
+            .build_cli_app()
+            .get_matches_from(vec!["toolbox", "tar", "create"]);
+
+        let tar = matches.subcommand_matches("tar").unwrap();
+        assert_eq!(tar.subcommand_name(), Some("create"));
+    }
+
+    #[test]
+    fn apps_are_listed_in_picker() {
+        assert_eq!(group().picker_items(), vec!["tar: archives", "git"]);
+        assert_eq!(group().app_index("git"), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "App name must be unique")]
+    fn duplicated_app_panics() {
+        group().app(Fui::new("git"));
+    }
+}
@@ -6,45 +6,94 @@
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "timezones")]
+extern crate chrono;
+#[cfg(feature = "timezones")]
+extern crate chrono_tz;
 extern crate clap;
 extern crate clipboard;
 #[macro_use]
 extern crate cursive as _cursive;
 extern crate dirs;
+#[cfg(feature = "web")]
+extern crate form_urlencoded;
 extern crate glob;
 extern crate regex;
 #[macro_use]
 extern crate serde_json;
-
+extern crate serde_yaml;
+extern crate signal_hook;
+extern crate toml;
+extern crate unicode_width;
+#[cfg(feature = "remote-defaults")]
+extern crate ureq;
+#[cfg(feature = "uuid")]
+extern crate uuid;
+
+mod audit;
 // TODO: make it public when ready
 mod clap_conv;
+pub mod config;
+pub mod context;
+pub mod cron;
+mod editor;
+pub mod execution;
+#[cfg(feature = "expr")]
+pub mod expr;
 
 /// Re-export of [Cursive](../cursive/index.html) crate.
 pub mod cursive {
     pub use _cursive::*;
 }
+pub use group::Group;
 pub use serde_json::value::Value;
 pub mod feeders;
 pub mod fields;
 pub mod form;
+mod frontend;
+mod group;
+mod locales;
+pub mod metrics;
+pub mod ranking;
+#[cfg(feature = "remote-defaults")]
+mod remote;
+pub mod script;
+pub mod semver;
+mod session;
+mod usage;
 pub mod utils;
 pub mod validators;
 pub mod views;
+#[cfg(feature = "web")]
+mod web;
 
 use clipboard::ClipboardContext;
 use clipboard::ClipboardProvider;
+use config::{Format, Overwrite};
+use context::{ActionContext, Cancellation, Logger};
+use cursive::align::HAlign;
 use cursive::event::Event;
 use cursive::traits::{Nameable, Resizable};
+use cursive::utils::markup::StyledString;
 use cursive::view::Scrollable;
-use cursive::views::{Dialog, LayerPosition, OnEventView};
-use cursive::Cursive;
+use cursive::view::View;
+use cursive::views::{Dialog, LayerPosition, LinearLayout, OnEventView, TextView};
+use cursive::{CbSink, Cursive, CursiveRunnable};
 use fields::autocomplete::AutocompleteManager;
-use form::FormView;
+use form::{Density, FormView, LayoutProfile};
+use metrics::Observer;
+use session::LastSession;
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsString;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::thread;
+use usage::ActionUsage;
 use validators::OneOf;
 use views::Autocomplete;
 
@@ -53,24 +102,121 @@ const DEFAULT_THEME: &'static str = "
     highlight_inactive = \"light black\"
 ";
 const COMMAND_PICKER_ID: &'static str = "fui-command-picker";
+const VERSION_NOTICE_ID: &str = "fui-version-notice";
+const SPLASH_ID: &str = "fui-splash";
+const ACTION_ARG: &str = "action";
+/// Key copying form's values as CLI command to clipboard.
+const COPY_CLI_KEY: Event = Event::CtrlChar('k');
+/// Key pinning highlighted action on top of the picker.
+const PIN_KEY: Event = Event::CtrlChar('t');
+
+type VersionCheck = Box<dyn Fn() -> Option<String> + Send>;
+type Preload = Box<dyn FnOnce() -> Value + Send>;
+
+/// What happens after action's handler finishes.
+///
+/// Handlers returning nothing (`()`) are [Done].
+///
+/// [Done]: #variant.Done
+#[derive(Clone, Debug, PartialEq)]
+pub enum NextStep {
+    /// Program ends.
+    Done,
+    /// Form of action named like the first item opens filled with values of the second one
+    /// (keyed by fields' labels), e.g. to offer copying just created archive somewhere.
+    OpenForm(String, Value),
+}
+
+impl From<()> for NextStep {
+    fn from(_: ()) -> Self {
+        NextStep::Done
+    }
+}
 
 struct Action<'action> {
     name: &'action str,
     help: &'action str,
     form: Option<FormView>,
-    handler: Rc<dyn Fn(Value)>,
+    handler: Rc<dyn Fn(ActionContext) -> NextStep>,
+    // handler checks `ActionContext::cancellation`, so `Ctrl+c` doesn't end program
+    interruptible: bool,
+    cwd: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    aliases: Vec<&'action str>,
 }
 
 impl<'action> Action<'action> {
     fn cmd_with_desc(&self) -> String {
-        format!("{}: {}", self.name, self.help)
+        if self.aliases.is_empty() {
+            format!("{}: {}", self.name, self.help)
+        } else {
+            format!("{} ({}): {}", self.name, self.aliases.join(", "), self.help)
+        }
+    }
+
+    /// Sets working dir & environment variables before running handler.
+    fn apply_environment(&self) -> io::Result<()> {
+        if let Some(ref cwd) = self.cwd {
+            env::set_current_dir(cwd)?;
+        }
+        for (key, value) in self.env.iter() {
+            env::set_var(key, value);
+        }
+        Ok(())
+    }
+
+    /// Describes working dir & environment variables the handler runs with.
+    fn environment_summary(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(ref cwd) = self.cwd {
+            lines.push(format!("Runs in: {}", cwd.display()));
+        }
+        for (key, value) in self.env.iter() {
+            lines.push(format!("Sets: {}={}", key, value));
+        }
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+}
+
+/// Gets dir where data of `app` is kept across runs, `None` for nameless apps.
+fn app_data_dir(app: &str) -> Option<PathBuf> {
+    if app.is_empty() {
+        return None;
+    }
+    dirs::data_dir().map(|dir| dir.join("fui").join(app))
+}
+
+/// Gets message of panic with `payload` (like `panic!("...")` one).
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload
+            .downcast_ref::<String>()
+            .map(|m| m.as_str())
+            .unwrap_or("unknown error"),
     }
 }
 
+/// Checks if program's `args` open form in TUI with `--action <name>`.
+fn opens_form(args: &[OsString]) -> bool {
+    let flag = format!("--{}", ACTION_ARG);
+    args.iter().skip(1).any(|arg| {
+        let arg = arg.to_string_lossy();
+        arg == flag || arg.starts_with(&format!("{}=", flag))
+    })
+}
+
+/// Converts `value` to cli arguments, positional ones (with digit keys) go first.
 fn value2array(value: &Value) -> Vec<String> {
     let mut result: Vec<String> = Vec::new();
     if let Value::Object(map) = value {
-        for (key, val) in map {
+        let mut entries = map.iter().collect::<Vec<(&String, &Value)>>();
+        entries.sort_by_key(|(key, _)| key.parse::<u32>().map_or((1, 0), |idx| (0, idx)));
+        for (key, val) in entries {
             let key_is_digit = key.parse::<u32>().is_ok();
             match val {
                 Value::Bool(true) => {
@@ -112,13 +258,30 @@ fn value2array(value: &Value) -> Vec<String> {
     result
 }
 
+/// Converts form's `data` to cli arguments of fields described by `paths`
+/// (pairs of key & data path, see [FormView::group]).
+///
+/// [FormView::group]: form/struct.FormView.html#method.group
+fn data2array(data: &Value, paths: &[(String, String)]) -> Vec<String> {
+    if paths.is_empty() {
+        return value2array(data);
+    }
+    let mut by_keys = serde_json::Map::new();
+    for (key, path) in paths {
+        if let Some(value) = utils::get_path(data, path) {
+            by_keys.insert(key.to_owned(), value.clone());
+        }
+    }
+    value2array(&Value::Object(by_keys))
+}
+
 trait DumpAsCli {
-    fn dump_as_cli(&self) -> String;
+    fn dump_as_cli(&self, paths: &[(String, String)]) -> String;
 }
 
 impl DumpAsCli for Value {
-    fn dump_as_cli(&self) -> String {
-        return value2array(&self)
+    fn dump_as_cli(&self, paths: &[(String, String)]) -> String {
+        return data2array(&self, paths)
             .iter()
             .map(|a| {
                 if a.contains(" ") {
@@ -150,6 +313,34 @@ pub struct Fui<'attrs, 'action> {
     skip_empty_form: bool,
     /// Holds active step in wizard
     active_step: Rc<RefCell<u8>>,
+    observer: Option<Rc<dyn Observer>>,
+    audit_log: Option<PathBuf>,
+    /// stores labels of sensitive fields for each form
+    sensitive_fields: BTreeMap<&'action str, Vec<String>>,
+    // keys & data paths of fields in order of declaration
+    field_paths: BTreeMap<&'action str, Vec<(String, String)>>,
+    #[cfg(feature = "remote-defaults")]
+    remote_defaults: Option<String>,
+    banner: Option<StyledString>,
+    // key of the recently added action (for setting its details)
+    last_action: Option<String>,
+    version_check: Option<VersionCheck>,
+    // message shown while slow task prepares defaults of forms
+    preload: Option<(String, Preload)>,
+    locale: Option<String>,
+    // kept between runs of handlers, because forms live in it
+    tui: Option<CursiveRunnable>,
+    /// if true user's input is always asked with line prompts instead of TUI
+    prompt: bool,
+    /// if true form left open on quit is reopened on the next start
+    resume_session: bool,
+    // if true cancelling TUI returns to caller (e.g. app picker of `Group`) instead of exiting
+    back_on_cancel: bool,
+    // where usage of actions & the last session are kept
+    data_dir: Option<PathBuf>,
+    density: Density,
+    // compact & comfortable profiles of density
+    layout_profiles: (LayoutProfile, LayoutProfile),
 }
 impl<'attrs, 'action> Fui<'attrs, 'action> {
     /// Creates a new `Fui` with empty actions.
@@ -167,10 +358,34 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             skip_single_action: false,
             skip_empty_form: false,
             active_step: Rc::new(RefCell::new(1)),
+            observer: None,
+            audit_log: None,
+            sensitive_fields: BTreeMap::new(),
+            field_paths: BTreeMap::new(),
+            #[cfg(feature = "remote-defaults")]
+            remote_defaults: None,
+            banner: None,
+            last_action: None,
+            version_check: None,
+            preload: None,
+            locale: None,
+            tui: None,
+            prompt: false,
+            resume_session: false,
+            back_on_cancel: false,
+            data_dir: app_data_dir(program_name),
+            density: Density::Auto,
+            layout_profiles: (LayoutProfile::compact(), LayoutProfile::comfortable()),
         }
     }
     /// Defines action by providing `name`, `help`, `form`, `hdlr`.
     ///
+    /// `hdlr` may return [NextStep] to open another action's form afterwards.
+    ///
+    /// `hdlr` gets form's data with keys in order fields were declared.
+    ///
+    /// [NextStep]: enum.NextStep.html
+    ///
     /// NOTE:
     ///
     /// `name` is also translated into CLI argument, so:
@@ -180,28 +395,101 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
     ///
     /// # Panics:
     ///
-    /// Panics if action name is duplicated.
+    /// Panics if action name is duplicated or already used as [alias].
     ///
-    pub fn action<F>(
-        mut self,
+    /// [alias]: #method.alias
+    ///
+    pub fn action<F, R>(
+        self,
+        name: &'action str,
+        help: &'action str,
+        form: FormView,
+        hdlr: F,
+    ) -> Self
+    where
+        F: Fn(Value) -> R + 'static,
+        R: Into<NextStep>,
+    {
+        let handler = Rc::new(move |ctx: ActionContext| hdlr(ctx.values).into());
+        self.add_action(name, help, form, handler, false)
+    }
+
+    /// Defines action like [action], but its `hdlr` gets [ActionContext] with form's values
+    /// and means of posting UI updates, logging & checking cancellation.
+    ///
+    /// While `hdlr` runs, the first `Ctrl+c` only marks it cancelled and the second one
+    /// ends program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    /// use fui::Fui;
+    ///
+    /// Fui::new("sync")
+    ///     .action_with_context(
+    ///         "sync",
+    ///         "Syncs files",
+    ///         FormView::new().field(Text::new("dir")),
+    ///         |ctx| {
+    ///             for file in &["a", "b"] {
+    ///                 if ctx.cancellation.is_cancelled() {
+    ///                     ctx.logger.warn("cancelled");
+    ///                     return;
+    ///                 }
+    ///                 ctx.logger.info(&format!("syncing {} of {}", file, ctx.values["dir"]));
+    ///             }
+    ///         },
+    ///     )
+    ///     .run();
+    /// ```
+    ///
+    /// [action]: #method.action
+    /// [ActionContext]: context/struct.ActionContext.html
+    pub fn action_with_context<F, R>(
+        self,
         name: &'action str,
         help: &'action str,
         form: FormView,
         hdlr: F,
     ) -> Self
     where
-        F: Fn(Value) + 'static,
+        F: Fn(ActionContext) -> R + 'static,
+        R: Into<NextStep>,
     {
+        self.add_action(name, help, form, Rc::new(move |ctx| hdlr(ctx).into()), true)
+    }
+
+    fn add_action(
+        mut self,
+        name: &'action str,
+        help: &'action str,
+        mut form: FormView,
+        handler: Rc<dyn Fn(ActionContext) -> NextStep>,
+        interruptible: bool,
+    ) -> Self {
+        if let Some(ref locale) = self.locale {
+            form.set_locale(locale);
+        }
         let action_details = Action {
             name: name,
             help: help,
             form: Some(form),
-            handler: Rc::new(hdlr),
+            handler,
+            interruptible,
+            cwd: None,
+            env: Vec::new(),
+            aliases: Vec::new(),
         };
 
-        if let Some(item) = self.action_by_name(&name) {
+        if let Some(item) = self
+            .actions
+            .values()
+            .find(|a| a.name == name || a.aliases.contains(&name))
+        {
             panic!(
-                "Action name must be unique, but it's already defined ({:?})",
+                "Action name must be unique, but it's already used by ({:?})",
                 item.cmd_with_desc()
             );
         }
@@ -210,33 +498,357 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         let fields_count = action_details.form.as_ref().unwrap().get_fields().len();
         self.form_fields_count
             .insert(action_details.name, fields_count as u8);
+        let form = action_details.form.as_ref().unwrap();
+        let paths = form.data_paths();
+        let sensitive = form
+            .get_fields()
+            .iter()
+            .zip(paths.iter())
+            .filter(|(f, _)| f.is_sensitive())
+            .map(|(_, (_, path))| path.to_owned())
+            .collect();
+        self.sensitive_fields.insert(action_details.name, sensitive);
+        self.field_paths.insert(action_details.name, paths);
+        self.last_action = Some(action_details.cmd_with_desc());
         self.actions
             .insert(action_details.cmd_with_desc(), action_details);
         self
     }
 
+    /// Sets working dir in which handler of the last defined action runs.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is defined yet.
+    pub fn cwd<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.last_action_mut("cwd").cwd = Some(path.into());
+        self
+    }
+
+    /// Sets environment variable `key` to `value` for handler of the last defined action.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is defined yet.
+    pub fn env<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.last_action_mut("env")
+            .env
+            .push((key.into(), value.into()));
+        self
+    }
+
+    /// Adds `alias` of the last defined action.
+    ///
+    /// Alias works as CLI subcommand and matches in action picker.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is defined yet or `alias` is already used.
+    pub fn alias(mut self, alias: &'action str) -> Self {
+        if let Some(item) = self
+            .actions
+            .values()
+            .find(|a| a.name == alias || a.aliases.contains(&alias))
+        {
+            panic!(
+                "Alias must be unique, but it's already used by ({:?})",
+                item.cmd_with_desc()
+            );
+        }
+        // actions are keyed by text containing aliases
+        let old_key = self.last_action_mut("alias").cmd_with_desc();
+        let mut action = self.actions.remove(&old_key).unwrap();
+        action.aliases.push(alias);
+        let new_key = action.cmd_with_desc();
+        self.actions.insert(new_key.clone(), action);
+        self.last_action = Some(new_key);
+        self
+    }
+
+    fn last_action_mut(&mut self, method: &str) -> &mut Action<'action> {
+        let key = match self.last_action {
+            Some(ref key) => key,
+            None => panic!("`{}` must follow an `action`", method),
+        };
+        self.actions.get_mut(key).unwrap()
+    }
+
+    /// Adds action `generate` which writes values of `form` to a config file at `path`.
+    ///
+    /// Help texts of fields become comments in the file (except JSON, which has no comments),
+    /// so it's a quick way to build an "init wizard" for another program.
+    ///
+    /// When the file already exists, differences are shown and user decides if it's
+    /// overwritten, merged (old entries missing in the form are kept with comments above them)
+    /// or left untouched. File which can't be parsed isn't merged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fui::config::Format;
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    /// use fui::Fui;
+    ///
+    /// let form = FormView::new().field(Text::new("name").help("Name of the project"));
+    /// Fui::new("init")
+    ///     .config_generator(form, "project.toml", Format::Toml)
+    ///     .run();
+    /// ```
+    pub fn config_generator<P: Into<PathBuf>>(
+        self,
+        form: FormView,
+        path: P,
+        format: Format,
+    ) -> Self {
+        let path = path.into();
+        let helps: Vec<(String, String)> = form
+            .get_fields()
+            .iter()
+            .zip(form.data_paths())
+            .map(|(f, (_, path))| (path, f.get_help().to_owned()))
+            .collect();
+        let handler = Rc::new(move |ctx: ActionContext| {
+            let values = ctx.values;
+            let text = match ::std::fs::read_to_string(&path) {
+                Ok(old) => {
+                    match Self::ask_overwrite(&old, &values, &helps, format, &ctx.cursive_sink) {
+                        Overwrite::Replace => config::render(&values, &helps, format),
+                        Overwrite::Merge => match config::merge(&old, &values, &helps, format) {
+                            Ok(text) => text,
+                            Err(e) => {
+                                eprintln!("Merging config {:?} failed: {}", path, e);
+                                ::std::process::exit(1);
+                            }
+                        },
+                        Overwrite::Cancel => {
+                            println!("Config {:?} left untouched", path);
+                            return NextStep::Done;
+                        }
+                    }
+                }
+                Err(_) => config::render(&values, &helps, format),
+            };
+            if let Err(e) = ::std::fs::write(&path, text) {
+                eprintln!("Writing config {:?} failed: {}", path, e);
+                ::std::process::exit(1);
+            }
+            NextStep::Done
+        });
+        self.add_action("generate", "Write config file", form, handler, false)
+    }
+
+    /// Asks what to do with existing config, in TUI if it was used, otherwise line by line.
+    fn ask_overwrite(
+        old: &str,
+        values: &Value,
+        helps: &[(String, String)],
+        format: Format,
+        cursive_sink: &Option<CbSink>,
+    ) -> Overwrite {
+        if cursive_sink.is_some() {
+            return config::confirm_overwrite(old, values, helps, format);
+        }
+        if !frontend::is_interactive() {
+            eprintln!(
+                "Config file exists and terminal isn't interactive to confirm overwriting it"
+            );
+            ::std::process::exit(1);
+        }
+        let stdin = io::stdin();
+        let asked = config::ask_overwrite(old, values, helps, format, stdin.lock(), io::stdout());
+        asked.unwrap_or(Overwrite::Cancel)
+    }
+
     fn action_by_name(&self, name: &str) -> Option<&Action> {
         self.actions.values().find(|a| a.name == name)
     }
 
     /// Coordinates flow from action picking to handler running.
+    ///
+    /// On terminals which can't show TUI (`TERM=dumb`) action & form are asked for
+    /// with plain line prompts.
+    ///
+    /// When stdin or stdout isn't a terminal (e.g. output is piped or program runs from cron)
+    /// nothing is asked for: action & its fields are taken from CLI args only, and missing
+    /// ones end program with an error instead of drawing TUI into the pipe.
     // This must be moving, until FormView implements copy or FormViews are added to cursive once
     // then top layer are switched (instead of current inserting/popping)
-    pub fn run(mut self) {
-        let args = env::args_os();
+    pub fn run(self) {
+        let args = env::args_os().collect::<Vec<OsString>>();
+        self.run_with_args(args);
+    }
+
+    /// Same as [run] but asks for action & fields with line prompts instead of TUI,
+    /// for SSH sessions and minimal terminals where TUI misbehaves.
+    ///
+    /// Values are validated like in TUI. Typing `Tab` before `Enter` completes values
+    /// of fields with suggestions (like [Autocomplete]) or lists the matching ones.
+    ///
+    /// [run]: #method.run
+    /// [Autocomplete]: fields/struct.Autocomplete.html
+    pub fn run_prompt(mut self) {
+        self.prompt = true;
+        self.run();
+    }
+
+    /// Serves forms of actions over HTTP at `addr` (like `127.0.0.1:8080`) as simple HTML pages,
+    /// for people who won't use terminal.
+    ///
+    /// Submitted forms are validated like in TUI and handlers run in the serving process.
+    /// Pages are reachable only through the address (with random token) printed on start,
+    /// requests from other sites are rejected. Connections are served one at a time.
+    /// Requires `web` feature.
+    #[cfg(feature = "web")]
+    pub fn serve<A: ::std::net::ToSocketAddrs>(mut self, addr: A) -> io::Result<()> {
+        self.install_observer();
+        self.run_preload();
+        web::serve(&self, addr)
+    }
+
+    /// Same as [run] but with given program's `args` (including program's name).
+    ///
+    /// [run]: #method.run
+    pub(crate) fn run_with_args(mut self, args: Vec<OsString>) {
+        self.run_args(args);
+    }
+
+    /// Same as [run_with_args] but cancelling the action picker in TUI returns `false`
+    /// instead of ending program, so the app can be run again.
+    ///
+    /// [run_with_args]: #method.run_with_args
+    pub(crate) fn run_until_cancel(&mut self, args: Vec<OsString>) -> bool {
+        self.back_on_cancel = true;
+        self.run_args(args)
+    }
+
+    /// Runs handlers of actions picked with `args`, returns `false` when none was picked.
+    fn run_args(&mut self, args: Vec<OsString>) -> bool {
+        self.install_observer();
+        let interactive = self.prompt || frontend::is_interactive();
+        if args.len() > 1 && !opens_form(&args) {
+            // CLI has no splash, so defaults are loaded before args are checked
+            self.run_preload();
+        }
         let input_data = if args.len() > 1 {
-            // input from CLI
-            self.input_from_cli(args)
-        } else {
+            match self.action_from_cli(&args) {
+                Some(action_with_desc) if interactive => {
+                    // input from TUI, starting at form of action picked in CLI
+                    *self.picked_action.borrow_mut() = Some(action_with_desc);
+                    *self.active_step.borrow_mut() = 2;
+                    self.input_from_frontend()
+                }
+                Some(_) => frontend::not_interactive(self.name).exit(),
+                // input from CLI
+                None => self.input_from_cli(args),
+            }
+        } else if interactive {
             // input from TUI
-            self.input_from_tui()
+            self.input_from_frontend()
+        } else {
+            frontend::not_interactive(self.name).exit()
+        };
+        let picked = input_data.is_some();
+        let mut input_data = input_data;
+        while let Some((action_name, data)) = input_data.take() {
+            input_data = match self.run_handler(&action_name, data) {
+                NextStep::Done => None,
+                NextStep::OpenForm(name, values) if interactive => {
+                    self.open_next_form(&name, &values)
+                }
+                NextStep::OpenForm(name, _) => {
+                    eprintln!("Next step {:?} needs interactive terminal", name);
+                    None
+                }
+            };
+        }
+        picked
+    }
+
+    /// Runs handler of action named `action_name` with `data`.
+    fn run_handler(&self, action_name: &str, data: Value) -> NextStep {
+        let action = match self.action_by_name(action_name) {
+            Some(action) => action,
+            None => return NextStep::Done,
+        };
+        metrics::notify(|o| o.on_submit(action.name, &data));
+        self.record_use(action.name);
+        if let Err(e) = action.apply_environment() {
+            eprintln!("Setting up environment of {:?} failed: {}", action.name, e);
+            ::std::process::exit(1);
+        }
+        let hdlr = action.handler.clone();
+        // `Ctrl+c` ends program as usual unless handler checks cancellation
+        let (cancellation, _interrupts) = if action.interruptible {
+            let (cancellation, guard) = Cancellation::on_interrupt();
+            (cancellation, Some(guard))
+        } else {
+            (Cancellation::new(), None)
+        };
+        let context = |values| self.action_context(action.name, values, cancellation.clone());
+        match self.audit_log {
+            Some(ref path) => {
+                let run = || hdlr(context(data.clone()));
+                let result = panic::catch_unwind(panic::AssertUnwindSafe(run));
+                let outcome = if result.is_ok() { "ok" } else { "panicked" };
+                let sensitive = &self.sensitive_fields[action.name];
+                if let Err(e) = audit::write_record(path, action.name, &data, sensitive, outcome) {
+                    eprintln!("Writing audit log {:?} failed: {}", path, e);
+                }
+                match result {
+                    Ok(next) => next,
+                    Err(e) => panic::resume_unwind(e),
+                }
+            }
+            None => hdlr(context(data)),
+        }
+    }
+
+    /// Builds context of running action named `action_name` with `values`.
+    fn action_context(
+        &self,
+        action_name: &str,
+        values: Value,
+        cancellation: Cancellation,
+    ) -> ActionContext {
+        ActionContext {
+            action_name: action_name.to_owned(),
+            values,
+            cursive_sink: self.tui.as_ref().map(|c| c.cb_sink().clone()),
+            cancellation,
+            logger: Logger::new(action_name),
+        }
+    }
+
+    /// Opens form of action named `action_name` filled with `values` and gets user's input.
+    fn open_next_form(&mut self, action_name: &str, values: &Value) -> Option<(String, Value)> {
+        let key = match self.action_by_name(action_name) {
+            Some(action) => action.cmd_with_desc(),
+            None => {
+                eprintln!("Next step {:?} isn't a defined action", action_name);
+                return None;
+            }
         };
-        // run handler
-        if let Some((action_name, data)) = input_data {
-            if let Some(action) = self.action_by_name(&action_name) {
-                let hdlr = action.handler.clone();
-                hdlr(data);
+        self.fill_form(action_name, values);
+        *self.picked_action.borrow_mut() = Some(key);
+        *self.active_step.borrow_mut() = 2;
+        self.input_from_frontend()
+    }
+
+    /// Fills form of action named `action_name` with `values`,
+    /// wherever the form is (still in action or already in TUI).
+    fn fill_form(&mut self, action_name: &str, values: &Value) {
+        let form = self
+            .actions
+            .values_mut()
+            .find(|a| a.name == action_name)
+            .and_then(|a| a.form.as_mut());
+        match (form, self.tui.as_mut()) {
+            (Some(form), _) => form.set_values(values),
+            (None, Some(c)) => {
+                c.call_on_name(action_name, |form: &mut FormView| form.set_values(values));
             }
+            (None, None) => {}
         }
     }
 
@@ -248,7 +860,14 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             }
         }
         if let Some(f) = self.form_data.borrow().as_ref() {
-            arg_vec.append(&mut value2array(&f));
+            let paths = self
+                .picked_action
+                .borrow()
+                .as_ref()
+                .and_then(|a| self.actions.get(a).or_else(|| self.action_by_name(a)))
+                .map(|a| self.field_paths[a.name].as_slice())
+                .unwrap_or(&[]);
+            arg_vec.append(&mut data2array(&f, paths));
         }
         arg_vec
     }
@@ -272,7 +891,8 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
 
     /// Gets user input converted to cli-like format
     pub fn get_cli_input(mut self) -> Vec<String> {
-        self.input_from_tui();
+        self.install_observer();
+        self.input_from_frontend();
         self.dump_as_cli()
     }
 
@@ -285,16 +905,41 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             let args = action.form.as_ref().unwrap().fields2clap_args();
             let sub_cmd = clap::SubCommand::with_name(action.name)
                 .about(action.help)
+                .visible_aliases(&action.aliases)
                 .args(args.as_slice());
             sub_cmds.push(sub_cmd);
         }
+        let mut names = Vec::new();
+        for action in self.actions.values() {
+            names.push(action.name);
+            names.extend(action.aliases.iter().cloned());
+        }
+        let action_arg = clap::Arg::with_name(ACTION_ARG)
+            .long(ACTION_ARG)
+            .takes_value(true)
+            .value_name("name")
+            .possible_values(&names)
+            .hide_possible_values(true)
+            .help("Opens form of action <name> in TUI");
         clap::App::new(self.name)
             .version(self.version)
             .about(self.about)
             .author(self.author)
+            .arg(action_arg)
             .subcommands(sub_cmds)
     }
 
+    /// Gets action (as "name: desc") picked in CLI with `--action <name>`.
+    fn action_from_cli(&self, user_args: &[OsString]) -> Option<String> {
+        let app = self.build_cli_app();
+        let matches = app.get_matches_from(user_args);
+        let name = matches.value_of(ACTION_ARG)?;
+        self.actions
+            .iter()
+            .find(|(_, a)| a.name == name || a.aliases.contains(&name))
+            .map(|(key, _)| key.clone())
+    }
+
     fn input_from_cli<I, T>(&self, user_args: I) -> Option<(String, Value)>
     where
         I: IntoIterator<Item = T>,
@@ -307,7 +952,7 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
 
         let app = self.build_cli_app();
 
-        let matches = app.get_matches_from(user_args);
+        let matches = app.get_matches_from(user_args.clone());
         let cmd_name = matches.subcommand_name().unwrap();
         let cmd_matches = matches.subcommand_matches(cmd_name).unwrap();
         let action = self
@@ -315,12 +960,21 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             .values()
             .find(|action| action.name == cmd_name)
             .unwrap();
-        let value = action
-            .form
-            .as_ref()
-            .unwrap()
-            .clap_arg_matches2value(cmd_matches);
-        Some((action.cmd_with_desc(), value))
+        let form = action.form.as_ref().unwrap();
+        let args = user_args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<String>>();
+        for warning in form.cli_deprecation_warnings(&args) {
+            eprintln!("WARNING: {}", warning);
+        }
+        let (value, errors) = form.clap_arg_matches2data(cmd_matches);
+        if !errors.is_empty() {
+            // rejected data isn't passed to handler
+            form::print_cli_errors(&errors);
+            ::std::process::exit(1);
+        }
+        Some((action.name.to_string(), value))
     }
 
     fn header(&self) -> String {
@@ -334,6 +988,169 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         return header;
     }
 
+    /// Runs version check in background, its result is shown below action picker.
+    fn spawn_version_check(&mut self, c: &Cursive) {
+        let check = match self.version_check.take() {
+            Some(check) => check,
+            None => return,
+        };
+        let sink = c.cb_sink().clone();
+        thread::spawn(move || {
+            if let Some(notice) = check() {
+                // it fails only when UI is already closed, so notice isn't needed
+                let _ = sink.send(Box::new(move |c: &mut Cursive| {
+                    c.call_on_name(VERSION_NOTICE_ID, |view: &mut TextView| {
+                        view.set_content(notice)
+                    });
+                }));
+            }
+        });
+    }
+
+    /// Shows splash while preload task runs in background, then fills forms with its result.
+    fn spawn_preload(&mut self, c: &mut Cursive) {
+        let (message, task) = match self.preload.take() {
+            Some(preload) => preload,
+            None => return,
+        };
+        c.add_layer(Dialog::around(views::Splash::new(message)).with_name(SPLASH_ID));
+        let fps = c.fps().map_or(0, |fps| fps.get());
+        c.set_autorefresh(true);
+        let sink = c.cb_sink().clone();
+        thread::spawn(move || {
+            let defaults = panic::catch_unwind(panic::AssertUnwindSafe(task));
+            // it fails only when UI is already closed, so defaults aren't needed
+            let _ = sink.send(Box::new(move |c: &mut Cursive| {
+                if let Some(pos) = c.screen_mut().find_layer_from_name(SPLASH_ID) {
+                    c.screen_mut().remove_layer(pos);
+                }
+                c.set_fps(fps);
+                match defaults {
+                    Ok(Value::Object(defaults)) => {
+                        for (action_name, values) in defaults.iter() {
+                            c.call_on_name(action_name, |form: &mut FormView| {
+                                form.set_values(values)
+                            });
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        let error = format!("Preloading failed: {}", panic_message(&*e));
+                        c.add_layer(Dialog::info(error));
+                    }
+                }
+            }));
+        });
+    }
+
+    /// Remembers that action `name` was used in TUI, so it's listed higher in action picker.
+    ///
+    /// Actions run from CLI args (e.g. by scripts) aren't recorded.
+    fn record_use(&self, name: &str) {
+        if self.tui.is_none() {
+            return;
+        }
+        if let Some(ref dir) = self.data_dir {
+            let path = ActionUsage::path(dir);
+            let mut usage = ActionUsage::load(&path);
+            usage.record_use(name);
+            usage.save(&path);
+        }
+    }
+
+    /// Reopens form of action which was open when user quit last time, filled with
+    /// values it had then (unless it was too long ago).
+    fn resume_last_session_in(&self, c: &mut Cursive) {
+        let session = match self
+            .data_dir
+            .as_ref()
+            .and_then(|dir| LastSession::take(&LastSession::path(dir), session::MAX_AGE))
+        {
+            Some(session) => session,
+            None => return,
+        };
+        let action = match self.action_by_name(&session.action) {
+            Some(action) => action,
+            None => return,
+        };
+        c.call_on_name(action.name, |form: &mut FormView| {
+            for (label, value) in session.values.iter() {
+                form.set_field_value(label, value);
+            }
+        });
+        *self.picked_action.borrow_mut() = Some(action.cmd_with_desc());
+        *self.active_step.borrow_mut() = 2;
+    }
+
+    /// Remembers form of action `action_name` left open in TUI with its values,
+    /// except sensitive ones which never get to disk.
+    fn save_session(&self, c: &mut Cursive, action_name: &str) {
+        let path = match self.data_dir {
+            Some(ref dir) => LastSession::path(dir),
+            None => return,
+        };
+        let values = c.call_on_name(action_name, |form: &mut FormView| {
+            let mut values = form.snapshot();
+            for field in form.get_fields().iter().filter(|f| f.is_sensitive()) {
+                values.remove(field.get_label());
+            }
+            values
+        });
+        if let Some(values) = values {
+            LastSession::new(action_name, values).save(&path);
+        }
+    }
+
+    fn install_observer(&self) {
+        if let Some(ref observer) = self.observer {
+            metrics::set_shared_observer(Rc::clone(observer));
+        }
+    }
+
+    /// Loads defaults from [remote_defaults] url (once), filling forms with them.
+    ///
+    /// [remote_defaults]: #method.remote_defaults
+    #[cfg(feature = "remote-defaults")]
+    fn load_remote_defaults(&mut self) {
+        let defaults = self
+            .remote_defaults
+            .take()
+            .and_then(|url| remote::load(&url));
+        if let Some(defaults) = defaults {
+            self.apply_remote_defaults(&defaults);
+        }
+    }
+
+    /// Fills fields without values with `defaults` like [apply_defaults],
+    /// values set by the app stay.
+    ///
+    /// [apply_defaults]: #method.apply_defaults
+    #[cfg(feature = "remote-defaults")]
+    fn apply_remote_defaults(&mut self, defaults: &Value) {
+        for action in self.actions.values_mut() {
+            if let (Some(form), Some(values)) = (action.form.as_mut(), defaults.get(action.name)) {
+                form.set_defaults(values);
+            }
+        }
+    }
+
+    /// Runs preload task in foreground (CLI has no splash), filling forms with its result.
+    fn run_preload(&mut self) {
+        if let Some((_, task)) = self.preload.take() {
+            let defaults = task();
+            self.apply_defaults(&defaults);
+        }
+    }
+
+    /// Fills forms with `defaults` which is an object of form values keyed by action name.
+    fn apply_defaults(&mut self, defaults: &Value) {
+        for action in self.actions.values_mut() {
+            if let (Some(form), Some(values)) = (action.form.as_mut(), defaults.get(action.name)) {
+                form.set_values(values);
+            }
+        }
+    }
+
     fn set_form_events(&self, form: &mut FormView) {
         // set form events
         let form_data = Rc::clone(&self.form_data);
@@ -351,20 +1168,26 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
     }
 
     fn add_form(&self, c: &mut Cursive, form: FormView, form_name: &str) {
+        let mut hints = form.hints();
+        hints.push((COPY_CLI_KEY, "Copy as CLI".to_string()));
         // `with_name` must be before `OnEventView`
         let form = form.with_name(form_name).full_width().scrollable();
         let prog_name = self.name.to_owned();
         let form_name = form_name.to_owned();
-        let form = OnEventView::new(form).on_event(Event::CtrlChar('k'), move |c| {
+        let form = OnEventView::new(form).on_event(COPY_CLI_KEY, move |c| {
             let err = c.call_on_name(&form_name, |form: &mut FormView| match form.validate() {
                 Ok(s) => {
-                    let msg = format!("{} {} {}", prog_name, form_name, s.dump_as_cli());
+                    let cli = s.dump_as_cli(&form.data_paths());
+                    let msg = format!("{} {} {}", prog_name, form_name, cli);
                     let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
                     ctx.set_contents(msg).unwrap();
                     Ok(())
                 }
-                Err(_) => {
-                    let err = format!("Copying to clipboard - FAILED.\nFix form errors first.");
+                Err(errors) => {
+                    let err = format!(
+                        "Copying to clipboard - FAILED.\nFix form errors first:\n{}",
+                        errors
+                    );
                     Err(err)
                 }
             });
@@ -372,7 +1195,16 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
                 c.add_layer(Dialog::info(e).title("Form invalid!"));
             }
         });
-        c.add_layer(form);
+        c.add_layer(self.with_banner(form).child(views::HintBar::new(&hints)));
+    }
+
+    /// Puts banner (if any) above `view`.
+    fn with_banner<V: View>(&self, view: V) -> LinearLayout {
+        let mut layout = LinearLayout::vertical();
+        if let Some(ref banner) = self.banner {
+            layout.add_child(views::Banner::new(banner.clone()));
+        }
+        layout.child(view)
     }
 
     fn add_forms(&mut self, c: &mut Cursive) {
@@ -380,10 +1212,21 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         let action_form_list = self
             .actions
             .iter_mut()
-            .map(|(_, a)| (a.name, a.form.take().unwrap()))
+            .map(|(_, a)| {
+                let form = a.form.take().unwrap();
+                let form = match a.environment_summary() {
+                    Some(summary) => form.note(summary),
+                    None => form,
+                };
+                (a.name, form)
+            })
             .collect::<Vec<(&str, FormView)>>();
         for (form_name, mut form) in action_form_list.into_iter() {
             self.set_form_events(&mut form);
+            let (compact, comfortable) = self.layout_profiles.clone();
+            form.set_layout_profiles(compact, comfortable);
+            form.set_density(self.density);
+            form.set_name(form_name);
             self.add_form(c, form, form_name);
         }
     }
@@ -398,12 +1241,33 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             .keys()
             .map(|x| x.to_owned())
             .collect::<Vec<String>>();
-        let feeder = actions.clone();
+        let usage_path = self.data_dir.as_ref().map(|dir| ActionUsage::path(dir));
+        let usage = usage_path
+            .as_ref()
+            .map(|path| ActionUsage::load(path))
+            .unwrap_or_default();
+        let feeder = Rc::new(RefCell::new(usage.order(&actions)));
+        let usage = Rc::new(RefCell::new(usage));
+        let all_actions = actions.clone();
         let mngr = AutocompleteManager::with_factory_view(Rc::new(move || {
-            Autocomplete::new(feeder.clone()).shown_count(12)
+            let usage = Rc::clone(&usage);
+            let ordered = Rc::clone(&feeder);
+            let all_actions = all_actions.clone();
+            let usage_path = usage_path.clone();
+            Autocomplete::new(Rc::clone(&feeder))
+                .shown_count(12)
+                .on_selection_key(PIN_KEY, move |item| {
+                    let mut usage = usage.borrow_mut();
+                    usage.toggle_favorite(usage::action_name(item));
+                    *ordered.borrow_mut() = usage.order(&all_actions);
+                    if let Some(ref path) = usage_path {
+                        usage.save(path);
+                    }
+                })
         }));
         let form = FormView::new()
             .title(&self.header())
+            .discard_changes_silently()
             .field(
                 fields::Field::new("action", mngr, "".to_string())
                     .help("Pick action")
@@ -421,10 +1285,18 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             .on_cancel(move |c| {
                 *step_cancel.borrow_mut() -= 1;
                 c.quit();
-            })
-            .with_name(COMMAND_PICKER_ID)
-            .full_screen();
-        c.add_layer(form)
+            });
+        let mut hints = form.hints();
+        hints.insert(1, (PIN_KEY, "Pin action".to_string()));
+        let form = form.with_name(COMMAND_PICKER_ID).full_screen();
+        let notice = TextView::new("")
+            .h_align(HAlign::Right)
+            .with_name(VERSION_NOTICE_ID);
+        let layout = self
+            .with_banner(form)
+            .child(notice)
+            .child(views::HintBar::new(&hints));
+        c.add_layer(layout)
     }
 
     fn top_layer_by_name(&self, cursive: &mut Cursive, layer_name: &str) {
@@ -445,15 +1317,45 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         return false;
     }
 
+    /// Gets user's input in TUI or with line prompts when terminal can't show TUI
+    /// (`TERM` is `dumb` or stdin isn't a terminal).
+    fn input_from_frontend(&mut self) -> Option<(String, Value)> {
+        #[cfg(feature = "remote-defaults")]
+        self.load_remote_defaults();
+        let mut frontend = if self.prompt {
+            frontend::prompt()
+        } else {
+            frontend::detect()
+        };
+        frontend.input(self)
+    }
+
     fn input_from_tui(&mut self) -> Option<(String, Value)> {
-        // Cursive blocks stdout, unless it's dropped, so
-        // deattached cursive here to allow destroying it at the end of this fn
-        let mut c = cursive::default();
-        self.add_forms(&mut c);
-        self.add_cmd_picker(&mut c);
+        // Cursive blocks stdout only while it runs, so it's kept between handlers
+        // (with forms in it) for next steps
+        let mut c = match self.tui.take() {
+            Some(c) => c,
+            None => {
+                let mut c = cursive::default();
+                self.add_forms(&mut c);
+                self.add_cmd_picker(&mut c);
+                self.spawn_version_check(&c);
+                self.spawn_preload(&mut c);
+                if self.resume_session && *self.active_step.borrow() == 1 {
+                    self.resume_last_session_in(&mut c);
+                }
+                c
+            }
+        };
         loop {
             let current_step = *self.active_step.borrow();
             match current_step {
+                0 if self.back_on_cancel => {
+                    // TUI with forms is kept for the next run
+                    *self.active_step.borrow_mut() = 1;
+                    self.tui = Some(c);
+                    return None;
+                }
                 0 => ::std::process::exit(0),
                 1 => {
                     // show action picker
@@ -483,17 +1385,31 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
                         *self.active_step.borrow_mut() = 3;
                         continue;
                     }
+                    metrics::notify(|o| o.on_form_open(action_name));
                     self.top_layer_by_name(&mut c, action_name);
                 }
                 3 => break,
                 _ => unimplemented!(),
             }
+            if c.screen_mut().find_layer_from_name(SPLASH_ID).is_some() {
+                // forms wait for defaults being loaded
+                self.top_layer_by_name(&mut c, SPLASH_ID);
+            }
             c.run();
+            // TUI stops for external editor & resumes after it
+            while editor::run_pending() {
+                c.run();
+            }
             if current_step == *self.active_step.borrow() {
                 // step didn't change? => ctrl-c pressed
-                *self.active_step.borrow_mut() = 0;
+                if current_step == 2 && self.resume_session {
+                    let action_name = self.picked_action.borrow().clone().unwrap();
+                    self.save_session(&mut c, &action_name);
+                }
+                ::std::process::exit(0);
             }
         }
+        self.tui = Some(c);
         Some((
             self.picked_action.borrow().clone().unwrap(),
             self.form_data.borrow().clone().unwrap(),
@@ -595,12 +1511,179 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         self
     }
 
+    /// Sets `observer` notified about user's activity, see [metrics].
+    ///
+    /// [metrics]: metrics/index.html
+    pub fn observer<O: Observer + 'static>(mut self, observer: O) -> Self {
+        self.observer = Some(Rc::new(observer));
+        self
+    }
+
+    /// Appends a record of each submission to the log at `path`.
+    ///
+    /// A record is a JSON line with a timestamp, action name, submitted values and outcome
+    /// of the handler (`"ok"` or `"panicked"`).
+    /// Values of fields marked as [sensitive] are redacted.
+    ///
+    /// [sensitive]: fields/struct.Field.html#method.sensitive
+    pub fn audit_log<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// Loads defaults for forms from `url` when the program starts.
+    ///
+    /// The document at `url` is JSON or TOML object with form values keyed by action name,
+    /// e.g. `{"deploy": {"region": "eu", "dry-run": true}}`, so a team can manage
+    /// defaults of its tools in one place.
+    /// The document is cached and refreshed at most once an hour.
+    /// When the server is unreachable (request times out after 3 seconds) the cached copy is used.
+    ///
+    /// Defaults fill fields which have no value set by the app (e.g. with `initial`),
+    /// when forms are shown in TUI (or with line prompts), so CLI and [serve] don't wait
+    /// for the server. Requires `remote-defaults` feature.
+    ///
+    /// [serve]: #method.serve
+    #[cfg(feature = "remote-defaults")]
+    pub fn remote_defaults<U: Into<String>>(mut self, url: U) -> Self {
+        self.remote_defaults = Some(url.into());
+        self
+    }
+
+    /// Shows `banner` (e.g. ASCII-art logo made with figlet) above action picker and forms.
+    ///
+    /// Pass [StyledString] to color it.
+    /// The banner collapses on short terminals.
+    ///
+    /// [StyledString]: cursive/utils/markup/type.StyledString.html
+    pub fn banner<S: Into<StyledString>>(mut self, banner: S) -> Self {
+        self.banner = Some(banner.into());
+        self
+    }
+
+    /// Sets `check` run at startup (in background, so it never blocks) which may return
+    /// a notice like `"v2.1 available"` shown below action picker.
+    pub fn version_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + 'static,
+    {
+        self.version_check = Some(Box::new(check));
+        self
+    }
+
+    /// Sets slow `task` (like priming caches of feeders or fetching config) run in background
+    /// when TUI starts, while splash with `message` & spinner is shown instead of blank terminal.
+    /// When `task` panics, the splash is replaced with the error.
+    ///
+    /// `task` returns defaults filling forms, an object of forms' values keyed by action name
+    /// (return `json!({})` to fill nothing). In CLI (or when forms are served on web)
+    /// it runs before args are parsed and its values are used for args which aren't passed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[macro_use] extern crate serde_json;
+    /// # extern crate fui;
+    /// # fn main() {
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    /// use fui::Fui;
+    ///
+    /// Fui::new("deploy")
+    ///     .action(
+    ///         "deploy",
+    ///         "Deploys app",
+    ///         FormView::new().field(Text::new("host")),
+    ///         |_| {},
+    ///     )
+    ///     .preload("Fetching hosts…", || {
+    ///         // slow request here
+    ///         json!({"deploy": {"host": "prod-1"}})
+    ///     })
+    ///     .run();
+    /// # }
+    /// ```
+    pub fn preload<F>(mut self, message: &str, task: F) -> Self
+    where
+        F: FnOnce() -> Value + Send + 'static,
+    {
+        self.preload = Some((message.to_owned(), Box::new(task)));
+        self
+    }
+
+    /// Makes fields of all forms accept & show numbers in format of `locale`
+    /// (like `de-DE` with comma as decimal separator), both in TUI and CLI.
+    ///
+    /// Handlers always get numbers in canonical form.
+    pub fn locale(mut self, locale: &str) -> Self {
+        for action in self.actions.values_mut() {
+            if let Some(ref mut form) = action.form {
+                form.set_locale(locale);
+            }
+        }
+        self.locale = Some(locale.to_string());
+        self
+    }
+
     /// Sets value for skip_single_action
+    ///
+    /// Same as [skip_picker].
+    ///
+    /// [skip_picker]: #method.skip_picker
     pub fn skip_single_action(mut self, skip: bool) -> Self {
         self.skip_single_action = skip;
         self
     }
 
+    /// Jumps straight into action's form if there is only one action,
+    /// instead of letting user pick it.
+    ///
+    /// Form of any action can be opened directly with `--action <name>` argument too.
+    pub fn skip_picker(mut self, skip: bool) -> Self {
+        self.skip_single_action = skip;
+        self
+    }
+
+    /// Reopens action's form which user left open when quitting (with `Ctrl+c`)
+    /// the last time, with values it had then. Values of sensitive fields aren't kept.
+    ///
+    /// Session is resumed once and only when it was left within the last 24 hours and
+    /// no action is given in CLI args. It's stored in [data_dir].
+    ///
+    /// [data_dir]: #method.data_dir
+    pub fn resume_last_session(mut self, resume: bool) -> Self {
+        self.resume_session = resume;
+        self
+    }
+
+    /// Sets `dir` where usage of actions (ordering them in picker) & the last session
+    /// are kept across runs.
+    ///
+    /// It defaults to `fui/<name>` in user's data dir, programs with empty `name`
+    /// keep nothing unless it's set.
+    pub fn data_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.data_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets how densely fields of forms are laid out.
+    ///
+    /// By default (`Density::Auto`) forms are compact on terminals lower than 30 rows
+    /// & comfortable otherwise, other levels force the same layout everywhere.
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Declares layouts of forms (spacing, helps, heights of dropdowns)
+    /// used for compact & comfortable [density].
+    ///
+    /// [density]: #method.density
+    pub fn layout_profiles(mut self, compact: LayoutProfile, comfortable: LayoutProfile) -> Self {
+        self.layout_profiles = (compact, comfortable);
+        self
+    }
+
     /// Sets value for skip_empty_form
     pub fn skip_empty_form(mut self, skip: bool) -> Self {
         self.skip_empty_form = skip;
@@ -624,7 +1707,69 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--ch1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ch1": true }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
+    }
+
+    #[test]
+    fn handler_runs_with_input_from_cli() {
+        let submitted = Rc::new(RefCell::new(None));
+        let data = Rc::clone(&submitted);
+        let data_dir = ::std::env::temp_dir().join(format!("fui-cli-{}", ::std::process::id()));
+        Fui::new("app")
+            .data_dir(&data_dir)
+            .action(
+                "action1",
+                "desc",
+                FormView::new().field(fields::Text::new("t1")),
+                move |value| *data.borrow_mut() = Some(value),
+            )
+            .run_with_args(vec!["app".into(), "action1".into(), "--t1=v1".into()]);
+
+        assert_eq!(*submitted.borrow(), Some(json!({"t1": "v1"})));
+        // only actions picked in TUI are recorded
+        assert!(!ActionUsage::path(&data_dir).exists());
+    }
+
+    #[test]
+    fn preloaded_values_fill_args_missing_in_cli() {
+        let submitted = Rc::new(RefCell::new(None));
+        let data = Rc::clone(&submitted);
+        let fui = || {
+            let data = Rc::clone(&data);
+            Fui::new("app")
+                .action(
+                    "action1",
+                    "desc",
+                    FormView::new()
+                        .field(fields::Text::new("host").validator(validators::Required))
+                        .field(fields::Text::new("port")),
+                    move |value| *data.borrow_mut() = Some(value),
+                )
+                .preload(
+                    "Loading…",
+                    || json!({"action1": {"host": "h1", "port": "80"}}),
+                )
+        };
+
+        fui().run_with_args(vec!["app".into(), "action1".into()]);
+        assert_eq!(
+            submitted.borrow_mut().take(),
+            Some(json!({"host": "h1", "port": "80"}))
+        );
+
+        fui().run_with_args(vec!["app".into(), "action1".into(), "--port=81".into()]);
+        assert_eq!(
+            submitted.borrow_mut().take(),
+            Some(json!({"host": "h1", "port": "81"}))
+        );
+    }
+
+    #[test]
+    fn action_arg_opens_form() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert!(opens_form(&args(&["app", "--action", "a1"])));
+        assert!(opens_form(&args(&["app", "--action=a1"])));
+        assert!(!opens_form(&args(&["app", "a1", "--t1=--action"])));
     }
 
     #[test]
@@ -639,7 +1784,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ch1": false }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     #[test]
@@ -654,7 +1799,23 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--t1", "v1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "t1": "v1" }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
+    }
+
+    #[test]
+    fn cli_number_is_parsed_in_format_of_locale() {
+        let value = Fui::new("app")
+            .locale("de-DE")
+            .action(
+                "action1",
+                "desc",
+                FormView::new().field(fields::Number::new("n1")),
+                |_| {},
+            )
+            .input_from_cli(vec!["my_app", "action1", "--n1", "1.000,5"]);
+
+        let exp: Value = serde_json::from_str(r#"{ "n1": 1000.5 }"#).unwrap();
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
@@ -674,7 +1835,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--ac", "v1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ac": "v1" }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
@@ -693,7 +1854,7 @@ mod test_date_getting_from_program_args {
             )
             .input_from_cli(vec!["my_app", "action1", "--mf", "v1"]);
         let exp: Value = serde_json::from_str(r#"{ "mf": ["v1"] }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
@@ -709,25 +1870,25 @@ mod dump_as_cli {
     #[test]
     fn test_value_is_converted_to_cmd_ok_when_is_string() {
         let v: Value = serde_json::from_str(r#"{ "arg": "abc" }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg abc"#);
+        assert_eq!(v.dump_as_cli(&[]), r#"--arg abc"#);
     }
 
     #[test]
     fn test_value_string_includes_quotes_when_include_space() {
         let v: Value = serde_json::from_str(r#"{ "arg": "a b" }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg "a b""#);
+        assert_eq!(v.dump_as_cli(&[]), r#"--arg "a b""#);
     }
 
     #[test]
     fn test_value_is_converted_to_cmd_ok_when_is_array() {
         let v: Value = serde_json::from_str(r#"{ "arg": ["a", "b c"] }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg a "b c""#);
+        assert_eq!(v.dump_as_cli(&[]), r#"--arg a "b c""#);
     }
 
     #[test]
     fn test_value_is_empty_when_arg_is_false() {
         let v: Value = serde_json::from_str(r#"{ "arg": false }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#""#);
+        assert_eq!(v.dump_as_cli(&[]), r#""#);
     }
 }
 
@@ -823,4 +1984,223 @@ mod value2array_tests {
         let expected: Vec<String> = vec!["a", "b", "c"].iter().map(|x| x.to_string()).collect();
         assert_eq!(found, expected);
     }
+
+    #[test]
+    fn test_nested_data_is_converted_to_args_of_fields() {
+        let v: Value = serde_json::from_str(r#"{"db": {"host": "h"}, "force": true}"#).unwrap();
+        let paths = vec![
+            ("force".to_string(), "force".to_string()),
+            ("host".to_string(), "db.host".to_string()),
+        ];
+        let found: Vec<String> = data2array(&v, &paths);
+        let expected: Vec<String> = ["--force", "--host", "h"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_declaration_order_is_respected() {
+        let v: Value = serde_json::from_str(r#"{"src": "a", "1": "x", "force": true}"#).unwrap();
+        let found: Vec<String> = value2array(&v);
+        let expected: Vec<String> = ["x", "--src", "a", "--force"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(found, expected);
+    }
+}
+
+#[cfg(all(test, feature = "remote-defaults"))]
+mod remote_defaults_tests {
+    use super::*;
+
+    #[test]
+    fn defaults_fill_forms_of_matching_actions() {
+        let mut fui = Fui::new("app")
+            .action(
+                "action1",
+                "desc",
+                FormView::new()
+                    .field(fields::Text::new("name"))
+                    .field(fields::Checkbox::new("force")),
+                |_| {},
+            )
+            .action(
+                "action2",
+                "desc",
+                FormView::new().field(fields::Text::new("name").initial("own")),
+                |_| {},
+            );
+
+        fui.apply_remote_defaults(&json!({
+            "action1": {"name": "team", "force": true},
+            "action2": {"name": "team"},
+        }));
+
+        let mut form1 = fui
+            .actions
+            .get_mut("action1: desc")
+            .unwrap()
+            .form
+            .take()
+            .unwrap();
+        let mut form2 = fui
+            .actions
+            .get_mut("action2: desc")
+            .unwrap()
+            .form
+            .take()
+            .unwrap();
+        assert_eq!(
+            form1.validate().unwrap(),
+            json!({"name": "team", "force": true})
+        );
+        // defaults sit beneath values of the app
+        assert_eq!(form2.validate().unwrap(), json!({"name": "own"}));
+    }
+}
+
+#[cfg(test)]
+mod action_environment {
+    use super::*;
+
+    #[test]
+    fn cwd_and_env_are_set_on_last_action() {
+        let fui = Fui::new("app")
+            .action("action1", "desc", FormView::new(), |_| {})
+            .action("action2", "desc", FormView::new(), |_| {})
+            .cwd("/tmp")
+            .env("KEY", "VAL");
+
+        let action1 = fui.action_by_name("action1").unwrap();
+        let action2 = fui.action_by_name("action2").unwrap();
+        assert_eq!(action1.environment_summary(), None);
+        assert_eq!(
+            action2.environment_summary(),
+            Some("Runs in: /tmp\nSets: KEY=VAL".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`env` must follow an `action`")]
+    fn env_panics_without_action() {
+        Fui::new("app").env("KEY", "VAL");
+    }
+}
+
+#[cfg(test)]
+mod aliases {
+    use super::*;
+
+    fn fui() -> Fui<'static, 'static> {
+        Fui::new("app")
+            .action(
+                "action1",
+                "desc",
+                FormView::new().field(fields::Text::new("arg")),
+                |_| {},
+            )
+            .alias("a1")
+            .alias("first")
+    }
+
+    #[test]
+    fn alias_works_as_subcommand() {
+        let value = fui().input_from_cli(vec!["app", "a1", "--arg", "v"]);
+
+        assert_eq!(value, Some(("action1".to_string(), json!({"arg": "v"}))));
+    }
+
+    #[test]
+    fn aliases_are_listed_in_picker_items() {
+        let keys: Vec<String> = fui().actions.keys().cloned().collect();
+
+        assert_eq!(keys, vec!["action1 (a1, first): desc"]);
+    }
+
+    #[test]
+    fn action_argument_picks_action_by_name_or_alias() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+
+        assert_eq!(
+            fui().action_from_cli(&args(&["app", "--action", "first"])),
+            Some("action1 (a1, first): desc".to_string())
+        );
+        assert_eq!(
+            fui().action_from_cli(&args(&["app", "a1", "--arg", "v"])),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Alias must be unique")]
+    fn alias_panics_when_duplicated() {
+        fui()
+            .action("action2", "desc", FormView::new(), |_| {})
+            .alias("first");
+    }
+
+    #[test]
+    #[should_panic(expected = "Action name must be unique")]
+    fn action_panics_when_its_name_is_alias() {
+        fui().action("first", "desc", FormView::new(), |_| {});
+    }
+}
+
+#[cfg(test)]
+mod next_step {
+    use super::*;
+
+    #[test]
+    fn handler_output_fills_form_of_next_action() {
+        let mut fui = Fui::new("app")
+            .action("archive", "desc", FormView::new(), |_| {
+                NextStep::OpenForm("copy".to_string(), json!({"src": "backup.tar"}))
+            })
+            .action(
+                "copy",
+                "desc",
+                FormView::new().field(fields::Text::new("src")),
+                |_| {},
+            );
+
+        let copy = fui.action_by_name("copy").unwrap().handler.clone();
+        let context = |name| fui.action_context(name, json!({}), Cancellation::new());
+        assert_eq!(copy(context("copy")), NextStep::Done);
+        let archive = fui.action_by_name("archive").unwrap().handler.clone();
+        let (name, values) = match archive(context("archive")) {
+            NextStep::OpenForm(name, values) => (name, values),
+            NextStep::Done => panic!("next step expected"),
+        };
+        fui.fill_form(&name, &values);
+
+        let form = fui.action_by_name("copy").unwrap().form.as_ref().unwrap();
+        assert_eq!(form.get_field_value("src"), Some("backup.tar".to_string()));
+    }
+
+    #[test]
+    fn handler_with_context_gets_action_details() {
+        let fui = Fui::new("app").action_with_context("copy", "desc", FormView::new(), |ctx| {
+            if ctx.cancellation.is_cancelled() {
+                return NextStep::Done;
+            }
+            NextStep::OpenForm(ctx.action_name, ctx.values)
+        });
+        let copy = fui.action_by_name("copy").unwrap().handler.clone();
+
+        let next = copy(fui.action_context("copy", json!({"a": 1}), Cancellation::new()));
+
+        assert_eq!(
+            next,
+            NextStep::OpenForm("copy".to_string(), json!({"a": 1}))
+        );
+        let cancelled = Cancellation::new();
+        cancelled.cancel();
+        assert_eq!(
+            copy(fui.action_context("copy", json!({}), cancelled)),
+            NextStep::Done
+        );
+    }
 }
@@ -0,0 +1,158 @@
+//! Built-in dataset of common locales as (BCP 47 tag, English name).
+pub(crate) const LOCALES: &[(&str, &str)] = &[
+    ("af-ZA", "Afrikaans (South Africa)"),
+    ("ar-AE", "Arabic (United Arab Emirates)"),
+    ("ar-EG", "Arabic (Egypt)"),
+    ("ar-SA", "Arabic (Saudi Arabia)"),
+    ("az-AZ", "Azerbaijani (Azerbaijan)"),
+    ("be-BY", "Belarusian (Belarus)"),
+    ("bg-BG", "Bulgarian (Bulgaria)"),
+    ("bn-BD", "Bangla (Bangladesh)"),
+    ("bn-IN", "Bangla (India)"),
+    ("bs-BA", "Bosnian (Bosnia and Herzegovina)"),
+    ("ca-ES", "Catalan (Spain)"),
+    ("cs-CZ", "Czech (Czechia)"),
+    ("cy-GB", "Welsh (United Kingdom)"),
+    ("da-DK", "Danish (Denmark)"),
+    ("de-AT", "German (Austria)"),
+    ("de-CH", "German (Switzerland)"),
+    ("de-DE", "German (Germany)"),
+    ("el-GR", "Greek (Greece)"),
+    ("en-AU", "English (Australia)"),
+    ("en-CA", "English (Canada)"),
+    ("en-GB", "English (United Kingdom)"),
+    ("en-IE", "English (Ireland)"),
+    ("en-IN", "English (India)"),
+    ("en-NZ", "English (New Zealand)"),
+    ("en-SG", "English (Singapore)"),
+    ("en-US", "English (United States)"),
+    ("en-ZA", "English (South Africa)"),
+    ("es-AR", "Spanish (Argentina)"),
+    ("es-CL", "Spanish (Chile)"),
+    ("es-CO", "Spanish (Colombia)"),
+    ("es-ES", "Spanish (Spain)"),
+    ("es-MX", "Spanish (Mexico)"),
+    ("es-US", "Spanish (United States)"),
+    ("et-EE", "Estonian (Estonia)"),
+    ("eu-ES", "Basque (Spain)"),
+    ("fa-IR", "Persian (Iran)"),
+    ("fi-FI", "Finnish (Finland)"),
+    ("fil-PH", "Filipino (Philippines)"),
+    ("fr-BE", "French (Belgium)"),
+    ("fr-CA", "French (Canada)"),
+    ("fr-CH", "French (Switzerland)"),
+    ("fr-FR", "French (France)"),
+    ("ga-IE", "Irish (Ireland)"),
+    ("gl-ES", "Galician (Spain)"),
+    ("gu-IN", "Gujarati (India)"),
+    ("he-IL", "Hebrew (Israel)"),
+    ("hi-IN", "Hindi (India)"),
+    ("hr-HR", "Croatian (Croatia)"),
+    ("hu-HU", "Hungarian (Hungary)"),
+    ("hy-AM", "Armenian (Armenia)"),
+    ("id-ID", "Indonesian (Indonesia)"),
+    ("is-IS", "Icelandic (Iceland)"),
+    ("it-CH", "Italian (Switzerland)"),
+    ("it-IT", "Italian (Italy)"),
+    ("ja-JP", "Japanese (Japan)"),
+    ("ka-GE", "Georgian (Georgia)"),
+    ("kk-KZ", "Kazakh (Kazakhstan)"),
+    ("km-KH", "Khmer (Cambodia)"),
+    ("kn-IN", "Kannada (India)"),
+    ("ko-KR", "Korean (South Korea)"),
+    ("lt-LT", "Lithuanian (Lithuania)"),
+    ("lv-LV", "Latvian (Latvia)"),
+    ("mk-MK", "Macedonian (North Macedonia)"),
+    ("ml-IN", "Malayalam (India)"),
+    ("mn-MN", "Mongolian (Mongolia)"),
+    ("mr-IN", "Marathi (India)"),
+    ("ms-MY", "Malay (Malaysia)"),
+    ("mt-MT", "Maltese (Malta)"),
+    ("nb-NO", "Norwegian Bokmål (Norway)"),
+    ("ne-NP", "Nepali (Nepal)"),
+    ("nl-BE", "Dutch (Belgium)"),
+    ("nl-NL", "Dutch (Netherlands)"),
+    ("pa-IN", "Punjabi (India)"),
+    ("pl-PL", "Polish (Poland)"),
+    ("pt-BR", "Portuguese (Brazil)"),
+    ("pt-PT", "Portuguese (Portugal)"),
+    ("ro-RO", "Romanian (Romania)"),
+    ("ru-RU", "Russian (Russia)"),
+    ("si-LK", "Sinhala (Sri Lanka)"),
+    ("sk-SK", "Slovak (Slovakia)"),
+    ("sl-SI", "Slovenian (Slovenia)"),
+    ("sq-AL", "Albanian (Albania)"),
+    ("sr-RS", "Serbian (Serbia)"),
+    ("sv-FI", "Swedish (Finland)"),
+    ("sv-SE", "Swedish (Sweden)"),
+    ("sw-KE", "Swahili (Kenya)"),
+    ("ta-IN", "Tamil (India)"),
+    ("te-IN", "Telugu (India)"),
+    ("th-TH", "Thai (Thailand)"),
+    ("tr-TR", "Turkish (Turkey)"),
+    ("uk-UA", "Ukrainian (Ukraine)"),
+    ("ur-PK", "Urdu (Pakistan)"),
+    ("uz-UZ", "Uzbek (Uzbekistan)"),
+    ("vi-VN", "Vietnamese (Vietnam)"),
+    ("zh-CN", "Chinese (China)"),
+    ("zh-HK", "Chinese (Hong Kong)"),
+    ("zh-TW", "Chinese (Taiwan)"),
+];
+
+/// Gets separators of thousands and decimals used in numbers by locale `tag`.
+///
+/// Unknown locales use English separators.
+pub(crate) fn number_separators(tag: &str) -> (char, char) {
+    let tag = tag.replace('_', "-").to_lowercase();
+    let language = tag.split('-').next().unwrap_or("");
+    match tag.as_str() {
+        "de-ch" | "it-ch" | "fr-ch" => return ('\'', '.'),
+        "pt-pt" => return (' ', ','),
+        "es-mx" | "es-us" => return (',', '.'),
+        _ => {}
+    }
+    match language {
+        "bs" | "ca" | "da" | "de" | "el" | "es" | "hr" | "id" | "it" | "nl" | "pt" | "ro"
+        | "sl" | "sr" | "tr" | "vi" => ('.', ','),
+        "af" | "be" | "bg" | "cs" | "et" | "fi" | "fr" | "hu" | "kk" | "lt" | "lv" | "nb"
+        | "nn" | "no" | "pl" | "ru" | "sk" | "sv" | "uk" => (' ', ','),
+        _ => (',', '.'),
+    }
+}
+
+/// Order of year, month & day in dates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum DateOrder {
+    Ymd,
+    Dmy,
+    Mdy,
+}
+
+/// Gets order of date's parts and their separator used by locale `tag`
+/// (like `(DateOrder::Dmy, '.')` for `31.01.2024` in `de-DE`).
+///
+/// Unknown locales and ones using other calendars write dates as in ISO 8601 (`2024-01-31`).
+pub(crate) fn date_format(tag: &str) -> (DateOrder, char) {
+    let tag = tag.replace('_', "-").to_lowercase();
+    let language = tag.split('-').next().unwrap_or("");
+    match tag.as_str() {
+        "en-us" | "es-us" => return (DateOrder::Mdy, '/'),
+        "en-ca" | "fr-ca" => return (DateOrder::Ymd, '-'),
+        "en-za" => return (DateOrder::Ymd, '/'),
+        _ => {}
+    }
+    match language {
+        "fil" => (DateOrder::Mdy, '/'),
+        "ja" | "zh" => (DateOrder::Ymd, '/'),
+        "hu" | "ko" => (DateOrder::Ymd, '.'),
+        "af" | "lt" | "mn" | "sv" => (DateOrder::Ymd, '-'),
+        "az" | "be" | "bg" | "bs" | "cs" | "da" | "de" | "et" | "fi" | "hr" | "hy" | "is"
+        | "ka" | "kk" | "lv" | "mk" | "nb" | "nn" | "no" | "pl" | "ro" | "ru" | "sk" | "sl"
+        | "sq" | "sr" | "tr" | "uk" | "uz" => (DateOrder::Dmy, '.'),
+        "nl" => (DateOrder::Dmy, '-'),
+        "ar" | "bn" | "ca" | "cy" | "el" | "en" | "es" | "eu" | "fr" | "ga" | "gl" | "gu"
+        | "he" | "hi" | "id" | "it" | "km" | "kn" | "ml" | "mr" | "ms" | "mt" | "ne" | "pa"
+        | "pt" | "si" | "sw" | "ta" | "te" | "ur" | "vi" => (DateOrder::Dmy, '/'),
+        _ => (DateOrder::Ymd, '-'),
+    }
+}
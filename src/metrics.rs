@@ -0,0 +1,54 @@
+//! Hooks for instrumenting programs using `fui`.
+//!
+//! Implement [Observer] and register it with [Fui::observer] (or [set_observer] when using
+//! [FormView] directly) to send telemetry about actions and fields users struggle with.
+//!
+//! [Observer]: trait.Observer.html
+//! [Fui::observer]: ../struct.Fui.html#method.observer
+//! [set_observer]: fn.set_observer.html
+//! [FormView]: ../form/struct.FormView.html
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use form::FormErrors;
+use serde_json::value::Value;
+
+/// Receives events happening in `fui`.
+///
+/// All methods do nothing by default, so implement only what you need.
+pub trait Observer {
+    /// Called when form of `action` is shown.
+    fn on_form_open(&self, _action: &str) {}
+    /// Called after `form` was validated, `errors` are empty when form is valid.
+    fn on_validation(&self, _form: &str, _errors: &FormErrors) {}
+    /// Called when `data` is submitted for `action`, just before running its handler.
+    fn on_submit(&self, _action: &str, _data: &Value) {}
+    /// Called when a feeder answered query for `text` after `duration`.
+    fn feeder_query_duration(&self, _text: &str, _duration: Duration) {}
+}
+
+/// Observer which ignores all events.
+#[derive(Clone, Debug)]
+pub struct NoopObserver;
+
+impl Observer for NoopObserver {}
+
+thread_local! {
+    static OBSERVER: RefCell<Rc<dyn Observer>> = RefCell::new(Rc::new(NoopObserver));
+}
+
+/// Sets `observer` notified about events in the current thread.
+pub fn set_observer<O: Observer + 'static>(observer: O) {
+    set_shared_observer(Rc::new(observer));
+}
+
+pub(crate) fn set_shared_observer(observer: Rc<dyn Observer>) {
+    OBSERVER.with(|o| *o.borrow_mut() = observer);
+}
+
+/// Runs `notify` with the current observer.
+pub(crate) fn notify<F: FnOnce(&dyn Observer)>(notify: F) {
+    let observer = OBSERVER.with(|o| Rc::clone(&o.borrow()));
+    notify(&*observer);
+}
@@ -0,0 +1,313 @@
+//! Ordering of suggestions.
+//!
+//! [Ranker] scores how well a candidate matches a query,
+//! [Ranked] applies it to any [Feeder].
+//!
+//! [Ranker]: trait.Ranker.html
+//! [Ranked]: struct.Ranked.html
+//! [Feeder]: ../feeders/trait.Feeder.html
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use feeders::Feeder;
+
+/// How well a candidate matches, the higher the better.
+pub type Score = i64;
+
+/// Scores candidates for a query.
+pub trait Ranker: 'static {
+    /// Scores `candidate` for `query`, `None` means it doesn't match at all.
+    fn score(&self, query: &str, candidate: &str) -> Option<Score>;
+    /// Notes that `candidate` was picked by user.
+    fn record_use(&self, _candidate: &str) {}
+}
+
+/// Matches candidates including query (case insensitive), keeping their order.
+///
+/// ```
+/// use fui::ranking::{Ranker, Substring};
+/// assert_eq!(Substring.score("ab", "xAbc"), Some(0));
+/// assert_eq!(Substring.score("ab", "ba"), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Substring;
+
+impl Ranker for Substring {
+    fn score(&self, query: &str, candidate: &str) -> Option<Score> {
+        if candidate.to_lowercase().contains(&query.to_lowercase()) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Like [Substring] but candidates starting with query (or with its word starting with query)
+/// go first.
+///
+/// ```
+/// use fui::ranking::{PrefixBiased, Ranker};
+/// let prefix = PrefixBiased.score("re", "release");
+/// let word = PrefixBiased.score("re", "git-rebase");
+/// let inside = PrefixBiased.score("re", "store");
+/// assert!(prefix > word && word > inside);
+/// ```
+///
+/// [Substring]: struct.Substring.html
+#[derive(Clone, Debug)]
+pub struct PrefixBiased;
+
+impl Ranker for PrefixBiased {
+    fn score(&self, query: &str, candidate: &str) -> Option<Score> {
+        let query = query.to_lowercase();
+        let candidate = candidate.to_lowercase();
+        if candidate.starts_with(&query) {
+            Some(2)
+        } else if candidate
+            .match_indices(&query)
+            .any(|(idx, _)| is_word_start(&candidate, idx))
+        {
+            Some(1)
+        } else if candidate.contains(&query) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Matches candidates including query's chars in order (not necessarily adjacent),
+/// preferring adjacent chars and starts of words (the start of candidate the most).
+///
+/// ```
+/// use fui::ranking::{Fuzzy, Ranker};
+/// assert!(Fuzzy.score("fb", "foo-bar").is_some());
+/// assert!(Fuzzy.score("fb", "bar-foo").is_none());
+/// assert!(Fuzzy.score("foo", "foo-bar") > Fuzzy.score("foo", "f-o-o"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fuzzy;
+
+impl Ranker for Fuzzy {
+    fn score(&self, query: &str, candidate: &str) -> Option<Score> {
+        let candidate = candidate.to_lowercase();
+        let mut score = 0;
+        let mut last_match: Option<usize> = None;
+        let mut search_from = 0;
+        for q in query.to_lowercase().chars() {
+            let (idx, c) = candidate[search_from..]
+                .char_indices()
+                .map(|(i, c)| (i + search_from, c))
+                .find(|(_, c)| *c == q)?;
+            score += 1;
+            match last_match {
+                Some(last)
+                    if last + candidate[last..].chars().next().unwrap().len_utf8() == idx =>
+                {
+                    score += 5
+                }
+                Some(last) => score -= (candidate[last..idx].chars().count() as Score).min(5),
+                None => {}
+            }
+            if idx == 0 {
+                score += 5;
+            } else if is_word_start(&candidate, idx) {
+                score += 3;
+            }
+            last_match = Some(idx);
+            search_from = idx + c.len_utf8();
+        }
+        Some(score)
+    }
+}
+
+/// Checks if `idx` starts a word in `text`.
+fn is_word_start(text: &str, idx: usize) -> bool {
+    text[..idx]
+        .chars()
+        .last()
+        .map_or(true, |c| !c.is_alphanumeric())
+}
+
+/// Boosts candidates of `inner` ranker which were picked frequently and recently.
+///
+/// Uses are counted in memory, call [record_use] to note them
+/// (`Autocomplete` does it when its value is submitted).
+///
+/// ```
+/// use fui::ranking::{Frecency, Ranker, Substring};
+/// let ranker = Frecency::new(Substring);
+/// ranker.record_use("beta");
+/// assert!(ranker.score("", "beta") > ranker.score("", "alpha"));
+/// ```
+///
+/// [record_use]: trait.Ranker.html#method.record_use
+#[derive(Clone)]
+pub struct Frecency<R: Ranker> {
+    inner: R,
+    // candidate => (uses count, last use)
+    uses: Rc<RefCell<HashMap<String, (u32, Instant)>>>,
+}
+
+impl<R: Ranker> Frecency<R> {
+    /// Creates a new `Frecency` boosting results of `inner` ranker.
+    pub fn new(inner: R) -> Self {
+        Frecency {
+            inner,
+            uses: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    fn boost(&self, candidate: &str) -> Score {
+        let uses = self.uses.borrow();
+        let (count, last_use) = match uses.get(candidate) {
+            Some(entry) => *entry,
+            None => return 0,
+        };
+        let age = last_use.elapsed();
+        let weight = if age < Duration::from_secs(60 * 60) {
+            100
+        } else if age < Duration::from_secs(24 * 60 * 60) {
+            70
+        } else if age < Duration::from_secs(7 * 24 * 60 * 60) {
+            50
+        } else {
+            30
+        };
+        count as Score * weight
+    }
+}
+
+impl<R: Ranker> Ranker for Frecency<R> {
+    fn score(&self, query: &str, candidate: &str) -> Option<Score> {
+        self.inner
+            .score(query, candidate)
+            .map(|score| score + self.boost(candidate))
+    }
+
+    fn record_use(&self, candidate: &str) {
+        let mut uses = self.uses.borrow_mut();
+        let entry = uses
+            .entry(candidate.to_string())
+            .or_insert((0, Instant::now()));
+        *entry = (entry.0 + 1, Instant::now());
+        self.inner.record_use(candidate);
+    }
+}
+
+impl Ranker for Rc<dyn Ranker> {
+    fn score(&self, query: &str, candidate: &str) -> Option<Score> {
+        (**self).score(query, candidate)
+    }
+
+    fn record_use(&self, candidate: &str) {
+        (**self).record_use(candidate)
+    }
+}
+
+/// Feeder ordering [candidates] of wrapped feeder with a [Ranker].
+///
+/// ```
+/// use fui::feeders::Feeder;
+/// use fui::ranking::{Fuzzy, Ranked};
+/// let feeder = Ranked::new(vec!["git-stash", "git-status", "stat"], Fuzzy);
+/// assert_eq!(feeder.query("stat", 0, 2), vec!["stat", "git-status"]);
+/// ```
+///
+/// [candidates]: ../feeders/trait.Feeder.html#method.candidates
+/// [Ranker]: trait.Ranker.html
+pub struct Ranked<F: Feeder, R: Ranker> {
+    feeder: F,
+    ranker: R,
+}
+
+impl<F: Feeder, R: Ranker> Ranked<F, R> {
+    /// Creates a new `Ranked` ordering data of `feeder` with `ranker`.
+    pub fn new(feeder: F, ranker: R) -> Self {
+        Ranked { feeder, ranker }
+    }
+}
+
+/// Orders `candidates` for `text` with `ranker`, returns `items_count` of them
+/// from `position`.
+pub(crate) fn rank<R: Ranker + ?Sized>(
+    ranker: &R,
+    text: &str,
+    candidates: Vec<String>,
+    position: usize,
+    items_count: usize,
+) -> Vec<String> {
+    let mut scored: Vec<(Score, String)> = candidates
+        .into_iter()
+        .filter_map(|c| ranker.score(text, &c).map(|score| (score, c)))
+        .collect();
+    // stable sort keeps feeder's order for equal scores
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+    scored
+        .into_iter()
+        .skip(position)
+        .take(items_count)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+impl<F: Feeder, R: Ranker> Feeder for Ranked<F, R> {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        rank(
+            &self.ranker,
+            text,
+            self.feeder.candidates(text),
+            position,
+            items_count,
+        )
+    }
+
+    fn contains(&self, item: &str) -> bool {
+        self.feeder.contains(item)
+    }
+
+    fn candidates(&self, text: &str) -> Vec<String> {
+        self.feeder.candidates(text)
+    }
+
+    fn watched_dir(&self, text: &str) -> Option<PathBuf> {
+        self.feeder.watched_dir(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranked_orders_by_score_and_pages() {
+        let feeder = Ranked::new(vec!["restore", "rebase", "reset", "store"], PrefixBiased);
+
+        assert_eq!(
+            feeder.query("re", 0, 10),
+            vec!["restore", "rebase", "reset", "store"]
+        );
+        assert_eq!(feeder.query("sto", 0, 10), vec!["store", "restore"]);
+        assert_eq!(feeder.query("re", 1, 2), vec!["rebase", "reset"]);
+    }
+
+    #[test]
+    fn fuzzy_skips_candidates_not_matching() {
+        let feeder = Ranked::new(vec!["checkout", "cherry-pick", "commit"], Fuzzy);
+
+        assert_eq!(feeder.query("co", 0, 10), vec!["commit", "checkout"]);
+    }
+
+    #[test]
+    fn frecency_prefers_used_candidates() {
+        let ranker = Frecency::new(PrefixBiased);
+        ranker.record_use("reset");
+        let feeder = Ranked::new(vec!["rebase", "reset"], ranker);
+
+        assert_eq!(feeder.query("re", 0, 10), vec!["reset", "rebase"]);
+    }
+}
@@ -0,0 +1,113 @@
+//! Defaults shared by a team, see [Fui::remote_defaults].
+//!
+//! [Fui::remote_defaults]: ../struct.Fui.html#method.remote_defaults
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde_json::value::Value;
+use toml;
+use ureq;
+
+/// How long request for defaults can take.
+const TIMEOUT: Duration = Duration::from_secs(3);
+/// How long cached defaults are used without asking server again.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Parses defaults document, which is JSON or TOML.
+fn parse(document: &str) -> Option<Value> {
+    serde_json::from_str(document)
+        .ok()
+        .or_else(|| toml::from_str(document).ok())
+        .filter(|v: &Value| v.is_object())
+}
+
+/// Path where document fetched from `url` is cached.
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    dirs::cache_dir().map(|dir| {
+        dir.join("fui")
+            .join(format!("defaults-{:x}", hasher.finish()))
+    })
+}
+
+fn fetch(url: &str) -> Result<String, String> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    agent
+        .get(url)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())
+}
+
+/// Loads defaults document from `url`.
+///
+/// Fresh enough cached copy is used without a request.
+/// Stale copy is used only when the request fails or gets invalid document,
+/// which isn't cached.
+pub(crate) fn load(url: &str) -> Option<Value> {
+    let cache = cache_path(url);
+    let cached = cache.as_ref().and_then(|path| {
+        let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+        Some((fs::read_to_string(path).ok()?, age))
+    });
+    if let Some((ref document, age)) = cached {
+        if age < MAX_AGE {
+            return parse(document);
+        }
+    }
+    let fetched = fetch(url).and_then(|document| match parse(&document) {
+        Some(defaults) => Ok((document, defaults)),
+        None => Err("it's not JSON or TOML object".to_string()),
+    });
+    match fetched {
+        Ok((document, defaults)) => {
+            if let Some(ref path) = cache {
+                let _ = path
+                    .parent()
+                    .map(fs::create_dir_all)
+                    .map(|_| fs::write(path, &document));
+            }
+            Some(defaults)
+        }
+        Err(e) => {
+            eprintln!("Loading defaults from {:?} failed: {}", url, e);
+            cached.and_then(|(document, _)| parse(&document))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_json() {
+        let document = r#"{"action": {"name": "x", "force": true}}"#;
+
+        assert_eq!(
+            parse(document),
+            Some(json!({"action": {"name": "x", "force": true}}))
+        );
+    }
+
+    #[test]
+    fn parse_accepts_toml() {
+        let document = "[action]\nname = \"x\"\nforce = true\n";
+
+        assert_eq!(
+            parse(document),
+            Some(json!({"action": {"name": "x", "force": true}}))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert_eq!(parse("[1, 2]"), None);
+        assert_eq!(parse("not = [valid"), None);
+    }
+}
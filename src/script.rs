@@ -0,0 +1,184 @@
+//! Drives [Fui] programmatically, without a terminal.
+//!
+//! Useful for integration tests of programs using `fui` or for replaying saved submissions.
+//!
+//! ```
+//! use fui::fields::Text;
+//! use fui::form::FormView;
+//! use fui::script::Session;
+//! use fui::Fui;
+//!
+//! let app = Fui::new("app").action(
+//!     "greet",
+//!     "Greets somebody",
+//!     FormView::new().field(Text::new("name")),
+//!     |v| println!("Hello {}", v["name"]),
+//! );
+//! let submitted = Session::new(app)
+//!     .choose("greet")
+//!     .fill("name", "Alice")
+//!     .submit()
+//!     .unwrap();
+//! assert_eq!(submitted["name"], "Alice");
+//! ```
+//!
+//! [Fui]: ../struct.Fui.html
+use context::Cancellation;
+use form::{FormErrors, FormView};
+use metrics;
+use serde_json::value::Value;
+use Fui;
+
+/// Scripted user of [Fui].
+///
+/// [Fui]: ../struct.Fui.html
+pub struct Session<'attrs, 'action> {
+    fui: Fui<'attrs, 'action>,
+    action: Option<String>,
+}
+
+impl<'attrs, 'action> Session<'attrs, 'action> {
+    /// Creates a new `Session` using actions defined in `fui`.
+    pub fn new(fui: Fui<'attrs, 'action>) -> Self {
+        Session { fui, action: None }
+    }
+
+    /// Picks action named `action`.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if there is no such action.
+    pub fn choose(mut self, action: &str) -> Self {
+        if self.fui.action_by_name(action).is_none() {
+            panic!("Action {:?} is not defined", action);
+        }
+        self.action = Some(action.to_owned());
+        metrics::notify(|o| o.on_form_open(action));
+        self
+    }
+
+    /// Sets `value` of field labeled `label` in form of the chosen action.
+    ///
+    /// `value` is the same text a user would type in.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is chosen or the form has no such field.
+    pub fn fill(mut self, label: &str, value: &str) -> Self {
+        if !self.form_mut().set_field_value(label, value) {
+            panic!("Field {:?} is not defined", label);
+        }
+        self
+    }
+
+    /// Fills form of the chosen action with `values` (like ones passed to a handler).
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is chosen.
+    pub fn fill_values(mut self, values: &Value) -> Self {
+        self.form_mut().set_values(values);
+        self
+    }
+
+    /// Validates form of the chosen action (including its submit hooks) and runs its handler.
+    ///
+    /// Returns data passed to the handler.
+    ///
+    /// # Panics:
+    ///
+    /// Panics if no action is chosen.
+    pub fn submit(&mut self) -> Result<Value, FormErrors> {
+        let data = self.form_mut().submit_data()?;
+        self.form_mut().notify_submitted(&data);
+        let action = self.fui.action_by_name(self.chosen()).unwrap();
+        metrics::notify(|o| o.on_submit(action.name, &data));
+        let context = self
+            .fui
+            .action_context(action.name, data.clone(), Cancellation::new());
+        (action.handler)(context);
+        Ok(data)
+    }
+
+    fn chosen(&self) -> &str {
+        self.action.as_ref().expect("Action must be chosen first")
+    }
+
+    fn form_mut(&mut self) -> &mut FormView {
+        let name = self.chosen().to_owned();
+        self.fui
+            .actions
+            .values_mut()
+            .find(|a| a.name == name)
+            .and_then(|a| a.form.as_mut())
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::{Checkbox, Text};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use validators::Required;
+
+    fn app(handled: Rc<RefCell<Option<Value>>>) -> Fui<'static, 'static> {
+        Fui::new("app").action(
+            "action1",
+            "desc",
+            FormView::new()
+                .field(Text::new("t1").validator(Required))
+                .field(Checkbox::new("ch1")),
+            move |v| *handled.borrow_mut() = Some(v),
+        )
+    }
+
+    #[test]
+    fn submit_runs_handler_with_filled_data() {
+        let handled = Rc::new(RefCell::new(None));
+
+        let submitted = Session::new(app(Rc::clone(&handled)))
+            .choose("action1")
+            .fill("t1", "v1")
+            .fill("ch1", "true")
+            .submit()
+            .unwrap();
+
+        assert_eq!(submitted, json!({"t1": "v1", "ch1": true}));
+        assert_eq!(*handled.borrow(), Some(submitted));
+    }
+
+    #[test]
+    fn submit_returns_errors_of_invalid_form() {
+        let handled = Rc::new(RefCell::new(None));
+
+        let errors = Session::new(app(Rc::clone(&handled)))
+            .choose("action1")
+            .submit()
+            .unwrap_err();
+
+        assert_eq!(errors["t1"], vec!["Field is required".to_string()]);
+        assert_eq!(*handled.borrow(), None);
+    }
+
+    #[test]
+    fn fill_values_replays_submission() {
+        let handled = Rc::new(RefCell::new(None));
+        let saved = json!({"t1": "v1", "ch1": true});
+
+        let submitted = Session::new(app(handled))
+            .choose("action1")
+            .fill_values(&saved)
+            .submit()
+            .unwrap();
+
+        assert_eq!(submitted, saved);
+    }
+
+    #[test]
+    #[should_panic(expected = "Action \"missing\" is not defined")]
+    fn choose_panics_for_unknown_action() {
+        Session::new(app(Rc::new(RefCell::new(None)))).choose("missing");
+    }
+}
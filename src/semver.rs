@@ -0,0 +1,488 @@
+//! Parses [semantic versions] and version requirements, used by [fields::SemVer]
+//! and [fields::SemVerReq].
+//!
+//! Requirements follow Cargo's syntax: comma separated comparators made of an operator
+//! (`^` (default), `~`, `=`, `>`, `>=`, `<`, `<=`) and a version which may be partial
+//! (`1.2`) or contain wildcards (`1.*`).
+//!
+//! ```
+//! use fui::semver::{Version, VersionReq};
+//!
+//! let version = Version::parse("v1.2.3-rc.1+build.5").unwrap();
+//! assert_eq!(version.to_string(), "1.2.3-rc.1+build.5");
+//!
+//! let req = VersionReq::parse(">= 1.2, < 2").unwrap();
+//! assert_eq!(req.to_string(), ">=1.2, <2");
+//! assert!(req.matches(&Version::parse("1.9.0").unwrap()));
+//! assert!(!req.matches(&Version::parse("2.0.0").unwrap()));
+//! ```
+//!
+//! [semantic versions]: https://semver.org
+//! [fields::SemVer]: ../fields/struct.SemVer.html
+//! [fields::SemVerReq]: ../fields/struct.SemVerReq.html
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Version like `1.2.3-rc.1+build.5`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    /// Major version.
+    pub major: u64,
+    /// Minor version.
+    pub minor: u64,
+    /// Patch version.
+    pub patch: u64,
+    /// Dot separated pre-release identifiers (like `["rc", "1"]`).
+    pub pre: Vec<String>,
+    /// Dot separated build metadata (ignored in comparisons).
+    pub build: Vec<String>,
+}
+
+impl Version {
+    /// Parses `text` as a version, optionally prefixed with `v`.
+    pub fn parse(text: &str) -> Result<Version, String> {
+        let text = text.trim();
+        let core = text
+            .strip_prefix('v')
+            .or_else(|| text.strip_prefix('V'))
+            .unwrap_or(text);
+        let (core, build) = match core.find('+') {
+            Some(idx) => (&core[..idx], Some(&core[idx + 1..])),
+            None => (core, None),
+        };
+        let (core, pre) = match core.find('-') {
+            Some(idx) => (&core[..idx], Some(&core[idx + 1..])),
+            None => (core, None),
+        };
+        let numbers = core.split('.').collect::<Vec<&str>>();
+        if numbers.len() != 3 {
+            return Err(format!(
+                "Version must be like MAJOR.MINOR.PATCH, got: {}",
+                text
+            ));
+        }
+        Ok(Version {
+            major: parse_number(numbers[0])?,
+            minor: parse_number(numbers[1])?,
+            patch: parse_number(numbers[2])?,
+            pre: match pre {
+                Some(pre) => parse_identifiers(pre, true)?,
+                None => Vec::new(),
+            },
+            build: match build {
+                Some(build) => parse_identifiers(build, false)?,
+                None => Vec::new(),
+            },
+        })
+    }
+
+    /// Checks if it's a pre-release (like `1.0.0-alpha`).
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+/// Parses version's number which can't have leading zeros.
+fn parse_number(text: &str) -> Result<u64, String> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid version number: {:?}", text));
+    }
+    if text.len() > 1 && text.starts_with('0') {
+        return Err(format!("Version number has leading zero: {}", text));
+    }
+    text.parse()
+        .map_err(|_| format!("Version number is too big: {}", text))
+}
+
+/// Parses dot separated identifiers of pre-release (`numeric_strict`) or build metadata.
+fn parse_identifiers(text: &str, numeric_strict: bool) -> Result<Vec<String>, String> {
+    let mut identifiers = Vec::new();
+    for identifier in text.split('.') {
+        if identifier.is_empty()
+            || !identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(format!("Invalid identifier: {:?}", identifier));
+        }
+        let is_numeric = identifier.chars().all(|c| c.is_ascii_digit());
+        if numeric_strict && is_numeric && identifier.len() > 1 && identifier.starts_with('0') {
+            return Err(format!("Identifier has leading zero: {}", identifier));
+        }
+        identifiers.push(identifier.to_string());
+    }
+    Ok(identifiers)
+}
+
+/// Compares pre-release identifiers by semver precedence.
+fn cmp_pre(a: &[String], b: &[String]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        _ => {}
+    }
+    for (x, y) in a.iter().zip(b.iter()) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| cmp_pre(&self.pre, &other.pre))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Version::parse(text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Caret,
+    Tilde,
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Wildcard,
+}
+
+impl Op {
+    fn as_str(self) -> &'static str {
+        match self {
+            Op::Caret => "^",
+            Op::Tilde => "~",
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Wildcard => "",
+        }
+    }
+}
+
+/// Single comparator of [VersionReq] (like `>=1.2`).
+///
+/// [VersionReq]: struct.VersionReq.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<String>,
+}
+
+impl Comparator {
+    fn parse(text: &str) -> Result<Comparator, String> {
+        let text = text.trim();
+        let ops = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("^", Op::Caret),
+            ("~", Op::Tilde),
+        ];
+        let (mut op, rest) = ops
+            .iter()
+            .find(|&&(prefix, _)| text.starts_with(prefix))
+            .map(|&(prefix, op)| (op, text[prefix.len()..].trim_start()))
+            .unwrap_or((Op::Caret, text));
+        let (core, pre) = match rest.find('-') {
+            Some(idx) => (&rest[..idx], parse_identifiers(&rest[idx + 1..], true)?),
+            None => (rest, Vec::new()),
+        };
+        if core.is_empty() {
+            return Err(format!("Missing version in requirement: {:?}", text));
+        }
+        let parts = core.split('.').collect::<Vec<&str>>();
+        if parts.len() > 3 {
+            return Err(format!("Invalid version in requirement: {}", rest));
+        }
+        let mut numbers = [None, None, None];
+        let mut wildcard = false;
+        for (idx, part) in parts.iter().enumerate() {
+            if *part == "*" || *part == "x" || *part == "X" {
+                wildcard = true;
+            } else if wildcard {
+                return Err(format!("Number after wildcard in requirement: {}", rest));
+            } else {
+                numbers[idx] = Some(parse_number(part)?);
+            }
+        }
+        if wildcard {
+            if op != Op::Caret || text.starts_with('^') || !pre.is_empty() {
+                return Err(format!("Wildcard can't be used with operator: {}", text));
+            }
+            op = Op::Wildcard;
+        }
+        if !pre.is_empty() && numbers[2].is_none() {
+            return Err(format!("Pre-release requires full version: {}", rest));
+        }
+        Ok(Comparator {
+            op,
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre,
+        })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let major = match self.major {
+            Some(major) => major,
+            None => return true,
+        };
+        let v = (version.major, version.minor, version.patch);
+        let lower = (major, self.minor.unwrap_or(0), self.patch.unwrap_or(0));
+        let above = |v: (u64, u64, u64), bound: (u64, u64, u64)| {
+            v > bound || (v == bound && cmp_pre(&version.pre, &self.pre) != Ordering::Less)
+        };
+        let below = |v: (u64, u64, u64), bound: (u64, u64, u64)| {
+            v < bound || (v == bound && cmp_pre(&version.pre, &self.pre) == Ordering::Less)
+        };
+        // versions matching "=I.J" or "=I" which have equal precision
+        let same_prefix = version.major == major && self.minor.map_or(true, |m| m == version.minor);
+        match (self.op, self.minor, self.patch) {
+            (Op::Exact, _, Some(_)) => v == lower && version.pre == self.pre,
+            (Op::Exact, _, None) | (Op::Wildcard, _, _) => same_prefix,
+            (Op::Greater, _, Some(_)) => {
+                v > lower || (v == lower && cmp_pre(&version.pre, &self.pre) == Ordering::Greater)
+            }
+            (Op::Greater, _, None) => {
+                v.0 > major || (v.0 == major && self.minor.is_some_and(|m| v.1 > m))
+            }
+            (Op::GreaterEq, _, _) => above(v, lower),
+            (Op::Less, _, _) => below(v, lower),
+            (Op::LessEq, _, Some(_)) => {
+                !(v > lower
+                    || (v == lower && cmp_pre(&version.pre, &self.pre) == Ordering::Greater))
+            }
+            (Op::LessEq, _, None) => same_prefix || below(v, lower),
+            (Op::Tilde, Some(minor), Some(_)) => above(v, lower) && v < (major, minor + 1, 0),
+            (Op::Tilde, _, None) => same_prefix,
+            (Op::Caret, Some(minor), patch) => {
+                let upper = if major > 0 {
+                    (major + 1, 0, 0)
+                } else if minor > 0 || patch.is_none() {
+                    (0, minor + 1, 0)
+                } else {
+                    (0, 0, patch.unwrap() + 1)
+                };
+                above(v, lower) && v < upper
+            }
+            (_, None, _) => version.major == major,
+        }
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.op.as_str())?;
+        let parts = [self.major, self.minor, self.patch];
+        let shown = parts.iter().take_while(|p| p.is_some()).count();
+        let mut text = parts[..shown]
+            .iter()
+            .map(|p| p.unwrap().to_string())
+            .collect::<Vec<String>>();
+        if self.op == Op::Wildcard {
+            text.push("*".to_string());
+        }
+        write!(f, "{}", text.join("."))?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// Version requirement like `^1.2` or `>=1.2, <2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Parses `text` as comma separated comparators.
+    pub fn parse(text: &str) -> Result<VersionReq, String> {
+        if text.trim().is_empty() {
+            return Err("Version requirement is empty".to_string());
+        }
+        let comparators = text
+            .split(',')
+            .map(Comparator::parse)
+            .collect::<Result<Vec<Comparator>, String>>()?;
+        Ok(VersionReq { comparators })
+    }
+
+    /// Checks if `version` satisfies all comparators.
+    ///
+    /// Like in Cargo, pre-release versions match only when some comparator
+    /// mentions a pre-release of the same `MAJOR.MINOR.PATCH`.
+    pub fn matches(&self, version: &Version) -> bool {
+        if !self.comparators.iter().all(|c| c.matches(version)) {
+            return false;
+        }
+        !version.is_prerelease()
+            || self.comparators.iter().any(|c| {
+                !c.pre.is_empty()
+                    && (c.major, c.minor, c.patch)
+                        == (
+                            Some(version.major),
+                            Some(version.minor),
+                            Some(version.patch),
+                        )
+            })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let comparators = self
+            .comparators
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{}", comparators.join(", "))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        VersionReq::parse(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn matches(req: &str, version: &str) -> bool {
+        VersionReq::parse(req).unwrap().matches(&v(version))
+    }
+
+    #[test]
+    fn versions_are_parsed_and_ordered() {
+        assert_eq!(v(" 1.2.3 ").to_string(), "1.2.3");
+        assert_eq!(v("v0.1.0-alpha.1+001").to_string(), "0.1.0-alpha.1+001");
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("01.2.3").is_err());
+        assert!(Version::parse("1.2.3-01").is_err());
+        assert!(Version::parse("1.2.3-").is_err());
+        assert!(Version::parse("1.2.3-a..b").is_err());
+
+        let mut versions = [
+            v("1.0.0"),
+            v("1.0.0-rc.1"),
+            v("1.0.0-beta.11"),
+            v("1.0.0-beta.2"),
+            v("1.0.0-alpha.beta"),
+            v("1.0.0-alpha"),
+            v("1.0.0-alpha.1"),
+        ];
+        versions.sort();
+        let sorted = versions.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            vec![
+                "1.0.0-alpha",
+                "1.0.0-alpha.1",
+                "1.0.0-alpha.beta",
+                "1.0.0-beta.2",
+                "1.0.0-beta.11",
+                "1.0.0-rc.1",
+                "1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn requirements_are_normalized() {
+        let normalized = |text: &str| VersionReq::parse(text).unwrap().to_string();
+        assert_eq!(normalized("1.2"), "^1.2");
+        assert_eq!(normalized(">= 1.2 ,< 2"), ">=1.2, <2");
+        assert_eq!(normalized("1.*"), "1.*");
+        assert_eq!(normalized("*"), "*");
+        assert_eq!(normalized("~1.2.3-rc.1"), "~1.2.3-rc.1");
+        assert!(VersionReq::parse("").is_err());
+        assert!(VersionReq::parse(">=").is_err());
+        assert!(VersionReq::parse("^1.*").is_err());
+        assert!(VersionReq::parse("1.*.3").is_err());
+        assert!(VersionReq::parse("1.2.3.4").is_err());
+    }
+
+    #[test]
+    fn requirements_match_like_cargo() {
+        assert!(matches("^1.2.3", "1.9.0"));
+        assert!(!matches("^1.2.3", "2.0.0"));
+        assert!(!matches("^1.2.3", "1.2.2"));
+        assert!(matches("^0.2.3", "0.2.9"));
+        assert!(!matches("^0.2.3", "0.3.0"));
+        assert!(matches("^0.0.3", "0.0.3"));
+        assert!(!matches("^0.0.3", "0.0.4"));
+        assert!(matches("^0.0", "0.0.7"));
+        assert!(matches("^1", "1.7.0"));
+        assert!(matches("~1.2.3", "1.2.9"));
+        assert!(!matches("~1.2.3", "1.3.0"));
+        assert!(matches("~1", "1.9.9"));
+        assert!(matches("=1.2", "1.2.5"));
+        assert!(!matches("=1.2.3", "1.2.4"));
+        assert!(matches(">1.2", "1.3.0"));
+        assert!(!matches(">1.2", "1.2.9"));
+        assert!(matches("<=1.2", "1.2.9"));
+        assert!(!matches("<=1.2", "1.3.0"));
+        assert!(matches(">=1.2, <2", "1.9.0"));
+        assert!(matches("1.*", "1.4.0"));
+        assert!(!matches("1.2.*", "1.3.0"));
+        assert!(matches("*", "3.0.0"));
+        assert!(!matches("^1.2.3", "1.3.0-rc.1"));
+        assert!(matches(">=1.3.0-rc.1", "1.3.0-rc.2"));
+        assert!(!matches(">=1.3.0-rc.1", "1.3.0-alpha"));
+    }
+}
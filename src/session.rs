@@ -0,0 +1,116 @@
+//! Action & values of form left open when user quit, persisted for resuming them
+//! on the next start.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::value::Value;
+
+use form::FormSnapshot;
+
+/// Sessions older than this aren't resumed, user rather starts over then.
+pub(crate) const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Action (by name) with values of its form (by field labels).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LastSession {
+    pub(crate) action: String,
+    pub(crate) values: FormSnapshot,
+    // seconds since UNIX epoch
+    saved_at: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl LastSession {
+    /// Creates session of `action` with `values` left right now.
+    pub(crate) fn new(action: &str, values: FormSnapshot) -> Self {
+        LastSession {
+            action: action.to_string(),
+            values,
+            saved_at: now(),
+        }
+    }
+
+    /// Path where the last session is stored in app's `data_dir`.
+    pub(crate) fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("session.json")
+    }
+
+    /// Loads session from `path` & removes it, so it's resumed once only.
+    ///
+    /// Returns `None` when file is missing, broken or saved more than `max_age` ago.
+    pub(crate) fn take(path: &Path, max_age: Duration) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+        let _ = fs::remove_file(path);
+        let value: Value = serde_json::from_str(&text).ok()?;
+        let saved_at = value.get("saved_at")?.as_u64()?;
+        if now().saturating_sub(saved_at) > max_age.as_secs() {
+            return None;
+        }
+        let values = value
+            .get("values")?
+            .as_object()?
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|v| (k.to_owned(), v.to_owned())))
+            .collect();
+        Some(LastSession {
+            action: value.get("action")?.as_str()?.to_string(),
+            values,
+            saved_at,
+        })
+    }
+
+    /// Saves session to `path`, failures are ignored because it's not critical.
+    pub(crate) fn save(&self, path: &Path) {
+        let value = json!({
+            "action": self.action,
+            "values": self.values,
+            "saved_at": self.saved_at,
+        });
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(path, value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("fui-session-{}", ::std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn session_is_resumed_once() {
+        let path = path("once.json");
+        let mut values = FormSnapshot::new();
+        values.insert("name".to_string(), "x".to_string());
+        let session = LastSession::new("greet", values);
+        session.save(&path);
+
+        assert_eq!(LastSession::take(&path, MAX_AGE), Some(session));
+        assert_eq!(LastSession::take(&path, MAX_AGE), None);
+    }
+
+    #[test]
+    fn stale_session_is_skipped() {
+        let path = path("stale.json");
+        let mut session = LastSession::new("greet", FormSnapshot::new());
+        session.saved_at -= MAX_AGE.as_secs() + 1;
+        session.save(&path);
+
+        assert_eq!(LastSession::take(&path, MAX_AGE), None);
+        assert!(!path.exists());
+    }
+}
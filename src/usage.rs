@@ -0,0 +1,146 @@
+//! Favorite & recently used actions, persisted across runs.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::value::Value;
+
+/// How many recently used actions are remembered.
+const RECENT_LIMIT: usize = 50;
+
+/// Favorite & recently used actions (by name).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ActionUsage {
+    favorites: Vec<String>,
+    // the most recent first
+    recent: Vec<String>,
+}
+
+/// Extracts action name from picker's item formatted as "name: desc"
+/// or "name (aliases): desc".
+pub(crate) fn action_name(item: &str) -> &str {
+    let name = item.split(": ").next().unwrap_or(item);
+    name.split(" (").next().unwrap_or(name)
+}
+
+impl ActionUsage {
+    /// Path where usage of actions is stored in app's `data_dir`.
+    pub(crate) fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("actions.json")
+    }
+
+    /// Loads usage from `path`, returns empty one when file is missing or broken.
+    pub(crate) fn load(path: &Path) -> Self {
+        let value: Value = fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or(Value::Null);
+        let names = |key: &str| -> Vec<String> {
+            value
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|i| i.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        ActionUsage {
+            favorites: names("favorites"),
+            recent: names("recent"),
+        }
+    }
+
+    /// Saves usage to `path`, failures are ignored because it's not critical.
+    pub(crate) fn save(&self, path: &Path) {
+        let value = json!({"favorites": self.favorites, "recent": self.recent});
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(path, value.to_string());
+    }
+
+    /// Marks action used right now.
+    pub(crate) fn record_use(&mut self, name: &str) {
+        self.recent.retain(|n| n != name);
+        self.recent.insert(0, name.to_string());
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Marks action as favorite or removes the mark.
+    pub(crate) fn toggle_favorite(&mut self, name: &str) {
+        if self.favorites.iter().any(|n| n == name) {
+            self.favorites.retain(|n| n != name);
+        } else {
+            self.favorites.push(name.to_string());
+        }
+    }
+
+    /// Orders picker's `items`: favorites first, then recently used, then the rest.
+    pub(crate) fn order(&self, items: &[String]) -> Vec<String> {
+        let rank = |item: &String| {
+            let name = action_name(item);
+            let favorite = self.favorites.iter().position(|n| n == name);
+            let recent = self.recent.iter().position(|n| n == name);
+            (
+                favorite.is_none(),
+                favorite.unwrap_or(0),
+                recent.is_none(),
+                recent.unwrap_or(0),
+            )
+        };
+        let mut ordered = items.to_vec();
+        // stable sort keeps the original order of unused actions
+        ordered.sort_by_key(rank);
+        ordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<String> {
+        vec!["a: x", "b (bb): x", "c: x", "d: x"]
+            .into_iter()
+            .map(|i| i.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn favorites_go_first_then_recent() {
+        let mut usage = ActionUsage::default();
+        usage.record_use("b");
+        usage.record_use("c");
+        usage.toggle_favorite("d");
+
+        assert_eq!(
+            usage.order(&items()),
+            vec!["d: x", "c: x", "b (bb): x", "a: x"]
+        );
+    }
+
+    #[test]
+    fn toggling_favorite_twice_unmarks_it() {
+        let mut usage = ActionUsage::default();
+        usage.toggle_favorite("c");
+        usage.toggle_favorite("c");
+
+        assert_eq!(usage, ActionUsage::default());
+    }
+
+    #[test]
+    fn usage_survives_save_and_load() {
+        let path = ::std::env::temp_dir()
+            .join("fui-usage-test")
+            .join("actions.json");
+        let mut usage = ActionUsage::default();
+        usage.record_use("a");
+        usage.toggle_favorite("b");
+
+        usage.save(&path);
+
+        assert_eq!(ActionUsage::load(&path), usage);
+    }
+}
@@ -1,5 +1,9 @@
 //! Various kinds of helpers.
 use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::map::Map;
+use serde_json::value::Value;
 
 /// Returns current working dir as String.
 pub fn cwd() -> String {
@@ -15,3 +19,328 @@ pub fn home_dir() -> String {
         .map(|p| p.into_os_string().into_string().unwrap())
         .unwrap()
 }
+
+/// Expands leading `~` to home-dir and `$VAR` or `${VAR}` to environment variables in `path`.
+///
+/// Unknown variables, `$` not followed by variable name and `~` (when home-dir
+/// isn't known) are left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use fui::utils::{expand_path, home_dir};
+/// use std::env;
+///
+/// env::set_var("FUI_DOC_DIR", "docs");
+/// assert_eq!(expand_path("~/x"), format!("{}/x", home_dir()));
+/// assert_eq!(expand_path("$FUI_DOC_DIR/a"), "docs/a");
+/// assert_eq!(expand_path("${FUI_DOC_DIR}-old/a"), "docs-old/a");
+/// assert_eq!(expand_path("$FUI_MISSING_VAR/a"), "$FUI_MISSING_VAR/a");
+/// assert_eq!(expand_path("price$/${FUI DOC DIR}"), "price$/${FUI DOC DIR}");
+/// ```
+pub fn expand_path(path: &str) -> String {
+    let home = dirs::home_dir().and_then(|p| p.into_os_string().into_string().ok());
+    let path = match home {
+        Some(ref home) if path == "~" || path.starts_with("~/") => path.replacen("~", home, 1),
+        _ => path.to_string(),
+    };
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path.as_str();
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, raw_len) = if after.starts_with('{') {
+            match after.find('}') {
+                Some(end) => (&after[1..end], end + 1),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+        match env::var(name) {
+            Ok(ref value) if is_var_name(name) => {
+                expanded.push_str(value);
+                rest = &after[raw_len..];
+            }
+            _ => {
+                // literal `$`, the rest is scanned again
+                expanded.push('$');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Checks that `name` is a valid name of environment variable (like `HOME` or `_DIR2`).
+fn is_var_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Converts days since 1970-01-01 to (year, month, day) of proleptic Gregorian calendar.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Converts (year, month, day) of proleptic Gregorian calendar to days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Formats `time` as RFC 3339 timestamp in UTC, like `2021-01-31T12:00:00Z`.
+pub(crate) fn format_rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    format_rfc3339_secs(secs)
+}
+
+/// Formats `secs` since 1970-01-01 as RFC 3339 timestamp in UTC.
+pub(crate) fn format_rfc3339_secs(secs: i64) -> String {
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let secs_of_day = secs.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Separates names of nested objects in paths of values, like `network.proxy.host`.
+pub const PATH_SEP: char = '.';
+
+/// Escapes [PATH_SEP] (and `\`) in `key` with `\`, so it's a single key of a path.
+///
+/// [PATH_SEP]: constant.PATH_SEP.html
+///
+/// # Examples
+///
+/// ```
+/// use fui::utils::{escape_key, path_keys};
+///
+/// let path = format!("servers.{}", escape_key("example.com"));
+/// assert_eq!(path, "servers.example\\.com");
+/// assert_eq!(path_keys(&path), vec!["servers", "example.com"]);
+/// ```
+pub fn escape_key(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+    for c in key.chars() {
+        if c == PATH_SEP || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Splits dotted `path` into keys, see [escape_key].
+///
+/// [escape_key]: fn.escape_key.html
+pub fn path_keys(path: &str) -> Vec<String> {
+    let mut keys = vec![String::new()];
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => keys.last_mut().unwrap().extend(chars.next()),
+            PATH_SEP => keys.push(String::new()),
+            c => keys.last_mut().unwrap().push(c),
+        }
+    }
+    keys
+}
+
+/// Gets value at dotted `path` (see [unflatten]) of `value`.
+///
+/// [unflatten]: fn.unflatten.html
+pub fn get_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path_keys(path)
+        .iter()
+        .try_fold(value, |value, key| value.as_object()?.get(key))
+}
+
+/// Gets mutable value at dotted `path` (see [unflatten]) of `value`.
+///
+/// [unflatten]: fn.unflatten.html
+pub fn get_path_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path_keys(path)
+        .iter()
+        .try_fold(value, |value, key| value.as_object_mut()?.get_mut(key))
+}
+
+/// Nests values of object `value` with dotted keys (like `network.proxy.host`)
+/// into sub-objects, escaped dots (see [escape_key]) stay in keys.
+///
+/// # Errors
+///
+/// Returns key of a value which is also a prefix of other keys (like `a` for `a` & `a.b`),
+/// as it can't be kept together with the nested values.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// # fn main() {
+/// use fui::utils::{flatten, unflatten};
+///
+/// let flat = json!({"name": "app", "network.proxy.host": "h", "network.proxy.port": 80});
+/// let nested = json!({"name": "app", "network": {"proxy": {"host": "h", "port": 80}}});
+///
+/// assert_eq!(unflatten(&flat), Ok(nested.clone()));
+/// assert_eq!(flatten(&nested), flat);
+/// assert_eq!(unflatten(&json!({"a": 1, "a.b": 2})), Err("a".to_string()));
+/// # }
+/// ```
+///
+/// [escape_key]: fn.escape_key.html
+pub fn unflatten(value: &Value) -> Result<Value, String> {
+    let map = match value {
+        Value::Object(map) => map,
+        _ => return Ok(value.clone()),
+    };
+    let mut paths: Vec<Vec<String>> = Vec::with_capacity(map.len());
+    for key in map.keys() {
+        let path = path_keys(key);
+        let conflict = paths
+            .iter()
+            .find(|other| other.starts_with(&path) || path.starts_with(other));
+        if let Some(other) = conflict {
+            let value_path = if other.len() < path.len() {
+                other
+            } else {
+                &path
+            };
+            let keys = value_path.iter().map(|k| escape_key(k)).collect::<Vec<_>>();
+            return Err(keys.join(&PATH_SEP.to_string()));
+        }
+        paths.push(path);
+    }
+    let mut nested = Map::new();
+    for (mut parts, value) in paths.into_iter().zip(map.values()) {
+        let last = parts.pop().unwrap_or_default();
+        let mut target = &mut nested;
+        for part in parts {
+            target = target
+                .entry(part)
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .unwrap();
+        }
+        target.insert(last, value.clone());
+    }
+    Ok(Value::Object(nested))
+}
+
+/// Reverses [unflatten], joining keys of nested (non-empty) objects with dots.
+///
+/// [unflatten]: fn.unflatten.html
+pub fn flatten(value: &Value) -> Value {
+    fn flatten_into(prefix: &str, map: &Map<String, Value>, flat: &mut Map<String, Value>) {
+        for (key, value) in map.iter() {
+            let path = if prefix.is_empty() {
+                escape_key(key)
+            } else {
+                format!("{}{}{}", prefix, PATH_SEP, escape_key(key))
+            };
+            match value {
+                Value::Object(nested) if !nested.is_empty() => flatten_into(&path, nested, flat),
+                _ => {
+                    flat.insert(path, value.clone());
+                }
+            }
+        }
+    }
+    match value {
+        Value::Object(map) => {
+            let mut flat = Map::new();
+            flatten_into("", map, &mut flat);
+            Value::Object(flat)
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn values_are_found_by_path() {
+        let mut value = json!({"a": {"b": {"c": 1}}, "d": 2});
+
+        assert_eq!(get_path(&value, "a.b.c"), Some(&json!(1)));
+        assert_eq!(get_path(&value, "d"), Some(&json!(2)));
+        assert_eq!(get_path(&value, "d.e"), None);
+        *get_path_mut(&mut value, "a.b").unwrap() = json!(3);
+        assert_eq!(value, json!({"a": {"b": 3}, "d": 2}));
+    }
+
+    #[test]
+    fn unflatten_rejects_values_nesting_others() {
+        let flat = json!({"a.b": 2, "a.c.d": 3, "a\\.c": 4});
+        assert_eq!(
+            unflatten(&flat),
+            Ok(json!({"a": {"b": 2, "c": {"d": 3}}, "a.c": 4}))
+        );
+
+        assert_eq!(unflatten(&json!({"a": 1, "a.b": 2})), Err("a".to_string()));
+        assert_eq!(
+            unflatten(&json!({"a.b.c": 1, "a.b": 2})),
+            Err("a.b".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_path_keeps_literal_dollars() {
+        env::set_var("FUI_TEST_NAME", "fui");
+
+        assert_eq!(expand_path("$"), "$");
+        assert_eq!(expand_path("a$$FUI_TEST_NAME"), "a$fui");
+        assert_eq!(expand_path("${FUI_TEST_NAME"), "${FUI_TEST_NAME");
+        assert_eq!(expand_path("$1/${}/$-"), "$1/${}/$-");
+        assert_eq!(expand_path("~fui/$FUI_TEST_NAMEé"), "~fui/fuié");
+    }
+
+    #[test]
+    fn days_from_civil_reverts_civil_from_days() {
+        for days in [-719_468, -1, 0, 11_016, 19_782] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn format_rfc3339_works() {
+        assert_eq!(format_rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_400 + 3_723);
+        assert_eq!(format_rfc3339(leap_day), "2000-02-29T01:02:03Z");
+    }
+}
@@ -1,9 +1,15 @@
 //! Provides data validators used by `Fields`.
+use feeders::Feeder;
 use regex::Regex;
 use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
 use std::fmt::Debug;
 use std::ops::Deref;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use utils::expand_path;
 
 /// Adds behaviour of validation.
 pub trait Validator: Debug {
@@ -11,6 +17,16 @@ pub trait Validator: Debug {
     fn validate(&self, data: &str) -> Option<String>;
     /// Allows downcasting `self` to a `Any`.
     fn as_any(&self) -> &dyn Any;
+    /// Returns values allowed by validator if it limits data to a fixed set.
+    fn allowed_values(&self) -> Option<Vec<String>> {
+        None
+    }
+    /// Describes rule enforced by validator in human terms (like `"required"`).
+    ///
+    /// Defaults to validator's debug representation.
+    fn describe(&self) -> String {
+        format!("{:?}", self)
+    }
 }
 
 /// Ensures data is included.
@@ -23,6 +39,7 @@ pub trait Validator: Debug {
 ///
 /// assert_eq!(Required.validate("some-data"), None);
 /// assert_eq!(Required.validate(""), Some("Field is required".to_string()));
+/// assert_eq!(Required.describe(), "required");
 /// ```
 #[derive(Clone, Debug)]
 pub struct Required;
@@ -39,6 +56,10 @@ impl Validator for Required {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> String {
+        "required".to_string()
+    }
 }
 
 /// Ensures path is free.
@@ -60,9 +81,17 @@ impl Validator for Required {
 #[derive(Clone, Debug)]
 pub struct PathFree;
 
+impl PathFree {
+    /// Resolves relative paths against `base` before validation.
+    pub fn relative_to<P: Into<PathBuf>>(self, base: P) -> RelativeTo<Self> {
+        RelativeTo::new(base, self)
+    }
+}
+
 impl Validator for PathFree {
     fn validate(&self, data: &str) -> Option<String> {
-        let path = Path::new(data);
+        let path = expand_path(data);
+        let path = Path::new(&path);
         if path.exists() {
             Some("Path is already used".to_string())
         } else {
@@ -73,6 +102,10 @@ impl Validator for PathFree {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> String {
+        "path must not exist".to_string()
+    }
 }
 
 /// Ensures data is dir path which exists.
@@ -94,9 +127,17 @@ impl Validator for PathFree {
 #[derive(Clone, Debug)]
 pub struct DirExists;
 
+impl DirExists {
+    /// Resolves relative paths against `base` before validation.
+    pub fn relative_to<P: Into<PathBuf>>(self, base: P) -> RelativeTo<Self> {
+        RelativeTo::new(base, self)
+    }
+}
+
 impl Validator for DirExists {
     fn validate(&self, data: &str) -> Option<String> {
-        let path = Path::new(data);
+        let path = expand_path(data);
+        let path = Path::new(&path);
         if path.exists() {
             if path.metadata().unwrap().is_dir() {
                 None
@@ -111,6 +152,10 @@ impl Validator for DirExists {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> String {
+        "dir must exist".to_string()
+    }
 }
 
 /// Ensures data is file path which exists.
@@ -132,9 +177,17 @@ impl Validator for DirExists {
 #[derive(Clone, Debug)]
 pub struct FileExists;
 
+impl FileExists {
+    /// Resolves relative paths against `base` before validation.
+    pub fn relative_to<P: Into<PathBuf>>(self, base: P) -> RelativeTo<Self> {
+        RelativeTo::new(base, self)
+    }
+}
+
 impl Validator for FileExists {
     fn validate(&self, data: &str) -> Option<String> {
-        let path = Path::new(data);
+        let path = expand_path(data);
+        let path = Path::new(&path);
         if path.exists() {
             if path.metadata().unwrap().is_file() {
                 None
@@ -149,6 +202,125 @@ impl Validator for FileExists {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> String {
+        "file must exist".to_string()
+    }
+}
+
+/// Ensures data is path of an executable file.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+///
+/// use fui::validators::IsExecutable;
+/// use fui::validators::Validator;
+///
+/// # fn main() {
+/// # #[cfg(unix)]
+/// assert_eq!(IsExecutable.validate("/bin/sh"), None);
+/// # #[cfg(unix)]
+/// assert_eq!(IsExecutable.validate("./LICENSE"), Some("File isn't executable".to_string()));
+/// assert_eq!(IsExecutable.validate("./missing-file"), Some("File doesn't exist".to_string()));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct IsExecutable;
+
+impl IsExecutable {
+    /// Resolves relative paths against `base` before validation.
+    pub fn relative_to<P: Into<PathBuf>>(self, base: P) -> RelativeTo<Self> {
+        RelativeTo::new(base, self)
+    }
+}
+
+impl Validator for IsExecutable {
+    fn validate(&self, data: &str) -> Option<String> {
+        if let Some(e) = FileExists.validate(data) {
+            return Some(e);
+        }
+        if is_executable(Path::new(&expand_path(data))) {
+            None
+        } else {
+            Some("File isn't executable".to_string())
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> String {
+        "must be an executable file".to_string()
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Validates paths relative to `base` dir instead of the current working dir.
+///
+/// Created by `relative_to` method of path validators, like [FileExists::relative_to].
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{DirExists, FileExists};
+/// use fui::validators::Validator;
+///
+/// assert_eq!(FileExists.relative_to("./src").validate("lib.rs"), None);
+/// assert_eq!(DirExists.relative_to("./src").validate("fields"), None);
+/// assert_eq!(FileExists.relative_to("./src").validate("/etc/missing"), Some("File doesn't exist".to_string()));
+/// ```
+///
+/// [FileExists::relative_to]: struct.FileExists.html#method.relative_to
+#[derive(Clone, Debug)]
+pub struct RelativeTo<V> {
+    base: PathBuf,
+    validator: V,
+}
+
+impl<V: Validator> RelativeTo<V> {
+    /// Creates a new `RelativeTo` which resolves paths against `base` and validates them
+    /// with `validator`.
+    pub fn new<P: Into<PathBuf>>(base: P, validator: V) -> Self {
+        RelativeTo {
+            base: base.into(),
+            validator,
+        }
+    }
+}
+
+impl<V: Validator + 'static> Validator for RelativeTo<V> {
+    fn validate(&self, data: &str) -> Option<String> {
+        let base = expand_path(&self.base.to_string_lossy());
+        let path = Path::new(&base).join(expand_path(data));
+        self.validator.validate(&path.to_string_lossy())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} (relative to {})",
+            self.validator.describe(),
+            self.base.display()
+        )
+    }
 }
 
 /// Ensures value is one of provided options.
@@ -162,6 +334,7 @@ impl Validator for FileExists {
 /// let v = OneOf(vec!["a", "b"]);
 /// assert_eq!(v.validate("a"), None);
 /// assert_eq!(v.validate("xxx"), Some("Value must be one of options".to_string()));
+/// assert_eq!(v.describe(), "must be one of: a, b");
 /// ```
 #[derive(Clone, Debug)]
 pub struct OneOf<T>(pub T);
@@ -181,6 +354,70 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn allowed_values(&self) -> Option<Vec<String>> {
+        Some(self.0.iter().map(|x| (**x).to_string()).collect())
+    }
+
+    fn describe(&self) -> String {
+        let options = self.0.iter().map(|x| &**x).collect::<Vec<&str>>();
+        format!("must be one of: {}", options.join(", "))
+    }
+}
+
+/// Ensures value is one of items provided by a [Feeder].
+///
+/// Unlike [OneOf] it uses the same (possibly dynamic) data source as suggestions
+/// of [Autocomplete] or [Multiselect], so it never gets stale.
+///
+/// # Examples
+///
+/// ```
+/// use fui::feeders::DirItems;
+/// use fui::validators::InFeeder;
+/// use fui::validators::Validator;
+///
+/// let v = InFeeder::new(DirItems::dirs());
+/// assert_eq!(v.validate("src"), None);
+/// assert_eq!(v.validate("Cargo.toml"), Some("Value must be one of options".to_string()));
+/// ```
+///
+/// [Feeder]: ../feeders/trait.Feeder.html
+/// [OneOf]: struct.OneOf.html
+/// [Autocomplete]: ../fields/struct.Autocomplete.html
+/// [Multiselect]: ../fields/struct.Multiselect.html
+#[derive(Clone)]
+pub struct InFeeder(Rc<dyn Feeder>);
+
+impl InFeeder {
+    /// Creates a new `InFeeder` checking values against `feeder`.
+    pub fn new<F: Feeder>(feeder: F) -> Self {
+        InFeeder(Rc::new(feeder))
+    }
+}
+
+impl Debug for InFeeder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "InFeeder")
+    }
+}
+
+impl Validator for InFeeder {
+    fn validate(&self, data: &str) -> Option<String> {
+        if self.0.contains(data) {
+            None
+        } else {
+            Some("Value must be one of options".to_string())
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> String {
+        "must be one of suggested values".to_string()
+    }
 }
 
 impl Validator for Regex {
@@ -198,4 +435,155 @@ impl Validator for Regex {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> String {
+        format!("must match regular expression: {}", self.as_str())
+    }
+}
+
+/// Ensures value is a number from `min` to `max` (inclusive), empty value is valid.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::InRange;
+/// use fui::validators::Validator;
+///
+/// let v = InRange::new(1.0, 10.0);
+/// assert_eq!(v.validate("10"), None);
+/// assert_eq!(v.validate(""), None);
+/// assert_eq!(v.validate("0.5"), Some("Value must be between 1 and 10".to_string()));
+/// assert_eq!(v.validate("ten"), Some("Value must be a number".to_string()));
+/// assert_eq!(v.describe(), "must be between 1 and 10");
+/// ```
+#[derive(Clone, Debug)]
+pub struct InRange {
+    min: f64,
+    max: f64,
+}
+
+impl InRange {
+    /// Creates a new `InRange` accepting numbers from `min` to `max`.
+    pub fn new(min: f64, max: f64) -> Self {
+        InRange { min, max }
+    }
+}
+
+impl Validator for InRange {
+    fn validate(&self, data: &str) -> Option<String> {
+        if data.trim().is_empty() {
+            return None;
+        }
+        match data.trim().parse::<f64>() {
+            Ok(n) if n >= self.min && n <= self.max => None,
+            Ok(_) => Some(format!("Value {}", self.describe())),
+            Err(_) => Some("Value must be a number".to_string()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> String {
+        format!("must be between {} and {}", self.min, self.max)
+    }
+}
+
+/// Builds validator from arguments of its spec (text after `:`).
+pub type ValidatorConstructor = Rc<dyn Fn(&str) -> Result<Rc<dyn Validator>, String>>;
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<String, ValidatorConstructor>> =
+        RefCell::new(builtin_constructors());
+}
+
+fn builtin_constructors() -> HashMap<String, ValidatorConstructor> {
+    fn constant<V: Validator + Clone + 'static>(validator: V) -> ValidatorConstructor {
+        Rc::new(move |_| Ok(Rc::new(validator.clone()) as Rc<dyn Validator>))
+    }
+    let mut constructors = HashMap::new();
+    constructors.insert("required".to_string(), constant(Required));
+    constructors.insert("path-free".to_string(), constant(PathFree));
+    constructors.insert("dir-exists".to_string(), constant(DirExists));
+    constructors.insert("file-exists".to_string(), constant(FileExists));
+    constructors.insert("is-executable".to_string(), constant(IsExecutable));
+    let regex: ValidatorConstructor = Rc::new(|args| {
+        Regex::new(args)
+            .map(|r| Rc::new(r) as Rc<dyn Validator>)
+            .map_err(|e| format!("Invalid regular expression {:?}: {}", args, e))
+    });
+    constructors.insert("regex".to_string(), regex);
+    let range: ValidatorConstructor = Rc::new(|args| {
+        let bounds = args
+            .split_once("..")
+            .and_then(|(min, max)| Some((min.trim().parse().ok()?, max.trim().parse().ok()?)));
+        match bounds {
+            Some((min, max)) => Ok(Rc::new(InRange::new(min, max)) as Rc<dyn Validator>),
+            None => Err(format!("Invalid range {:?}, expected like 1..10", args)),
+        }
+    });
+    constructors.insert("range".to_string(), range);
+    let one_of: ValidatorConstructor = Rc::new(|args| {
+        let options = args.split(',').map(|o| o.to_string()).collect();
+        Ok(Rc::new(OneOf::<Vec<String>>(options)) as Rc<dyn Validator>)
+    });
+    constructors.insert("one-of".to_string(), one_of);
+    constructors
+}
+
+/// Makes validators built by `constructor` available under `name` for [from_spec]
+/// in the current thread, replacing validator registered with the same name.
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+/// use fui::validators::{self, Required, Validator};
+///
+/// validators::register("not-blank", |_| Ok(Rc::new(Required)));
+/// let v = validators::from_spec("not-blank").unwrap();
+/// assert!(v.validate("").is_some());
+/// ```
+///
+/// [from_spec]: fn.from_spec.html
+pub fn register<S, F>(name: S, constructor: F)
+where
+    S: Into<String>,
+    F: Fn(&str) -> Result<Rc<dyn Validator>, String> + 'static,
+{
+    REGISTRY.with(|r| r.borrow_mut().insert(name.into(), Rc::new(constructor)));
+}
+
+/// Builds validator described by `spec`, like `"required"`, `"regex:^[a-z]+$"`
+/// or `"range:1..10"`, so validators can be declared without Rust code.
+///
+/// Spec is a name of registered validator (see [register]) optionally followed by
+/// `:` and its arguments. Built-in ones are: `required`, `path-free`, `dir-exists`,
+/// `file-exists`, `is-executable`, `regex:<expression>`, `range:<min>..<max>`
+/// and `one-of:<option>,<option>`.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{self, Validator};
+///
+/// let v = validators::from_spec("range:1..10").unwrap();
+/// assert_eq!(v.validate("5"), None);
+/// assert!(v.validate("11").is_some());
+/// assert!(validators::from_spec("regex:^[a-z]+$").unwrap().validate("A").is_some());
+/// assert_eq!(
+///     validators::from_spec("unknown").unwrap_err(),
+///     "Unknown validator \"unknown\""
+/// );
+/// ```
+///
+/// [register]: fn.register.html
+pub fn from_spec(spec: &str) -> Result<Rc<dyn Validator>, String> {
+    let (name, args) = spec.split_once(':').unwrap_or((spec, ""));
+    let constructor = REGISTRY.with(|r| r.borrow().get(name.trim()).cloned());
+    match constructor {
+        Some(constructor) => constructor(args),
+        None => Err(format!("Unknown validator {:?}", name.trim())),
+    }
 }
@@ -1,32 +1,161 @@
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use cursive::event::{Callback, Event, EventResult, Key};
+use cursive::theme::{ColorStyle, Effect};
 use cursive::traits::Resizable;
 use cursive::traits::View;
+use cursive::utils::markup::StyledString;
 use cursive::view::SizeConstraint;
 use cursive::view::ViewWrapper;
 use cursive::views::ResizedView;
 use cursive::views::{EditView, LinearLayout, SelectView};
 use cursive::Cursive;
+use cursive::Printer;
+use cursive::Vec2;
 use cursive::With;
+use unicode_width::UnicodeWidthStr;
 
-use super::is_value_from_select;
-use feeders::Feeder;
+#[cfg(feature = "watch")]
+use super::dir_watch::DirWatch;
+use super::{is_value_from_select, ItemRenderer, Redraw};
+use feeders::{CancelToken, Feeder, Suggestion};
+use metrics;
+use ranking;
+use ranking::{Ranked, Ranker};
 
-// TODO: better performance while typing
+/// Max number of suggestions cycled with Tab.
+const CYCLE_LIMIT: usize = 100;
+/// Max number of suggestions searched by type-ahead.
+const TYPE_AHEAD_LIMIT: usize = 1000;
+/// Pause after which typing in suggestions starts a new type-ahead search.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+/// Queries of feeder in background don't start more often than this, so fast typing
+/// doesn't start a query for every key.
+const QUERY_THROTTLE: Duration = Duration::from_millis(150);
 
 pub type OnSubmit = Option<Rc<dyn Fn(&mut Cursive, Rc<String>)>>;
+type SelectionKey = (Event, Rc<dyn Fn(&str)>);
+type Row = (StyledString, String);
+/// Query of feeder: text, offset & count of suggestions.
+type Query = (Rc<String>, usize, usize);
+/// Found suggestions & how long the query took, filled by thread running the query.
+type QueryResult = Arc<Mutex<Option<(Vec<Suggestion>, Duration)>>>;
+
+/// Feeder queried in a thread while text is typed, see [Autocomplete::background].
+///
+/// [Autocomplete::background]: struct.Autocomplete.html#method.background
+struct Background {
+    feeder: Arc<dyn Feeder + Send + Sync>,
+    // the latest query, its token & result
+    running: Option<(Query, CancelToken, QueryResult)>,
+    // when the latest query starts (or started)
+    starts_at: Option<Instant>,
+}
+
+impl Background {
+    /// Cancels the latest query, its result won't be listed.
+    fn cancel(&mut self) {
+        if let Some((_, token, _)) = self.running.take() {
+            token.cancel();
+        }
+    }
+
+    /// Starts `query` (or its `candidates`), superseding the latest one. Queries start
+    /// at most once per `QUERY_THROTTLE`, so it waits for the slot of the previous
+    /// query when that didn't start yet.
+    fn start(&mut self, query: Query, candidates: bool, redraw: Redraw) {
+        self.cancel();
+        let now = Instant::now();
+        let starts_at = match self.starts_at {
+            Some(at) if at > now => at,
+            Some(at) => now.max(at + QUERY_THROTTLE),
+            None => now,
+        };
+        self.starts_at = Some(starts_at);
+        let token = CancelToken::new();
+        let result: QueryResult = Arc::new(Mutex::new(None));
+        let feeder = Arc::clone(&self.feeder);
+        let (text, offset, count) = (query.0.to_string(), query.1, query.2);
+        let (job_token, job_result) = (token.clone(), Arc::clone(&result));
+        thread::spawn(move || {
+            thread::sleep(starts_at.saturating_duration_since(Instant::now()));
+            if job_token.is_cancelled() {
+                return;
+            }
+            let started = Instant::now();
+            let data = if candidates {
+                let found = feeder.candidates(&text);
+                found.into_iter().map(Suggestion::from).collect()
+            } else {
+                feeder.query_cancellable(&text, offset, count, &job_token)
+            };
+            if !job_token.is_cancelled() {
+                *job_result.lock().unwrap() = Some((data, started.elapsed()));
+                redraw.request();
+            }
+        });
+        self.running = Some((query, token, result));
+    }
+
+    /// Takes result of the latest query once it has finished.
+    fn take_finished(&mut self) -> Option<(Query, Vec<Suggestion>, Duration)> {
+        let (data, duration) = self.running.as_ref()?.2.lock().unwrap().take()?;
+        let (query, _, _) = self.running.take()?;
+        Some((query, data, duration))
+    }
+}
+
+impl Drop for Background {
+    fn drop(&mut self) {
+        // e.g. walking dirs stops when form is closed
+        self.cancel();
+    }
+}
 
 /// Single selection view with suggestions.
+///
+/// When the focused view is close to the bottom of the screen, suggestions are shown
+/// above the input (over views above it), so they aren't clipped.
 pub struct Autocomplete {
     view: ResizedView<LinearLayout>,
 
     feeder: Rc<dyn Feeder>,
+    // feeder passed to `new`, `feeder` wraps it when suggestions are ranked
+    unranked_feeder: Rc<dyn Feeder>,
+    background: Option<Background>,
     shown_count: u8,
+    // limit of `shown_count` set by layout of form
+    max_shown_count: Option<u8>,
     submit_anything: bool,
     suggestion_offset: usize,
     // User typed text handled manually (EditView content is changing by selection)
     typed_value: Rc<String>,
+    // Query (text, offset, count) whose results are currently listed
+    listed_query: Option<(Rc<String>, usize, usize)>,
+    query_duration: Option<Duration>,
+    masked: bool,
+    inline_completion: bool,
+    // EditView doesn't expose its cursor, see `probe_cursor_at_end`
+    cursor_at_end: bool,
+    tab_cycling: bool,
+    // Suggestions cycled with Tab and index of the one put in edit view
+    cycle: Option<(Vec<String>, usize)>,
+    selection_keys: Vec<SelectionKey>,
+    ranker: Option<Rc<dyn Ranker>>,
+    item_renderer: Option<ItemRenderer>,
+    // Text typed while suggestions are focused and when it was last typed
+    type_ahead: String,
+    type_ahead_at: Option<Instant>,
+    min_chars: usize,
+    show_on_empty: bool,
+    // Listing is refreshed lazily (on layout or event), so builders don't query the feeder
+    listing_stale: bool,
+    #[cfg(feature = "watch")]
+    dir_watch: Option<DirWatch>,
+    redraw: Redraw,
 
     on_submit: OnSubmit,
 }
@@ -36,27 +165,75 @@ impl Autocomplete {
     pub fn new<T: Feeder>(feeder: T) -> Autocomplete {
         let shown_count = 5;
 
-        let select =
-            SelectView::<String>::new().with_all_str(feeder.query("", 0, shown_count).into_iter());
+        let select = SelectView::<String>::new();
 
         let layout = LinearLayout::vertical()
             .child(EditView::new())
             .child(select)
             .fixed_height(shown_count + 1);
+        let feeder: Rc<dyn Feeder> = Rc::new(feeder);
 
-        let ac = Autocomplete {
+        Autocomplete {
             view: layout,
 
-            feeder: Rc::new(feeder),
+            feeder: Rc::clone(&feeder),
+            unranked_feeder: feeder,
+            background: None,
             shown_count: shown_count as u8,
+            max_shown_count: None,
             submit_anything: false,
             suggestion_offset: 0usize,
             typed_value: Rc::new("".to_string()),
+            listed_query: None,
+            query_duration: None,
+            masked: false,
+            inline_completion: false,
+            cursor_at_end: true,
+            tab_cycling: false,
+            cycle: None,
+            selection_keys: Vec::new(),
+            ranker: None,
+            item_renderer: None,
+            type_ahead: String::new(),
+            type_ahead_at: None,
+            min_chars: 0,
+            show_on_empty: true,
+            listing_stale: true,
+            #[cfg(feature = "watch")]
+            dir_watch: None,
+            redraw: Redraw::default(),
 
             on_submit: None,
-        };
+        }
+    }
 
-        ac
+    /// Creates a new `Autocomplete` querying `feeder` in a background thread
+    /// while text is typed, so slow feeders (walking dirs, calling network)
+    /// don't block typing.
+    ///
+    /// Query superseded by newer text is cancelled (see [Feeder::query_cancellable])
+    /// and queries don't start more often than once per 150 ms. How long they take
+    /// is told by [query_duration] and [Observer::feeder_query_duration].
+    /// Scrolling beyond listed suggestions queries `feeder` right away.
+    ///
+    /// [Feeder::query_cancellable]: ../feeders/trait.Feeder.html#method.query_cancellable
+    /// [query_duration]: #method.query_duration
+    /// [Observer::feeder_query_duration]: ../metrics/trait.Observer.html#method.feeder_query_duration
+    pub fn background<T: Feeder + Send + Sync>(feeder: T) -> Autocomplete {
+        Self::with_background(Arc::new(feeder))
+    }
+
+    /// Creates a new `Autocomplete` querying shared `feeder` in background, see [background].
+    ///
+    /// [background]: #method.background
+    pub(crate) fn with_background(feeder: Arc<dyn Feeder + Send + Sync>) -> Autocomplete {
+        let mut view = Autocomplete::new(Arc::clone(&feeder));
+        view.background = Some(Background {
+            feeder,
+            running: None,
+            starts_at: None,
+        });
+        view
     }
 
     /// Gets typed in value.
@@ -72,24 +249,209 @@ impl Autocomplete {
 
     /// Sets text value.
     pub fn value(mut self, initial: &str) -> Self {
-        self.get_edit_view_mut().set_content(initial);
-        self.refresh_listing();
+        self.set_value(initial);
+        self
+    }
+
+    /// Sets text value of existing view.
+    pub fn set_value(&mut self, value: &str) {
+        self.get_edit_view_mut().set_content(value);
+        self.typed_value = self.get_edit_view().get_content();
+        self.listing_stale = true;
+    }
+
+    /// Doesn't query the feeder until at least `min_chars` characters are typed
+    /// (0 by default).
+    pub fn min_chars(mut self, min_chars: usize) -> Self {
+        self.min_chars = min_chars;
+        self.listing_stale = true;
+        self
+    }
+
+    /// Controls if empty input lists the first page of all suggestions (it does by default).
+    pub fn show_on_empty(mut self, show: bool) -> Self {
+        self.show_on_empty = show;
+        self.listing_stale = true;
+        self
+    }
+
+    /// Refreshes suggestions when directory they come from changes
+    /// (see [Feeder::watched_dir]), so e.g. newly created files are listed without retyping.
+    ///
+    /// Directory is checked in background twice a second. Requires `watch` feature.
+    ///
+    /// [Feeder::watched_dir]: ../feeders/trait.Feeder.html#method.watched_dir
+    #[cfg(feature = "watch")]
+    pub fn watch(mut self, enabled: bool) -> Self {
+        self.set_watch(enabled);
         self
     }
 
+    /// Refreshes suggestions when directory they come from changes, see [watch].
+    ///
+    /// [watch]: #method.watch
+    #[cfg(feature = "watch")]
+    pub fn set_watch(&mut self, enabled: bool) {
+        self.dir_watch = if enabled {
+            Some(DirWatch::new(self.redraw.clone()))
+        } else {
+            None
+        };
+    }
+
+    /// Refreshes listing when the watched directory changed, keeping highlighted suggestion.
+    #[cfg(feature = "watch")]
+    fn check_watched_dir(&mut self) {
+        let watch = match self.dir_watch {
+            Some(ref watch) => watch,
+            None => return,
+        };
+        watch.set_dir(self.feeder.watched_dir(&self.get_edit_view().get_content()));
+        if !watch.take_changed() {
+            return;
+        }
+        let selected = self.get_select_view().selection();
+        self.listed_query = None;
+        self.refresh_listing();
+        if let Some(selected) = selected {
+            let select = self.get_select_view_mut();
+            let idx = select.iter().position(|(_, v)| *v == *selected);
+            if let Some(idx) = idx {
+                select.set_selection(idx);
+            }
+        }
+    }
+
+    /// Checks if suggestions for typed `text` should be queried.
+    fn is_queried(&self, text: &str) -> bool {
+        let typed = text.chars().count();
+        typed >= self.min_chars && (typed > 0 || self.show_on_empty)
+    }
+
     /// Refresh suggestions.
     fn refresh_listing(&mut self) {
-        let feeder = Rc::clone(&self.feeder);
+        self.listing_stale = false;
         let text = self.get_edit_view().get_content();
-        let shown_count = self.shown_count as usize;
+        let shown_count = self.shown_rows();
+        if self.background.is_some() && self.is_queried(&text) {
+            self.query_in_background((text, 0, shown_count));
+            return;
+        }
+        if let Some(data) = self.query(text, 0, shown_count) {
+            self.list_rows(suggestion_rows(data, shown_count));
+        }
+    }
+
+    /// Replaces listed suggestions with `rows`, selecting the first suggestion.
+    fn list_rows(&mut self, rows: Vec<Row>) {
+        let masked = self.masked;
+        let renderer = self.item_renderer.clone();
         let select = self.get_select_view_mut();
         select.clear();
-        select.add_all_str((*feeder).query(text.as_ref(), 0, shown_count).into_iter());
+        for (label, value) in rows {
+            select.add_item(label, value);
+        }
+        super::mask_select(select, masked, renderer.as_ref());
+        if let Some(row) = self.row_of_item(0) {
+            self.get_select_view_mut().set_selection(row);
+        }
+    }
+
+    /// Gets row of `nth` listed suggestion, section headers aren't counted.
+    fn row_of_item(&self, nth: usize) -> Option<usize> {
+        self.get_select_view()
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, value))| !super::is_header(value))
+            .nth(nth)
+            .map(|(row, _)| row)
+    }
+
+    /// Counts listed suggestions, section headers aren't counted.
+    fn listed_items(&self) -> usize {
+        self.get_select_view()
+            .iter()
+            .filter(|(_, value)| !super::is_header(value))
+            .count()
+    }
+
+    /// Checks if section header is highlighted.
+    fn is_header_selected(&self) -> bool {
+        self.get_select_view()
+            .selection()
+            .is_some_and(|x| super::is_header(&x))
+    }
+
+    /// Queries feeder, returns `None` if the same query results are already listed.
+    fn query(&mut self, text: Rc<String>, offset: usize, count: usize) -> Option<Vec<Suggestion>> {
+        let query = (text, offset, count);
+        if let Some(ref mut background) = self.background {
+            // listing is replaced right away, so query in background is outdated
+            background.cancel();
+        }
+        if self.listed_query.as_ref() == Some(&query) {
+            return None;
+        }
+        if !self.is_queried(&query.0) {
+            self.listed_query = Some(query);
+            return Some(Vec::new());
+        }
+        let started = Instant::now();
+        let data = self.feeder.query_grouped(&query.0, offset, count);
+        let duration = started.elapsed();
+        metrics::notify(|o| o.feeder_query_duration(&query.0, duration));
+        self.query_duration = Some(duration);
+        self.listed_query = Some(query);
+        Some(data)
+    }
+
+    /// Starts `query` in background unless it's listed or running already.
+    fn query_in_background(&mut self, query: Query) {
+        let ranked = self.ranker.is_some();
+        let redraw = self.redraw.clone();
+        let listed = self.listed_query.as_ref() == Some(&query);
+        let background = self.background.as_mut().unwrap();
+        if listed {
+            background.cancel();
+            return;
+        }
+        if background.running.as_ref().map(|(running, _, _)| running) == Some(&query) {
+            return;
+        }
+        // ranker isn't shared with thread, so it orders candidates found in background
+        background.start(query, ranked, redraw);
+    }
+
+    /// Lists suggestions found in background once the query finished.
+    fn list_background_result(&mut self) {
+        let finished = self.background.as_mut().and_then(|b| b.take_finished());
+        let (query, data, duration) = match finished {
+            Some(finished) => finished,
+            None => return,
+        };
+        let data = match self.ranker {
+            Some(ref ranker) => {
+                let candidates = data.into_iter().map(|x| x.value).collect();
+                let ranked = ranking::rank(&**ranker, &query.0, candidates, query.1, query.2);
+                ranked.into_iter().map(Suggestion::from).collect()
+            }
+            None => data,
+        };
+        metrics::notify(|o| o.feeder_query_duration(&query.0, duration));
+        self.query_duration = Some(duration);
+        let count = query.2;
+        self.listed_query = Some(query);
+        self.list_rows(suggestion_rows(data, count));
+    }
+
+    /// Gets how long the last query to the feeder took.
+    pub fn query_duration(&self) -> Option<Duration> {
+        self.query_duration
     }
 
     /// Copy selected text to edit view.
     fn selection_to_edit(&mut self) {
-        if !self.get_select_view().is_empty() {
+        if !self.get_select_view().is_empty() && !self.is_header_selected() {
             if let Some(x) = self.get_select_view_mut().selection() {
                 self.get_edit_view_mut().set_content((&*x).clone());
             }
@@ -105,12 +467,241 @@ impl Autocomplete {
     /// Controls how many suggested items should be shown.
     pub fn shown_count(mut self, shown_count: u8) -> Self {
         self.shown_count = shown_count;
-        self.view
-            .set_height(SizeConstraint::AtLeast(self.shown_count as usize + 1));
-        self.refresh_listing();
+        self.resize_listing();
+        self
+    }
+
+    /// Shows at most `max_count` suggested items even if [shown_count] is bigger,
+    /// `None` removes the limit. It's used by compact layouts of forms.
+    ///
+    /// [shown_count]: #method.shown_count
+    pub fn set_max_shown_count(&mut self, max_count: Option<u8>) {
+        self.max_shown_count = max_count;
+        self.resize_listing();
+    }
+
+    /// Gets how many suggested items are shown.
+    fn shown_rows(&self) -> usize {
+        let count = match self.max_shown_count {
+            Some(max_count) => self.shown_count.min(max_count),
+            None => self.shown_count,
+        };
+        (count as usize).max(1)
+    }
+
+    fn resize_listing(&mut self) {
+        if !self.tab_cycling {
+            self.view
+                .set_height(SizeConstraint::AtLeast(self.shown_rows() + 1));
+        }
+        self.listing_stale = true;
+    }
+
+    /// Shows remainder of the top suggestion as dimmed text after the cursor.
+    ///
+    /// The remainder is accepted with `Right` or `Tab` key.
+    pub fn inline_completion(mut self, enabled: bool) -> Self {
+        self.inline_completion = enabled;
+        self
+    }
+
+    /// Gets remainder of the top suggestion completing typed text.
+    fn ghost_text(&self) -> Option<String> {
+        if !self.inline_completion || self.masked || self.suggestion_offset != 0 {
+            return None;
+        }
+        let typed = self.get_edit_view().get_content();
+        if typed.is_empty() || !self.cursor_at_end {
+            return None;
+        }
+        let (_, top) = self.get_select_view().get_item(self.row_of_item(0)?)?;
+        if top.starts_with(&*typed) && top.len() > typed.len() {
+            Some(top[typed.len()..].to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Cycles through suggestions with `Tab` (`Shift+Tab` backwards) directly in the input
+    /// instead of showing them below, which suits very small terminals.
+    pub fn tab_cycling(mut self, enabled: bool) -> Self {
+        self.tab_cycling = enabled;
+        let height = if enabled { 1 } else { self.shown_rows() + 1 };
+        self.view.set_height(SizeConstraint::Fixed(height));
+        self
+    }
+
+    /// Renders listed suggestions with `renderer` instead of plain text, e.g. to colorize
+    /// big files or put icons before names. Data of suggestions stays the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fui::views::Autocomplete;
+    /// use cursive::theme::{BaseColor, Color};
+    /// use cursive::utils::markup::StyledString;
+    /// # extern crate cursive;
+    /// # extern crate fui;
+    ///
+    /// let view = Autocomplete::new(vec!["notes.txt", "backup.tar"]).item_renderer(|item| {
+    ///     if item.ends_with(".tar") {
+    ///         StyledString::styled(item, Color::Dark(BaseColor::Red))
+    ///     } else {
+    ///         StyledString::plain(item)
+    ///     }
+    /// });
+    /// ```
+    pub fn item_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&str) -> StyledString + 'static,
+    {
+        self.set_item_renderer(Rc::new(renderer));
+        self
+    }
+
+    /// Renders listed suggestions with `renderer`, see [item_renderer].
+    ///
+    /// [item_renderer]: #method.item_renderer
+    pub fn set_item_renderer(&mut self, renderer: ItemRenderer) {
+        self.item_renderer = Some(renderer);
+        let masked = self.masked;
+        let renderer = self.item_renderer.clone();
+        super::mask_select(self.get_select_view_mut(), masked, renderer.as_ref());
+    }
+
+    /// Orders suggestions with `ranker`.
+    pub fn ranker<R: Ranker>(mut self, ranker: R) -> Self {
+        self.set_ranker(Rc::new(ranker));
+        self
+    }
+
+    /// Orders suggestions with `ranker`, replacing the previous one.
+    pub fn set_ranker(&mut self, ranker: Rc<dyn Ranker>) {
+        let feeder = Rc::clone(&self.unranked_feeder);
+        self.feeder = Rc::new(Ranked::new(feeder, Rc::clone(&ranker)));
+        self.ranker = Some(ranker);
+        self.listed_query = None;
+        self.listing_stale = true;
+    }
+
+    /// Calls `callback` with highlighted suggestion when `key` is pressed while suggestions
+    /// are focused.
+    ///
+    /// Suggestions are refreshed afterwards, so `callback` may change data of the feeder.
+    /// Printable keys are taken by type-ahead, so use e.g. `Event::CtrlChar` or a function key.
+    pub fn on_selection_key<F>(mut self, key: Event, callback: F) -> Self
+    where
+        F: Fn(&str) + 'static,
+    {
+        self.selection_keys.push((key, Rc::new(callback)));
         self
     }
 
+    /// Calls callbacks bound to `key` with highlighted suggestion, keeping it highlighted.
+    fn run_selection_key(&mut self, key: &Event) {
+        let selected = match self.get_select_view().selection() {
+            Some(ref selected) if super::is_header(selected) => return,
+            Some(selected) => selected,
+            None => return,
+        };
+        for (_, callback) in self.selection_keys.iter().filter(|(k, _)| k == key) {
+            callback(&selected);
+        }
+        self.listed_query = None;
+        self.refresh_listing();
+        let select = self.get_select_view_mut();
+        let idx = select.iter().position(|(_, v)| *v == *selected);
+        if let Some(idx) = idx {
+            select.set_selection(idx);
+        }
+    }
+
+    /// Puts next (or previous when `backwards`) suggestion for typed text in edit view.
+    ///
+    /// Returns `false` if there are no suggestions.
+    fn cycle_suggestion(&mut self, backwards: bool) -> bool {
+        let (candidates, idx) = match self.cycle.take() {
+            Some((candidates, idx)) => {
+                let len = candidates.len();
+                let idx = if backwards {
+                    (idx + len - 1) % len
+                } else {
+                    (idx + 1) % len
+                };
+                (candidates, idx)
+            }
+            None if !self.is_queried(&self.typed_value) => return false,
+            None => {
+                let candidates = self.feeder.query(&self.typed_value, 0, CYCLE_LIMIT);
+                if candidates.is_empty() {
+                    return false;
+                }
+                let idx = if backwards { candidates.len() - 1 } else { 0 };
+                (candidates, idx)
+            }
+        };
+        self.get_edit_view_mut()
+            .set_content(candidates[idx].clone());
+        self.cycle = Some((candidates, idx));
+        true
+    }
+
+    /// Checks if cursor of edit view is at the end of text, leaving the cursor untouched.
+    fn probe_cursor_at_end(&mut self) -> bool {
+        let edit = self.get_edit_view_mut();
+        match edit.on_event(Event::Key(Key::Right)) {
+            EventResult::Ignored => true,
+            _ => {
+                edit.on_event(Event::Key(Key::Left));
+                false
+            }
+        }
+    }
+
+    /// Completes typed text with the top suggestion.
+    fn accept_ghost_text(&mut self) -> bool {
+        match self.ghost_text() {
+            Some(ghost) => {
+                let completed = format!("{}{}", self.get_edit_view().get_content(), ghost);
+                self.get_edit_view_mut().set_content(completed);
+                self.typed_value = self.get_edit_view().get_content();
+                self.refresh_listing();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Puts a checkbox before each listed suggestion, ticked when `checked` returns `true`.
+    pub(crate) fn check_items<F: Fn(&str) -> bool>(&mut self, checked: F) {
+        let masked = self.masked;
+        let renderer = self.item_renderer.clone();
+        for (label, value) in self.get_select_view_mut().iter_mut() {
+            if super::is_header(value) {
+                continue;
+            }
+            let glyph = if checked(value) { "[X]" } else { "[ ]" };
+            let mut text = StyledString::plain(format!("{} ", glyph));
+            text.append(super::item_label(value, masked, renderer.as_ref()));
+            *label = text;
+        }
+    }
+
+    /// Gets highlighted suggestion.
+    pub(crate) fn highlighted(&self) -> Option<Rc<String>> {
+        self.get_select_view()
+            .selection()
+            .filter(|x| !super::is_header(x))
+    }
+
+    /// Shows placeholders instead of typed text and suggestions when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        let renderer = self.item_renderer.clone();
+        super::mask_select(self.get_select_view_mut(), masked, renderer.as_ref());
+    }
+
     fn get_edit_view(&self) -> &EditView {
         self.view
             .get_inner()
@@ -151,18 +742,28 @@ impl Autocomplete {
             .unwrap()
     }
 
-    fn load_data(&mut self) -> bool {
-        let shown_count = self.shown_count as usize;
-        let feeder = Rc::clone(&self.feeder);
-        let typed_value = &*self.typed_value.clone();
-        let data = (*feeder).query(typed_value, self.suggestion_offset, shown_count);
-        if data.len() == shown_count {
-            let select = self.get_select_view_mut();
-            select.clear();
-            select.add_all_str(data.into_iter());
-            true
-        } else {
-            false
+    /// Lists page starting at `suggestion_offset` if it shows `required` suggestion.
+    fn load_data(&mut self, required: usize) -> bool {
+        let shown_count = self.shown_rows();
+        let text = Rc::clone(&self.typed_value);
+        let listed = self.listed_query.clone();
+        let nth = match required.checked_sub(self.suggestion_offset) {
+            Some(nth) => nth,
+            None => return false,
+        };
+        match self.query(text, self.suggestion_offset, shown_count) {
+            Some(data) => {
+                let rows = suggestion_rows(data, shown_count);
+                let shown = rows.iter().filter(|(_, v)| !super::is_header(v)).count();
+                if shown <= nth {
+                    // page isn't shown, so the listed query stays the same
+                    self.listed_query = listed;
+                    return false;
+                }
+                self.list_rows(rows);
+                true
+            }
+            None => self.row_of_item(nth).is_some(),
         }
     }
 
@@ -174,23 +775,35 @@ impl Autocomplete {
     }
 
     fn is_top(&mut self) -> bool {
-        self.is_nth(0)
+        match self.row_of_item(0) {
+            Some(row) => self.is_nth(row),
+            None => false,
+        }
     }
 
     fn is_bottom(&mut self) -> bool {
-        let last_idx = self.shown_count as usize - 1;
+        let last_idx = self.get_select_view().len().saturating_sub(1);
         self.is_nth(last_idx)
     }
 
     fn select_up_was_top(&mut self) -> bool {
         let is_top_before_select = self.is_top();
-        self.get_select_view_mut().select_up(1);
+        if !is_top_before_select {
+            self.get_select_view_mut().select_up(1);
+            if self.is_header_selected() {
+                self.get_select_view_mut().select_up(1);
+            }
+        }
         return is_top_before_select;
     }
 
     fn select_down_was_bottom(&mut self) -> bool {
         let is_bottom_before_selected = self.is_bottom();
         self.get_select_view_mut().select_down(1);
+        if self.is_header_selected() {
+            // header is never the last row
+            self.get_select_view_mut().select_down(1);
+        }
         is_bottom_before_selected
     }
 
@@ -201,7 +814,8 @@ impl Autocomplete {
         let was_top = self.select_up_was_top();
         if was_top {
             self.suggestion_offset = self.suggestion_offset.saturating_sub(1);
-            self.load_data();
+            let required = self.suggestion_offset;
+            self.load_data(required);
         }
         self.selection_to_edit();
     }
@@ -210,19 +824,128 @@ impl Autocomplete {
         if self.get_select_view().is_empty() {
             return;
         }
-        let last_idx = self.shown_count as usize - 1;
         let was_bottom = self.select_down_was_bottom();
         if was_bottom {
-            self.suggestion_offset += 1;
-            if self.load_data() {
-                self.get_select_view_mut().set_selection(last_idx);
-            } else {
-                self.suggestion_offset -= 1;
+            // headers take rows, so more than one step may be needed to list the next item
+            let listed_offset = self.suggestion_offset;
+            let next = listed_offset + self.listed_items();
+            for offset in listed_offset + 1..=next {
+                self.suggestion_offset = offset;
+                if self.load_data(next) {
+                    let row = self.row_of_item(next - offset).unwrap();
+                    self.get_select_view_mut().set_selection(row);
+                    self.selection_to_edit();
+                    return;
+                }
             }
+            self.suggestion_offset = listed_offset;
         }
         self.selection_to_edit();
     }
 
+    /// Gets index of highlighted suggestion among all suggestions.
+    fn selected_item(&self) -> Option<usize> {
+        let selected = self.get_select_view().selected_id()?;
+        let nth = self
+            .get_select_view()
+            .iter()
+            .take(selected)
+            .filter(|(_, value)| !super::is_header(value))
+            .count();
+        Some(self.suggestion_offset + nth)
+    }
+
+    /// Counts all suggestions for typed text, up to 10 000.
+    fn count_items(&self) -> usize {
+        let page_size = 100;
+        let mut count = 0;
+        let mut previous = Vec::new();
+        while count < 10_000 {
+            let page = self.feeder.query(&self.typed_value, count, page_size);
+            // feeders ignoring position return the same page forever
+            if page == previous {
+                break;
+            }
+            count += page.len();
+            if page.len() < page_size {
+                break;
+            }
+            previous = page;
+        }
+        count
+    }
+
+    /// Highlights `item`-th suggestion, scrolling as little as possible.
+    ///
+    /// Returns `false` if there is no such suggestion.
+    fn jump_to(&mut self, item: usize) -> bool {
+        let listed_offset = self.suggestion_offset;
+        let offsets = if item < listed_offset {
+            // item goes to the top
+            item..item + 1
+        } else {
+            // item stays where it is or goes to the bottom
+            let lowest = item.saturating_sub(self.shown_rows() - 1);
+            ::std::cmp::max(lowest, listed_offset)..item + 1
+        };
+        let mut found = false;
+        for offset in offsets {
+            self.suggestion_offset = offset;
+            if self.load_data(item) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            self.suggestion_offset = listed_offset;
+            return false;
+        }
+        let row = self.row_of_item(item - self.suggestion_offset).unwrap();
+        self.get_select_view_mut().set_selection(row);
+        self.selection_to_edit();
+        true
+    }
+
+    /// Moves highlight by a page of suggestions up or down.
+    fn jump_page(&mut self, up: bool) {
+        let page = ::std::cmp::max(self.listed_items(), 1);
+        let selected = self.selected_item().unwrap_or(0);
+        if up {
+            self.jump_to(selected.saturating_sub(page));
+        } else if !self.jump_to(selected + page) {
+            let last = self.count_items().saturating_sub(1);
+            self.jump_to(last);
+        }
+    }
+
+    /// Highlights the first suggestion starting with text typed while suggestions
+    /// are focused.
+    ///
+    /// Returns `false` if there is no such suggestion.
+    fn type_ahead(&mut self, ch: char) -> bool {
+        let now = Instant::now();
+        let expired = self
+            .type_ahead_at
+            .map_or(true, |at| now.duration_since(at) > TYPE_AHEAD_TIMEOUT);
+        if expired {
+            self.type_ahead.clear();
+        }
+        self.type_ahead_at = Some(now);
+        self.type_ahead.extend(ch.to_lowercase());
+        let found = self
+            .feeder
+            .query(&self.typed_value, 0, TYPE_AHEAD_LIMIT)
+            .iter()
+            .position(|x| x.to_lowercase().starts_with(&self.type_ahead));
+        match found {
+            Some(item) => self.jump_to(item),
+            None => {
+                self.type_ahead.clear();
+                false
+            }
+        }
+    }
+
     /// Sets the function to be called when submit is triggered.
     pub fn set_on_submit<F>(&mut self, callback: F)
     where
@@ -245,7 +968,7 @@ impl Autocomplete {
         self.view.get_inner().get_focus_index() == 0
     }
 
-    fn is_select_focused(&self) -> bool {
+    pub(crate) fn is_select_focused(&self) -> bool {
         self.view.get_inner().get_focus_index() == 1
     }
 
@@ -264,26 +987,160 @@ impl Autocomplete {
     }
 }
 
+impl Autocomplete {
+    /// Draws input & its list of `list_height` rows over the rows above the input,
+    /// because the list would be clipped by the bottom of the screen.
+    fn draw_list_above(&self, printer: &Printer, list_height: usize) {
+        let edit_printer = printer
+            .cropped((printer.size.x, 1))
+            .focused(self.is_edit_focused());
+        self.get_edit_view().draw(&edit_printer);
+
+        let mut list_printer = printer.clone();
+        list_printer.offset.y -= list_height;
+        list_printer.output_size.y = list_height;
+        list_printer.size.y = list_height;
+        list_printer.focused = printer.focused && self.is_select_focused();
+        // views drawn there before would show through empty rows
+        let blank = " ".repeat(printer.size.x);
+        for row in 0..list_height {
+            list_printer.print((0, row), &blank);
+        }
+        self.get_select_view().draw(&list_printer);
+    }
+}
+
+/// Checks if list of `list_height` rows should go above the input, because only
+/// `visible_rows` rows (the input included) are visible below the top of the view
+/// and `rows_above` rows are on the screen above it.
+fn list_goes_above(visible_rows: usize, rows_above: usize, list_height: usize) -> bool {
+    list_height > 0 && visible_rows <= list_height && rows_above >= list_height
+}
+
 impl ViewWrapper for Autocomplete {
     wrap_impl!(self.view: ResizedView<LinearLayout>);
 
+    fn wrap_draw(&self, printer: &Printer) {
+        let list_height = printer.size.y.saturating_sub(1);
+        let above = printer.focused
+            && !self.tab_cycling
+            && printer.content_offset.y == 0
+            && list_goes_above(printer.output_size.y, printer.offset.y, list_height);
+        if above {
+            self.draw_list_above(printer, list_height);
+        } else {
+            self.view.draw(printer);
+        }
+        if !printer.focused || !self.is_edit_focused() {
+            return;
+        }
+        if let Some(ghost) = self.ghost_text() {
+            let typed_width = self.get_edit_view().get_content().width();
+            if typed_width + ghost.width() >= printer.size.x {
+                // edit view is scrolled or ghost text doesn't fit
+                return;
+            }
+            let mut chars = ghost.chars();
+            let first = chars.next().unwrap().to_string();
+            let rest: String = chars.collect();
+            printer.with_color(ColorStyle::secondary(), |p| {
+                // first char stays under the cursor
+                p.print((typed_width, 0), &first);
+                p.with_effect(Effect::Reverse, |p| {
+                    p.with_effect(Effect::Dim, |p| {
+                        p.print((typed_width + first.width(), 0), &rest);
+                    })
+                });
+            });
+        }
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.list_background_result();
+        #[cfg(feature = "watch")]
+        self.check_watched_dir();
+        if self.listing_stale {
+            self.refresh_listing();
+        }
+        self.view.layout(size);
+    }
+
     fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        self.list_background_result();
+        if self.listing_stale {
+            self.refresh_listing();
+        }
+        let result = self.handle_event(event);
+        if self.inline_completion {
+            self.cursor_at_end = self.probe_cursor_at_end();
+        }
+        self.redraw.learn(result)
+    }
+}
+
+impl Autocomplete {
+    fn handle_event(&mut self, event: Event) -> EventResult {
         match event {
+            ref key
+                if self.is_select_focused()
+                    && self.selection_keys.iter().any(|(k, _)| k == key) =>
+            {
+                self.run_selection_key(key);
+                EventResult::Consumed(None)
+            }
+            Event::Char(ch) if self.is_select_focused() && !self.masked && self.type_ahead(ch) => {
+                EventResult::Consumed(None)
+            }
             Event::Char(_) | Event::Key(Key::Backspace) | Event::Key(Key::Del) => {
                 // typing
                 self.focus_edit();
+                let before = self.get_edit_view().get_content();
                 self.with_view_mut(|v| v.on_event(event))
                     .unwrap_or(EventResult::Ignored);
-                self.typed_value = self.get_edit_view().get_content();
-                self.suggestion_offset = 0;
-                self.refresh_listing();
+                let after = self.get_edit_view().get_content();
+                if before != after {
+                    self.typed_value = after;
+                    self.suggestion_offset = 0;
+                    self.cycle = None;
+                    self.refresh_listing();
+                }
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Tab) | Event::Shift(Key::Tab)
+                if self.tab_cycling && self.is_edit_focused() =>
+            {
+                if self.cycle_suggestion(event == Event::Shift(Key::Tab)) {
+                    EventResult::Consumed(None)
+                } else {
+                    EventResult::Ignored
+                }
+            }
+            Event::Key(Key::Down)
+            | Event::CtrlChar('n')
+            | Event::Key(Key::Up)
+            | Event::CtrlChar('p')
+            | Event::Key(Key::PageUp)
+            | Event::Key(Key::PageDown)
+                if self.tab_cycling =>
+            {
+                // there is no dropdown to move to
+                EventResult::Ignored
+            }
+            Event::Key(Key::Right) | Event::Key(Key::Tab)
+                if self.is_edit_focused() && self.accept_ghost_text() =>
+            {
                 EventResult::Consumed(None)
             }
+            Event::Key(Key::Tab) | Event::Shift(Key::Tab) => {
+                // suggestions are reached with arrows, so `Tab` leaves the view
+                EventResult::Ignored
+            }
             Event::CtrlChar('u') => {
                 self.focus_edit();
                 self.get_edit_view_mut().set_content("");
                 self.typed_value = Rc::new("".to_string());
                 self.suggestion_offset = 0;
+                self.cycle = None;
                 self.refresh_listing();
                 EventResult::Consumed(None)
             }
@@ -303,6 +1160,25 @@ impl ViewWrapper for Autocomplete {
                 self.focus_select();
                 EventResult::Consumed(None)
             }
+            Event::Key(Key::Home) if self.is_select_focused() => {
+                self.jump_to(0);
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::End) if self.is_select_focused() => {
+                let last = self.count_items().saturating_sub(1);
+                self.jump_to(last);
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::PageUp) | Event::Key(Key::PageDown) => {
+                if self.is_edit_focused() && self.is_top() {
+                    // allow to select first element
+                    self.selection_to_edit();
+                } else {
+                    self.jump_page(event == Event::Key(Key::PageUp));
+                }
+                self.focus_select();
+                EventResult::Consumed(None)
+            }
             Event::Key(Key::Enter) => {
                 // submitting
                 self.with_view_mut(|v| v.on_event(event))
@@ -312,10 +1188,17 @@ impl ViewWrapper for Autocomplete {
 
                 if !self.submit_anything {
                     let from_select = self.is_value_from_select(&*to_submit);
-                    if !from_select {
+                    let from_cycle = self
+                        .cycle
+                        .as_ref()
+                        .is_some_and(|(candidates, _)| candidates.contains(&*to_submit));
+                    if !from_select && !from_cycle {
                         return EventResult::Ignored;
                     }
                 }
+                if let Some(ref ranker) = self.ranker {
+                    ranker.record_use(&to_submit);
+                }
 
                 let cb = self
                     .on_submit
@@ -325,9 +1208,266 @@ impl ViewWrapper for Autocomplete {
             }
             _ => {
                 // default behaviour from ViewWrapper
-                self.with_view_mut(|v| v.on_event(event))
-                    .unwrap_or(EventResult::Ignored)
+                let result = self
+                    .with_view_mut(|v| v.on_event(event))
+                    .unwrap_or(EventResult::Ignored);
+                if self.is_header_selected() {
+                    // e.g. clicked, highlight the first item of the section instead
+                    self.get_select_view_mut().select_down(1);
+                }
+                result
+            }
+        }
+    }
+}
+
+/// Lays `data` out in at most `count` rows, starting every group with its header.
+fn suggestion_rows(data: Vec<Suggestion>, count: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut group = None;
+    for suggestion in data {
+        if suggestion.group.is_some() && suggestion.group != group {
+            if rows.len() + 2 > count {
+                // header without any item below it
+                break;
+            }
+            let title = suggestion.group.clone().unwrap();
+            let value = format!("{}{}", super::HEADER_MARK, title);
+            rows.push((StyledString::styled(title, Effect::Bold), value));
+        }
+        if rows.len() == count {
+            break;
+        }
+        group = suggestion.group;
+        rows.push((
+            StyledString::plain(suggestion.value.clone()),
+            suggestion.value,
+        ));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feeders::Grouped;
+
+    fn press_down(ac: &mut Autocomplete, times: usize) -> Vec<String> {
+        (0..times)
+            .map(|_| {
+                ac.on_event(Event::Key(Key::Down));
+                ac.get_value().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn navigation_skips_section_headers() {
+        let feeder = Grouped::new()
+            .group("Branches", vec!["master", "develop"])
+            .group("Tags", vec!["v1.0", "v1.1"]);
+        let mut ac = Autocomplete::new(feeder).shown_count(4);
+        assert_eq!(
+            press_down(&mut ac, 5),
+            vec!["master", "develop", "v1.0", "v1.1", "v1.1"]
+        );
+        ac.on_event(Event::Key(Key::Up));
+        assert_eq!(ac.get_value().to_string(), "v1.0");
+    }
+
+    #[test]
+    fn navigation_pages_through_suggestions() {
+        let mut ac = Autocomplete::new((0..5).collect::<Vec<_>>()).shown_count(3);
+        assert_eq!(press_down(&mut ac, 6), vec!["0", "1", "2", "3", "4", "4"]);
+        for expected in &["3", "2", "1", "0", "0"] {
+            ac.on_event(Event::Key(Key::Up));
+            assert_eq!(ac.get_value().to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn navigation_jumps_by_keys() {
+        let mut ac = Autocomplete::new((0..10).collect::<Vec<_>>()).shown_count(3);
+        let mut press = |event| {
+            ac.on_event(event);
+            ac.get_value().to_string()
+        };
+        assert_eq!(press(Event::Key(Key::Down)), "0");
+        assert_eq!(press(Event::Key(Key::End)), "9");
+        assert_eq!(press(Event::Key(Key::PageUp)), "6");
+        assert_eq!(press(Event::Key(Key::Home)), "0");
+        assert_eq!(press(Event::Key(Key::PageDown)), "3");
+        assert_eq!(press(Event::Key(Key::PageDown)), "6");
+        assert_eq!(press(Event::Key(Key::PageDown)), "9");
+        assert_eq!(press(Event::Char('5')), "5");
+        assert_eq!(press(Event::Key(Key::Down)), "6");
+    }
+
+    #[test]
+    fn selection_keys_leave_letters_to_type_ahead() {
+        let picked = Rc::new(::std::cell::RefCell::new(Vec::new()));
+        let log = Rc::clone(&picked);
+        let mut ac = Autocomplete::new(vec!["build", "fmt", "test"])
+            .on_selection_key(Event::CtrlChar('t'), move |item| {
+                log.borrow_mut().push(item.to_string())
+            });
+        ac.on_event(Event::Key(Key::Down));
+
+        ac.on_event(Event::Char('f'));
+        assert_eq!(ac.get_value().to_string(), "fmt");
+        ac.on_event(Event::CtrlChar('t'));
+        assert_eq!(*picked.borrow(), vec!["fmt".to_string()]);
+        assert_eq!(ac.get_value().to_string(), "fmt");
+    }
+
+    #[test]
+    fn end_stops_at_repeated_page() {
+        struct Endless;
+        impl Feeder for Endless {
+            fn query(&self, _: &str, _: usize, items_count: usize) -> Vec<String> {
+                (0..items_count).map(|i| i.to_string()).collect()
+            }
+        }
+        let mut ac = Autocomplete::new(Endless).shown_count(3);
+        ac.on_event(Event::Key(Key::Down));
+
+        ac.on_event(Event::Key(Key::End));
+
+        // the 100th item is shown as the last of a page starting at position ignored by feeder
+        assert_eq!(ac.get_value().to_string(), "2");
+    }
+
+    struct CountingFeeder(Rc<::std::cell::Cell<usize>>);
+
+    impl Feeder for CountingFeeder {
+        fn query(&self, text: &str, _: usize, _: usize) -> Vec<String> {
+            self.0.set(self.0.get() + 1);
+            vec![format!("{}-suggestion", text)]
+        }
+    }
+
+    fn listed(ac: &Autocomplete) -> Vec<String> {
+        ac.get_select_view()
+            .iter()
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    #[test]
+    fn items_are_rendered_with_renderer_unless_masked() {
+        let labels = |ac: &Autocomplete| {
+            ac.get_select_view()
+                .iter()
+                .map(|(l, _)| l.to_string())
+                .collect::<Vec<String>>()
+        };
+        let mut ac = Autocomplete::new(vec!["a.rs", "b.tar"])
+            .item_renderer(|item| StyledString::plain(format!("> {}", item)));
+        ac.layout(Vec2::new(20, 6));
+        assert_eq!(labels(&ac), vec!["> a.rs", "> b.tar"]);
+        assert_eq!(listed(&ac), vec!["a.rs", "b.tar"]);
+
+        ac.set_masked(true);
+        assert_eq!(labels(&ac), vec!["****", "*****"]);
+        ac.set_masked(false);
+        assert_eq!(labels(&ac), vec!["> a.rs", "> b.tar"]);
+
+        ac.check_items(|item| item == "a.rs");
+        assert_eq!(labels(&ac), vec!["[X] > a.rs", "[ ] > b.tar"]);
+    }
+
+    #[test]
+    fn list_goes_above_when_clipped_by_screen_bottom() {
+        // whole list visible
+        assert!(!list_goes_above(6, 10, 5));
+        // only input & 2 rows of list visible
+        assert!(list_goes_above(3, 10, 5));
+        // no room above
+        assert!(!list_goes_above(3, 4, 5));
+        assert!(!list_goes_above(1, 10, 0));
+    }
+
+    #[test]
+    fn feeder_is_queried_after_min_chars() {
+        let queries = Rc::new(::std::cell::Cell::new(0));
+        let mut ac = Autocomplete::new(CountingFeeder(Rc::clone(&queries))).min_chars(2);
+        ac.layout(Vec2::new(20, 6));
+        assert_eq!((queries.get(), listed(&ac)), (0, vec![]));
+
+        ac.on_event(Event::Char('a'));
+        assert_eq!((queries.get(), listed(&ac)), (0, vec![]));
+        ac.on_event(Event::Char('b'));
+        assert_eq!(
+            (queries.get(), listed(&ac)),
+            (1, vec!["ab-suggestion".into()])
+        );
+    }
+
+    /// Feeder taking a while, logs queried texts & if they were cancelled.
+    struct SlowFeeder(Arc<Mutex<Vec<(String, bool)>>>);
+
+    impl Feeder for SlowFeeder {
+        fn query(&self, text: &str, _: usize, _: usize) -> Vec<String> {
+            vec![format!("{}-suggestion", text)]
+        }
+
+        fn query_cancellable(
+            &self,
+            text: &str,
+            position: usize,
+            items_count: usize,
+            token: &CancelToken,
+        ) -> Vec<Suggestion> {
+            for _ in 0..30 {
+                if token.is_cancelled() {
+                    self.0.lock().unwrap().push((text.to_string(), true));
+                    return Vec::new();
+                }
+                thread::sleep(Duration::from_millis(10));
             }
+            self.0.lock().unwrap().push((text.to_string(), false));
+            self.query_grouped(text, position, items_count)
+        }
+    }
+
+    #[test]
+    fn background_queries_are_cancelled_and_throttled() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut ac = Autocomplete::background(SlowFeeder(Arc::clone(&log))).show_on_empty(false);
+        ac.layout(Vec2::new(20, 6));
+
+        ac.on_event(Event::Char('a'));
+        thread::sleep(Duration::from_millis(50));
+        // cancels running "a", "ab" waits for its slot and "abc" replaces it
+        ac.on_event(Event::Char('b'));
+        ac.on_event(Event::Char('c'));
+        assert_eq!(listed(&ac), Vec::<String>::new());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while listed(&ac).is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+            ac.layout(Vec2::new(20, 6));
         }
+
+        assert_eq!(listed(&ac), vec!["abc-suggestion".to_string()]);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![("a".to_string(), true), ("abc".to_string(), false)]
+        );
+        assert!(ac.query_duration().unwrap() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn empty_input_lists_nothing_unless_shown_on_empty() {
+        let queries = Rc::new(::std::cell::Cell::new(0));
+        let mut ac = Autocomplete::new(CountingFeeder(Rc::clone(&queries))).show_on_empty(false);
+        ac.layout(Vec2::new(20, 6));
+        assert_eq!((queries.get(), listed(&ac)), (0, vec![]));
+
+        let mut ac = Autocomplete::new(CountingFeeder(Rc::clone(&queries)));
+        ac.layout(Vec2::new(20, 6));
+        assert_eq!(
+            (queries.get(), listed(&ac)),
+            (1, vec!["-suggestion".into()])
+        );
     }
 }
@@ -0,0 +1,35 @@
+use cursive::align::HAlign;
+use cursive::utils::markup::StyledString;
+use cursive::view::{View, ViewWrapper};
+use cursive::views::TextView;
+use cursive::Vec2;
+
+/// Centered ASCII-art banner or logo, shown above forms.
+///
+/// It collapses when it would take more than a third of available height,
+/// so short terminals are left for the content.
+pub struct Banner {
+    view: TextView,
+}
+
+impl Banner {
+    /// Creates a new `Banner` with (possibly styled) `text`.
+    pub fn new<S: Into<StyledString>>(text: S) -> Self {
+        Banner {
+            view: TextView::new(text).h_align(HAlign::Center),
+        }
+    }
+}
+
+impl ViewWrapper for Banner {
+    wrap_impl!(self.view: TextView);
+
+    fn wrap_required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let size = self.view.required_size(constraint);
+        if size.y * 3 > constraint.y {
+            Vec2::zero()
+        } else {
+            size
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, TextView};
+
+use cron::Schedule;
+use utils::format_rfc3339;
+
+/// Count of next runs shown in preview.
+const NEXT_RUNS: usize = 3;
+
+/// Edit view for cron expression showing its description and next runs below it.
+pub struct Cron {
+    view: LinearLayout,
+    masked: bool,
+}
+
+impl Cron {
+    /// Creates a new empty `Cron`.
+    pub fn new() -> Self {
+        let view = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(TextView::new(""));
+        Cron {
+            view,
+            masked: false,
+        }
+    }
+
+    /// Sets typed expression.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self.refresh_preview();
+        self
+    }
+
+    /// Gets typed expression.
+    pub fn get_content(&self) -> Rc<String> {
+        self.get_edit_view().get_content()
+    }
+
+    /// Shows placeholders instead of typed expression and hides preview when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        self.refresh_preview();
+    }
+
+    fn refresh_preview(&mut self) {
+        let content = self.get_content();
+        let preview = if content.trim().is_empty() || self.masked {
+            String::new()
+        } else {
+            match Schedule::parse(&content) {
+                Ok(schedule) => preview(&schedule),
+                Err(e) => e,
+            }
+        };
+        self.get_preview_view_mut().set_content(preview);
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditView>()
+            .unwrap()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+
+    fn get_preview_view_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+}
+
+/// Describes `schedule` and lists its next runs.
+fn preview(schedule: &Schedule) -> String {
+    let mut lines = vec![schedule.describe()];
+    for run in schedule.next_runs(SystemTime::now(), NEXT_RUNS) {
+        // "2024-01-06T09:00:00Z" -> "2024-01-06 09:00 UTC"
+        let time = format_rfc3339(run);
+        lines.push(format!("  {} {} UTC", &time[..10], &time[11..16]));
+    }
+    lines.join("\n")
+}
+
+impl Default for Cron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Cron {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.refresh_preview();
+        result
+    }
+}
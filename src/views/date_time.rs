@@ -0,0 +1,277 @@
+#[cfg(feature = "timezones")]
+use chrono::{DateTime as ChronoDateTime, Offset, TimeZone};
+#[cfg(feature = "timezones")]
+use chrono_tz::Tz;
+use cursive::traits::Resizable;
+use cursive::view::ViewWrapper;
+#[cfg(feature = "timezones")]
+use cursive::views::TextView;
+use cursive::views::{EditView, LinearLayout, ResizedView};
+
+#[cfg(feature = "timezones")]
+use feeders::Timezones;
+use locales::DateOrder;
+use utils::format_rfc3339_secs;
+use views::date_time_range::{localized_to_iso, parse_date_time};
+
+/// Width of time zone's input.
+#[cfg(feature = "timezones")]
+const TIMEZONE_WIDTH: usize = 20;
+
+/// Parses offset like `+02:00` to seconds.
+fn parse_offset(text: &str) -> Option<i64> {
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = text[1..].split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours = hours.parse::<i64>().ok().filter(|&h| h < 24)?;
+    let minutes = minutes.parse::<i64>().ok().filter(|&m| m < 60)?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Splits date-time text to date-time & its UTC offset (`Z` or like `+02:00`) if any.
+fn split_offset(text: &str) -> (&str, Option<i64>) {
+    if let Some(date_time) = text.strip_suffix('Z') {
+        return (date_time, Some(0));
+    }
+    match text.len().checked_sub(6) {
+        Some(idx) if text.is_char_boundary(idx) && idx > 10 => match parse_offset(&text[idx..]) {
+            Some(offset) => (&text[..idx], Some(offset)),
+            None => (text, None),
+        },
+        _ => (text, None),
+    }
+}
+
+/// Gets offset (in seconds) of time zone named `name` at `local` time
+/// (seconds since 1970-01-01 as shown on clocks there).
+#[cfg(feature = "timezones")]
+fn zone_offset(name: &str, local: i64) -> Option<i64> {
+    let tz = name.parse::<Tz>().ok()?;
+    let naive = ChronoDateTime::from_timestamp(local, 0)?.naive_utc();
+    let offset = tz.offset_from_local_datetime(&naive).earliest()?;
+    Some(i64::from(offset.fix().local_minus_utc()))
+}
+
+/// Splits time zone name (like `Europe/Warsaw`) ending `text` from the rest.
+#[cfg(feature = "timezones")]
+fn split_zone(text: &str) -> (&str, &str) {
+    match text.rsplit_once(' ') {
+        Some((rest, zone)) if zone.parse::<Tz>().is_ok() => (rest.trim_end(), zone),
+        _ => (text, ""),
+    }
+}
+
+#[cfg(not(feature = "timezones"))]
+fn split_zone(text: &str) -> (&str, &str) {
+    (text, "")
+}
+
+/// Parses date with time (see [parse_date_time], date can be also written in `order`
+/// like `31.01.2024`) followed by UTC offset (`Z`, `+02:00`) or (with feature `timezones`)
+/// name of time zone (`Europe/Warsaw`) to RFC 3339 timestamp.
+/// Time without offset or zone is in UTC.
+pub(crate) fn parse(text: &str, order: DateOrder) -> Result<String, String> {
+    let (date_time, zone) = split_zone(text.trim());
+    let (date_time, offset) = split_offset(date_time.trim_end());
+    let local = parse_date_time(&localized_to_iso(date_time, order))
+        .map_err(|_| format!("Invalid date: {}", date_time.trim()))?;
+    let offset = match (offset, zone) {
+        (Some(_), zone) if !zone.is_empty() => {
+            return Err("Offset and time zone can't be both used".to_string());
+        }
+        (Some(offset), _) => offset,
+        #[cfg(feature = "timezones")]
+        (None, zone) if !zone.is_empty() => zone_offset(zone, local)
+            .ok_or_else(|| format!("Time doesn't exist in {}: {}", zone, date_time))?,
+        _ => 0,
+    };
+    let formatted = format_rfc3339_secs(local);
+    if offset == 0 {
+        return Ok(formatted);
+    }
+    let sign = if offset < 0 { '-' } else { '+' };
+    Ok(format!(
+        "{}{}{:02}:{:02}",
+        formatted.trim_end_matches('Z'),
+        sign,
+        offset.abs() / 3600,
+        offset.abs() % 3600 / 60
+    ))
+}
+
+/// Edit view for date with time, optionally followed by a time zone picker.
+pub struct DateTime {
+    view: LinearLayout,
+}
+
+impl DateTime {
+    /// Creates a new empty `DateTime`.
+    pub fn new() -> Self {
+        let view = LinearLayout::horizontal().child(EditView::new().full_width());
+        DateTime { view }
+    }
+
+    /// Adds input of time zone with suggestions of IANA names (like `Europe/Warsaw`).
+    #[cfg(feature = "timezones")]
+    pub fn timezone_picker(mut self, enabled: bool) -> Self {
+        if enabled && self.view.len() == 1 {
+            self.view.add_child(TextView::new(" "));
+            let zones = ::views::Autocomplete::new(Timezones);
+            self.view.add_child(zones.fixed_width(TIMEZONE_WIDTH));
+        }
+        self
+    }
+
+    /// Sets date with time, followed by time zone when it's picked separately.
+    pub fn content(mut self, content: &str) -> Self {
+        let (date_time, zone) = if self.view.len() > 1 {
+            split_zone(content.trim())
+        } else {
+            (content, "")
+        };
+        self.get_edit_view_mut().set_content(date_time);
+        #[cfg(feature = "timezones")]
+        {
+            if let Some(zones) = self.get_zones_mut() {
+                zones.set_value(zone);
+            }
+        }
+        #[cfg(not(feature = "timezones"))]
+        let _ = zone;
+        self
+    }
+
+    /// Gets date with time, followed by picked time zone (if any).
+    pub fn get_content(&self) -> String {
+        let date_time = (*self.get_edit_view().get_content()).clone();
+        #[cfg(feature = "timezones")]
+        {
+            if let Some(zones) = self.get_zones() {
+                let zone = zones.get_value();
+                if !zone.is_empty() {
+                    return format!("{} {}", date_time, zone);
+                }
+            }
+        }
+        date_time
+    }
+
+    /// Shows placeholders instead of typed date when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.get_edit_view_mut().set_secret(masked);
+        #[cfg(feature = "timezones")]
+        {
+            if let Some(zones) = self.get_zones_mut() {
+                zones.set_masked(masked);
+            }
+        }
+    }
+
+    #[cfg(feature = "timezones")]
+    fn get_zones(&self) -> Option<&::views::Autocomplete> {
+        let child = self.view.get_child(2)?;
+        let zones = child
+            .as_any()
+            .downcast_ref::<ResizedView<::views::Autocomplete>>()?;
+        Some(zones.get_inner())
+    }
+
+    #[cfg(feature = "timezones")]
+    fn get_zones_mut(&mut self) -> Option<&mut ::views::Autocomplete> {
+        let child = self.view.get_child_mut(2)?;
+        let zones = child
+            .as_any_mut()
+            .downcast_mut::<ResizedView<::views::Autocomplete>>()?;
+        Some(zones.get_inner_mut())
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+impl Default for DateTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for DateTime {
+    wrap_impl!(self.view: LinearLayout);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_time_is_converted_to_rfc3339() {
+        assert_eq!(
+            parse("2024-01-31 12:00", DateOrder::Ymd),
+            Ok("2024-01-31T12:00:00Z".to_string())
+        );
+        assert_eq!(
+            parse("2024-01-31T12:00:30+05:30", DateOrder::Ymd),
+            Ok("2024-01-31T12:00:30+05:30".to_string())
+        );
+        assert_eq!(
+            parse("2024-01-31 12:00 -08:00", DateOrder::Ymd),
+            Ok("2024-01-31T12:00:00-08:00".to_string())
+        );
+        assert_eq!(
+            parse("2024-01-31", DateOrder::Ymd),
+            Ok("2024-01-31T00:00:00Z".to_string())
+        );
+        assert!(parse("2024-01-31 12:00 +25:00", DateOrder::Ymd).is_err());
+        assert_eq!(
+            parse("31.01.2024 12:00 +01:00", DateOrder::Dmy),
+            Ok("2024-01-31T12:00:00+01:00".to_string())
+        );
+        assert_eq!(
+            parse("31.01.2024", DateOrder::Mdy),
+            Err("Invalid date: 31.01.2024".to_string())
+        );
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn time_zone_gives_offset_at_that_time() {
+        assert_eq!(
+            parse("2024-01-31 12:00 Europe/Warsaw", DateOrder::Ymd),
+            Ok("2024-01-31T12:00:00+01:00".to_string())
+        );
+        assert_eq!(
+            parse("2024-07-31 12:00 Europe/Warsaw", DateOrder::Ymd),
+            Ok("2024-07-31T12:00:00+02:00".to_string())
+        );
+        assert!(parse("2024-07-31 12:00+02:00 Europe/Warsaw", DateOrder::Ymd).is_err());
+
+        let view = DateTime::new()
+            .timezone_picker(true)
+            .content("2024-01-31 12:00 Asia/Tokyo");
+        assert_eq!(view.get_content(), "2024-01-31 12:00 Asia/Tokyo");
+        assert_eq!(*view.get_edit_view().get_content(), "2024-01-31 12:00");
+    }
+}
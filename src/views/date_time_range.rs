@@ -0,0 +1,346 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, ResizedView, SelectView, TextView};
+use cursive::Vec2;
+
+use locales::DateOrder;
+use utils::{civil_from_days, days_from_civil, format_rfc3339_secs};
+use views::time;
+
+/// Separates start & end of range in its text, like in ISO 8601 intervals.
+pub(crate) const RANGE_SEP: char = '/';
+const DAY: i64 = 24 * 60 * 60;
+
+/// Ranges which can be picked from menu, relative to the current time (in UTC).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Preset {
+    LastHour,
+    Last24Hours,
+    Last7Days,
+    Today,
+    ThisWeek,
+}
+
+const PRESETS: [Preset; 5] = [
+    Preset::LastHour,
+    Preset::Last24Hours,
+    Preset::Last7Days,
+    Preset::Today,
+    Preset::ThisWeek,
+];
+
+impl Preset {
+    fn label(&self) -> &'static str {
+        match self {
+            Preset::LastHour => "last hour",
+            Preset::Last24Hours => "last 24h",
+            Preset::Last7Days => "last 7 days",
+            Preset::Today => "today",
+            Preset::ThisWeek => "this week",
+        }
+    }
+
+    /// Gets start & end (seconds since 1970-01-01) of range ending `now`.
+    fn range(&self, now: i64) -> (i64, i64) {
+        let midnight = now - now.rem_euclid(DAY);
+        let start = match self {
+            Preset::LastHour => now - 3600,
+            Preset::Last24Hours => now - DAY,
+            Preset::Last7Days => now - 7 * DAY,
+            Preset::Today => midnight,
+            // 1970-01-01 was Thursday, weeks start on Monday
+            Preset::ThisWeek => midnight - (now.div_euclid(DAY) + 3).rem_euclid(7) * DAY,
+        };
+        (start, now)
+    }
+}
+
+/// Parses date (`YYYY-MM-DD`) optionally followed by time (`HH:MM[:SS]`,
+/// separated by space or `T`, possibly ending with `Z`) in UTC
+/// to seconds since 1970-01-01.
+pub(crate) fn parse_date_time(text: &str) -> Result<i64, String> {
+    let invalid = || format!("Invalid date: {}", text.trim());
+    let text = text.trim();
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, clock) = match text.find(['T', ' ']) {
+        Some(idx) => (&text[..idx], Some(&text[idx + 1..])),
+        None => (text, None),
+    };
+    let parts = date
+        .split('-')
+        .map(|p| p.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()
+        .ok_or_else(invalid)?;
+    let (year, month, day) = match parts[..] {
+        [y, m, d] if date.len() == 10 => (i64::from(y), m, d),
+        _ => return Err(invalid()),
+    };
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) {
+        return Err(invalid());
+    }
+    let seconds = match clock {
+        Some(clock) => {
+            let t = time::parse(clock, false).map_err(|_| invalid())?;
+            i64::from(t.hour * 3600 + t.minute * 60 + t.second)
+        }
+        None => 0,
+    };
+    Ok(days * DAY + seconds)
+}
+
+/// Converts date written in `order` (like `31.01.2024` or `01/31/2024`, with parts
+/// separated by `.`, `/` or `-`) at the start of `text` to `YYYY-MM-DD`, keeping time after it.
+///
+/// Text which isn't such a date (like ISO 8601 one) is returned unchanged.
+pub(crate) fn localized_to_iso(text: &str, order: DateOrder) -> String {
+    let text = text.trim();
+    let (date, rest) = text.split_at(text.find([' ', 'T']).unwrap_or(text.len()));
+    let parts: Vec<&str> = date.trim_end_matches('.').split(['.', '/', '-']).collect();
+    let numeric = |part: &&str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    if parts.len() != 3
+        || !parts.iter().all(numeric)
+        || (parts[0].len() == 4 && !date.contains(['.', '/']))
+    {
+        return text.to_string();
+    }
+    let (year, month, day) = match order {
+        _ if parts[0].len() == 4 => (parts[0], parts[1], parts[2]),
+        DateOrder::Dmy => (parts[2], parts[1], parts[0]),
+        DateOrder::Mdy => (parts[2], parts[0], parts[1]),
+        DateOrder::Ymd => return text.to_string(),
+    };
+    if year.len() != 4 || month.len() > 2 || day.len() > 2 {
+        return text.to_string();
+    }
+    format!("{}-{:0>2}-{:0>2}{}", year, month, day, rest)
+}
+
+/// Writes date `YYYY-MM-DD` at the start of `text` (like RFC 3339 timestamp) in `order`
+/// with parts separated by `sep`, time after it is separated by space.
+pub(crate) fn iso_to_localized(text: &str, order: DateOrder, sep: char) -> String {
+    let date = match text.get(..10) {
+        Some(date) if (order, sep) != (DateOrder::Ymd, '-') && parse_date_time(date).is_ok() => {
+            date
+        }
+        _ => return text.to_string(),
+    };
+    let (year, month, day) = (&date[..4], &date[5..7], &date[8..]);
+    let date = match order {
+        DateOrder::Ymd => [year, month, day],
+        DateOrder::Dmy => [day, month, year],
+        DateOrder::Mdy => [month, day, year],
+    }
+    .join(&sep.to_string());
+    match text[10..].strip_prefix('T') {
+        Some(time) => format!("{} {}", date, time),
+        None => format!("{}{}", date, &text[10..]),
+    }
+}
+
+/// Splits range at [RANGE_SEP] to start & end.
+///
+/// Dates written with the separator (like `01/31/2024/02/01/2024`) are split in the middle.
+pub(crate) fn split(range: &str) -> (&str, &str) {
+    let is_date = |text: &str| {
+        let date = text.trim().split([' ', 'T']).next().unwrap_or("");
+        text.trim().is_empty() || date.split(['.', '/', '-']).count() == 3
+    };
+    range
+        .match_indices(RANGE_SEP)
+        .map(|(idx, _)| (&range[..idx], &range[idx + 1..]))
+        .find(|&(start, end)| is_date(start) && is_date(end))
+        .or_else(|| range.split_once(RANGE_SEP))
+        .unwrap_or((range, ""))
+}
+
+/// Pair of inputs for start & end of a date-time range with a menu of presets
+/// (like "last 24h" or "this week").
+pub struct DateTimeRange {
+    view: LinearLayout,
+}
+
+impl DateTimeRange {
+    /// Creates a new empty `DateTimeRange`.
+    pub fn new() -> Self {
+        let mut presets = SelectView::<Option<Preset>>::new().popup();
+        presets.add_item("presets", None);
+        for preset in PRESETS.iter() {
+            presets.add_item(preset.label(), Some(*preset));
+        }
+        let view = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(" – "))
+            .child(EditView::new().full_width())
+            .child(TextView::new(" "))
+            .child(presets);
+        DateTimeRange { view }
+    }
+
+    /// Sets range as start & end separated by `/`, like `2024-01-01/2024-01-31`.
+    pub fn content(mut self, content: &str) -> Self {
+        let (start, end) = split(content);
+        self.get_edit_view_mut(0).set_content(start.trim());
+        self.get_edit_view_mut(2).set_content(end.trim());
+        self
+    }
+
+    /// Gets range as start & end separated by `/`.
+    pub fn get_content(&self) -> String {
+        let start = self.get_edit_view(0).get_content();
+        let end = self.get_edit_view(2).get_content();
+        if start.is_empty() && end.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}{}", start, RANGE_SEP, end)
+        }
+    }
+
+    /// Shows placeholders instead of typed range when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.get_edit_view_mut(0).set_secret(masked);
+        self.get_edit_view_mut(2).set_secret(masked);
+    }
+
+    /// Fills inputs with preset picked from menu (if any), then resets the menu.
+    fn apply_preset(&mut self, now: i64) {
+        let presets = self.get_presets_mut();
+        let preset = match presets.selection().and_then(|p| *p) {
+            Some(preset) => preset,
+            None => return,
+        };
+        let _ = presets.set_selection(0);
+        let (start, end) = preset.range(now);
+        self.get_edit_view_mut(0)
+            .set_content(format_rfc3339_secs(start));
+        self.get_edit_view_mut(2)
+            .set_content(format_rfc3339_secs(end));
+    }
+
+    fn get_presets_mut(&mut self) -> &mut SelectView<Option<Preset>> {
+        self.view
+            .get_child_mut(4)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<SelectView<Option<Preset>>>()
+            .unwrap()
+    }
+
+    fn get_edit_view(&self, idx: usize) -> &EditView {
+        self.view
+            .get_child(idx)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self, idx: usize) -> &mut EditView {
+        self.view
+            .get_child_mut(idx)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+impl Default for DateTimeRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for DateTimeRange {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // menu of presets is a popup, picked preset is noticed afterwards
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        self.apply_preset(now);
+        self.view.layout(size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_time_is_parsed() {
+        assert_eq!(parse_date_time("1970-01-02"), Ok(DAY));
+        assert_eq!(parse_date_time("1970-01-01 01:00"), Ok(3600));
+        assert_eq!(
+            parse_date_time("2000-02-29T01:02:03Z"),
+            Ok(951_782_400 + 3_723)
+        );
+        assert!(parse_date_time("2001-02-29").is_err());
+        assert!(parse_date_time("2001-2-3").is_err());
+        assert!(parse_date_time("2001-02-03 25:00").is_err());
+    }
+
+    #[test]
+    fn localized_dates_are_converted() {
+        assert_eq!(
+            localized_to_iso("31.01.2024 18:00", DateOrder::Dmy),
+            "2024-01-31 18:00"
+        );
+        assert_eq!(localized_to_iso("1/31/2024", DateOrder::Mdy), "2024-01-31");
+        assert_eq!(localized_to_iso("2024/01/31", DateOrder::Mdy), "2024-01-31");
+        assert_eq!(localized_to_iso("2024-1-31", DateOrder::Dmy), "2024-1-31");
+        assert_eq!(localized_to_iso("31/01/2024", DateOrder::Ymd), "31/01/2024");
+        assert_eq!(
+            iso_to_localized("2024-01-31T18:00:00Z", DateOrder::Dmy, '.'),
+            "31.01.2024 18:00:00Z"
+        );
+        assert_eq!(
+            iso_to_localized("2024-01-31", DateOrder::Mdy, '/'),
+            "01/31/2024"
+        );
+        assert_eq!(
+            iso_to_localized("2024-01-31T18:00:00Z", DateOrder::Ymd, '-'),
+            "2024-01-31T18:00:00Z"
+        );
+    }
+
+    #[test]
+    fn ranges_of_dates_with_separator_are_split_in_middle() {
+        assert_eq!(split("2024-01-01/2024-01-31"), ("2024-01-01", "2024-01-31"));
+        assert_eq!(split("01/01/2024/01/31/2024"), ("01/01/2024", "01/31/2024"));
+        assert_eq!(split("2024-01-01/01/31/2024"), ("2024-01-01", "01/31/2024"));
+        assert_eq!(split("01/01/2024/"), ("01/01/2024", ""));
+        assert_eq!(split("a/b/c"), ("a", "b/c"));
+    }
+
+    #[test]
+    fn presets_end_now() {
+        // Wednesday 2024-01-03 12:00:00
+        let now = parse_date_time("2024-01-03T12:00:00").unwrap();
+        let monday = parse_date_time("2024-01-01").unwrap();
+        assert_eq!(Preset::ThisWeek.range(now), (monday, now));
+        assert_eq!(Preset::Today.range(now), (monday + 2 * DAY, now));
+        assert_eq!(Preset::Last24Hours.range(now), (now - DAY, now));
+    }
+
+    #[test]
+    fn picked_preset_fills_inputs() {
+        let mut view = DateTimeRange::new().content("2024-01-01/2024-01-02");
+        assert_eq!(view.get_content(), "2024-01-01/2024-01-02");
+
+        let now = parse_date_time("2024-01-03T12:00:00").unwrap();
+        let _ = view.get_presets_mut().set_selection(2);
+        view.apply_preset(now);
+        assert_eq!(
+            view.get_content(),
+            "2024-01-02T12:00:00Z/2024-01-03T12:00:00Z"
+        );
+        assert_eq!(view.get_presets_mut().selected_id(), Some(0));
+    }
+}
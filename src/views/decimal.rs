@@ -0,0 +1,194 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::EditView;
+
+use views::number::normalize;
+
+/// Edit view for decimal numbers with fixed precision,
+/// formatted with thousands separators once it loses focus (like `1,234.50`).
+pub struct Decimal {
+    view: EditView,
+    precision: u8,
+    group: char,
+    decimal: char,
+}
+
+impl Decimal {
+    /// Creates a new empty `Decimal` with `precision` decimal places and English separators.
+    pub fn new(precision: u8) -> Self {
+        Decimal {
+            view: EditView::new(),
+            precision,
+            group: ',',
+            decimal: '.',
+        }
+    }
+
+    /// Sets separators of thousands (`group`) and decimals (`decimal`).
+    pub fn separators(mut self, group: char, decimal: char) -> Self {
+        self.group = group;
+        self.decimal = decimal;
+        self
+    }
+
+    /// Sets typed number.
+    pub fn content(mut self, content: &str) -> Self {
+        self.view.set_content(content);
+        self.reformat();
+        self
+    }
+
+    /// Gets typed number.
+    pub fn get_content(&self) -> Rc<String> {
+        self.view.get_content()
+    }
+
+    /// Shows placeholders instead of typed number when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.view.set_secret(masked);
+    }
+
+    /// Formats typed number with separators if it's valid.
+    fn reformat(&mut self) {
+        let content = self.view.get_content();
+        if let Ok(minor) = parse(&content, self.precision, self.group, self.decimal) {
+            let formatted = format(minor, self.precision, Some(self.group), self.decimal);
+            if *content != formatted {
+                self.view.set_content(formatted);
+            }
+        }
+    }
+}
+
+/// Checks if `c` is a space which may separate thousands.
+pub(crate) fn is_space(c: char) -> bool {
+    c == ' ' || c == '\u{a0}' || c == '\u{202f}'
+}
+
+/// Parses `text` as a number with `precision` decimal places to an integer of minor units
+/// (e.g. `"12.5"` with precision 2 is `1250`).
+pub(crate) fn parse(text: &str, precision: u8, group: char, decimal: char) -> Result<i128, String> {
+    let text = text.trim();
+    let (negative, digits) = if let Some(rest) = text.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, text.strip_prefix('+').unwrap_or(text))
+    };
+    let digits =
+        normalize(digits, group, decimal).ok_or_else(|| format!("Invalid number: {}", text))?;
+    let mut parts = digits.splitn(2, '.');
+    let whole = parts.next().unwrap_or("");
+    let fraction = parts.next().unwrap_or("");
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("Invalid number: {}", text));
+    }
+    if fraction.len() > precision as usize {
+        return Err(format!("At most {} decimal places allowed", precision));
+    }
+    let too_big = || "Number is too big".to_string();
+    let mut minor: i128 = 0;
+    let padding = precision as usize - fraction.len();
+    for c in whole.chars().chain(fraction.chars()) {
+        minor = minor
+            .checked_mul(10)
+            .and_then(|m| m.checked_add(i128::from(c as u8 - b'0')))
+            .ok_or_else(too_big)?;
+    }
+    for _ in 0..padding {
+        minor = minor.checked_mul(10).ok_or_else(too_big)?;
+    }
+    Ok(if negative { -minor } else { minor })
+}
+
+/// Formats `minor` units as a number with `precision` decimal places,
+/// separating thousands with `group` if given.
+pub(crate) fn format(minor: i128, precision: u8, group: Option<char>, decimal: char) -> String {
+    let digits = minor.unsigned_abs().to_string();
+    let precision = precision as usize;
+    let digits = if digits.len() <= precision {
+        format!("{:0>width$}", digits, width = precision + 1)
+    } else {
+        digits
+    };
+    let (whole, fraction) = digits.split_at(digits.len() - precision);
+    let mut formatted = String::with_capacity(digits.len() * 2);
+    if minor < 0 {
+        formatted.push('-');
+    }
+    for (idx, c) in whole.chars().enumerate() {
+        if idx > 0 && (whole.len() - idx) % 3 == 0 {
+            if let Some(group) = group {
+                formatted.push(group);
+            }
+        }
+        formatted.push(c);
+    }
+    if precision > 0 {
+        formatted.push(decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+impl ViewWrapper for Decimal {
+    wrap_impl!(self.view: EditView);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let focus_lost = event == Event::FocusLost;
+        let result = self.view.on_event(event);
+        if focus_lost {
+            self.reformat();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_parsed_to_minor_units() {
+        assert_eq!(parse("1,234.5", 2, ',', '.'), Ok(123450));
+        assert_eq!(parse("-0.05", 2, ',', '.'), Ok(-5));
+        assert_eq!(parse("1.234,56", 2, '.', ','), Ok(123456));
+        assert_eq!(parse("1 234,5", 2, ' ', ','), Ok(123450));
+        assert_eq!(parse("7", 0, ',', '.'), Ok(7));
+        assert_eq!(parse(".5", 1, ',', '.'), Ok(5));
+        assert_eq!(
+            parse("1,5", 2, ',', '.'),
+            Err("Invalid number: 1,5".to_string())
+        );
+        assert_eq!(
+            parse("1.005", 2, ',', '.'),
+            Err("At most 2 decimal places allowed".to_string())
+        );
+        assert_eq!(
+            parse("1e3", 2, ',', '.'),
+            Err("Invalid number: 1e3".to_string())
+        );
+        assert_eq!(
+            parse("-", 2, ',', '.'),
+            Err("Invalid number: -".to_string())
+        );
+    }
+
+    #[test]
+    fn minor_units_are_formatted() {
+        assert_eq!(format(123456789, 2, Some(','), '.'), "1,234,567.89");
+        assert_eq!(format(-5, 2, Some(','), '.'), "-0.05");
+        assert_eq!(format(123450, 2, None, '.'), "1234.50");
+        assert_eq!(format(1234, 0, Some('.'), ','), "1.234");
+
+        let view = Decimal::new(2).separators('.', ',').content("1234,5");
+        assert_eq!(*view.get_content(), "1.234,50");
+    }
+}
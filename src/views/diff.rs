@@ -0,0 +1,127 @@
+use cursive::theme::{BaseColor, Color, ColorStyle};
+use cursive::view::View;
+use cursive::{Printer, Vec2};
+use unicode_width::UnicodeWidthStr;
+
+/// Kind of difference between old and new entry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Change {
+    Same,
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Side-by-side comparison of old and new entries (pairs of key and value).
+pub struct Diff {
+    // key, old value, new value
+    rows: Vec<(String, Option<String>, Option<String>)>,
+}
+
+impl Diff {
+    /// Creates a new `Diff` comparing `old` with `new` entries.
+    ///
+    /// Rows are ordered like `new` entries, followed by removed ones.
+    pub fn new(old: Vec<(String, String)>, new: Vec<(String, String)>) -> Self {
+        let mut rows: Vec<(String, Option<String>, Option<String>)> = new
+            .into_iter()
+            .map(|(key, value)| {
+                let old_value = old.iter().find(|(k, _)| *k == key).map(|(_, v)| v.clone());
+                (key, old_value, Some(value))
+            })
+            .collect();
+        for (key, value) in old {
+            if !rows.iter().any(|(k, _, _)| *k == key) {
+                rows.push((key, Some(value), None));
+            }
+        }
+        Diff { rows }
+    }
+
+    /// Checks if old and new entries differ.
+    pub fn has_changes(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|row| Self::change(row) != Change::Same)
+    }
+
+    /// Lists differing entries as plain lines, like `- key = old` & `+ key = new`.
+    pub fn changes(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for row in self.rows.iter() {
+            if Self::change(row) == Change::Same {
+                continue;
+            }
+            let (key, old, new) = row;
+            if old.is_some() {
+                lines.push(format!("- {}", Self::cell(key, old)));
+            }
+            if new.is_some() {
+                lines.push(format!("+ {}", Self::cell(key, new)));
+            }
+        }
+        lines
+    }
+
+    fn change(row: &(String, Option<String>, Option<String>)) -> Change {
+        match row {
+            (_, Some(old), Some(new)) if old == new => Change::Same,
+            (_, Some(_), Some(_)) => Change::Changed,
+            (_, None, _) => Change::Added,
+            (_, _, None) => Change::Removed,
+        }
+    }
+
+    fn cell(key: &str, value: &Option<String>) -> String {
+        value
+            .as_ref()
+            .map(|v| format!("{} = {}", key, v))
+            .unwrap_or_default()
+    }
+
+    fn column_width(&self, old: bool) -> usize {
+        let header = if old { "old" } else { "new" };
+        self.rows
+            .iter()
+            .map(|(key, o, n)| Self::cell(key, if old { o } else { n }).width())
+            .chain(Some(header.len()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl View for Diff {
+    fn draw(&self, printer: &Printer) {
+        let old_width = self.column_width(true);
+        let left_width = if printer.size.x >= old_width + 3 + self.column_width(false) {
+            old_width
+        } else {
+            printer.size.x.saturating_sub(3) / 2
+        };
+        let separator_x = left_width + 1;
+        printer.print((0, 0), "old");
+        printer.print((separator_x + 2, 0), "new");
+        for (y, row) in self.rows.iter().enumerate() {
+            let y = y + 1;
+            let color = match Self::change(row) {
+                Change::Same => ColorStyle::primary(),
+                Change::Added => ColorStyle::front(Color::Dark(BaseColor::Green)),
+                Change::Removed => ColorStyle::front(Color::Dark(BaseColor::Red)),
+                Change::Changed => ColorStyle::front(Color::Dark(BaseColor::Yellow)),
+            };
+            let (key, old, new) = row;
+            printer.with_color(color, |p| {
+                p.cropped((left_width, y + 1))
+                    .print((0, y), &Self::cell(key, old));
+                p.print((separator_x, y), "│");
+                p.offset((separator_x + 2, 0))
+                    .print((0, y), &Self::cell(key, new));
+            });
+        }
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        let width = self.column_width(true) + 3 + self.column_width(false);
+        Vec2::new(width, self.rows.len() + 1)
+    }
+}
@@ -0,0 +1,352 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::traits::{Resizable, Scrollable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{
+    Dialog, EditView, LinearLayout, ResizedView, ScrollView, SelectView, TextView,
+};
+use cursive::Vec2;
+
+use views::file_picker::{list_dir, start_dir};
+
+/// Key opening the tree.
+const BROWSE_KEY: Event = Event::CtrlChar('o');
+/// Text of button opening the tree.
+const BROWSE: &str = " [browse]";
+/// Size of the tree.
+const TREE_SIZE: (usize, usize) = (60, 16);
+
+/// Dir shown as a row of the tree.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    path: PathBuf,
+    depth: usize,
+    expanded: bool,
+}
+
+impl Node {
+    fn label(&self) -> String {
+        let name = match self.path.file_name() {
+            Some(name) => format!("{}/", name.to_string_lossy()),
+            None => self.path.display().to_string(),
+        };
+        let mark = if self.expanded { "▾" } else { "▸" };
+        format!("{}{} {}", "  ".repeat(self.depth), mark, name)
+    }
+}
+
+/// Lists subdirs of `dir`.
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    list_dir(dir, "")
+        .into_iter()
+        .filter(|e| e.is_dir)
+        .map(|e| dir.join(e.name))
+        .collect()
+}
+
+/// Modal tree of dirs starting at the root of file system. Dirs are expanded
+/// with `Right` (or `Space`) & collapsed with `Left`.
+///
+/// Dir picked with `Enter` is put to `picked` and the tree closes.
+pub(crate) struct DirTree {
+    view: ResizedView<ScrollView<SelectView<usize>>>,
+    nodes: Vec<Node>,
+    picked: Rc<RefCell<Option<String>>>,
+}
+
+impl DirTree {
+    /// Creates tree expanded down to `dir`, which gets selected.
+    pub(crate) fn new(dir: &Path, picked: Rc<RefCell<Option<String>>>) -> Self {
+        let mut tree = DirTree {
+            view: SelectView::new().scrollable().fixed_size(TREE_SIZE),
+            nodes: Vec::new(),
+            picked,
+        };
+        let mut ancestors = dir.ancestors().collect::<Vec<&Path>>();
+        ancestors.reverse();
+        let mut selected = 0;
+        if let Some(root) = ancestors.first() {
+            tree.nodes.push(Node {
+                path: root.to_path_buf(),
+                depth: 0,
+                expanded: false,
+            });
+        }
+        for ancestor in ancestors.iter().skip(1) {
+            tree.expand(selected);
+            match tree.nodes.iter().position(|n| n.path == *ancestor) {
+                Some(idx) => selected = idx,
+                None => break,
+            }
+        }
+        tree.refresh(selected);
+        tree
+    }
+
+    /// Shows subdirs of node at `idx` below it.
+    fn expand(&mut self, idx: usize) {
+        if self.nodes[idx].expanded {
+            return;
+        }
+        self.nodes[idx].expanded = true;
+        let depth = self.nodes[idx].depth + 1;
+        let children = subdirs(&self.nodes[idx].path)
+            .into_iter()
+            .map(|path| Node {
+                path,
+                depth,
+                expanded: false,
+            })
+            .collect::<Vec<Node>>();
+        self.nodes.splice(idx + 1..idx + 1, children);
+    }
+
+    /// Hides all descendants of node at `idx`.
+    fn collapse(&mut self, idx: usize) {
+        self.nodes[idx].expanded = false;
+        let depth = self.nodes[idx].depth;
+        let end = self.nodes[idx + 1..]
+            .iter()
+            .position(|n| n.depth <= depth)
+            .map(|pos| idx + 1 + pos)
+            .unwrap_or(self.nodes.len());
+        self.nodes.drain(idx + 1..end);
+    }
+
+    /// Gets index of parent of node at `idx`.
+    fn parent(&self, idx: usize) -> Option<usize> {
+        let depth = self.nodes[idx].depth;
+        self.nodes[..idx].iter().rposition(|n| n.depth < depth)
+    }
+
+    /// Shows nodes in the list, selecting one at `selected`.
+    fn refresh(&mut self, selected: usize) {
+        let labels = self.nodes.iter().map(Node::label).collect::<Vec<String>>();
+        let list = self.get_list_mut();
+        list.clear();
+        for (idx, label) in labels.into_iter().enumerate() {
+            list.add_item(label, idx);
+        }
+        list.set_selection(selected);
+        self.view.get_inner_mut().scroll_to_important_area();
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.view.get_inner().get_inner().selected_id()
+    }
+
+    fn get_list_mut(&mut self) -> &mut SelectView<usize> {
+        self.view.get_inner_mut().get_inner_mut()
+    }
+}
+
+impl ViewWrapper for DirTree {
+    wrap_impl!(self.view: ResizedView<ScrollView<SelectView<usize>>>);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let idx = match self.selected() {
+            Some(idx) => idx,
+            None => return self.view.on_event(event),
+        };
+        match event {
+            Event::Key(Key::Right) if self.nodes[idx].expanded => {
+                let depth = self.nodes[idx].depth;
+                let has_children = self.nodes.get(idx + 1).is_some_and(|n| n.depth > depth);
+                self.refresh(if has_children { idx + 1 } else { idx });
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Right) => {
+                self.expand(idx);
+                self.refresh(idx);
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Left) if self.nodes[idx].expanded => {
+                self.collapse(idx);
+                self.refresh(idx);
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Left) => {
+                let parent = self.parent(idx).unwrap_or(idx);
+                self.refresh(parent);
+                EventResult::Consumed(None)
+            }
+            Event::Char(' ') => {
+                if self.nodes[idx].expanded {
+                    self.collapse(idx);
+                } else {
+                    self.expand(idx);
+                }
+                self.refresh(idx);
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) => {
+                let path = self.nodes[idx].path.to_string_lossy().into_owned();
+                *self.picked.borrow_mut() = Some(path);
+                EventResult::with_cb(|s| {
+                    s.pop_layer();
+                })
+            }
+            Event::Key(Key::Esc) => EventResult::with_cb(|s| {
+                s.pop_layer();
+            }),
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+/// Edit view for a dir path with a button opening modal tree of dirs
+/// (also opened with `Ctrl+o`), where the dir can be picked.
+pub struct DirPicker {
+    view: LinearLayout,
+    masked: bool,
+    size: Vec2,
+    // dir picked in tree
+    picked: Rc<RefCell<Option<String>>>,
+}
+
+impl DirPicker {
+    /// Creates a new empty `DirPicker`.
+    pub fn new() -> Self {
+        let view = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(BROWSE));
+        DirPicker {
+            view,
+            masked: false,
+            size: Vec2::zero(),
+            picked: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Sets path.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self
+    }
+
+    /// Gets path.
+    pub fn get_content(&self) -> String {
+        (*self.get_edit_view().get_content()).clone()
+    }
+
+    /// Shows placeholders instead of path when `masked` (tree can't be opened then).
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+    }
+
+    fn browse(&self) -> EventResult {
+        let dir = start_dir(&self.get_content());
+        let dir = dir.canonicalize().unwrap_or(dir);
+        let picked = Rc::clone(&self.picked);
+        EventResult::with_cb(move |s| {
+            let tree = DirTree::new(&dir, Rc::clone(&picked));
+            s.add_layer(Dialog::around(tree).title("Pick dir"));
+        })
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+impl Default for DirPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for DirPicker {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // tree is a separate layer, picked dir is noticed afterwards
+        let picked = self.picked.borrow_mut().take();
+        if let Some(path) = picked {
+            self.get_edit_view_mut().set_content(path);
+        }
+        self.size = size;
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if self.masked {
+            return self.view.on_event(event);
+        }
+        match event {
+            BROWSE_KEY => self.browse(),
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } if position
+                .checked_sub(offset)
+                .is_some_and(|p| p.y == 0 && p.x + BROWSE.len() >= self.size.x) =>
+            {
+                self.browse()
+            }
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_is_expanded_down_to_start_dir() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let picked = Rc::new(RefCell::new(None));
+        let mut tree = DirTree::new(root, Rc::clone(&picked));
+        let idx = tree.selected().unwrap();
+        assert_eq!(tree.nodes[idx].path, root);
+        assert_eq!(tree.nodes[idx].depth, root.ancestors().count() - 1);
+        assert_eq!(tree.nodes[0].label(), "▾ /");
+
+        tree.on_event(Event::Key(Key::Right));
+        tree.on_event(Event::Key(Key::Right));
+        let src = tree.selected().unwrap();
+        assert!(tree.nodes[src].path.starts_with(root));
+        assert_ne!(src, idx);
+
+        // collapsing selects the parent, which collapses with the next press
+        tree.on_event(Event::Key(Key::Left));
+        assert_eq!(tree.selected(), Some(idx));
+        let count = tree.nodes.len();
+        tree.on_event(Event::Key(Key::Left));
+        assert!(tree.nodes.len() < count);
+        assert!(!tree.nodes[idx].expanded);
+
+        tree.on_event(Event::Key(Key::Enter));
+        let expected = root.to_string_lossy().into_owned();
+        assert_eq!(*picked.borrow(), Some(expected));
+    }
+
+    #[test]
+    fn picked_dir_is_set() {
+        let mut view = DirPicker::new().content("a");
+        *view.picked.borrow_mut() = Some("/tmp".to_string());
+        view.layout(Vec2::new(40, 1));
+        assert_eq!(view.get_content(), "/tmp");
+    }
+}
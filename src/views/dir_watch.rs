@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use views::Redraw;
+
+/// Directories aren't checked more often than this, so busy ones don't cause constant refreshes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Notices changes of a directory (files created, removed or renamed in it)
+/// by comparing its modification time.
+#[derive(Debug, Default)]
+struct DirState {
+    dir: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    checked: bool,
+}
+
+impl DirState {
+    /// Checks if `dir` changed since it was checked last time.
+    ///
+    /// Switching to another directory isn't a change, it only starts watching it.
+    fn changed(&mut self, dir: Option<PathBuf>) -> bool {
+        let modified = dir
+            .as_ref()
+            .and_then(|d| fs::metadata(d).ok())
+            .and_then(|m| m.modified().ok());
+        let changed = self.checked && self.dir == dir && self.modified != modified;
+        self.dir = dir;
+        self.modified = modified;
+        self.checked = true;
+        changed
+    }
+}
+
+/// Watches directory listed by a view in background (checking it once per `WATCH_INTERVAL`),
+/// the thread stops when the watch is dropped.
+pub(crate) struct DirWatch {
+    dir: Arc<Mutex<Option<PathBuf>>>,
+    changed: Arc<AtomicBool>,
+}
+
+impl DirWatch {
+    /// Starts watching, changes of directory are shown by `redraw`.
+    pub(crate) fn new(redraw: Redraw) -> Self {
+        let dir = Arc::new(Mutex::new(None));
+        let changed = Arc::new(AtomicBool::new(false));
+        let watched = Arc::downgrade(&dir);
+        let notice = Arc::clone(&changed);
+        thread::spawn(move || {
+            let mut state = DirState::default();
+            while let Some(dir) = watched.upgrade() {
+                let dir = dir.lock().unwrap().clone();
+                if state.changed(dir) {
+                    notice.store(true, Ordering::SeqCst);
+                    redraw.request();
+                }
+                thread::sleep(WATCH_INTERVAL);
+            }
+        });
+        DirWatch { dir, changed }
+    }
+
+    /// Sets directory to watch (`None` stops watching any).
+    pub(crate) fn set_dir(&self, dir: Option<PathBuf>) {
+        *self.dir.lock().unwrap() = dir;
+    }
+
+    /// Checks if the watched directory changed since the last call.
+    pub(crate) fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::File;
+
+    #[test]
+    fn changes_of_dir_are_noticed() {
+        let dir = env::temp_dir().join(format!("fui-dir-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut state = DirState::default();
+
+        assert!(!state.changed(Some(dir.clone())));
+        // modification time may have coarse resolution
+        state.modified = Some(SystemTime::UNIX_EPOCH);
+        File::create(dir.join("new.txt")).unwrap();
+        assert!(state.changed(Some(dir.clone())));
+        assert!(!state.changed(Some(dir.clone())));
+        assert!(!state.changed(Some(env::temp_dir())));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
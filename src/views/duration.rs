@@ -0,0 +1,206 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::theme::ColorStyle;
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, TextView};
+
+/// Units of duration with their length in seconds, from the biggest one.
+const UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+/// Parses duration like `1h30m`, `90s` or `2d` into total seconds.
+///
+/// Parts can be separated with spaces, each unit can be used once
+/// and a lone number (like `45`) is in seconds.
+pub(crate) fn parse(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Duration is empty".to_string());
+    }
+    if text.chars().all(|c| c.is_ascii_digit()) {
+        return text.parse().map_err(|_| "Duration is too long".to_string());
+    }
+    let mut total: u64 = 0;
+    let mut used = Vec::new();
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let secs = match UNITS
+            .iter()
+            .find(|&&(unit, _)| unit == c.to_ascii_lowercase())
+        {
+            Some(&(_, secs)) => secs,
+            None => return Err(format!("Unknown unit {:?}, use d, h, m or s", c)),
+        };
+        if number.is_empty() {
+            return Err(format!("Unit {:?} has no number", c));
+        }
+        if used.contains(&secs) {
+            return Err(format!("Unit {:?} is repeated", c));
+        }
+        used.push(secs);
+        total = number
+            .parse::<u64>()
+            .ok()
+            .and_then(|n| n.checked_mul(secs))
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| "Duration is too long".to_string())?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(format!("Number {} has no unit", number));
+    }
+    Ok(total)
+}
+
+/// Formats `secs` as normalized duration like `1h30m` (`0s` for zero).
+pub(crate) fn format(secs: u64) -> String {
+    if secs == 0 {
+        return "0s".to_string();
+    }
+    let mut left = secs;
+    let mut text = String::new();
+    for &(unit, unit_secs) in UNITS.iter() {
+        if left >= unit_secs {
+            text.push_str(&format!("{}{}", left / unit_secs, unit));
+            left %= unit_secs;
+        }
+    }
+    text
+}
+
+/// Edit view for duration (like `1h30m`) showing it normalized or its error below it.
+pub struct Duration {
+    view: LinearLayout,
+    masked: bool,
+}
+
+impl Duration {
+    /// Creates a new empty `Duration`.
+    pub fn new() -> Self {
+        let view = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(TextView::new("").style(ColorStyle::secondary()));
+        Duration {
+            view,
+            masked: false,
+        }
+    }
+
+    /// Sets typed duration.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self.refresh_preview();
+        self
+    }
+
+    /// Gets typed duration.
+    pub fn get_content(&self) -> Rc<String> {
+        self.get_edit_view().get_content()
+    }
+
+    /// Shows placeholders instead of typed duration and hides preview when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        self.refresh_preview();
+    }
+
+    fn refresh_preview(&mut self) {
+        let content = self.get_content();
+        let preview = if content.trim().is_empty() || self.masked {
+            String::new()
+        } else {
+            match parse(&content) {
+                Ok(secs) => format!("= {} ({} s)", format(secs), secs),
+                Err(e) => e,
+            }
+        };
+        self.get_preview_view_mut().set_content(preview);
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditView>()
+            .unwrap()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+
+    fn get_preview_view_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+}
+
+impl Default for Duration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Duration {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.refresh_preview();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse("1h30m"), Ok(5_400));
+        assert_eq!(parse("90s"), Ok(90));
+        assert_eq!(parse(" 2d 4H "), Ok(187_200));
+        assert_eq!(parse("45"), Ok(45));
+        assert!(parse("").is_err());
+        assert!(parse("1h1h").is_err());
+        assert!(parse("1w").is_err());
+        assert!(parse("h").is_err());
+        assert!(parse("1h30").is_err());
+        assert!(parse("999999999999999999d").is_err());
+        assert_eq!(format(5_400), "1h30m");
+        assert_eq!(format(90), "1m30s");
+        assert_eq!(format(172_801), "2d1s");
+        assert_eq!(format(0), "0s");
+    }
+
+    #[test]
+    fn preview_follows_typed_duration() {
+        let mut view = Duration::new().content("1h");
+        view.on_event(Event::Char('5'));
+        let preview = |view: &mut Duration| {
+            view.get_preview_view_mut()
+                .get_content()
+                .source()
+                .to_string()
+        };
+        assert_eq!(preview(&mut view), "Number 5 has no unit");
+        view.on_event(Event::Char('m'));
+        assert_eq!(preview(&mut view), "= 1h5m (3900 s)");
+    }
+}
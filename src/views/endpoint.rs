@@ -0,0 +1,279 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(feature = "endpoint-ping")]
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(feature = "endpoint-ping")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "endpoint-ping")]
+use std::sync::Arc;
+#[cfg(feature = "endpoint-ping")]
+use std::thread;
+#[cfg(feature = "endpoint-ping")]
+use std::time::Duration;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, ResizedView, TextContent, TextView};
+
+#[cfg(feature = "endpoint-ping")]
+use views::Redraw;
+
+/// Width of port's input.
+const PORT_WIDTH: usize = 6;
+/// How long reachability check waits for connection.
+#[cfg(feature = "endpoint-ping")]
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Pair of inputs for host and port of a network endpoint (like `localhost:8080`).
+///
+/// With feature `endpoint-ping` it can also check in background whether
+/// the endpoint accepts connections (see [ping]).
+///
+/// [ping]: #method.ping
+pub struct Endpoint {
+    view: LinearLayout,
+    status: TextContent,
+    #[cfg(feature = "endpoint-ping")]
+    masked: bool,
+    #[cfg(feature = "endpoint-ping")]
+    ping: bool,
+    // identifies the latest check, so results of older ones are dropped
+    #[cfg(feature = "endpoint-ping")]
+    ping_generation: Arc<AtomicUsize>,
+    #[cfg(feature = "endpoint-ping")]
+    redraw: Redraw,
+}
+
+impl Endpoint {
+    /// Creates a new empty `Endpoint`.
+    pub fn new() -> Self {
+        let status = TextContent::new("");
+        let inputs = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(":"))
+            .child(EditView::new().fixed_width(PORT_WIDTH));
+        let view = LinearLayout::vertical()
+            .child(inputs)
+            .child(TextView::new_with_content(status.clone()));
+        Endpoint {
+            view,
+            status,
+            #[cfg(feature = "endpoint-ping")]
+            masked: false,
+            #[cfg(feature = "endpoint-ping")]
+            ping: false,
+            #[cfg(feature = "endpoint-ping")]
+            ping_generation: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "endpoint-ping")]
+            redraw: Redraw::default(),
+        }
+    }
+
+    /// Sets endpoint, e.g. `localhost:8080` or `[::1]:8080`.
+    pub fn content(mut self, content: &str) -> Self {
+        let (host, port) = split(content);
+        self.get_edit_view_mut(0).set_content(host);
+        self.get_edit_view_mut(2).set_content(port);
+        self
+    }
+
+    /// Gets endpoint joined from host and port.
+    pub fn get_content(&self) -> String {
+        let host = self.get_edit_view(0).get_content();
+        let port = self.get_edit_view(2).get_content();
+        if port.is_empty() {
+            (*host).clone()
+        } else if host.contains(':') && !host.starts_with('[') {
+            format!("[{}]:{}", host, port)
+        } else {
+            format!("{}:{}", host, port)
+        }
+    }
+
+    /// Checks if endpoint accepts connections when the view loses focus,
+    /// showing the result below it.
+    #[cfg(feature = "endpoint-ping")]
+    pub fn ping(mut self, enabled: bool) -> Self {
+        self.ping = enabled;
+        self
+    }
+
+    /// Shows placeholders instead of typed endpoint and hides status when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        #[cfg(feature = "endpoint-ping")]
+        {
+            self.masked = masked;
+        }
+        self.get_edit_view_mut(0).set_secret(masked);
+        self.get_edit_view_mut(2).set_secret(masked);
+        self.status.set_content("");
+    }
+
+    #[cfg(feature = "endpoint-ping")]
+    fn spawn_ping(&self) {
+        let generation = self.ping_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let (host, port) = match parse(&self.get_content()) {
+            Ok(endpoint) => endpoint,
+            Err(_) => {
+                self.status.set_content("");
+                return;
+            }
+        };
+        self.status.set_content("Checking...");
+        let status = self.status.clone();
+        let latest = Arc::clone(&self.ping_generation);
+        let redraw = self.redraw.clone();
+        thread::spawn(move || {
+            let reachable = (host.trim_matches(|c| c == '[' || c == ']'), port)
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .map(|addr| TcpStream::connect_timeout(&addr, PING_TIMEOUT).is_ok())
+                .unwrap_or(false);
+            if latest.load(Ordering::SeqCst) == generation {
+                status.set_content(if reachable {
+                    "Reachable"
+                } else {
+                    "Unreachable"
+                });
+                redraw.request();
+            }
+        });
+    }
+
+    fn get_edit_view(&self, idx: usize) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+            .get_child(idx)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self, idx: usize) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+            .get_child_mut(idx)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+/// Splits `endpoint` to host and port without validating them.
+fn split(endpoint: &str) -> (&str, &str) {
+    let endpoint = endpoint.trim();
+    if endpoint.starts_with('[') {
+        // [IPv6]:port
+        return match endpoint.find("]:") {
+            Some(idx) => (&endpoint[..idx + 1], &endpoint[idx + 2..]),
+            None => (endpoint, ""),
+        };
+    }
+    match endpoint.rfind(':') {
+        Some(idx) if endpoint[..idx].find(':').is_none() => {
+            (&endpoint[..idx], &endpoint[idx + 1..])
+        }
+        _ => (endpoint, ""),
+    }
+}
+
+/// Checks if `host` is a hostname, IPv4 or IPv6 address (in brackets).
+fn is_valid_host(host: &str) -> bool {
+    if host.starts_with('[') && host.ends_with(']') {
+        return host[1..host.len() - 1].parse::<Ipv6Addr>().is_ok();
+    }
+    if host.parse::<Ipv4Addr>().is_ok() {
+        return true;
+    }
+    host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Parses `endpoint` (like `localhost:8080`) to host and port.
+pub(crate) fn parse(endpoint: &str) -> Result<(String, u16), String> {
+    let (host, port) = split(endpoint);
+    if host.is_empty() {
+        return Err("Host is missing".to_string());
+    }
+    if !is_valid_host(host) {
+        return Err(format!("Invalid host: {}", host));
+    }
+    if port.is_empty() {
+        return Err("Port is missing".to_string());
+    }
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok((host.to_string(), port)),
+        _ => Err(format!("Invalid port: {}", port)),
+    }
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Endpoint {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        #[cfg(feature = "endpoint-ping")]
+        {
+            if event == Event::FocusLost && self.ping && !self.masked {
+                self.spawn_ping();
+            }
+        }
+        let result = self.view.on_event(event);
+        #[cfg(feature = "endpoint-ping")]
+        let result = self.redraw.learn(result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_is_split_to_host_and_port() {
+        let view = Endpoint::new().content("[::1]:8080");
+        assert_eq!(view.get_edit_view(0).get_content().as_str(), "[::1]");
+        assert_eq!(view.get_edit_view(2).get_content().as_str(), "8080");
+        assert_eq!(view.get_content(), "[::1]:8080");
+
+        assert_eq!(split("localhost:80"), ("localhost", "80"));
+        assert_eq!(split("localhost"), ("localhost", ""));
+        assert_eq!(split("::1"), ("::1", ""));
+    }
+
+    #[test]
+    fn endpoint_is_validated() {
+        assert_eq!(parse("db.local:5432"), Ok(("db.local".to_string(), 5432)));
+        assert_eq!(parse("10.0.0.1:80"), Ok(("10.0.0.1".to_string(), 80)));
+        assert_eq!(parse("[::1]:443"), Ok(("[::1]".to_string(), 443)));
+        assert_eq!(parse(":80"), Err("Host is missing".to_string()));
+        assert_eq!(parse("db_1:80"), Err("Invalid host: db_1".to_string()));
+        assert_eq!(parse("db"), Err("Port is missing".to_string()));
+        assert_eq!(parse("db:70000"), Err("Invalid port: 70000".to_string()));
+        assert_eq!(parse("db:0"), Err("Invalid port: 0".to_string()));
+    }
+}
@@ -0,0 +1,106 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, ResizedView, TextView};
+
+use expr;
+
+/// Edit view evaluating typed expression (like `2h+30m`) and showing its result next to it.
+pub struct Expr {
+    view: LinearLayout,
+    masked: bool,
+}
+
+impl Expr {
+    /// Creates a new empty `Expr`.
+    pub fn new() -> Self {
+        let view = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(""));
+        Expr {
+            view,
+            masked: false,
+        }
+    }
+
+    /// Sets typed expression.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self.refresh_result();
+        self
+    }
+
+    /// Gets typed expression.
+    pub fn get_content(&self) -> Rc<String> {
+        self.get_edit_view().get_content()
+    }
+
+    /// Shows placeholders instead of typed expression and its result when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        self.refresh_result();
+    }
+
+    fn refresh_result(&mut self) {
+        let content = self.get_content();
+        let result = if content.trim().is_empty() {
+            String::new()
+        } else if self.masked {
+            " = ***".to_string()
+        } else {
+            match expr::eval(&content) {
+                Ok(number) => format!(" = {}", expr::to_value(number)),
+                Err(_) => " = ?".to_string(),
+            }
+        };
+        self.get_result_view_mut().set_content(result);
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    fn get_result_view_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+}
+
+impl Default for Expr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Expr {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.refresh_result();
+        result
+    }
+}
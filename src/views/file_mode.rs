@@ -0,0 +1,210 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{Checkbox, EditView, HideableView, LinearLayout, TextView};
+
+/// Owners of permissions in order of mode's digits.
+const OWNERS: [&str; 3] = ["user", "group", "other"];
+/// Mask of permission bits toggled in grid.
+const PERMISSION_BITS: u32 = 0o777;
+/// The biggest mode (including setuid, setgid and sticky bits).
+const MAX_MODE: u32 = 0o7777;
+
+/// Edit view for octal file mode (like `644`) with a grid of `rwx` toggles below it.
+///
+/// Typed mode and toggles are kept in sync.
+pub struct FileMode {
+    view: LinearLayout,
+    // permission bits shown in grid after last sync
+    grid_mode: u32,
+}
+
+impl FileMode {
+    /// Creates a new empty `FileMode`.
+    pub fn new() -> Self {
+        let mut grid = LinearLayout::vertical().child(TextView::new("       r   w   x"));
+        for owner in OWNERS.iter() {
+            grid.add_child(
+                LinearLayout::horizontal()
+                    .child(TextView::new(format!("{:<6}", owner)))
+                    .child(Checkbox::new())
+                    .child(TextView::new(" "))
+                    .child(Checkbox::new())
+                    .child(TextView::new(" "))
+                    .child(Checkbox::new()),
+            );
+        }
+        let view = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(HideableView::new(grid));
+        FileMode { view, grid_mode: 0 }
+    }
+
+    /// Sets typed mode.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        if let Ok(mode) = parse(content) {
+            self.set_grid(mode);
+        }
+        self
+    }
+
+    /// Gets typed mode.
+    pub fn get_content(&self) -> Rc<String> {
+        self.get_edit_view().get_content()
+    }
+
+    /// Shows placeholders instead of typed mode and hides grid when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.get_edit_view_mut().set_secret(masked);
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<HideableView<LinearLayout>>()
+            .unwrap()
+            .set_visible(!masked);
+    }
+
+    fn set_grid(&mut self, mode: u32) {
+        for bit in 0..9 {
+            let checked = mode & mode_bit(bit) != 0;
+            self.get_checkbox_mut(bit).set_checked(checked);
+        }
+        self.grid_mode = mode & PERMISSION_BITS;
+    }
+
+    fn get_grid_mode(&mut self) -> u32 {
+        (0..9)
+            .filter(|&bit| self.get_checkbox_mut(bit).is_checked())
+            .fold(0, |mode, bit| mode | mode_bit(bit))
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditView>()
+            .unwrap()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+
+    fn get_checkbox_mut(&mut self, bit: usize) -> &mut Checkbox {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<HideableView<LinearLayout>>()
+            .unwrap()
+            .get_inner_mut()
+            .get_child_mut(1 + bit / 3)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+            .get_child_mut(1 + (bit % 3) * 2)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Checkbox>()
+            .unwrap()
+    }
+}
+
+/// Gets bit of mode represented by `nth` toggle of `rwxrwxrwx` grid.
+fn mode_bit(nth: usize) -> u32 {
+    1 << (8 - nth)
+}
+
+/// Formats `mode` as octal with at least 3 digits.
+pub(crate) fn format(mode: u32) -> String {
+    format!("{:03o}", mode)
+}
+
+/// Parses octal mode (like `644` or `0755`).
+pub(crate) fn parse(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    let digits = text.strip_prefix("0o").unwrap_or(text);
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| ('0'..='7').contains(&c)) {
+        return Err(format!("Invalid octal mode: {}", text));
+    }
+    let mode = u32::from_str_radix(digits, 8).unwrap();
+    if mode > MAX_MODE {
+        return Err(format!("Invalid octal mode: {}", text));
+    }
+    Ok(mode)
+}
+
+impl Default for FileMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for FileMode {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let before = self.get_content();
+        let result = self.view.on_event(event);
+        let typed = parse(&self.get_content());
+        if before != self.get_content() {
+            if let Ok(mode) = typed {
+                self.set_grid(mode);
+            }
+        } else {
+            let grid_mode = self.get_grid_mode();
+            if grid_mode != self.grid_mode {
+                let special_bits = typed.map(|m| m & !PERMISSION_BITS).unwrap_or(0);
+                let content = format(special_bits | grid_mode);
+                self.get_edit_view_mut().set_content(content);
+                self.grid_mode = grid_mode;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::event::Key;
+
+    #[test]
+    fn octal_input_and_grid_are_synced() {
+        let mut view = FileMode::new().content("640");
+        assert_eq!(view.get_grid_mode(), 0o640);
+
+        // toggle "other: r"
+        view.get_checkbox_mut(6).set_checked(true);
+        view.on_event(Event::Refresh);
+        assert_eq!(*view.get_content(), "644");
+
+        view.on_event(Event::Key(Key::End));
+        view.on_event(Event::Key(Key::Backspace));
+        view.on_event(Event::Char('0'));
+        assert_eq!(view.get_grid_mode(), 0o640);
+    }
+
+    #[test]
+    fn parses_octal_modes() {
+        assert_eq!(parse("644"), Ok(0o644));
+        assert_eq!(parse("0o755"), Ok(0o755));
+        assert_eq!(parse("4755"), Ok(0o4755));
+        assert!(parse("8").is_err());
+        assert!(parse("17777").is_err());
+        assert!(parse("").is_err());
+        assert_eq!(format(0o4755), "4755");
+        assert_eq!(format(0o7), "007");
+    }
+}
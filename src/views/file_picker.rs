@@ -0,0 +1,375 @@
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::theme::ColorStyle;
+use cursive::traits::{Resizable, Scrollable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{
+    Dialog, EditView, LinearLayout, ResizedView, ScrollView, SelectView, TextContent, TextView,
+};
+use cursive::Vec2;
+
+use utils::expand_path;
+
+/// Key opening the browser.
+const BROWSE_KEY: Event = Event::CtrlChar('o');
+/// Text of button opening the browser.
+const BROWSE: &str = " [browse]";
+/// Size of the browser's list of entries.
+const LIST_SIZE: (usize, usize) = (60, 16);
+
+/// Item of a dir listing.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) name: String,
+    pub(crate) is_dir: bool,
+}
+
+/// Lists entries of `dir` containing `filter` (ignoring case), dirs first.
+///
+/// Hidden entries are listed only when `filter` starts with `.`.
+pub(crate) fn list_dir(dir: &Path, filter: &str) -> Vec<Entry> {
+    let filter = filter.to_lowercase();
+    let mut entries = fs::read_dir(dir)
+        .map(|items| {
+            items
+                .filter_map(Result::ok)
+                .map(|item| Entry {
+                    name: item.file_name().to_string_lossy().into_owned(),
+                    is_dir: item.path().is_dir(),
+                })
+                .filter(|e| !e.name.starts_with('.') || filter.starts_with('.'))
+                .filter(|e| e.name.to_lowercase().contains(&filter))
+                .collect::<Vec<Entry>>()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+/// Gets dir where browsing `path` starts: the path itself if it's a dir,
+/// its parent if it exists or the current dir.
+pub(crate) fn start_dir(path: &str) -> PathBuf {
+    let path = PathBuf::from(expand_path(path));
+    if path.is_dir() {
+        return path;
+    }
+    match path.parent() {
+        Some(parent) if parent.is_dir() => parent.to_path_buf(),
+        _ => env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    }
+}
+
+/// Modal browser of dirs, which are entered with `Enter` & left with `Backspace`.
+/// Typed characters filter entries.
+///
+/// File picked with `Enter` is put to `picked` and the browser closes.
+pub(crate) struct FileBrowser {
+    view: LinearLayout,
+    dir: PathBuf,
+    filter: String,
+    header: TextContent,
+    picked: Rc<RefCell<Option<String>>>,
+}
+
+impl FileBrowser {
+    pub(crate) fn new(dir: PathBuf, picked: Rc<RefCell<Option<String>>>) -> Self {
+        let header = TextContent::new("");
+        let list = SelectView::<Entry>::new()
+            .scrollable()
+            .fixed_size(LIST_SIZE);
+        let view = LinearLayout::vertical()
+            .child(TextView::new_with_content(header.clone()).style(ColorStyle::secondary()))
+            .child(list);
+        let mut browser = FileBrowser {
+            view,
+            dir,
+            filter: String::new(),
+            header,
+            picked,
+        };
+        browser.refresh();
+        let _ = browser.view.set_focus_index(1);
+        browser
+    }
+
+    /// Lists entries of the current dir matching filter.
+    fn refresh(&mut self) {
+        let mut header = self.dir.display().to_string();
+        if !self.filter.is_empty() {
+            header.push_str(&format!("  (filter: {})", self.filter));
+        }
+        self.header.set_content(header);
+        let entries = list_dir(&self.dir, &self.filter);
+        let has_parent = self.dir.parent().is_some();
+        let list = self.get_list_mut();
+        list.clear();
+        if has_parent {
+            let parent = Entry {
+                name: "..".to_string(),
+                is_dir: true,
+            };
+            list.add_item("../", parent);
+        }
+        for entry in entries {
+            let label = if entry.is_dir {
+                format!("{}/", entry.name)
+            } else {
+                entry.name.clone()
+            };
+            list.add_item(label, entry);
+        }
+    }
+
+    fn enter_dir(&mut self, dir: PathBuf) {
+        self.dir = dir;
+        self.filter.clear();
+        self.refresh();
+    }
+
+    /// Enters selected dir or picks selected file.
+    fn open_selected(&mut self) -> EventResult {
+        let entry = match self.get_list_mut().selection() {
+            Some(entry) => entry,
+            None => return EventResult::Consumed(None),
+        };
+        if entry.name == ".." {
+            let parent = self.dir.parent().map(Path::to_path_buf);
+            if let Some(parent) = parent {
+                self.enter_dir(parent);
+            }
+        } else if entry.is_dir {
+            let dir = self.dir.join(&entry.name);
+            self.enter_dir(dir);
+        } else {
+            let path = self.dir.join(&entry.name);
+            *self.picked.borrow_mut() = Some(path.to_string_lossy().into_owned());
+            return EventResult::with_cb(|s| {
+                s.pop_layer();
+            });
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn get_list_mut(&mut self) -> &mut SelectView<Entry> {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<ScrollView<SelectView<Entry>>>>()
+            .unwrap()
+            .get_inner_mut()
+            .get_inner_mut()
+    }
+}
+
+impl ViewWrapper for FileBrowser {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Enter) => self.open_selected(),
+            Event::Key(Key::Backspace) if self.filter.is_empty() => {
+                let parent = self.dir.parent().map(Path::to_path_buf);
+                if let Some(parent) = parent {
+                    self.enter_dir(parent);
+                }
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Backspace) => {
+                self.filter.pop();
+                self.refresh();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Esc) => EventResult::with_cb(|s| {
+                s.pop_layer();
+            }),
+            Event::Char(c) => {
+                self.filter.push(c);
+                self.refresh();
+                EventResult::Consumed(None)
+            }
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+/// Edit view for a file path with a button opening modal browser of dirs
+/// (also opened with `Ctrl+o`), where the file can be picked.
+pub struct FilePicker {
+    view: LinearLayout,
+    masked: bool,
+    size: Vec2,
+    // file picked in browser
+    picked: Rc<RefCell<Option<String>>>,
+}
+
+impl FilePicker {
+    /// Creates a new empty `FilePicker`.
+    pub fn new() -> Self {
+        let view = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(BROWSE));
+        FilePicker {
+            view,
+            masked: false,
+            size: Vec2::zero(),
+            picked: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Sets path.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self
+    }
+
+    /// Gets path.
+    pub fn get_content(&self) -> String {
+        (*self.get_edit_view().get_content()).clone()
+    }
+
+    /// Shows placeholders instead of path when `masked` (browser can't be opened then).
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+    }
+
+    fn browse(&self) -> EventResult {
+        let dir = start_dir(&self.get_content());
+        let picked = Rc::clone(&self.picked);
+        EventResult::with_cb(move |s| {
+            let browser = FileBrowser::new(dir.clone(), Rc::clone(&picked));
+            s.add_layer(Dialog::around(browser).title("Pick file"));
+        })
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+impl Default for FilePicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for FilePicker {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // browser is a separate layer, picked file is noticed afterwards
+        let picked = self.picked.borrow_mut().take();
+        if let Some(path) = picked {
+            self.get_edit_view_mut().set_content(path);
+        }
+        self.size = size;
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if self.masked {
+            return self.view.on_event(event);
+        }
+        match event {
+            BROWSE_KEY => self.browse(),
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } if position
+                .checked_sub(offset)
+                .is_some_and(|p| p.y == 0 && p.x + BROWSE.len() >= self.size.x) =>
+            {
+                self.browse()
+            }
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dir_is_listed_with_dirs_first() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let entries = list_dir(dir, "");
+        let first_file = entries.iter().position(|e| !e.is_dir).unwrap();
+        assert!(entries[..first_file].iter().all(|e| e.is_dir));
+        assert!(entries.iter().any(|e| e.name == "src" && e.is_dir));
+        assert!(entries.iter().all(|e| !e.name.starts_with('.')));
+
+        let filtered = list_dir(dir, "CARGO.T");
+        assert_eq!(
+            filtered,
+            vec![Entry {
+                name: "Cargo.toml".to_string(),
+                is_dir: false,
+            }]
+        );
+        assert!(list_dir(dir, ".git").iter().any(|e| e.name == ".gitignore"));
+    }
+
+    #[test]
+    fn browser_navigates_dirs_and_picks_file() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let picked = Rc::new(RefCell::new(None));
+        let mut browser = FileBrowser::new(root.to_path_buf(), Rc::clone(&picked));
+        browser.layout(Vec2::new(80, 20));
+        for c in "src".chars() {
+            browser.on_event(Event::Char(c));
+        }
+        // `..` goes first
+        browser.on_event(Event::Key(Key::Down));
+        browser.on_event(Event::Key(Key::Enter));
+        assert_eq!(browser.dir, root.join("src"));
+        assert!(browser.filter.is_empty());
+
+        for c in "lib.rs".chars() {
+            browser.on_event(Event::Char(c));
+        }
+        browser.on_event(Event::Key(Key::Down));
+        browser.on_event(Event::Key(Key::Enter));
+        let expected = root.join("src/lib.rs").to_string_lossy().into_owned();
+        assert_eq!(*picked.borrow(), Some(expected));
+
+        // the first removes filter's character, the next one leaves dir
+        browser.on_event(Event::Key(Key::Backspace));
+        assert_eq!(browser.filter, "lib.r");
+        browser.filter.clear();
+        browser.on_event(Event::Key(Key::Backspace));
+        assert_eq!(browser.dir, root);
+    }
+
+    #[test]
+    fn picked_file_is_set() {
+        let mut view = FilePicker::new().content("a.txt");
+        *view.picked.borrow_mut() = Some("/tmp/b.txt".to_string());
+        view.layout(Vec2::new(40, 1));
+        assert_eq!(view.get_content(), "/tmp/b.txt");
+        assert_eq!(start_dir("./src/lib.rs"), PathBuf::from("./src"));
+    }
+}
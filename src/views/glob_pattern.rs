@@ -0,0 +1,202 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use cursive::event::{Event, EventResult};
+use cursive::theme::ColorStyle;
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, TextContent, TextView};
+use glob::{glob, Pattern};
+
+use utils::expand_path;
+use views::Redraw;
+
+/// Counting of matches stops after this many paths.
+pub(crate) const MATCH_LIMIT: usize = 1000;
+
+/// Checks `pattern` is a valid glob.
+pub(crate) fn parse(pattern: &str) -> Result<Pattern, String> {
+    Pattern::new(&expand_path(pattern)).map_err(|e| format!("Invalid glob pattern: {}", e))
+}
+
+/// Counts paths matching `pattern` (up to `limit`), stops early (returning `None`)
+/// when `is_stale` says the result isn't needed anymore.
+pub(crate) fn count_matches<F>(pattern: &str, limit: usize, is_stale: F) -> Option<usize>
+where
+    F: Fn() -> bool,
+{
+    let paths = glob(&expand_path(pattern)).ok()?;
+    let mut count = 0;
+    for _ in paths.filter_map(Result::ok) {
+        if is_stale() {
+            return None;
+        }
+        count += 1;
+        if count >= limit {
+            break;
+        }
+    }
+    Some(count)
+}
+
+/// Describes `count` of matched paths for hint below the input.
+pub(crate) fn describe_count(count: usize, limit: usize) -> String {
+    match count {
+        0 => "matches no files".to_string(),
+        1 => "matches 1 file".to_string(),
+        _ if count >= limit => format!("matches {}+ files", limit),
+        _ => format!("matches {} files", count),
+    }
+}
+
+/// Edit view for a glob pattern (like `logs/**/*.gz`) showing below
+/// how many files the pattern matches.
+///
+/// Matches are counted in background after each change (up to a limit).
+pub struct GlobPattern {
+    view: LinearLayout,
+    hint: TextContent,
+    counted: String,
+    masked: bool,
+    // identifies the latest count, so results of older ones are dropped
+    generation: Arc<AtomicUsize>,
+    redraw: Redraw,
+}
+
+impl GlobPattern {
+    /// Creates a new empty `GlobPattern`.
+    pub fn new() -> Self {
+        let hint = TextContent::new("");
+        let view = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(TextView::new_with_content(hint.clone()).style(ColorStyle::secondary()));
+        GlobPattern {
+            view,
+            hint,
+            counted: String::new(),
+            masked: false,
+            generation: Arc::new(AtomicUsize::new(0)),
+            redraw: Redraw::default(),
+        }
+    }
+
+    /// Sets pattern.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self.refresh_hint();
+        self
+    }
+
+    /// Gets pattern.
+    pub fn get_content(&self) -> String {
+        (*self.get_edit_view().get_content()).clone()
+    }
+
+    /// Shows placeholders instead of typed pattern and hides hint when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        self.counted = String::new();
+        self.refresh_hint();
+    }
+
+    /// Starts counting matches of changed pattern.
+    fn refresh_hint(&mut self) {
+        let pattern = self.get_content();
+        if pattern == self.counted {
+            return;
+        }
+        self.counted = pattern.clone();
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.masked || pattern.trim().is_empty() {
+            self.hint.set_content("");
+            return;
+        }
+        if let Err(e) = parse(&pattern) {
+            self.hint.set_content(e);
+            return;
+        }
+        self.hint.set_content("counting matches...");
+        let hint = self.hint.clone();
+        let latest = Arc::clone(&self.generation);
+        let redraw = self.redraw.clone();
+        thread::spawn(move || {
+            let is_stale = || latest.load(Ordering::SeqCst) != generation;
+            if let Some(count) = count_matches(&pattern, MATCH_LIMIT, is_stale) {
+                if !is_stale() {
+                    hint.set_content(describe_count(count, MATCH_LIMIT));
+                    redraw.request();
+                }
+            }
+        });
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditView>()
+            .unwrap()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+}
+
+impl Default for GlobPattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for GlobPattern {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.refresh_hint();
+        self.redraw.learn(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_are_counted_up_to_limit() {
+        let pattern = format!("{}/src/*.rs", env!("CARGO_MANIFEST_DIR"));
+        let count = count_matches(&pattern, MATCH_LIMIT, || false).unwrap();
+        assert!(count > 1);
+        assert_eq!(count_matches(&pattern, 2, || false), Some(2));
+        assert_eq!(count_matches(&pattern, MATCH_LIMIT, || true), None);
+
+        assert_eq!(describe_count(0, 10), "matches no files");
+        assert_eq!(describe_count(1, 10), "matches 1 file");
+        assert_eq!(describe_count(7, 10), "matches 7 files");
+        assert_eq!(describe_count(10, 10), "matches 10+ files");
+    }
+
+    #[test]
+    fn invalid_pattern_is_shown_in_hint() {
+        let view = GlobPattern::new().content("src/[a");
+        assert!(view
+            .hint
+            .get_content()
+            .source()
+            .starts_with("Invalid glob pattern"));
+
+        let mut view = GlobPattern::new().content("");
+        assert_eq!(view.hint.get_content().source(), "");
+        view.set_masked(true);
+        assert_eq!(view.hint.get_content().source(), "");
+    }
+}
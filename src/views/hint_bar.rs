@@ -0,0 +1,64 @@
+use cursive::event::Event;
+use cursive::theme::Effect;
+use cursive::utils::markup::StyledString;
+use cursive::view::ViewWrapper;
+use cursive::views::TextView;
+
+/// Bar listing keys with descriptions of what they do, shown below forms.
+///
+/// Hints are rendered from keys' events (see [FormView::hints]), so they stay
+/// in sync with key bindings & localized labels.
+///
+/// [FormView::hints]: ../form/struct.FormView.html#method.hints
+pub struct HintBar {
+    view: TextView,
+}
+
+impl HintBar {
+    /// Creates a new `HintBar` from pairs of key & description.
+    pub fn new(hints: &[(Event, String)]) -> Self {
+        let mut text = StyledString::new();
+        for (idx, (key, description)) in hints.iter().enumerate() {
+            if idx > 0 {
+                text.append_plain("  ");
+            }
+            text.append_styled(key_name(key), Effect::Reverse);
+            text.append_plain(format!(" {}", description));
+        }
+        HintBar {
+            view: TextView::new(text),
+        }
+    }
+}
+
+impl ViewWrapper for HintBar {
+    wrap_impl!(self.view: TextView);
+}
+
+/// Names key pressed to trigger `event`, like `Ctrl+f` or `F2`.
+pub(crate) fn key_name(event: &Event) -> String {
+    match event {
+        Event::Char(c) => c.to_string(),
+        Event::CtrlChar(c) => format!("Ctrl+{}", c),
+        Event::AltChar(c) => format!("Alt+{}", c),
+        Event::Key(key) => format!("{:?}", key),
+        Event::Shift(key) => format!("Shift+{:?}", key),
+        Event::Alt(key) => format!("Alt+{:?}", key),
+        Event::Ctrl(key) => format!("Ctrl+{:?}", key),
+        _ => format!("{:?}", event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::event::Key;
+
+    #[test]
+    fn keys_are_named() {
+        assert_eq!(key_name(&Event::CtrlChar('f')), "Ctrl+f");
+        assert_eq!(key_name(&Event::Key(Key::F2)), "F2");
+        assert_eq!(key_name(&Event::Shift(Key::Tab)), "Shift+Tab");
+        assert_eq!(key_name(&Event::Char('f')), "f");
+    }
+}
@@ -0,0 +1,97 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::view::{View, ViewWrapper};
+
+use views::Number;
+
+/// Spin box for integers, adjusted with `[-]` `[+]` buttons (or `-` and `+` keys).
+///
+/// Only digits (and sign) can be typed, steps are kept within [bounds].
+///
+/// [bounds]: #method.bounds
+pub struct IntNumber {
+    view: Number,
+}
+
+impl IntNumber {
+    /// Creates a new empty `IntNumber` changed by 1 with buttons.
+    pub fn new() -> Self {
+        IntNumber {
+            view: Number::new(),
+        }
+    }
+
+    /// Sets how much the number changes with a button.
+    pub fn step(mut self, step: i64) -> Self {
+        self.view = self.view.step(step as f64);
+        self
+    }
+
+    /// Sets the lowest (`min`) & the highest (`max`) number reachable with buttons.
+    pub fn bounds(mut self, min: Option<i64>, max: Option<i64>) -> Self {
+        self.view = self
+            .view
+            .bounds(min.map(|v| v as f64), max.map(|v| v as f64));
+        self
+    }
+
+    /// Sets typed number.
+    pub fn content(mut self, content: &str) -> Self {
+        self.view = self.view.content(content);
+        self
+    }
+
+    /// Gets typed number.
+    pub fn get_content(&self) -> Rc<String> {
+        self.view.get_content()
+    }
+
+    /// Shows placeholders instead of typed number when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.view.set_masked(masked);
+    }
+}
+
+impl Default for IntNumber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for IntNumber {
+    wrap_impl!(self.view: Number);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Char(c) if !(c.is_ascii_digit() || c == '-' || c == '+') => EventResult::Ignored,
+            _ => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::direction::Direction;
+
+    #[test]
+    fn only_integers_can_be_typed() {
+        let mut view = IntNumber::new();
+        view.take_focus(Direction::none()).unwrap();
+        for c in "-1.5e3x".chars() {
+            view.on_event(Event::Char(c));
+        }
+        assert_eq!(*view.get_content(), "-153");
+    }
+
+    #[test]
+    fn buttons_step_within_bounds() {
+        let mut view = IntNumber::new()
+            .step(3)
+            .bounds(Some(0), Some(10))
+            .content("8");
+        view.on_event(Event::Char('+'));
+        assert_eq!(*view.get_content(), "10");
+    }
+}
@@ -0,0 +1,212 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::theme::{BaseColor, Color, ColorStyle, Effect};
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{LinearLayout, ResizedView, TextArea, TextView};
+use cursive::{Printer, Vec2};
+use serde_json;
+use serde_json::value::Value;
+use unicode_width::UnicodeWidthStr;
+
+use super::Masked;
+use editor;
+
+/// Lines of the editor shown at least.
+const MIN_HEIGHT: usize = 5;
+/// Key opening content in external editor.
+const EDITOR_KEY: Event = Event::CtrlChar('e');
+
+/// Multi-line editor checking that its content is JSON when it loses focus.
+///
+/// Position of a syntax error gets highlighted and the error is shown below the editor.
+///
+/// `Ctrl+e` opens content in external editor (`$VISUAL` or `$EDITOR`), when it's used
+/// in [Fui]'s form.
+///
+/// [Fui]: ../struct.Fui.html
+pub struct Json {
+    view: LinearLayout,
+    // line & column (1-based) of syntax error found by the last check
+    error_at: Option<(usize, usize)>,
+    // content coming back from external editor
+    edited: Rc<RefCell<Option<String>>>,
+}
+
+impl Json {
+    /// Creates a new empty `Json`.
+    pub fn new() -> Self {
+        let view = LinearLayout::vertical()
+            .child(Masked::new(TextArea::new()).min_height(MIN_HEIGHT))
+            .child(TextView::new(""));
+        Json {
+            view,
+            error_at: None,
+            edited: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Sets editor's content, placing cursor at its end.
+    pub fn content<S: Into<String>>(mut self, content: S) -> Self {
+        let text_area = self.get_text_area_mut();
+        text_area.set_content(content);
+        let end = text_area.get_content().len();
+        text_area.set_cursor(end);
+        self
+    }
+
+    /// Gets editor's content.
+    pub fn get_content(&self) -> &str {
+        self.get_masked().get_inner().get_content()
+    }
+
+    /// Shows placeholders instead of editor's content when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.get_masked_mut().set_masked(masked);
+    }
+
+    /// Checks content, showing syntax error if there is any.
+    ///
+    /// Returns `false` when content isn't valid JSON.
+    pub fn check(&mut self) -> bool {
+        let (error_at, message) = match parse(self.get_content()) {
+            Ok(_) => (None, String::new()),
+            Err(e) => (Some((e.line(), e.column())), format!("{}", e)),
+        };
+        self.error_at = error_at;
+        self.get_status_view_mut().set_content(message);
+        error_at.is_none()
+    }
+
+    fn get_masked(&self) -> &Masked<TextArea> {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<Masked<TextArea>>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_masked_mut(&mut self) -> &mut Masked<TextArea> {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<Masked<TextArea>>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    fn get_text_area_mut(&mut self) -> &mut TextArea {
+        self.get_masked_mut().get_inner_mut()
+    }
+
+    fn get_status_view_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+}
+
+/// Parses `text` as JSON, empty text means `null`.
+pub(crate) fn parse(text: &str) -> Result<Value, serde_json::Error> {
+    if text.trim().is_empty() {
+        Ok(Value::Null)
+    } else {
+        serde_json::from_str(text)
+    }
+}
+
+impl Default for Json {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Json {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        self.view.draw(printer);
+        let (line, column) = match self.error_at {
+            Some(position) => position,
+            None => return,
+        };
+        let content = self.get_content();
+        let lines: Vec<&str> = content.lines().collect();
+        let height = printer.size.y.saturating_sub(1);
+        // lines don't map to rows when the editor scrolls or wraps them
+        let fits = lines.len() <= height && lines.iter().all(|l| l.width() < printer.size.x);
+        if self.get_masked().is_masked() || !fits || line == 0 {
+            return;
+        }
+        let text = lines.get(line - 1).cloned().unwrap_or("");
+        let idx = column.saturating_sub(1);
+        let prefix: String = text.chars().take(idx).collect();
+        let marked = text.chars().nth(idx).unwrap_or(' ').to_string();
+        let style = ColorStyle::front(Color::Dark(BaseColor::Red));
+        printer.with_color(style, |p| {
+            p.with_effect(Effect::Reverse, |p| {
+                p.print((prefix.width(), line - 1), &marked);
+            })
+        });
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // external editor runs after TUI is stopped, its result is noticed on restart
+        let edited = self.edited.borrow_mut().take();
+        if let Some(content) = edited {
+            let text_area = self.get_text_area_mut();
+            text_area.set_content(content);
+            let end = text_area.get_content().len();
+            text_area.set_cursor(end);
+            self.check();
+        }
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if event == EDITOR_KEY && !self.get_masked().is_masked() {
+            let text = self.get_content().to_string();
+            let edited = Rc::clone(&self.edited);
+            return EventResult::with_cb(move |s| {
+                editor::request_edit(s, text.clone(), "json", Rc::clone(&edited));
+            });
+        }
+        if event == Event::FocusLost {
+            self.check();
+        } else if self.error_at.is_some() {
+            // position gets stale after editing
+            self.error_at = None;
+        }
+        self.view.on_event(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_starts_at_end_of_content() {
+        let content = "{\n  \"name\": \"fui\"\n}";
+        let mut view = Json::new().content(content);
+        assert_eq!(view.get_text_area_mut().cursor(), content.len());
+    }
+
+    #[test]
+    fn edited_content_is_loaded_and_checked() {
+        let mut view = Json::new().content("{}");
+        *view.edited.borrow_mut() = Some("{\"a\": }".to_string());
+        view.layout(Vec2::new(20, 10));
+        assert_eq!(view.get_content(), "{\"a\": }");
+        assert!(view.error_at.is_some());
+        assert!(view.edited.borrow().is_none());
+    }
+}
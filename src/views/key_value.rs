@@ -0,0 +1,213 @@
+use cursive::event::{Event, EventResult, Key};
+use cursive::theme::ColorStyle;
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, ResizedView, TextView};
+
+/// Key removing the focused row.
+const REMOVE_KEY: Event = Event::CtrlChar('d');
+/// Width of keys' inputs.
+const KEY_WIDTH: usize = 20;
+/// Columns of inputs in a row.
+const KEY: usize = 0;
+const VALUE: usize = 2;
+/// Separates key & value in rows of content.
+pub(crate) const PAIR_SEP: char = '=';
+
+/// Splits `row` like `key=value` into trimmed key & value (empty without separator).
+pub(crate) fn split_row(row: &str) -> (&str, &str) {
+    match row.find(PAIR_SEP) {
+        Some(idx) => (row[..idx].trim(), row[idx + 1..].trim()),
+        None => (row.trim(), ""),
+    }
+}
+
+/// Editable rows of keys & values (like environment variables or HTTP headers).
+///
+/// `Enter` adds a row below the focused one, `Ctrl+d` removes the focused row.
+/// Content has a `key=value` line for each row which isn't empty.
+pub struct KeyValue {
+    view: LinearLayout,
+    masked: bool,
+}
+
+impl KeyValue {
+    /// Creates a new `KeyValue` with one empty row.
+    pub fn new() -> Self {
+        let hint = "Enter: add row · Ctrl+d: remove row";
+        let view = LinearLayout::vertical()
+            .child(LinearLayout::vertical())
+            .child(TextView::new(hint).style(ColorStyle::secondary()));
+        let mut kv = KeyValue {
+            view,
+            masked: false,
+        };
+        kv.insert_row(0, "", "");
+        kv
+    }
+
+    /// Sets rows from lines like `key=value`.
+    pub fn content(mut self, content: &str) -> Self {
+        let rows = content
+            .lines()
+            .filter(|row| !row.trim().is_empty())
+            .map(split_row)
+            .collect::<Vec<(&str, &str)>>();
+        if rows.is_empty() {
+            return self;
+        }
+        self.get_rows_mut().clear();
+        for (idx, (key, value)) in rows.into_iter().enumerate() {
+            self.insert_row(idx, key, value);
+        }
+        self
+    }
+
+    /// Gets `key=value` line for each row which isn't empty.
+    pub fn get_content(&self) -> String {
+        let rows = self.get_rows();
+        (0..rows.len())
+            .map(|idx| {
+                let key = self.get_edit(idx, KEY).get_content();
+                (key, self.get_edit(idx, VALUE).get_content())
+            })
+            .filter(|(key, value)| !key.is_empty() || !value.is_empty())
+            .map(|(key, value)| format!("{}{}{}", key, PAIR_SEP, value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Shows placeholders instead of keys & values when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        for idx in 0..self.get_rows().len() {
+            self.get_edit_mut(idx, KEY).set_secret(masked);
+            self.get_edit_mut(idx, VALUE).set_secret(masked);
+        }
+    }
+
+    fn insert_row(&mut self, idx: usize, key: &str, value: &str) {
+        let mut key = EditView::new().content(key);
+        key.set_secret(self.masked);
+        let mut value = EditView::new().content(value);
+        value.set_secret(self.masked);
+        let row = LinearLayout::horizontal()
+            .child(key.fixed_width(KEY_WIDTH))
+            .child(TextView::new(format!(" {} ", PAIR_SEP)))
+            .child(value.full_width());
+        self.get_rows_mut().insert_child(idx, row);
+    }
+
+    /// Adds row below the focused one & focuses its key.
+    fn add_row(&mut self) -> EventResult {
+        let idx = self.get_rows().get_focus_index() + 1;
+        self.insert_row(idx, "", "");
+        let rows = self.get_rows_mut();
+        let _ = rows.set_focus_index(idx);
+        EventResult::Consumed(None)
+    }
+
+    /// Removes the focused row, the only one is cleared instead.
+    fn remove_row(&mut self) -> EventResult {
+        let idx = self.get_rows().get_focus_index();
+        let rows = self.get_rows_mut();
+        rows.remove_child(idx);
+        if rows.is_empty() {
+            self.insert_row(0, "", "");
+        }
+        let rows = self.get_rows_mut();
+        let _ = rows.set_focus_index(idx.min(rows.len() - 1));
+        EventResult::Consumed(None)
+    }
+
+    fn get_rows(&self) -> &LinearLayout {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+    }
+
+    fn get_rows_mut(&mut self) -> &mut LinearLayout {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+    }
+
+    /// Gets input in `column` (`KEY` or `VALUE`) of row at `idx`.
+    fn get_edit(&self, idx: usize, column: usize) -> &EditView {
+        self.get_rows()
+            .get_child(idx)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+            .get_child(column)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_mut(&mut self, idx: usize, column: usize) -> &mut EditView {
+        self.get_rows_mut()
+            .get_child_mut(idx)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+            .get_child_mut(column)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+impl Default for KeyValue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for KeyValue {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Enter) => self.add_row(),
+            REMOVE_KEY => self.remove_row(),
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_added_and_removed() {
+        let mut view = KeyValue::new().content("HOST=localhost\nPORT = 80");
+        assert_eq!(view.get_content(), "HOST=localhost\nPORT=80");
+
+        view.on_event(Event::Key(Key::Enter));
+        for c in "DEBUG".chars() {
+            view.on_event(Event::Char(c));
+        }
+        assert_eq!(view.get_content(), "HOST=localhost\nDEBUG=\nPORT=80");
+
+        view.on_event(REMOVE_KEY);
+        view.on_event(REMOVE_KEY);
+        assert_eq!(view.get_content(), "HOST=localhost");
+        view.on_event(REMOVE_KEY);
+        assert_eq!(view.get_content(), "");
+        assert_eq!(view.get_rows().len(), 1);
+    }
+}
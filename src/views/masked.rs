@@ -0,0 +1,53 @@
+use cursive::view::{View, ViewWrapper};
+use cursive::Printer;
+
+/// Wrapper showing placeholders instead of wrapped view when masked.
+pub struct Masked<V: View> {
+    view: V,
+    masked: bool,
+}
+
+impl<V: View> Masked<V> {
+    /// Creates a new `Masked` wrapping `view`.
+    pub fn new(view: V) -> Self {
+        Masked {
+            view,
+            masked: false,
+        }
+    }
+
+    /// Shows placeholders instead of `view` when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+
+    /// Checks if placeholders are shown.
+    pub fn is_masked(&self) -> bool {
+        self.masked
+    }
+
+    /// Gets access to wrapped view.
+    pub fn get_inner(&self) -> &V {
+        &self.view
+    }
+
+    /// Gets mutable access to wrapped view.
+    pub fn get_inner_mut(&mut self) -> &mut V {
+        &mut self.view
+    }
+}
+
+impl<V: View> ViewWrapper for Masked<V> {
+    wrap_impl!(self.view: V);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        if self.masked {
+            let placeholder = super::mask(&" ".repeat(printer.size.x));
+            for y in 0..printer.size.y {
+                printer.print((0, y), &placeholder);
+            }
+        } else {
+            self.view.draw(printer);
+        }
+    }
+}
@@ -5,12 +5,106 @@
 //! [Views]: ../views/index.html
 //! [Cursive]: ../../cursive/views/index.html
 mod autocomplete;
-mod multiselect;
+mod banner;
+mod cron;
+pub(crate) mod date_time;
+pub(crate) mod date_time_range;
+pub(crate) mod decimal;
+mod diff;
+mod dir_picker;
+#[cfg(feature = "watch")]
+mod dir_watch;
+pub(crate) mod duration;
+pub(crate) mod endpoint;
+#[cfg(feature = "expr")]
+mod expr;
+pub(crate) mod file_mode;
+pub(crate) mod file_picker;
+pub(crate) mod glob_pattern;
+mod hint_bar;
+mod int_number;
+pub(crate) mod json;
+pub(crate) mod key_value;
+mod masked;
+pub(crate) mod multiselect;
+pub(crate) mod number;
+mod output_pane;
+pub(crate) mod regex;
+pub(crate) mod save_path;
+pub(crate) mod slider;
+mod splash;
+mod tags;
+pub(crate) mod time;
+pub(crate) mod uuid;
 
 pub use self::autocomplete::Autocomplete;
+pub use self::banner::Banner;
+pub use self::cron::Cron;
+pub use self::date_time::DateTime;
+pub use self::date_time_range::DateTimeRange;
+pub use self::decimal::Decimal;
+pub use self::diff::Diff;
+pub use self::dir_picker::DirPicker;
+pub use self::duration::Duration;
+pub use self::endpoint::Endpoint;
+#[cfg(feature = "expr")]
+pub use self::expr::Expr;
+pub use self::file_mode::FileMode;
+pub use self::file_picker::FilePicker;
+pub use self::glob_pattern::GlobPattern;
+pub use self::hint_bar::HintBar;
+pub use self::int_number::IntNumber;
+pub use self::json::Json;
+pub use self::key_value::KeyValue;
+pub use self::masked::Masked;
 pub use self::multiselect::Multiselect;
+pub use self::number::Number;
+pub use self::output_pane::OutputPane;
+pub use self::regex::Regex;
+pub use self::save_path::SavePath;
+pub use self::slider::Slider;
+pub use self::splash::Splash;
+pub use self::tags::Tags;
+pub use self::time::Time;
+pub use self::uuid::Uuid;
 
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use cursive::event::EventResult;
+use cursive::utils::markup::StyledString;
 use cursive::views::SelectView;
+use cursive::{CbSink, Cursive};
+
+/// Redraws screen once a background thread of view changes what the view shows.
+///
+/// Sink of callbacks is learned from events consumed by the view (see [learn]),
+/// results coming before the first one are shown on the next redraw.
+///
+/// [learn]: #method.learn
+#[derive(Clone, Default)]
+pub(crate) struct Redraw(Arc<Mutex<Option<CbSink>>>);
+
+impl Redraw {
+    /// Asks for redrawing screen (sending any callback redraws it).
+    pub(crate) fn request(&self) {
+        if let Some(ref sink) = *self.0.lock().unwrap() {
+            // it fails only when UI is already closed, so nothing is shown anyway
+            let _ = sink.send(Box::new(|_: &mut Cursive| {}));
+        }
+    }
+
+    /// Adds callback learning sink of callbacks to `result` of event handled by view.
+    pub(crate) fn learn(&self, result: EventResult) -> EventResult {
+        if !result.is_consumed() || self.0.lock().unwrap().is_some() {
+            return result;
+        }
+        let sink = Arc::clone(&self.0);
+        result.and(EventResult::with_cb(move |c| {
+            *sink.lock().unwrap() = Some(c.cb_sink().clone());
+        }))
+    }
+}
 
 /// Checks if `select` includes `to_check`.
 fn is_value_from_select(select: &SelectView, to_check: &str) -> bool {
@@ -23,3 +117,42 @@ fn is_value_from_select(select: &SelectView, to_check: &str) -> bool {
     }
     return false;
 }
+
+/// Marks values of `SelectView` rows which are section headers instead of suggestions.
+const HEADER_MARK: char = '\u{0}';
+
+/// Checks if row's `value` belongs to a section header.
+fn is_header(value: &str) -> bool {
+    value.starts_with(HEADER_MARK)
+}
+
+/// Replaces every char of `text` with a placeholder.
+fn mask(text: &str) -> String {
+    text.chars().map(|_| '*').collect()
+}
+
+/// Renders suggestion's value as label, e.g. colorized by its kind (see
+/// [Autocomplete::item_renderer]).
+///
+/// [Autocomplete::item_renderer]: struct.Autocomplete.html#method.item_renderer
+pub type ItemRenderer = Rc<dyn Fn(&str) -> StyledString>;
+
+/// Builds label of `value` with `renderer` (or plain), made of placeholders when `masked`.
+fn item_label(value: &str, masked: bool, renderer: Option<&ItemRenderer>) -> StyledString {
+    match renderer {
+        _ if masked => mask(value).into(),
+        Some(render) => render(value),
+        None => value.into(),
+    }
+}
+
+/// Shows labels of `select` as placeholders when `masked`, otherwise as values
+/// rendered with `renderer`.
+fn mask_select(select: &mut SelectView, masked: bool, renderer: Option<&ItemRenderer>) {
+    for (label, value) in select.iter_mut() {
+        if is_header(value) {
+            continue;
+        }
+        *label = item_label(value, masked, renderer);
+    }
+}
@@ -1,18 +1,31 @@
 use std::rc::Rc;
 
-use cursive::event::{Callback, Event, EventResult, Key};
+use cursive::event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::theme::ColorStyle;
 use cursive::traits::{Resizable, View};
-use cursive::view::ViewWrapper;
-use cursive::views::{DummyView, LinearLayout, OnEventView, Panel, ResizedView, SelectView};
+use cursive::utils::markup::StyledString;
+use cursive::view::{SizeConstraint, ViewWrapper};
+use cursive::views::{
+    DummyView, LinearLayout, OnEventView, Panel, ResizedView, ScrollView, SelectView,
+};
 use cursive::Cursive;
+use cursive::Printer;
+use cursive::Rect;
+use cursive::Vec2;
 use cursive::With;
+use unicode_width::UnicodeWidthStr;
 
-use super::is_value_from_select;
+use super::{is_value_from_select, ItemRenderer};
 use feeders::Feeder;
+use ranking::Ranker;
 use views::Autocomplete;
 
 type OnSelect = Option<Rc<dyn Fn(&mut Cursive, Rc<String>)>>;
 type OnDeselect = Option<Rc<dyn Fn(&mut Cursive, Rc<String>)>>;
+type SelectedPanel = Panel<ResizedView<ScrollView<OnEventView<SelectedView>>>>;
+
+/// Height of selected items panel, same as the default suggestions.
+const SELECTED_HEIGHT: usize = 6;
 
 /// Multiple selection view with suggestions.
 pub struct Multiselect {
@@ -21,6 +34,7 @@ pub struct Multiselect {
     redundant_selection: bool,
     selected_idx: u8,
     options_idx: u8,
+    masked: bool,
     on_select: OnSelect,
     on_deselect: OnDeselect,
 }
@@ -37,31 +51,34 @@ impl Multiselect {
             ))
             .child(DummyView.fixed_width(separator_width))
             .child(Panel::new(
-                OnEventView::new(
-                    SelectView::<String>::new()
-                        //TODO: allow customization?
-                        .full_width(),
+                ScrollView::new(
+                    OnEventView::new(SelectedView::new())
+                        .on_pre_event_inner(Event::CtrlChar('p'), |s, _| {
+                            s.select.select_up(1);
+                            Some(EventResult::Consumed(None))
+                        })
+                        .on_pre_event_inner(Event::CtrlChar('n'), |s, _| {
+                            s.select.select_down(1);
+                            Some(EventResult::Consumed(None))
+                        }),
                 )
-                .on_pre_event_inner(Event::CtrlChar('p'), |s, _| {
-                    s.get_inner_mut().select_up(1);
-                    Some(EventResult::Consumed(None))
-                })
-                .on_pre_event_inner(Event::CtrlChar('n'), |s, _| {
-                    s.get_inner_mut().select_down(1);
-                    Some(EventResult::Consumed(None))
-                }),
+                // fixed height keeps the form still while items are selected
+                .resized(SizeConstraint::Full, SizeConstraint::Fixed(SELECTED_HEIGHT)),
             ));
 
-        Multiselect {
+        let mut ms = Multiselect {
             view: layout,
             select_anything: false,
             redundant_selection: false,
             // remove this when suitable tests are added?
             options_idx: 0,
             selected_idx: 2,
+            masked: false,
             on_select: None,
             on_deselect: None,
-        }
+        };
+        ms.refresh_checkboxes();
+        ms
     }
 
     fn get_options_view(&self) -> &Autocomplete {
@@ -75,32 +92,68 @@ impl Multiselect {
         box_view.get_inner().get_inner()
     }
 
-    fn get_selected_view(&self) -> &SelectView<String> {
+    fn get_options_view_mut(&mut self) -> &mut Autocomplete {
         let box_view = self
             .view
+            .get_child_mut(self.options_idx as usize)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Panel<ResizedView<Autocomplete>>>()
+            .unwrap();
+        box_view.get_inner_mut().get_inner_mut()
+    }
+
+    fn get_selected_panel(&self) -> &SelectedPanel {
+        self.view
             .get_child(self.selected_idx as usize)
             .unwrap()
             .as_any()
-            .downcast_ref::<Panel<OnEventView<ResizedView<SelectView<String>>>>>()
-            .unwrap();
-        box_view.get_inner().get_inner().get_inner()
+            .downcast_ref::<SelectedPanel>()
+            .unwrap()
     }
 
-    fn get_selected_view_mut(&mut self) -> &mut SelectView<String> {
-        let box_view = self
-            .view
+    fn get_selected_panel_mut(&mut self) -> &mut SelectedPanel {
+        self.view
             .get_child_mut(self.selected_idx as usize)
             .unwrap()
             .as_any_mut()
-            .downcast_mut::<Panel<OnEventView<ResizedView<SelectView<String>>>>>()
-            .unwrap();
-        box_view.get_inner_mut().get_inner_mut().get_inner_mut()
+            .downcast_mut::<SelectedPanel>()
+            .unwrap()
+    }
+
+    fn get_selected_view(&self) -> &SelectView<String> {
+        let panel = self.get_selected_panel();
+        &panel.get_inner().get_inner().get_inner().get_inner().select
+    }
+
+    fn get_selected_view_mut(&mut self) -> &mut SelectView<String> {
+        let panel = self.get_selected_panel_mut();
+        &mut panel
+            .get_inner_mut()
+            .get_inner_mut()
+            .get_inner_mut()
+            .get_inner_mut()
+            .select
+    }
+
+    /// Updates count badge of selected items.
+    fn refresh_count(&mut self) {
+        let count = self.get_selected_view().len();
+        let title = if count == 0 {
+            String::new()
+        } else {
+            format!("{} selected", count)
+        };
+        self.get_selected_panel_mut().set_title(title);
     }
 
     fn select_item(&mut self) -> Rc<String> {
         let selected_text = self.get_options_view().get_value();
-        self.get_selected_view_mut()
-            .add_item_str((&*selected_text).clone());
+        let masked = self.masked;
+        let selected_view = self.get_selected_view_mut();
+        selected_view.add_item_str((&*selected_text).clone());
+        super::mask_select(selected_view, masked, None);
+        self.refresh_count();
         selected_text
     }
 
@@ -118,8 +171,130 @@ impl Multiselect {
 
     /// Makes `items` selected
     pub fn select_items(&mut self, items: Vec<String>) {
+        let masked = self.masked;
         let selected_view = self.get_selected_view_mut();
         selected_view.add_all_str(items);
+        super::mask_select(selected_view, masked, None);
+        self.refresh_count();
+        self.refresh_checkboxes();
+    }
+
+    /// Ticks checkboxes of suggestions which are selected.
+    fn refresh_checkboxes(&mut self) {
+        let selected: Vec<String> = self.get_selected_items().into_iter().cloned().collect();
+        self.get_options_view_mut()
+            .check_items(|value| selected.iter().any(|x| x == value));
+    }
+
+    /// Checks if the list of suggestions (not the typed text) is focused.
+    fn is_suggestion_focused(&self) -> bool {
+        self.view.get_focus_index() == self.options_idx as usize
+            && self.get_options_view().is_select_focused()
+    }
+
+    /// Selects highlighted suggestion or deselects it if it's already selected.
+    fn toggle_highlighted(&mut self) -> EventResult {
+        let highlighted = match self.get_options_view().highlighted() {
+            Some(highlighted) => highlighted,
+            None => return EventResult::Consumed(None),
+        };
+        let masked = self.masked;
+        let selected_view = self.get_selected_view_mut();
+        let idx = selected_view.iter().position(|(_, v)| *v == *highlighted);
+        let cb = if let Some(idx) = idx {
+            selected_view.remove_item(idx);
+            self.on_deselect
+                .clone()
+                .map(|on_deselect| Callback::from_fn(move |c| on_deselect(c, highlighted.clone())))
+        } else {
+            selected_view.add_item_str((*highlighted).clone());
+            super::mask_select(selected_view, masked, None);
+            self.on_select
+                .clone()
+                .map(|on_select| Callback::from_fn(move |c| on_select(c, highlighted.clone())))
+        };
+        self.refresh_count();
+        EventResult::Consumed(cb)
+    }
+
+    /// Shows selected items as compact chips wrapped on lines instead of one per line.
+    pub fn chips(mut self) -> Self {
+        self.set_chips(true);
+        self
+    }
+
+    /// Shows selected items as compact chips wrapped on lines instead of one per line.
+    pub fn set_chips(&mut self, chips: bool) {
+        let panel = self.get_selected_panel_mut();
+        panel
+            .get_inner_mut()
+            .get_inner_mut()
+            .get_inner_mut()
+            .get_inner_mut()
+            .chips = chips;
+    }
+
+    /// Renders listed suggestions with `renderer` instead of plain text,
+    /// see [Autocomplete::item_renderer].
+    ///
+    /// [Autocomplete::item_renderer]: struct.Autocomplete.html#method.item_renderer
+    pub fn item_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&str) -> StyledString + 'static,
+    {
+        self.set_item_renderer(Rc::new(renderer));
+        self
+    }
+
+    /// Renders listed suggestions with `renderer`, see [item_renderer].
+    ///
+    /// [item_renderer]: #method.item_renderer
+    pub fn set_item_renderer(&mut self, renderer: ItemRenderer) {
+        self.get_options_view_mut().set_item_renderer(renderer);
+        self.refresh_checkboxes();
+    }
+
+    /// Orders suggestions with `ranker`.
+    pub fn ranker<R: Ranker>(mut self, ranker: R) -> Self {
+        self.set_ranker(Rc::new(ranker));
+        self
+    }
+
+    /// Orders suggestions with `ranker`.
+    pub fn set_ranker(&mut self, ranker: Rc<dyn Ranker>) {
+        self.get_options_view_mut().set_ranker(ranker);
+        self.refresh_checkboxes();
+    }
+
+    /// Refreshes suggestions when directory they come from changes,
+    /// see [Autocomplete::watch]. Requires `watch` feature.
+    ///
+    /// [Autocomplete::watch]: struct.Autocomplete.html#method.watch
+    #[cfg(feature = "watch")]
+    pub fn watch(mut self, enabled: bool) -> Self {
+        self.set_watch(enabled);
+        self
+    }
+
+    /// Refreshes suggestions when directory they come from changes, see [watch].
+    ///
+    /// [watch]: #method.watch
+    #[cfg(feature = "watch")]
+    pub fn set_watch(&mut self, enabled: bool) {
+        self.get_options_view_mut().set_watch(enabled);
+    }
+
+    /// Shows placeholders instead of typed text, suggestions and selected items when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_options_view_mut().set_masked(masked);
+        super::mask_select(self.get_selected_view_mut(), masked, None);
+        self.refresh_checkboxes();
+    }
+
+    /// Shows at most `max_count` suggestions, `None` removes the limit.
+    pub fn set_max_shown_count(&mut self, max_count: Option<u8>) {
+        self.get_options_view_mut().set_max_shown_count(max_count);
     }
 
     /// Checks if `to_check` is already selected
@@ -148,6 +323,7 @@ impl Multiselect {
         if let Some(idx) = selected_view.selected_id() {
             let item = selected_view.selection();
             selected_view.remove_item(idx);
+            self.refresh_count();
             return item;
         } else {
             None
@@ -206,8 +382,38 @@ impl Multiselect {
 impl ViewWrapper for Multiselect {
     wrap_impl!(self.view: LinearLayout);
 
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.view.layout(size);
+        // suggestions are listed lazily, on the first layout
+        self.refresh_checkboxes();
+    }
+
     fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.handle_event(event);
+        // suggestions might have been listed again
+        self.refresh_checkboxes();
+        result
+    }
+}
+
+impl Multiselect {
+    fn handle_event(&mut self, event: Event) -> EventResult {
         match event {
+            Event::Char(' ') if self.is_suggestion_focused() => self.toggle_highlighted(),
+            Event::Mouse {
+                event: MouseEvent::Release(MouseButton::Left),
+                ..
+            } => {
+                let result = self
+                    .with_view_mut(|v| v.on_event(event))
+                    .unwrap_or(EventResult::Ignored);
+                if self.is_suggestion_focused() {
+                    // click on a suggestion
+                    self.toggle_highlighted()
+                } else {
+                    result
+                }
+            }
             Event::Key(Key::Enter) => {
                 self.with_view_mut(|v| v.on_event(event))
                     .unwrap_or(EventResult::Ignored);
@@ -243,3 +449,126 @@ impl ViewWrapper for Multiselect {
         }
     }
 }
+
+/// Lists selected items one per line, or as chips wrapped on lines.
+pub(crate) struct SelectedView {
+    pub(crate) select: SelectView<String>,
+    pub(crate) chips: bool,
+}
+
+impl SelectedView {
+    pub(crate) fn new() -> Self {
+        SelectedView {
+            select: SelectView::new(),
+            chips: false,
+        }
+    }
+
+    /// Gets position of each chip when wrapped at `width`.
+    fn chip_positions(&self, width: usize) -> Vec<Vec2> {
+        let mut positions = Vec::new();
+        let mut pos = Vec2::zero();
+        for (label, _) in self.select.iter() {
+            let chip_width = chip(label).width();
+            if pos.x > 0 && pos.x + chip_width > width {
+                pos = Vec2::new(0, pos.y + 1);
+            }
+            positions.push(pos);
+            pos.x += chip_width + 1;
+        }
+        positions
+    }
+}
+
+/// Formats `label` as a chip.
+fn chip(label: &str) -> String {
+    format!("[{}]", label)
+}
+
+impl ViewWrapper for SelectedView {
+    wrap_impl!(self.select: SelectView<String>);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        if !self.chips {
+            return self.select.draw(printer);
+        }
+        let positions = self.chip_positions(printer.size.x);
+        for (idx, ((label, _), pos)) in self.select.iter().zip(positions).enumerate() {
+            let style = if self.select.selected_id() != Some(idx) {
+                ColorStyle::primary()
+            } else if printer.focused {
+                ColorStyle::highlight()
+            } else {
+                ColorStyle::highlight_inactive()
+            };
+            printer.with_color(style, |p| p.print(pos, &chip(label)));
+        }
+    }
+
+    fn wrap_required_size(&mut self, constraint: Vec2) -> Vec2 {
+        if !self.chips {
+            return self.select.required_size(constraint);
+        }
+        let height = self
+            .chip_positions(constraint.x)
+            .last()
+            .map_or(0, |pos| pos.y + 1);
+        Vec2::new(constraint.x, height)
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let event = match event {
+            Event::Key(Key::Left) if self.chips => Event::Key(Key::Up),
+            Event::Key(Key::Right) if self.chips => Event::Key(Key::Down),
+            // rows of the select don't match chips
+            Event::Mouse { .. } if self.chips => return EventResult::Ignored,
+            event => event,
+        };
+        self.select.on_event(event)
+    }
+
+    fn wrap_important_area(&self, size: Vec2) -> Rect {
+        if !self.chips {
+            return self.select.important_area(size);
+        }
+        let selected = self.select.selected_id();
+        let chip_area = selected.and_then(|idx| {
+            let pos = *self.chip_positions(size.x).get(idx)?;
+            let (label, _) = self.select.get_item(idx)?;
+            Some(Rect::from_size(pos, (chip(label).width(), 1)))
+        });
+        chip_area.unwrap_or_else(|| Rect::from_size((0, 0), (size.x, 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chips_wrap_at_width() {
+        let mut view = SelectedView::new();
+        view.select.add_all_str(vec!["one", "two", "three"]);
+        // "[one] [two]" is 11 chars wide
+        assert_eq!(
+            view.chip_positions(12),
+            vec![Vec2::new(0, 0), Vec2::new(6, 0), Vec2::new(0, 1)]
+        );
+        view.chips = true;
+        assert_eq!(view.required_size(Vec2::new(12, 10)), Vec2::new(12, 2));
+    }
+
+    #[test]
+    fn space_toggles_highlighted_suggestion() {
+        let mut ms = Multiselect::new(vec!["op1", "op2", "op3"]);
+        ms.on_event(Event::Key(Key::Down));
+        ms.on_event(Event::Key(Key::Down));
+        ms.on_event(Event::Char(' '));
+        assert_eq!(ms.get_selected_items(), vec!["op2"]);
+        ms.on_event(Event::Key(Key::Down));
+        ms.on_event(Event::Char(' '));
+        assert_eq!(ms.get_selected_items(), vec!["op2", "op3"]);
+        ms.on_event(Event::Char(' '));
+        assert_eq!(ms.get_selected_items(), vec!["op2"]);
+    }
+}
@@ -0,0 +1,381 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use cursive::event::{Event, EventResult, MouseButton, MouseEvent};
+use cursive::traits::{Resizable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, ResizedView, TextView};
+use cursive::Vec2;
+
+use views::decimal::is_space;
+
+/// Text of button decreasing number.
+const DECREASE: &str = "[-] ";
+/// Text of button increasing number.
+const INCREASE: &str = " [+]";
+/// Presses closer to each other than this are treated as key-repeat.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(200);
+/// Count of repeated presses after which the step is multiplied by acceleration.
+const REPEATS_PER_LEVEL: u32 = 10;
+
+/// Edit view for numbers with `[-]` `[+]` buttons.
+///
+/// Buttons can be clicked or used with `-` and `+` keys (`-` typed
+/// at the start or right after exponent `e` is a sign). Holding a key
+/// makes steps grow (see [acceleration]).
+///
+/// [acceleration]: #method.acceleration
+pub struct Number {
+    view: LinearLayout,
+    step: f64,
+    acceleration: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    group: char,
+    decimal: char,
+    size: Vec2,
+    // time of the last adjustment & count of presses repeated right after each other
+    last_press: Option<Instant>,
+    repeats: u32,
+}
+
+impl Number {
+    /// Creates a new empty `Number` changed by 1 with buttons.
+    pub fn new() -> Self {
+        let view = LinearLayout::horizontal()
+            .child(TextView::new(DECREASE))
+            .child(EditView::new().full_width())
+            .child(TextView::new(INCREASE));
+        Number {
+            view,
+            step: 1.0,
+            acceleration: 10.0,
+            min: None,
+            max: None,
+            group: ',',
+            decimal: '.',
+            size: Vec2::zero(),
+            last_press: None,
+            repeats: 0,
+        }
+    }
+
+    /// Sets how much the number changes with a button.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets how many times the step grows after every 10 repeated presses
+    /// (10 by default, 1 disables it).
+    pub fn acceleration(mut self, acceleration: f64) -> Self {
+        self.acceleration = acceleration;
+        self
+    }
+
+    /// Sets the lowest (`min`) & the highest (`max`) number reachable with buttons,
+    /// numbers aren't bounded by default.
+    pub fn bounds(mut self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Sets separators of thousands (`group`) and decimals (`decimal`),
+    /// English ones are used by default.
+    pub fn separators(mut self, group: char, decimal: char) -> Self {
+        self.group = group;
+        self.decimal = decimal;
+        self
+    }
+
+    /// Sets typed number.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self
+    }
+
+    /// Gets typed number.
+    pub fn get_content(&self) -> Rc<String> {
+        self.get_edit_view().get_content()
+    }
+
+    /// Shows placeholders instead of typed number when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.get_edit_view_mut().set_secret(masked);
+    }
+
+    /// Increases (or decreases when `direction` is negative) the number by step,
+    /// accelerated when presses repeat quickly.
+    fn adjust(&mut self, direction: f64) {
+        let now = Instant::now();
+        let repeated = self
+            .last_press
+            .is_some_and(|last| now.duration_since(last) < REPEAT_INTERVAL);
+        self.repeats = if repeated { self.repeats + 1 } else { 0 };
+        self.last_press = Some(now);
+
+        let content = self.get_content();
+        let value = if content.trim().is_empty() {
+            0.0
+        } else {
+            match parse(&content, self.group, self.decimal) {
+                Ok(value) => value,
+                // leave invalid input to be fixed by user
+                Err(_) => return,
+            }
+        };
+        let level = (self.repeats / REPEATS_PER_LEVEL) as i32;
+        let step = self.step * self.acceleration.powi(level);
+        let places =
+            decimal_places(&normalize(&content, self.group, self.decimal).unwrap_or_default())
+                .max(decimal_places(&self.step.to_string()));
+        let mut adjusted = value + direction * step;
+        if let Some(min) = self.min {
+            adjusted = adjusted.max(min);
+        }
+        if let Some(max) = self.max {
+            adjusted = adjusted.min(max);
+        }
+        let adjusted = format!("{:.*}", places, adjusted).replace('.', &self.decimal.to_string());
+        self.get_edit_view_mut().set_content(adjusted);
+    }
+
+    /// Types `-` as a sign at the start or after exponent, otherwise decreases the number.
+    fn on_minus(&mut self) -> EventResult {
+        let content = self.get_content();
+        // cursor isn't exposed, so it's found where `-` got inserted
+        let cb = self.get_edit_view_mut().insert('-');
+        let typed = content
+            .bytes()
+            .zip(self.get_content().bytes())
+            .position(|(old, new)| old != new)
+            .unwrap_or(content.len());
+        let before = content[..typed].trim_end();
+        if before.is_empty() || before.ends_with(['e', 'E']) {
+            return EventResult::Consumed(Some(cb));
+        }
+        let edit_view = self.get_edit_view_mut();
+        edit_view.set_content(content.as_str());
+        edit_view.set_cursor(typed);
+        self.adjust(-1.0);
+        EventResult::Consumed(None)
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(1)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<ResizedView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+}
+
+/// Counts digits after decimal point of `number`.
+fn decimal_places(number: &str) -> usize {
+    number
+        .trim()
+        .find('.')
+        .map(|idx| number.trim().len() - idx - 1)
+        .unwrap_or(0)
+}
+
+/// Converts number written with `group` & `decimal` separators to canonical form
+/// (like `1.234,5` to `1234.5`).
+///
+/// Returns `None` when group separators aren't between groups of 3 digits
+/// of the integer part (like `1,5` with `,` as group separator).
+pub(crate) fn normalize(text: &str, group: char, decimal: char) -> Option<String> {
+    let is_group = |c: char| c == group || (is_space(group) && is_space(c));
+    let text = text.trim();
+    let integer_end = text.find([decimal, 'e', 'E']).unwrap_or(text.len());
+    let (integer, rest) = text.split_at(integer_end);
+    if rest.chars().any(is_group) {
+        return None;
+    }
+    let unsigned = integer.trim_start_matches(['-', '+']);
+    let sign = &integer[..integer.len() - unsigned.len()];
+    let groups: Vec<&str> = unsigned.split(is_group).collect();
+    if groups.len() > 1
+        && !((1..=3).contains(&groups[0].len()) && groups[1..].iter().all(|g| g.len() == 3))
+    {
+        return None;
+    }
+    let rest: String = rest
+        .chars()
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+    Some(format!("{}{}{}", sign, groups.concat(), rest))
+}
+
+/// Parses `text` written with `group` & `decimal` separators as a finite number.
+pub(crate) fn parse(text: &str, group: char, decimal: char) -> Result<f64, String> {
+    let text = text.trim();
+    match normalize(text, group, decimal).map(|n| n.parse::<f64>()) {
+        Some(Ok(number)) if number.is_finite() => Ok(number),
+        _ => Err(format!("Invalid number: {}", text)),
+    }
+}
+
+impl Default for Number {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Number {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.size = size;
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Char('+') => {
+                self.adjust(1.0);
+                EventResult::Consumed(None)
+            }
+            Event::Char('-') => self.on_minus(),
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } => match position.checked_sub(offset) {
+                Some(pos) if pos.x < DECREASE.trim().len() => {
+                    self.adjust(-1.0);
+                    EventResult::Consumed(None)
+                }
+                Some(pos) if pos.x + INCREASE.trim().len() >= self.size.x => {
+                    self.adjust(1.0);
+                    EventResult::Consumed(None)
+                }
+                _ => self.view.on_event(event),
+            },
+            _ => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::direction::Direction;
+
+    #[test]
+    fn group_separators_only_split_groups_of_3_digits() {
+        assert_eq!(parse("1,234.5", ',', '.'), Ok(1234.5));
+        assert_eq!(parse("-12,345,678", ',', '.'), Ok(-12345678.0));
+        assert_eq!(parse("1.234,5", '.', ','), Ok(1234.5));
+        assert_eq!(parse("1 234,5", ' ', ','), Ok(1234.5));
+        assert_eq!(parse("1,5", '.', ','), Ok(1.5));
+        assert!(parse("1,5", ',', '.').is_err());
+        assert!(parse("12,34", ',', '.').is_err());
+        assert!(parse("1234,567", ',', '.').is_err());
+        assert!(parse(",123", ',', '.').is_err());
+        assert!(parse("1.5", '.', ',').is_err());
+        assert!(parse("1.234,5,6", '.', ',').is_err());
+    }
+
+    #[test]
+    fn keys_adjust_number_by_step() {
+        let mut view = Number::new().step(0.5).content("1.5");
+        view.on_event(Event::Char('+'));
+        assert_eq!(*view.get_content(), "2.0");
+        view.last_press = None;
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "1.5");
+
+        let mut view = Number::new().separators('.', ',').content("1.000,5");
+        view.on_event(Event::Char('+'));
+        assert_eq!(*view.get_content(), "1001,5");
+
+        // `-` in empty input is a sign
+        let mut view = Number::new();
+        view.take_focus(Direction::none()).unwrap();
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "-");
+    }
+
+    #[test]
+    fn minus_is_sign_at_start_and_after_exponent() {
+        let mut view = Number::new().content("5");
+        view.get_edit_view_mut().set_cursor(0);
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "-5");
+
+        let mut view = Number::new().content("1e");
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "1e-");
+
+        // elsewhere it decreases the number
+        let mut view = Number::new().content("15");
+        view.get_edit_view_mut().set_cursor(1);
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "14");
+    }
+
+    #[test]
+    fn repeated_presses_accelerate() {
+        let mut view = Number::new().content("0");
+        for _ in 0..REPEATS_PER_LEVEL + 2 {
+            view.on_event(Event::Char('+'));
+        }
+        // 10 presses by 1, then by 10
+        assert_eq!(*view.get_content(), "30");
+
+        let mut view = Number::new().acceleration(1.0).content("0");
+        for _ in 0..REPEATS_PER_LEVEL + 2 {
+            view.on_event(Event::Char('+'));
+        }
+        assert_eq!(*view.get_content(), "12");
+    }
+
+    #[test]
+    fn adjusting_stops_at_bounds() {
+        let mut view = Number::new()
+            .step(5.0)
+            .bounds(Some(-2.0), Some(7.0))
+            .content("4");
+        view.on_event(Event::Char('+'));
+        assert_eq!(*view.get_content(), "7");
+        view.last_press = None;
+        view.on_event(Event::Char('-'));
+        view.last_press = None;
+        view.on_event(Event::Char('-'));
+        assert_eq!(*view.get_content(), "-2");
+    }
+
+    #[test]
+    fn buttons_can_be_clicked() {
+        let mut view = Number::new().content("5");
+        view.layout(Vec2::new(20, 1));
+        let click = |x: usize| Event::Mouse {
+            offset: Vec2::new(2, 3),
+            position: Vec2::new(2 + x, 3),
+            event: MouseEvent::Press(MouseButton::Left),
+        };
+        view.on_event(click(19));
+        view.last_press = None;
+        assert_eq!(*view.get_content(), "6");
+        view.on_event(click(1));
+        view.last_press = None;
+        view.on_event(click(0));
+        assert_eq!(*view.get_content(), "4");
+        view.on_event(click(8));
+        assert_eq!(*view.get_content(), "4");
+    }
+}
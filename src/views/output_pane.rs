@@ -0,0 +1,141 @@
+use cursive::direction::Direction;
+use cursive::event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::theme::ColorStyle;
+use cursive::view::{CannotFocus, View};
+use cursive::views::{TextContent, TextView};
+use cursive::{Printer, Vec2};
+
+/// Collapsible pane with output of a command, like checks run before submitting form.
+///
+/// Header is toggled with `Enter`, `Space` or a click, the pane takes no space
+/// until it gets some output.
+pub struct OutputPane {
+    title: String,
+    output: TextContent,
+    view: TextView,
+    expanded: bool,
+}
+
+impl OutputPane {
+    /// Creates a new empty & collapsed `OutputPane` with header `title`.
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        let output = TextContent::new("");
+        OutputPane {
+            title: title.into(),
+            view: TextView::new_with_content(output.clone()),
+            output,
+            expanded: false,
+        }
+    }
+
+    /// Replaces shown output with `output`.
+    pub fn set_output<S: Into<String>>(&mut self, output: S) {
+        self.output.set_content(output.into());
+    }
+
+    /// Gets shown output.
+    pub fn get_output(&self) -> String {
+        self.output.get_content().source().to_string()
+    }
+
+    /// Gets output shared with the pane, so it can be appended from another thread.
+    pub fn get_shared_output(&self) -> TextContent {
+        self.output.clone()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.output.get_content().source().is_empty()
+    }
+
+    /// Shows output below header when `expanded`, otherwise only header is shown.
+    pub fn set_expanded(&mut self, expanded: bool) {
+        self.expanded = expanded;
+    }
+
+    /// Checks if output is shown below header.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    fn header(&self) -> String {
+        let mark = if self.expanded { '▾' } else { '▸' };
+        format!("{} {}", mark, self.title)
+    }
+}
+
+impl View for OutputPane {
+    fn draw(&self, printer: &Printer) {
+        if self.is_empty() {
+            return;
+        }
+        let style = if printer.focused {
+            ColorStyle::highlight()
+        } else {
+            ColorStyle::secondary()
+        };
+        printer.with_color(style, |p| p.print((0, 0), &self.header()));
+        if self.expanded {
+            self.view.draw(&printer.offset((2, 1)));
+        }
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.view.layout(size.saturating_sub((2, 1)));
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        if self.is_empty() {
+            return Vec2::zero();
+        }
+        let header = Vec2::new(self.header().chars().count(), 1);
+        if !self.expanded {
+            return header;
+        }
+        let body = self.view.required_size(constraint.saturating_sub((2, 1)));
+        Vec2::new(header.x.max(body.x + 2), body.y + 1)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> Result<EventResult, CannotFocus> {
+        if self.is_empty() {
+            Err(CannotFocus)
+        } else {
+            Ok(EventResult::Consumed(None))
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Enter) | Event::Char(' ') => {
+                self.expanded = !self.expanded;
+                EventResult::Consumed(None)
+            }
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } if position.checked_sub(offset).is_some_and(|p| p.y == 0) => {
+                self.expanded = !self.expanded;
+                EventResult::Consumed(None)
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pane_is_hidden_until_it_has_output() {
+        let mut pane = OutputPane::new("Check");
+        assert_eq!(pane.required_size(Vec2::new(20, 10)), Vec2::zero());
+        assert!(pane.take_focus(Direction::none()).is_err());
+
+        pane.set_output("line 1\nline 2");
+        assert_eq!(pane.required_size(Vec2::new(20, 10)), Vec2::new(7, 1));
+        pane.on_event(Event::Key(Key::Enter));
+        assert!(pane.is_expanded());
+        assert_eq!(pane.required_size(Vec2::new(20, 10)), Vec2::new(8, 3));
+    }
+}
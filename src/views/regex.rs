@@ -0,0 +1,164 @@
+use cursive::event::{Event, EventResult};
+use cursive::theme::ColorStyle;
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout, TextContent, TextView};
+use regex;
+
+/// Compiles `pattern`, shortening error to its last line (like `unclosed group`).
+pub(crate) fn parse(pattern: &str) -> Result<regex::Regex, String> {
+    regex::Regex::new(pattern).map_err(|e| {
+        let e = e.to_string();
+        let reason = e.lines().last().unwrap_or("");
+        format!("Invalid regex: {}", reason.trim_start_matches("error: "))
+    })
+}
+
+/// Describes what `pattern` captures in `sample`, like `match "ab" · 1: "b"`.
+pub(crate) fn preview(pattern: &str, sample: &str) -> String {
+    let re = match parse(pattern) {
+        Ok(re) => re,
+        Err(e) => return e,
+    };
+    let caps = match re.captures(sample) {
+        Some(caps) => caps,
+        None => return format!("no match in {:?}", sample),
+    };
+    let mut parts = vec![format!("match {:?}", &caps[0])];
+    for (idx, name) in re.capture_names().enumerate().skip(1) {
+        let text = match caps.get(idx) {
+            Some(m) => format!("{:?}", m.as_str()),
+            None => "-".to_string(),
+        };
+        match name {
+            Some(name) => parts.push(format!("{}: {}", name, text)),
+            None => parts.push(format!("{}: {}", idx, text)),
+        }
+    }
+    parts.join(" · ")
+}
+
+/// Edit view for a regular expression showing below what it captures in a sample text
+/// (see [sample]).
+///
+/// [sample]: #method.sample
+pub struct Regex {
+    view: LinearLayout,
+    preview: TextContent,
+    sample: Option<String>,
+    masked: bool,
+}
+
+impl Regex {
+    /// Creates a new empty `Regex`.
+    pub fn new() -> Self {
+        let preview = TextContent::new("");
+        let view = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(TextView::new_with_content(preview.clone()).style(ColorStyle::secondary()));
+        Regex {
+            view,
+            preview,
+            sample: None,
+            masked: false,
+        }
+    }
+
+    /// Sets text which is matched against the pattern while it's typed.
+    pub fn sample<S: Into<String>>(mut self, sample: S) -> Self {
+        self.sample = Some(sample.into());
+        self.refresh_preview();
+        self
+    }
+
+    /// Sets pattern.
+    pub fn content(mut self, content: &str) -> Self {
+        self.get_edit_view_mut().set_content(content);
+        self.refresh_preview();
+        self
+    }
+
+    /// Gets pattern.
+    pub fn get_content(&self) -> String {
+        (*self.get_edit_view().get_content()).clone()
+    }
+
+    /// Shows placeholders instead of typed pattern and hides preview when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        self.refresh_preview();
+    }
+
+    fn refresh_preview(&mut self) {
+        let pattern = self.get_content();
+        let text = match self.sample {
+            Some(ref sample) if !self.masked && !pattern.is_empty() => preview(&pattern, sample),
+            _ => String::new(),
+        };
+        self.preview.set_content(text);
+    }
+
+    fn get_edit_view(&self) -> &EditView {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<EditView>()
+            .unwrap()
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+}
+
+impl Default for Regex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Regex {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let result = self.view.on_event(event);
+        self.refresh_preview();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_in_sample_are_previewed() {
+        assert_eq!(
+            preview(r"(\d+)-(?P<minor>\d+)(x)?", "v1-22"),
+            r#"match "1-22" · 1: "1" · minor: "22" · 3: -"#
+        );
+        assert_eq!(preview("z", "abc"), r#"no match in "abc""#);
+        assert_eq!(preview("a(b", "abc"), "Invalid regex: unclosed group");
+    }
+
+    #[test]
+    fn preview_follows_typed_pattern() {
+        let mut view = Regex::new().sample("abc").content("b");
+        assert_eq!(view.preview.get_content().source(), r#"match "b""#);
+
+        view.get_edit_view_mut().set_content("c$");
+        view.on_event(Event::Refresh);
+        assert_eq!(view.preview.get_content().source(), r#"match "c""#);
+
+        view.set_masked(true);
+        assert_eq!(view.preview.get_content().source(), "");
+        assert_eq!(Regex::new().content("b").preview.get_content().source(), "");
+    }
+}
@@ -0,0 +1,210 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{LinearLayout, TextView};
+use cursive::Vec2;
+
+use editor;
+use feeders::DirItems;
+use utils::expand_path;
+use views::Autocomplete;
+
+/// Key running external picker.
+const PICKER_KEY: Event = Event::CtrlChar('o');
+
+/// Autocomplete of file paths noticing below it what saving to the typed path
+/// would do (overwrite an existing file or create missing dirs).
+///
+/// Path can also be picked with external program (see [picker]).
+///
+/// [picker]: #method.picker
+pub struct SavePath {
+    view: LinearLayout,
+    masked: bool,
+    picker: Option<String>,
+    // path coming back from external picker
+    picked: Rc<RefCell<Option<String>>>,
+}
+
+impl SavePath {
+    /// Creates a new empty `SavePath`.
+    pub fn new() -> Self {
+        let view = LinearLayout::vertical()
+            .child(autocomplete(""))
+            .child(TextView::new(""));
+        SavePath {
+            view,
+            masked: false,
+            picker: None,
+            picked: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Runs `command` (like `fzf` or `ranger --choosefile={file}`) with `Ctrl+o`
+    /// to pick the path, when it's used in [Fui]'s form.
+    ///
+    /// TUI is stopped while the command runs. Picked path is read from the command's
+    /// output or from the file replacing `{file}` in the command.
+    ///
+    /// [Fui]: ../struct.Fui.html
+    pub fn picker<S: Into<String>>(mut self, command: S) -> Self {
+        self.picker = Some(command.into());
+        self
+    }
+
+    /// Sets typed path.
+    pub fn value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    fn set_value(&mut self, value: &str) {
+        self.get_autocomplete_mut().set_value(value);
+        self.refresh_notice();
+    }
+
+    /// Gets typed path.
+    pub fn get_value(&self) -> Rc<String> {
+        self.get_autocomplete().get_value()
+    }
+
+    /// Shows placeholders instead of typed path and hides notice when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_autocomplete_mut().set_masked(masked);
+        self.refresh_notice();
+    }
+
+    fn refresh_notice(&mut self) {
+        let notice = if self.masked {
+            String::new()
+        } else {
+            notice(&self.get_value())
+        };
+        self.get_notice_view_mut().set_content(notice);
+    }
+
+    fn get_autocomplete(&self) -> &Autocomplete {
+        self.view
+            .get_child(0)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Autocomplete>()
+            .unwrap()
+    }
+
+    fn get_autocomplete_mut(&mut self) -> &mut Autocomplete {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Autocomplete>()
+            .unwrap()
+    }
+
+    fn get_notice_view_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+}
+
+/// Creates autocomplete of files and dirs accepting also paths which don't exist yet.
+fn autocomplete(value: &str) -> Autocomplete {
+    Autocomplete::new(DirItems::new())
+        .submit_anything()
+        .value(value)
+}
+
+/// Returns the outermost missing dir which has to be created before saving to `path`.
+pub(crate) fn missing_parent(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .filter(|p| !p.as_os_str().is_empty())
+        .take_while(|p| !p.exists())
+        .last()
+        .map(|p| p.to_path_buf())
+}
+
+/// Describes what saving to `path` would do, if it's worth noticing.
+fn notice(path: &str) -> String {
+    if path.trim().is_empty() {
+        return String::new();
+    }
+    let path = PathBuf::from(expand_path(path));
+    if path.is_file() {
+        "File exists and will be overwritten".to_string()
+    } else if let Some(dir) = missing_parent(&path) {
+        format!("Dir {} will be created", dir.display())
+    } else {
+        String::new()
+    }
+}
+
+impl Default for SavePath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for SavePath {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // picker runs after TUI is stopped, its result is noticed on restart
+        let picked = self.picked.borrow_mut().take();
+        if let Some(path) = picked {
+            self.set_value(&path);
+        }
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if event == PICKER_KEY && !self.masked {
+            if let Some(command) = self.picker.clone() {
+                let picked = Rc::clone(&self.picked);
+                return EventResult::with_cb(move |s| {
+                    editor::request_pick(s, command.clone(), Rc::clone(&picked));
+                });
+            }
+        }
+        let result = self.view.on_event(event);
+        self.refresh_notice();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notice_describes_save_effects() {
+        assert_eq!(notice(""), "");
+        assert_eq!(
+            notice("./Cargo.toml"),
+            "File exists and will be overwritten"
+        );
+        assert_eq!(notice("./src/new-file"), "");
+        assert_eq!(
+            notice("./missing-dir/nested/new-file"),
+            "Dir ./missing-dir will be created"
+        );
+    }
+
+    #[test]
+    fn picked_path_is_set() {
+        let mut view = SavePath::new().picker("fzf").value("a.txt");
+        *view.picked.borrow_mut() = Some("./Cargo.toml".to_string());
+        view.layout(Vec2::new(40, 5));
+        assert_eq!(*view.get_value(), "./Cargo.toml");
+        assert!(view.picked.borrow().is_none());
+    }
+}
@@ -0,0 +1,233 @@
+use cursive::direction::Direction;
+use cursive::event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::theme::ColorStyle;
+use cursive::view::{CannotFocus, View};
+use cursive::{Printer, Vec2};
+
+/// Bar is never narrower than this (unless there are fewer positions).
+const MIN_BAR_WIDTH: usize = 10;
+/// Bar is never wider than this, even when there are more positions.
+const MAX_BAR_WIDTH: usize = 30;
+/// Part of the range passed with `PageUp` & `PageDown`.
+const PAGE_STEPS: usize = 10;
+
+/// Counts digits after decimal point of `number`.
+fn decimal_places(number: f64) -> usize {
+    let number = number.to_string();
+    number
+        .find('.')
+        .map(|idx| number.len() - idx - 1)
+        .unwrap_or(0)
+}
+
+/// Formats `value` with as many decimal places as `step` has.
+pub(crate) fn format_value(value: f64, step: f64) -> String {
+    format!("{:.*}", decimal_places(step), value)
+}
+
+/// Bar with a knob for picking a number from a range by steps.
+///
+/// Knob is moved with arrow keys (`-` & `+` work too), `PageUp`, `PageDown`,
+/// `Home` & `End` or with mouse. The value is shown next to the bar.
+pub struct Slider {
+    min: f64,
+    max: f64,
+    step: f64,
+    position: usize,
+    masked: bool,
+    size: Vec2,
+}
+
+impl Slider {
+    /// Creates a new `Slider` from `min` to `max` changed by 1, set to `min`.
+    pub fn new(min: f64, max: f64) -> Self {
+        Slider {
+            min,
+            max: max.max(min),
+            step: 1.0,
+            position: 0,
+            masked: false,
+            size: Vec2::zero(),
+        }
+    }
+
+    /// Sets how much the value changes with each move of the knob.
+    pub fn step(mut self, step: f64) -> Self {
+        if step > 0.0 {
+            let value = self.value();
+            self.step = step;
+            self.set_value(value);
+        }
+        self
+    }
+
+    /// Sets value (like `7`), it's rounded to the nearest step within range.
+    pub fn content(mut self, content: &str) -> Self {
+        if let Ok(value) = content.trim().parse::<f64>() {
+            self.set_value(value);
+        }
+        self
+    }
+
+    /// Gets value formatted with as many decimal places as step has.
+    pub fn get_content(&self) -> String {
+        format_value(self.value(), self.step)
+    }
+
+    /// Hides value & knob when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+
+    /// Gets count of steps between `min` & `max`.
+    fn steps(&self) -> usize {
+        ((self.max - self.min) / self.step).floor() as usize
+    }
+
+    fn value(&self) -> f64 {
+        (self.min + self.position as f64 * self.step).min(self.max)
+    }
+
+    fn set_value(&mut self, value: f64) {
+        let position = ((value - self.min) / self.step).round().max(0.0) as usize;
+        self.position = position.min(self.steps());
+    }
+
+    /// Gets width of value shown next to bar.
+    fn label_width(&self) -> usize {
+        let min = format_value(self.min, self.step).len();
+        let max = format_value(self.max, self.step).len();
+        min.max(max)
+    }
+
+    /// Gets width of bar (without brackets) fitting in `width` of the whole view.
+    fn bar_width(&self, width: usize) -> usize {
+        width.saturating_sub(self.label_width() + 3).max(1)
+    }
+
+    /// Moves knob by `delta` steps.
+    fn slide(&mut self, delta: isize) -> EventResult {
+        let position = (self.position as isize + delta).max(0) as usize;
+        self.position = position.min(self.steps());
+        EventResult::Consumed(None)
+    }
+
+    /// Moves knob to `column` of bar.
+    fn slide_to(&mut self, column: usize) -> EventResult {
+        let bar = self.bar_width(self.size.x);
+        let column = column.saturating_sub(1).min(bar - 1);
+        self.position = if bar > 1 {
+            (column * self.steps() + (bar - 1) / 2) / (bar - 1)
+        } else {
+            0
+        };
+        EventResult::Consumed(None)
+    }
+}
+
+impl View for Slider {
+    fn draw(&self, printer: &Printer) {
+        let bar = self.bar_width(printer.size.x);
+        let knob = if self.steps() == 0 {
+            0
+        } else {
+            (self.position * (bar - 1) + self.steps() / 2) / self.steps()
+        };
+        let style = if printer.focused {
+            ColorStyle::highlight()
+        } else {
+            ColorStyle::primary()
+        };
+        printer.print((0, 0), "[");
+        for column in 0..bar {
+            if self.masked || column > knob {
+                printer.print((column + 1, 0), "-");
+            } else if column < knob {
+                printer.print((column + 1, 0), "=");
+            } else {
+                printer.with_color(style, |p| p.print((column + 1, 0), "|"));
+            }
+        }
+        printer.print((bar + 1, 0), "]");
+        let label = if self.masked {
+            "*".repeat(self.label_width())
+        } else {
+            format!("{:>1$}", self.get_content(), self.label_width())
+        };
+        printer.print((bar + 3, 0), &label);
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.size = size;
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        let bar = (self.steps() + 1).clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH);
+        Vec2::new(bar + 3 + self.label_width(), 1)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> Result<EventResult, CannotFocus> {
+        Ok(EventResult::Consumed(None))
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let page = (self.steps() / PAGE_STEPS).max(1) as isize;
+        match event {
+            Event::Key(Key::Left) | Event::Char('-') => self.slide(-1),
+            Event::Key(Key::Right) | Event::Char('+') => self.slide(1),
+            Event::Key(Key::PageDown) => self.slide(-page),
+            Event::Key(Key::PageUp) => self.slide(page),
+            Event::Key(Key::Home) => self.slide(-(self.steps() as isize)),
+            Event::Key(Key::End) => self.slide(self.steps() as isize),
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left) | MouseEvent::Hold(MouseButton::Left),
+            } => match position.checked_sub(offset) {
+                Some(p) if p.x <= self.bar_width(self.size.x) + 1 => self.slide_to(p.x),
+                _ => EventResult::Ignored,
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knob_moves_by_steps_within_range() {
+        let mut slider = Slider::new(1.0, 9.0).content("7");
+        slider.on_event(Event::Key(Key::Right));
+        slider.on_event(Event::Key(Key::Right));
+        slider.on_event(Event::Key(Key::Right));
+        assert_eq!(slider.get_content(), "9");
+        slider.on_event(Event::Key(Key::Home));
+        slider.on_event(Event::Char('-'));
+        assert_eq!(slider.get_content(), "1");
+
+        let slider = Slider::new(0.0, 1.0).step(0.25).content("0.6");
+        assert_eq!(slider.get_content(), "0.50");
+        assert_eq!(Slider::new(0.0, 10.0).content("42").get_content(), "10");
+    }
+
+    #[test]
+    fn knob_follows_mouse() {
+        let mut slider = Slider::new(0.0, 100.0);
+        let size = slider.required_size(Vec2::new(80, 1));
+        assert_eq!(size, Vec2::new(36, 1));
+        slider.layout(size);
+        let click = |x| Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::new(x, 0),
+            event: MouseEvent::Press(MouseButton::Left),
+        };
+        slider.on_event(click(30));
+        assert_eq!(slider.get_content(), "100");
+        slider.on_event(click(1));
+        assert_eq!(slider.get_content(), "0");
+        slider.on_event(click(15));
+        assert_eq!(slider.get_content(), "48");
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+use cursive::view::View;
+use cursive::{Printer, Vec2};
+use unicode_width::UnicodeWidthStr;
+
+/// Frames of spinner, each shown for `FRAME_TIME`.
+const FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const FRAME_TIME: Duration = Duration::from_millis(80);
+
+/// Spinner with a message like `Loading…`, shown while slow work runs in background.
+///
+/// The spinner moves only when screen is refreshed, so enable
+/// [Cursive::set_autorefresh] while it's shown.
+///
+/// [Cursive::set_autorefresh]: ../../cursive/struct.Cursive.html#method.set_autorefresh
+pub struct Splash {
+    message: String,
+    started: Instant,
+}
+
+impl Splash {
+    /// Creates a new `Splash` showing `message` next to spinner.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Splash {
+            message: message.into(),
+            started: Instant::now(),
+        }
+    }
+}
+
+/// Gets spinner's frame shown after `elapsed` time.
+fn frame(elapsed: Duration) -> &'static str {
+    let idx = elapsed.as_millis() / FRAME_TIME.as_millis();
+    FRAMES[idx as usize % FRAMES.len()]
+}
+
+impl View for Splash {
+    fn draw(&self, printer: &Printer) {
+        let text = format!("{} {}", frame(self.started.elapsed()), self.message);
+        printer.print((0, 0), &text);
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        Vec2::new(self.message.width() + 2, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spinner_cycles_through_frames() {
+        assert_eq!(frame(Duration::from_millis(0)), FRAMES[0]);
+        assert_eq!(frame(FRAME_TIME * 3), FRAMES[3]);
+        assert_eq!(frame(FRAME_TIME * FRAMES.len() as u32), FRAMES[0]);
+    }
+}
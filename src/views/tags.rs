@@ -0,0 +1,184 @@
+use cursive::event::{Event, EventResult, Key};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{EditView, LinearLayout};
+
+use views::multiselect::SelectedView;
+
+/// Edit view where typed value becomes a chip (tag) after `Enter`.
+///
+/// Chips are reached with `Down` and removed with `Delete` or `Backspace`,
+/// `Backspace` in empty input removes the last chip. Tags are unique,
+/// typing an existing one again does nothing.
+pub struct Tags {
+    view: LinearLayout,
+    masked: bool,
+}
+
+impl Tags {
+    /// Creates a new `Tags` without any tag.
+    pub fn new() -> Self {
+        let mut chips = SelectedView::new();
+        chips.chips = true;
+        let view = LinearLayout::vertical().child(EditView::new()).child(chips);
+        Tags {
+            view,
+            masked: false,
+        }
+    }
+
+    /// Sets tags.
+    pub fn tags<S: Into<String>, I: IntoIterator<Item = S>>(mut self, tags: I) -> Self {
+        for tag in tags {
+            self.add_tag(tag.into());
+        }
+        self
+    }
+
+    /// Gets tags in order they were added.
+    pub fn get_tags(&self) -> Vec<String> {
+        self.get_chips()
+            .select
+            .iter()
+            .map(|(_, tag)| tag.to_owned())
+            .collect()
+    }
+
+    /// Shows placeholders instead of tags & typed text when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+        self.get_edit_view_mut().set_secret(masked);
+        super::mask_select(&mut self.get_chips_mut().select, masked, None);
+    }
+
+    /// Adds `tag` unless it's empty or already added.
+    fn add_tag(&mut self, tag: String) {
+        let tag = tag.trim();
+        if tag.is_empty() || self.get_tags().iter().any(|t| t == tag) {
+            return;
+        }
+        let label = super::item_label(tag, self.masked, None);
+        self.get_chips_mut().select.add_item(label, tag.to_string());
+    }
+
+    /// Removes tag at `idx`, focusing input when no tag is left.
+    fn remove_tag(&mut self, idx: usize) -> EventResult {
+        let chips = self.get_chips_mut();
+        let cb = chips.select.remove_item(idx);
+        if chips.select.is_empty() {
+            let _ = self.view.set_focus_index(0);
+        }
+        EventResult::Consumed(Some(cb))
+    }
+
+    fn is_edit_focused(&self) -> bool {
+        self.view.get_focus_index() == 0
+    }
+
+    fn get_edit_view_mut(&mut self) -> &mut EditView {
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<EditView>()
+            .unwrap()
+    }
+
+    fn get_chips(&self) -> &SelectedView {
+        self.view
+            .get_child(1)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<SelectedView>()
+            .unwrap()
+    }
+
+    fn get_chips_mut(&mut self) -> &mut SelectedView {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<SelectedView>()
+            .unwrap()
+    }
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Tags {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        if self.is_edit_focused() {
+            let text = (*self.get_edit_view_mut().get_content()).clone();
+            match event {
+                Event::Key(Key::Enter) if !text.trim().is_empty() => {
+                    self.add_tag(text);
+                    let cb = self.get_edit_view_mut().set_content("");
+                    EventResult::Consumed(Some(cb))
+                }
+                Event::Key(Key::Backspace) if text.is_empty() => {
+                    match self.get_chips().select.len() {
+                        0 => EventResult::Ignored,
+                        count => self.remove_tag(count - 1),
+                    }
+                }
+                event => self.view.on_event(event),
+            }
+        } else {
+            match event {
+                Event::Key(Key::Del) | Event::Key(Key::Backspace) => {
+                    match self.get_chips().select.selected_id() {
+                        Some(idx) => self.remove_tag(idx),
+                        None => EventResult::Ignored,
+                    }
+                }
+                event => self.view.on_event(event),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(view: &mut Tags, text: &str) {
+        for c in text.chars() {
+            view.on_event(Event::Char(c));
+        }
+    }
+
+    #[test]
+    fn typed_values_become_unique_tags() {
+        let mut view = Tags::new().tags(vec!["web"]);
+        type_text(&mut view, " db ");
+        view.on_event(Event::Key(Key::Enter));
+        type_text(&mut view, "web");
+        view.on_event(Event::Key(Key::Enter));
+        view.on_event(Event::Key(Key::Enter));
+        assert_eq!(view.get_tags(), vec!["web", "db"]);
+
+        // input is empty after adding, so the last tag goes
+        view.on_event(Event::Key(Key::Backspace));
+        assert_eq!(view.get_tags(), vec!["web"]);
+    }
+
+    #[test]
+    fn focused_chip_is_removed() {
+        let mut view = Tags::new().tags(vec!["a", "b", "c"]);
+        view.on_event(Event::Key(Key::Down));
+        view.on_event(Event::Key(Key::Right));
+        view.on_event(Event::Key(Key::Del));
+        assert_eq!(view.get_tags(), vec!["a", "c"]);
+
+        view.on_event(Event::Key(Key::Backspace));
+        view.on_event(Event::Key(Key::Backspace));
+        assert!(view.get_tags().is_empty());
+        assert!(view.is_edit_focused());
+    }
+}
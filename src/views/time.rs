@@ -0,0 +1,301 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult, Key};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::EditView;
+
+const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Time of day as hours, minutes & seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TimeOfDay {
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+}
+
+impl TimeOfDay {
+    fn from_seconds(seconds: i64) -> Self {
+        let seconds = seconds.rem_euclid(DAY_SECONDS) as u32;
+        TimeOfDay {
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+        }
+    }
+
+    fn seconds(&self) -> i64 {
+        i64::from(self.hour * 3600 + self.minute * 60 + self.second)
+    }
+
+    /// Formats time as `HH:MM:SS`.
+    pub(crate) fn canonical(&self) -> String {
+        format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+/// Part of time changed with arrow keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Component {
+    Hour,
+    Minute,
+    Second,
+    Meridiem,
+}
+
+/// Parses `HH:MM[:SS]` or (when `twelve_hour`) `h:MM[:SS] am/pm`.
+pub(crate) fn parse(text: &str, twelve_hour: bool) -> Result<TimeOfDay, String> {
+    let invalid = || format!("Invalid time: {}", text.trim());
+    let lowered = text.trim().to_lowercase();
+    let (clock, pm) = if twelve_hour {
+        match lowered
+            .strip_suffix("am")
+            .or_else(|| lowered.strip_suffix("pm"))
+        {
+            Some(clock) => (clock.trim_end(), lowered.ends_with("pm")),
+            None => return Err(invalid()),
+        }
+    } else {
+        (lowered.as_str(), false)
+    };
+    let parts = clock
+        .split(':')
+        .map(|p| match p.len() {
+            1 | 2 if p.chars().all(|c| c.is_ascii_digit()) => p.parse::<u32>().ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<u32>>>()
+        .ok_or_else(invalid)?;
+    let (hour, minute, second) = match parts[..] {
+        [h, m] => (h, m, 0),
+        [h, m, s] => (h, m, s),
+        _ => return Err(invalid()),
+    };
+    let hour = if twelve_hour {
+        if hour == 0 || hour > 12 {
+            return Err(invalid());
+        }
+        hour % 12 + if pm { 12 } else { 0 }
+    } else {
+        hour
+    };
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    Ok(TimeOfDay {
+        hour,
+        minute,
+        second,
+    })
+}
+
+/// Formats `time` as `HH:MM` or (when `twelve_hour`) as `h:MM am/pm`,
+/// seconds are included when `with_seconds`.
+pub(crate) fn format(time: &TimeOfDay, twelve_hour: bool, with_seconds: bool) -> String {
+    let seconds = if with_seconds {
+        format!(":{:02}", time.second)
+    } else {
+        String::new()
+    };
+    if twelve_hour {
+        let hour = match time.hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        let meridiem = if time.hour < 12 { "am" } else { "pm" };
+        format!("{}:{:02}{} {}", hour, time.minute, seconds, meridiem)
+    } else {
+        format!("{:02}:{:02}{}", time.hour, time.minute, seconds)
+    }
+}
+
+/// Finds component of time `text` at `cursor` (byte offset).
+fn component_at(text: &str, cursor: usize) -> Component {
+    let colons = text
+        .match_indices(':')
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    match text.find(' ') {
+        Some(space) if cursor > space => return Component::Meridiem,
+        _ => {}
+    }
+    match colons.iter().filter(|&&idx| idx < cursor).count() {
+        0 => Component::Hour,
+        1 => Component::Minute,
+        _ => Component::Second,
+    }
+}
+
+/// Edit view for time of day.
+///
+/// `Up` & `Down` keys change the component under cursor (hours, minutes,
+/// seconds or am/pm).
+pub struct Time {
+    view: EditView,
+    twelve_hour: bool,
+    with_seconds: bool,
+}
+
+impl Time {
+    /// Creates a new empty `Time` in 24-hour mode.
+    pub fn new() -> Self {
+        Time {
+            view: EditView::new(),
+            twelve_hour: false,
+            with_seconds: false,
+        }
+    }
+
+    /// Switches to 12-hour mode (`h:MM am/pm`) when `twelve_hour`.
+    pub fn twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.twelve_hour = twelve_hour;
+        self
+    }
+
+    /// Makes arrow keys keep (or add) seconds segment when `with_seconds`,
+    /// otherwise seconds are shown only if they were typed.
+    pub fn with_seconds(mut self, with_seconds: bool) -> Self {
+        self.with_seconds = with_seconds;
+        self
+    }
+
+    /// Sets typed time.
+    pub fn content(mut self, content: &str) -> Self {
+        self.view.set_content(content);
+        self
+    }
+
+    /// Gets typed time.
+    pub fn get_content(&self) -> Rc<String> {
+        self.view.get_content()
+    }
+
+    /// Shows placeholders instead of typed time when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.view.set_secret(masked);
+    }
+
+    /// Gets position of cursor in typed text.
+    fn cursor(&mut self) -> usize {
+        // EditView doesn't expose cursor, so it's found by a marker typed at it
+        self.view.insert('\0');
+        let cursor = self.view.get_content().find('\0').unwrap_or(0);
+        self.view.set_cursor(cursor);
+        self.view.remove(1);
+        cursor
+    }
+
+    /// Changes component under cursor by `delta`, keeping the cursor in place.
+    fn adjust(&mut self, delta: i64) -> EventResult {
+        let content = self.get_content();
+        let time = if content.trim().is_empty() {
+            TimeOfDay::from_seconds(0)
+        } else {
+            match parse(&content, self.twelve_hour) {
+                Ok(time) => time,
+                // leave invalid input to be fixed by user
+                Err(_) => return EventResult::Ignored,
+            }
+        };
+        let cursor = self.cursor();
+        let step = match component_at(&content, cursor) {
+            Component::Hour => 3600,
+            Component::Minute => 60,
+            Component::Second => 1,
+            Component::Meridiem => 12 * 3600,
+        };
+        let with_seconds = self.with_seconds || content.matches(':').count() > 1;
+        let adjusted = TimeOfDay::from_seconds(time.seconds() + delta * step);
+        let text = format(&adjusted, self.twelve_hour, with_seconds);
+        // keeps cursor in the same component when hours change their width
+        let shift = text.find(':').unwrap_or(0) as isize - content.find(':').unwrap_or(0) as isize;
+        let cursor = (cursor as isize + shift).clamp(0, text.len() as isize) as usize;
+        self.view.set_content(text);
+        self.view.set_cursor(cursor);
+        EventResult::Consumed(None)
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Time {
+    wrap_impl!(self.view: EditView);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Up) => self.adjust(1),
+            Event::Key(Key::Down) => self.adjust(-1),
+            _ => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32, second: u32) -> TimeOfDay {
+        TimeOfDay {
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    #[test]
+    fn time_is_parsed_in_mode() {
+        assert_eq!(parse("07:05", false), Ok(time(7, 5, 0)));
+        assert_eq!(parse("23:59:58", false), Ok(time(23, 59, 58)));
+        assert!(parse("24:00", false).is_err());
+        assert!(parse("7:05 pm", false).is_err());
+
+        assert_eq!(parse("7:05 PM", true), Ok(time(19, 5, 0)));
+        assert_eq!(parse("12:00am", true), Ok(time(0, 0, 0)));
+        assert_eq!(parse("12:30:15 pm", true), Ok(time(12, 30, 15)));
+        assert!(parse("13:00 pm", true).is_err());
+        assert!(parse("07:05", true).is_err());
+    }
+
+    #[test]
+    fn time_is_formatted_in_mode() {
+        assert_eq!(format(&time(0, 5, 0), true, false), "12:05 am");
+        assert_eq!(format(&time(19, 5, 9), true, true), "7:05:09 pm");
+        assert_eq!(format(&time(7, 5, 9), false, false), "07:05");
+        assert_eq!(time(7, 5, 9).canonical(), "07:05:09");
+    }
+
+    #[test]
+    fn arrows_change_component_under_cursor() {
+        let mut view = Time::new().content("23:59:30");
+        view.view.set_cursor(1);
+        view.on_event(Event::Key(Key::Up));
+        assert_eq!(*view.get_content(), "00:59:30");
+        view.view.set_cursor(4);
+        view.on_event(Event::Key(Key::Up));
+        assert_eq!(*view.get_content(), "01:00:30");
+        view.view.set_cursor(8);
+        view.on_event(Event::Key(Key::Down));
+        assert_eq!(*view.get_content(), "01:00:29");
+        assert_eq!(view.cursor(), 8);
+
+        let mut view = Time::new().twelve_hour(true).content("9:15 am");
+        view.view.set_cursor(6);
+        view.on_event(Event::Key(Key::Up));
+        assert_eq!(*view.get_content(), "9:15 pm");
+        view.view.set_cursor(0);
+        view.on_event(Event::Key(Key::Up));
+        view.on_event(Event::Key(Key::Up));
+        view.on_event(Event::Key(Key::Up));
+        assert_eq!(*view.get_content(), "12:15 am");
+        assert_eq!(view.cursor(), 1);
+
+        let mut view = Time::new().with_seconds(true);
+        view.on_event(Event::Key(Key::Down));
+        assert_eq!(*view.get_content(), "23:00:00");
+    }
+}
@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use cursive::event::{Event, EventResult};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::EditView;
+
+/// Edit view for UUID.
+///
+/// With feature `uuid` a fresh random (v4) UUID replaces the content on `Ctrl+g`.
+pub struct Uuid {
+    view: EditView,
+}
+
+impl Uuid {
+    /// Creates a new empty `Uuid`.
+    pub fn new() -> Self {
+        Uuid {
+            view: EditView::new(),
+        }
+    }
+
+    /// Sets typed UUID.
+    pub fn content(mut self, content: &str) -> Self {
+        self.view.set_content(content);
+        self
+    }
+
+    /// Gets typed UUID.
+    pub fn get_content(&self) -> Rc<String> {
+        self.view.get_content()
+    }
+
+    /// Shows placeholders instead of typed UUID when `masked`.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.view.set_secret(masked);
+    }
+
+    /// Replaces content with a fresh random UUID.
+    #[cfg(feature = "uuid")]
+    pub fn generate(&mut self) {
+        let generated = ::uuid::Uuid::new_v4().hyphenated().to_string();
+        self.view.set_content(generated);
+    }
+}
+
+/// Parses UUID in any common notation (hyphenated or not, in braces, as URN)
+/// to lowercase hyphenated one.
+pub(crate) fn parse(text: &str) -> Result<String, String> {
+    let trimmed = text.trim();
+    let lowered = trimmed.to_lowercase();
+    let mut hex = lowered.as_str();
+    if let Some(rest) = hex.strip_prefix("urn:uuid:") {
+        hex = rest;
+    } else if hex.starts_with('{') && hex.ends_with('}') {
+        hex = &hex[1..hex.len() - 1];
+    }
+    let hyphens = hex
+        .match_indices('-')
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    let digits = hex.replace('-', "");
+    let valid_hyphens = hyphens.is_empty() || hyphens == [8, 13, 18, 23];
+    if !valid_hyphens || digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid UUID: {}", trimmed));
+    }
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &digits[..8],
+        &digits[8..12],
+        &digits[12..16],
+        &digits[16..20],
+        &digits[20..]
+    ))
+}
+
+impl Default for Uuid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViewWrapper for Uuid {
+    wrap_impl!(self.view: EditView);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        #[cfg(feature = "uuid")]
+        {
+            if event == Event::CtrlChar('g') {
+                self.generate();
+                return EventResult::Consumed(None);
+            }
+        }
+        self.view.on_event(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_is_normalized() {
+        let uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        assert_eq!(parse(uuid), Ok(uuid.to_string()));
+        assert_eq!(
+            parse(" 67E5504410B1426F9247BB680E5FE0C8 "),
+            Ok(uuid.to_string())
+        );
+        assert_eq!(
+            parse("{67e55044-10b1-426f-9247-bb680e5fe0c8}"),
+            Ok(uuid.to_string())
+        );
+        assert_eq!(
+            parse("urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Ok(uuid.to_string())
+        );
+        assert!(parse("67e55044-10b1426f-9247-bb680e5fe0c8").is_err());
+        assert!(parse("67e55044-10b1-426f-9247-bb680e5fe0c").is_err());
+        assert!(parse("g7e55044-10b1-426f-9247-bb680e5fe0c8").is_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn ctrl_g_generates_uuid() {
+        let mut view = Uuid::new();
+        view.on_event(Event::CtrlChar('g'));
+        let generated = view.get_content();
+        assert_eq!(parse(&generated).as_ref(), Ok(&*generated));
+        assert_eq!(&generated[14..15], "4");
+    }
+}
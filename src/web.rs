@@ -0,0 +1,485 @@
+//! Serves forms of actions over HTTP as simple HTML pages (see `Fui::serve`).
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{IpAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use form_urlencoded;
+use serde_json::value::Value;
+use uuid::Uuid;
+
+use fields::multiselect::VALUE_SEP;
+use form::{FormErrors, FormSnapshot, FormView};
+use {Fui, NextStep};
+
+/// Request is rejected when its body is bigger than this.
+const MAX_BODY: usize = 1024 * 1024;
+
+/// Slow clients are dropped after this, so they don't block the others.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    host: Option<String>,
+    origin: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Gets value of `name` parameter of query string.
+    fn query(&self, name: &str) -> Option<String> {
+        let query = self.path.split_once('?')?.1;
+        form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// Answers HTTP requests coming to `addr` one by one.
+///
+/// Every request must carry the token printed on start, so other pages opened
+/// in the browser can't submit forms.
+pub(crate) fn serve<A: ToSocketAddrs>(fui: &Fui, addr: A) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let token = Uuid::new_v4().simple().to_string();
+    println!(
+        "Serving forms at http://{}/?token={}",
+        listener.local_addr()?,
+        token
+    );
+    for stream in listener.incoming() {
+        let result = stream.and_then(|mut stream| handle(fui, &token, &mut stream));
+        if let Err(e) = result {
+            eprintln!("Serving request failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn handle(fui: &Fui, token: &str, stream: &mut TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let request = read_request(&mut BufReader::new(stream.try_clone()?))?;
+    let (status, html) = match check_access(token, &request) {
+        Ok(()) => respond(fui, token, &request),
+        Err(reason) => (
+            "403 Forbidden",
+            page("Forbidden", &format!("<p>{}</p>", reason)),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        html.len(),
+        html
+    )?;
+    stream.flush()
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Request> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or_else(|| invalid("Missing method"))?;
+    let path = parts.next().ok_or_else(|| invalid("Missing path"))?;
+    let mut content_length = 0;
+    let mut host = None;
+    let mut origin = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let mut pair = header.splitn(2, ':');
+        let name = pair.next().unwrap_or("").trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = pair
+                .next()
+                .and_then(|v| v.trim().parse().ok())
+                .ok_or_else(|| invalid("Invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("host") {
+            host = pair.next().map(|v| v.trim().to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = pair.next().map(|v| v.trim().to_string());
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(invalid("Body is too big"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        host,
+        origin,
+        body,
+    })
+}
+
+/// Checks that `request` carries `token` and comes to address of this server
+/// (not to a domain name rebound to it) from page of this server.
+fn check_access(token: &str, request: &Request) -> Result<(), &'static str> {
+    if request.query("token").as_deref() != Some(token) {
+        return Err("Missing or invalid token, open the address printed on start");
+    }
+    let host = request.host.as_deref().unwrap_or("");
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.parse::<u16>().is_ok() => name,
+        _ => host,
+    };
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    if name != "localhost" && name.parse::<IpAddr>().is_err() {
+        return Err("Invalid host");
+    }
+    match request.origin {
+        Some(ref origin) if origin.split_once("://").map(|(_, o)| o) != Some(host) => {
+            Err("Invalid origin")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Builds status & page answering `request` (allowed by [check_access]).
+///
+/// [check_access]: fn.check_access.html
+fn respond(fui: &Fui, token: &str, request: &Request) -> (&'static str, String) {
+    let path = request.path.split('?').next().unwrap_or("");
+    if path == "/" {
+        return ("200 OK", index_page(fui, token));
+    }
+    let action = match fui.action_by_name(path.trim_start_matches('/')) {
+        Some(action) => action,
+        None => return ("404 Not Found", page("Not found", "<p>No such action</p>")),
+    };
+    let form = match action.form {
+        Some(ref form) => form,
+        None => return ("404 Not Found", page("Not found", "<p>No such action</p>")),
+    };
+    match request.method.as_str() {
+        "GET" => {
+            let values = form
+                .get_fields()
+                .iter()
+                .map(|f| (f.get_label().to_owned(), f.get_initial()))
+                .collect();
+            (
+                "200 OK",
+                form_page(
+                    action.name,
+                    action.help,
+                    token,
+                    form,
+                    &values,
+                    &FormErrors::new(),
+                ),
+            )
+        }
+        "POST" => {
+            let values = parse_body(form, &request.body);
+            match form.validate_snapshot(&values) {
+                Ok(data) => match fui.run_handler(action.name, data) {
+                    NextStep::Done => ("200 OK", page(action.name, "<p>Submitted</p>")),
+                    NextStep::OpenForm(name, values) => next_form_page(fui, token, &name, &values),
+                },
+                Err(errors) => (
+                    "422 Unprocessable Entity",
+                    form_page(action.name, action.help, token, form, &values, &errors),
+                ),
+            }
+        }
+        _ => ("405 Method Not Allowed", page("Not allowed", "")),
+    }
+}
+
+/// Builds page with form of action named `name` filled with `values` returned by handler.
+fn next_form_page(fui: &Fui, token: &str, name: &str, values: &Value) -> (&'static str, String) {
+    let action = fui.action_by_name(name);
+    match action.and_then(|a| a.form.as_ref().map(|f| (a, f))) {
+        Some((action, form)) => {
+            let values = form.snapshot_of(values);
+            let html = form_page(
+                action.name,
+                action.help,
+                token,
+                form,
+                &values,
+                &FormErrors::new(),
+            );
+            ("200 OK", html)
+        }
+        None => ("404 Not Found", page("Not found", "<p>No such action</p>")),
+    }
+}
+
+/// Converts urlencoded `body` to values as they'd be typed in the form.
+fn parse_body(form: &FormView, body: &[u8]) -> FormSnapshot {
+    let mut pairs: HashMap<String, Vec<String>> = HashMap::new();
+    for (key, value) in form_urlencoded::parse(body) {
+        pairs
+            .entry(key.into_owned())
+            .or_default()
+            .push(value.into_owned());
+    }
+    form.get_fields()
+        .iter()
+        .map(|f| {
+            let label = f.get_label();
+            let sent = pairs.get(label);
+            let value = match f.json_schema()["type"].as_str() {
+                // unchecked checkboxes aren't sent at all
+                Some("boolean") => sent.is_some().to_string(),
+                Some("array") => sent.map(|v| v.join(VALUE_SEP)).unwrap_or_default(),
+                _ => sent.and_then(|v| v.first().cloned()).unwrap_or_default(),
+            };
+            (label.to_owned(), value)
+        })
+        .collect()
+}
+
+fn index_page(fui: &Fui, token: &str) -> String {
+    let items = fui
+        .actions
+        .values()
+        .map(|a| {
+            format!(
+                "<li><a href=\"/{}?token={}\">{}</a> &mdash; {}</li>",
+                escape(a.name),
+                token,
+                escape(a.name),
+                escape(a.help)
+            )
+        })
+        .collect::<Vec<String>>();
+    page(fui.get_name(), &format!("<ul>{}</ul>", items.join("")))
+}
+
+fn form_page(
+    name: &str,
+    help: &str,
+    token: &str,
+    form: &FormView,
+    values: &FormSnapshot,
+    errors: &FormErrors,
+) -> String {
+    let mut html = format!(
+        "<p>{}</p><form method=\"post\" action=\"?token={}\">",
+        escape(help),
+        token
+    );
+    for field in form.get_fields() {
+        let label = field.get_label();
+        // sensitive values aren't sent back to be kept in the page
+        let value = match values.get(label) {
+            Some(value) if !field.is_sensitive() => value.as_str(),
+            _ => "",
+        };
+        let required = if field.is_required() { " *" } else { "" };
+        html.push_str(&format!(
+            "<p><label>{}{}<br>{}</label>",
+            escape(label),
+            required,
+            input(field.json_schema(), label, value, field.is_sensitive())
+        ));
+        if !field.get_help().is_empty() {
+            html.push_str(&format!("<br><small>{}</small>", escape(field.get_help())));
+        }
+        for error in errors.for_field(label).into_iter().flatten() {
+            html.push_str(&format!("<br><strong>{}</strong>", escape(error)));
+        }
+        html.push_str("</p>");
+    }
+    // errors of the whole form, like ones of `before_submit` hooks
+    for (label, label_errors) in errors.iter() {
+        if form.get_fields().iter().all(|f| f.get_label() != label) {
+            for error in label_errors {
+                html.push_str(&format!(
+                    "<p><strong>{}: {}</strong></p>",
+                    escape(label),
+                    escape(error)
+                ));
+            }
+        }
+    }
+    html.push_str("<button type=\"submit\">Submit</button></form>");
+    page(name, &html)
+}
+
+/// Builds input for field described by `schema`, labeled `label` with `value`.
+fn input(schema: Value, label: &str, value: &str, sensitive: bool) -> String {
+    let name = escape(label);
+    let options = |allowed: &Value, selected: &[&str]| {
+        allowed
+            .as_array()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|v| {
+                        let mark = if selected.contains(&v) {
+                            " selected"
+                        } else {
+                            ""
+                        };
+                        format!("<option{}>{}</option>", mark, escape(v))
+                    })
+                    .collect::<Vec<String>>()
+                    .join("")
+            })
+            .unwrap_or_default()
+    };
+    match schema["type"].as_str() {
+        Some("boolean") => {
+            let checked = if value == "true" { " checked" } else { "" };
+            format!(
+                "<input type=\"checkbox\" name=\"{}\" value=\"true\"{}>",
+                name, checked
+            )
+        }
+        Some("array") if schema["items"]["enum"].is_array() => {
+            let selected = value.split(VALUE_SEP).collect::<Vec<&str>>();
+            format!(
+                "<select name=\"{}\" multiple>{}</select>",
+                name,
+                options(&schema["items"]["enum"], &selected)
+            )
+        }
+        _ if schema["enum"].is_array() => format!(
+            "<select name=\"{}\"><option></option>{}</select>",
+            name,
+            options(&schema["enum"], &[value])
+        ),
+        _ => {
+            let kind = if sensitive { "password" } else { "text" };
+            format!(
+                "<input type=\"{}\" name=\"{}\" value=\"{}\">",
+                kind,
+                name,
+                escape(value)
+            )
+        }
+    }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{0}</title></head>\
+         <body><h1>{0}</h1>{1}</body></html>",
+        escape(title),
+        body
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::io::Cursor;
+    use std::rc::Rc;
+
+    use fields::{Checkbox, Text};
+    use validators::Required;
+
+    const TOKEN: &str = "secret";
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: format!("{}?token={}", path, TOKEN),
+            host: Some("127.0.0.1:8080".to_string()),
+            origin: None,
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn request_is_read() {
+        let raw = "POST /copy?token=secret HTTP/1.1\r\nHost: 127.0.0.1:8080\r\n\
+                   Content-Length: 5\r\n\r\na=b&c";
+        let request = read_request(&mut Cursor::new(raw)).unwrap();
+        assert_eq!(request, post("/copy", "a=b&c"));
+    }
+
+    #[test]
+    fn access_requires_token_local_host_and_same_origin() {
+        let mut request = post("/copy", "");
+        assert_eq!(check_access(TOKEN, &request), Ok(()));
+
+        request.origin = Some("http://127.0.0.1:8080".to_string());
+        assert_eq!(check_access(TOKEN, &request), Ok(()));
+        request.origin = Some("http://evil.example".to_string());
+        assert_eq!(check_access(TOKEN, &request), Err("Invalid origin"));
+
+        request.origin = None;
+        request.host = Some("evil.example:8080".to_string());
+        assert_eq!(check_access(TOKEN, &request), Err("Invalid host"));
+        request.host = Some("localhost:8080".to_string());
+        assert_eq!(check_access(TOKEN, &request), Ok(()));
+
+        request.path = "/copy?token=guess".to_string();
+        assert!(check_access(TOKEN, &request).is_err());
+        request.path = "/copy".to_string();
+        assert!(check_access(TOKEN, &request).is_err());
+    }
+
+    #[test]
+    fn form_is_rendered_validated_and_submitted() {
+        let submitted = Rc::new(RefCell::new(None));
+        let data = Rc::clone(&submitted);
+        let fui = Fui::new("app").action(
+            "copy",
+            "Copies <files>",
+            FormView::new()
+                .field(Text::new("src").validator(Required))
+                .field(Text::new("password").sensitive())
+                .field(Checkbox::new("force")),
+            move |value| *data.borrow_mut() = Some(value),
+        );
+
+        let get = Request {
+            method: "GET".to_string(),
+            ..post("/copy", "")
+        };
+        let (status, html) = respond(&fui, TOKEN, &get);
+        assert_eq!(status, "200 OK");
+        assert!(html.contains("Copies &lt;files&gt;"));
+        assert!(html.contains("<input type=\"checkbox\" name=\"force\" value=\"true\">"));
+
+        assert!(html.contains("<form method=\"post\" action=\"?token=secret\">"));
+
+        let (status, html) = respond(&fui, TOKEN, &post("/copy", "src=&password=p4ss"));
+        assert_eq!(status, "422 Unprocessable Entity");
+        assert!(html.contains("<strong>Field is required</strong>"));
+        assert!(html.contains("<input type=\"password\" name=\"password\" value=\"\">"));
+        assert_eq!(*submitted.borrow(), None);
+
+        let (status, _) = respond(&fui, TOKEN, &post("/copy", "src=a+b.txt&force=true"));
+        assert_eq!(status, "200 OK");
+        assert_eq!(
+            *submitted.borrow(),
+            Some(json!({"src": "a b.txt", "password": "", "force": true}))
+        );
+
+        assert_eq!(respond(&fui, TOKEN, &post("/move", "")).0, "404 Not Found");
+    }
+}